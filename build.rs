@@ -7,4 +7,13 @@ fn main() {
     {
         println!("cargo:rustc-cfg=have_libinput_plugin_system")
     }
+
+    println!("cargo:rustc-check-cfg=cfg(have_libinput_custom_accel)");
+    if pkg_config::Config::new()
+        .atleast_version("1.23.0")
+        .probe("libinput")
+        .is_ok()
+    {
+        println!("cargo:rustc-cfg=have_libinput_custom_accel")
+    }
 }
@@ -21,6 +21,7 @@ pub struct Animations {
     pub screenshot_ui_open: ScreenshotUiOpenAnim,
     pub overview_open_close: OverviewOpenCloseAnim,
     pub recent_windows_close: RecentWindowsCloseAnim,
+    pub presets: Vec<AnimationPreset>,
 }
 
 impl Default for Animations {
@@ -41,6 +42,7 @@ impl Default for Animations {
             screenshot_ui_open: Default::default(),
             overview_open_close: Default::default(),
             recent_windows_close: Default::default(),
+            presets: Vec::new(),
         }
     }
 }
@@ -53,28 +55,30 @@ pub struct AnimationsPart {
     pub on: bool,
     #[knuffel(child, unwrap(argument))]
     pub slowdown: Option<FloatOrInt<0, { i32::MAX }>>,
+    #[knuffel(children(name = "animation-preset"))]
+    pub presets: Vec<AnimationPreset>,
     #[knuffel(child)]
-    pub workspace_switch: Option<WorkspaceSwitchAnim>,
+    pub workspace_switch: Option<PresetRef<WorkspaceSwitchAnim>>,
     #[knuffel(child)]
-    pub window_open: Option<WindowOpenAnim>,
+    pub window_open: Option<PresetRef<WindowOpenAnim>>,
     #[knuffel(child)]
-    pub window_close: Option<WindowCloseAnim>,
+    pub window_close: Option<PresetRef<WindowCloseAnim>>,
     #[knuffel(child)]
-    pub horizontal_view_movement: Option<HorizontalViewMovementAnim>,
+    pub horizontal_view_movement: Option<PresetRef<HorizontalViewMovementAnim>>,
     #[knuffel(child)]
-    pub window_movement: Option<WindowMovementAnim>,
+    pub window_movement: Option<PresetRef<WindowMovementAnim>>,
     #[knuffel(child)]
-    pub window_resize: Option<WindowResizeAnim>,
+    pub window_resize: Option<PresetRef<WindowResizeAnim>>,
     #[knuffel(child)]
-    pub config_notification_open_close: Option<ConfigNotificationOpenCloseAnim>,
+    pub config_notification_open_close: Option<PresetRef<ConfigNotificationOpenCloseAnim>>,
     #[knuffel(child)]
-    pub exit_confirmation_open_close: Option<ExitConfirmationOpenCloseAnim>,
+    pub exit_confirmation_open_close: Option<PresetRef<ExitConfirmationOpenCloseAnim>>,
     #[knuffel(child)]
-    pub screenshot_ui_open: Option<ScreenshotUiOpenAnim>,
+    pub screenshot_ui_open: Option<PresetRef<ScreenshotUiOpenAnim>>,
     #[knuffel(child)]
-    pub overview_open_close: Option<OverviewOpenCloseAnim>,
+    pub overview_open_close: Option<PresetRef<OverviewOpenCloseAnim>>,
     #[knuffel(child)]
-    pub recent_windows_close: Option<RecentWindowsCloseAnim>,
+    pub recent_windows_close: Option<PresetRef<RecentWindowsCloseAnim>>,
 }
 
 impl MergeWith<AnimationsPart> for Animations {
@@ -86,10 +90,26 @@ impl MergeWith<AnimationsPart> for Animations {
 
         merge!((self, part), slowdown);
 
+        // Later presets with the same name replace earlier ones.
+        for preset in &part.presets {
+            self.presets.retain(|p| p.name != preset.name);
+            self.presets.push(preset.clone());
+        }
+
         // Animation properties are fairly tied together, except maybe `off`. So let's just save
         // ourselves the work and not merge within individual animations.
-        merge_clone!(
-            (self, part),
+        //
+        // Preset references must be resolved with AnimationsPart::resolve_presets() beforehand.
+        macro_rules! merge_anim {
+            ($($field:ident),+ $(,)*) => {
+                $(
+                    if let Some(x) = &part.$field {
+                        self.$field.clone_from(&x.anim);
+                    }
+                )+
+            };
+        }
+        merge_anim!(
             workspace_switch,
             window_open,
             window_close,
@@ -105,6 +125,77 @@ impl MergeWith<AnimationsPart> for Animations {
     }
 }
 
+impl AnimationsPart {
+    /// Expands preset references into the presets' animation parameters.
+    ///
+    /// Presets defined in this part take precedence over the previously defined `presets`. On
+    /// failure, returns the first unknown preset name.
+    pub fn resolve_presets(&mut self, presets: &[AnimationPreset]) -> Result<(), String> {
+        let find = |name: &str| {
+            self.presets
+                .iter()
+                .rev()
+                .chain(presets.iter().rev())
+                .find(|p| p.name == name)
+                .map(|p| p.anim)
+        };
+
+        macro_rules! resolve {
+            ($($field:ident),+ $(,)*) => {
+                $(
+                    if let Some(x) = &mut self.$field
+                        && let Some(name) = x.preset.take()
+                    {
+                        let preset = find(&name).ok_or(name)?;
+                        x.anim.apply_preset(preset);
+                    }
+                )+
+            };
+        }
+        resolve!(
+            workspace_switch,
+            window_open,
+            window_close,
+            horizontal_view_movement,
+            window_movement,
+            window_resize,
+            config_notification_open_close,
+            exit_confirmation_open_close,
+            screenshot_ui_open,
+            overview_open_close,
+            recent_windows_close,
+        );
+
+        Ok(())
+    }
+}
+
+/// Named animation parameters that individual animations can refer to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationPreset {
+    pub name: String,
+    pub anim: Animation,
+}
+
+/// Animation that may take its parameters from a named preset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetRef<T> {
+    /// Name of the preset, until it is resolved.
+    pub preset: Option<String>,
+    pub anim: T,
+}
+
+/// Animation config types wrapping an [`Animation`].
+pub trait WithAnimation {
+    fn animation_mut(&mut self) -> &mut Animation;
+
+    fn apply_preset(&mut self, preset: Animation) {
+        let anim = self.animation_mut();
+        anim.off |= preset.off;
+        anim.kind = preset.kind;
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Animation {
     pub off: bool,
@@ -139,6 +230,13 @@ pub struct SpringParams {
     pub epsilon: f64,
 }
 
+/// Generic easing parameters for when the default animation is spring, but the user configured
+/// an easing animation.
+const FALLBACK_EASING: EasingParams = EasingParams {
+    duration_ms: 250,
+    curve: Curve::EaseOutCubic,
+};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct WorkspaceSwitchAnim(pub Animation);
 
@@ -566,6 +664,172 @@ where
     }
 }
 
+impl WithAnimation for WorkspaceSwitchAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.0
+    }
+}
+
+impl WithAnimation for WindowOpenAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.anim
+    }
+}
+
+impl WithAnimation for WindowCloseAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.anim
+    }
+}
+
+impl WithAnimation for HorizontalViewMovementAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.0
+    }
+}
+
+impl WithAnimation for WindowMovementAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.0
+    }
+}
+
+impl WithAnimation for WindowResizeAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.anim
+    }
+}
+
+impl WithAnimation for ConfigNotificationOpenCloseAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.0
+    }
+}
+
+impl WithAnimation for ExitConfirmationOpenCloseAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.0
+    }
+}
+
+impl WithAnimation for ScreenshotUiOpenAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.0
+    }
+}
+
+impl WithAnimation for OverviewOpenCloseAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.0
+    }
+}
+
+impl WithAnimation for RecentWindowsCloseAnim {
+    fn animation_mut(&mut self) -> &mut Animation {
+        &mut self.0
+    }
+}
+
+impl<S, T> knuffel::Decode<S> for PresetRef<T>
+where
+    S: knuffel::traits::ErrorSpan,
+    T: knuffel::Decode<S>,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        let mut preset = None;
+        for child in node.children() {
+            if &**child.node_name != "preset" {
+                continue;
+            }
+
+            if preset.is_some() {
+                ctx.emit_error(DecodeError::unexpected(
+                    &child.node_name,
+                    "node",
+                    "duplicate node `preset`, single node expected",
+                ));
+            }
+            preset = Some(parse_arg_node::<S, String>("preset", child, ctx)?);
+        }
+
+        if preset.is_some() {
+            for child in node.children() {
+                if matches!(&**child.node_name, "spring" | "duration-ms" | "curve") {
+                    ctx.emit_error(DecodeError::unexpected(
+                        child,
+                        "node",
+                        "cannot set both a preset and animation parameters at once",
+                    ));
+                }
+            }
+        }
+
+        let anim = T::decode_node(node, ctx)?;
+        Ok(Self { preset, anim })
+    }
+}
+
+impl<S> knuffel::Decode<S> for AnimationPreset
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        let mut iter_args = node.arguments.iter();
+        let val = iter_args
+            .next()
+            .ok_or_else(|| DecodeError::missing(node, "additional argument `name` is required"))?;
+        let name: String = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+
+        if let Some(val) = iter_args.next() {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "unexpected argument",
+            ));
+        }
+        for name in node.properties.keys() {
+            ctx.emit_error(DecodeError::unexpected(
+                name,
+                "property",
+                format!("unexpected property `{}`", name.escape_default()),
+            ));
+        }
+
+        let default = Animation {
+            off: false,
+            kind: Kind::Easing(FALLBACK_EASING),
+        };
+        let anim = Animation::decode_children(node, ctx, default, |child, ctx| {
+            if &**child.node_name == "preset" {
+                ctx.emit_error(DecodeError::unexpected(
+                    child,
+                    "node",
+                    "presets cannot refer to other presets",
+                ));
+                return Ok(true);
+            }
+
+            Ok(false)
+        })?;
+
+        Ok(Self { name, anim })
+    }
+}
+
 impl Animation {
     pub const fn new_off() -> Self {
         Self {
@@ -585,6 +849,26 @@ impl Animation {
             &knuffel::ast::SpannedNode<S>,
             &mut knuffel::decode::Context<S>,
         ) -> Result<bool, DecodeError<S>>,
+    ) -> Result<Self, DecodeError<S>> {
+        expect_only_children(node, ctx);
+        Self::decode_children(node, ctx, default, |child, ctx| {
+            // Handled in PresetRef.
+            if &**child.node_name == "preset" {
+                return Ok(true);
+            }
+
+            process_children(child, ctx)
+        })
+    }
+
+    fn decode_children<S: knuffel::traits::ErrorSpan>(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+        default: Self,
+        mut process_children: impl FnMut(
+            &knuffel::ast::SpannedNode<S>,
+            &mut knuffel::decode::Context<S>,
+        ) -> Result<bool, DecodeError<S>>,
     ) -> Result<Self, DecodeError<S>> {
         #[derive(Default, PartialEq)]
         struct OptionalEasingParams {
@@ -592,8 +876,6 @@ impl Animation {
             curve: Option<Curve>,
         }
 
-        expect_only_children(node, ctx);
-
         let mut off = false;
         let mut easing_params = OptionalEasingParams::default();
         let mut spring_params = None;
@@ -776,12 +1058,7 @@ impl Animation {
             let default = if let Kind::Easing(easing) = default.kind {
                 easing
             } else {
-                // Generic fallback values for when the default animation is spring, but the user
-                // configured an easing animation.
-                EasingParams {
-                    duration_ms: 250,
-                    curve: Curve::EaseOutCubic,
-                }
+                FALLBACK_EASING
             };
 
             Kind::Easing(EasingParams {
@@ -449,6 +449,7 @@ pub struct WorkspaceShadow {
     pub softness: f64,
     pub spread: f64,
     pub color: Color,
+    pub motion: bool,
 }
 
 impl Default for WorkspaceShadow {
@@ -462,6 +463,7 @@ impl Default for WorkspaceShadow {
             softness: 40.,
             spread: 10.,
             color: Color::from_rgba8_unpremul(0, 0, 0, 0x50),
+            motion: false,
         }
     }
 }
@@ -494,6 +496,8 @@ pub struct WorkspaceShadowPart {
     pub spread: Option<FloatOrInt<-1024, 1024>>,
     #[knuffel(child)]
     pub color: Option<Color>,
+    #[knuffel(child)]
+    pub motion: Option<Flag>,
 }
 
 impl MergeWith<WorkspaceShadowPart> for WorkspaceShadow {
@@ -504,7 +508,7 @@ impl MergeWith<WorkspaceShadowPart> for WorkspaceShadow {
         }
 
         merge_clone!((self, part), offset, color);
-        merge!((self, part), softness, spread);
+        merge!((self, part), softness, spread, motion);
     }
 }
 
@@ -526,6 +530,7 @@ pub struct TabIndicator {
     pub active_gradient: Option<Gradient>,
     pub inactive_gradient: Option<Gradient>,
     pub urgent_gradient: Option<Gradient>,
+    pub label_shadow: Shadow,
 }
 
 impl Default for TabIndicator {
@@ -549,6 +554,18 @@ impl Default for TabIndicator {
             active_gradient: None,
             inactive_gradient: None,
             urgent_gradient: None,
+            label_shadow: Shadow {
+                on: false,
+                offset: ShadowOffset {
+                    x: FloatOrInt(0.),
+                    y: FloatOrInt(1.),
+                },
+                softness: 3.,
+                spread: 0.,
+                draw_behind_window: false,
+                color: Color::from_rgba8_unpremul(0, 0, 0, 0xaa),
+                inactive_color: None,
+            },
         }
     }
 }
@@ -568,6 +585,7 @@ impl MergeWith<TabIndicatorPart> for TabIndicator {
             width,
             gaps_between_tabs,
             corner_radius,
+            label_shadow,
         );
 
         merge_clone!((self, part), title_font_size, length, position);
@@ -617,6 +635,8 @@ pub struct TabIndicatorPart {
     pub inactive_gradient: Option<Gradient>,
     #[knuffel(child)]
     pub urgent_gradient: Option<Gradient>,
+    #[knuffel(child)]
+    pub label_shadow: Option<ShadowRule>,
 }
 
 #[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
@@ -636,6 +656,7 @@ pub struct InsertHint {
     pub off: bool,
     pub color: Color,
     pub gradient: Option<Gradient>,
+    pub animation: InsertHintAnimation,
 }
 
 impl Default for InsertHint {
@@ -644,6 +665,7 @@ impl Default for InsertHint {
             off: false,
             color: Color::from_rgba8_unpremul(127, 200, 255, 128),
             gradient: None,
+            animation: InsertHintAnimation::None,
         }
     }
 }
@@ -656,6 +678,13 @@ impl MergeWith<InsertHintPart> for InsertHint {
         }
 
         merge_color_gradient!((self, part), (color, gradient));
+        merge_clone!((self, part), animation);
+    }
+}
+
+impl MergeWith<InsertHintRule> for InsertHint {
+    fn merge_with(&mut self, part: &InsertHintRule) {
+        merge_color_gradient!((self, part), (color, gradient));
     }
 }
 
@@ -669,6 +698,16 @@ pub struct InsertHintPart {
     pub color: Option<Color>,
     #[knuffel(child)]
     pub gradient: Option<Gradient>,
+    #[knuffel(child, unwrap(argument))]
+    pub animation: Option<InsertHintAnimation>,
+}
+
+#[derive(knuffel::DecodeScalar, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InsertHintAnimation {
+    #[default]
+    None,
+    Fade,
+    Pulse,
 }
 
 #[derive(knuffel::DecodeScalar, Debug, Clone, Copy, PartialEq, Eq)]
@@ -761,6 +800,14 @@ pub struct TabIndicatorRule {
     pub urgent_gradient: Option<Gradient>,
 }
 
+#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
+pub struct InsertHintRule {
+    #[knuffel(child)]
+    pub color: Option<Color>,
+    #[knuffel(child)]
+    pub gradient: Option<Gradient>,
+}
+
 impl MergeWith<Self> for BorderRule {
     fn merge_with(&mut self, part: &Self) {
         merge_on_off!((self, part));
@@ -810,6 +857,12 @@ impl MergeWith<Self> for ShadowRule {
     }
 }
 
+impl MergeWith<Self> for InsertHintRule {
+    fn merge_with(&mut self, part: &Self) {
+        merge_color_gradient_opt!((self, part), (color, gradient));
+    }
+}
+
 impl MergeWith<Self> for TabIndicatorRule {
     fn merge_with(&mut self, part: &Self) {
         merge_color_gradient_opt!(
@@ -1353,4 +1406,59 @@ mod tests {
         "
         );
     }
+
+    #[test]
+    fn blur_rule_overrides_color_adjustments() {
+        let config = Config::parse_mem(
+            r##"
+            layout {
+                blur {
+                    noise 1
+                    brightness 0.5
+                    contrast 2
+                    saturation 0.5
+                }
+            }
+
+            window-rule {
+                blur {
+                    noise 3
+                    brightness 1.5
+                    contrast 0.8
+                    saturation 0
+                }
+            }
+
+            layer-rule {
+                blur {
+                    contrast 1.2
+                }
+            }
+            "##,
+        )
+        .unwrap();
+
+        let mut window_blur = config.layout.blur;
+        for rule in &config.window_rules {
+            window_blur.merge_with(&rule.blur);
+        }
+
+        let mut layer_blur = config.layout.blur;
+        for rule in &config.layer_rules {
+            layer_blur.merge_with(&rule.blur);
+        }
+
+        let adjustments = |blur: Blur| {
+            (
+                blur.noise.0,
+                blur.brightness.0,
+                blur.contrast.0,
+                blur.saturation.0,
+            )
+        };
+
+        assert_eq!(adjustments(window_blur), (3., 1.5, 0.8, 0.));
+        // Fields not set in the rule keep the base value.
+        assert_eq!(adjustments(layer_blur), (1., 0.5, 1.2, 0.5));
+    }
 }
@@ -6,13 +6,16 @@ use bitflags::bitflags;
 use knuffel::DecodeScalar;
 use knuffel::errors::DecodeError;
 use miette::miette;
-use niri_ipc::{LayoutSwitchTarget, PositionChange, SizeChange, WorkspaceReferenceArg};
+use niri_ipc::{
+    Direction, GroupDisplayMode, LayoutSwitchTarget, PositionChange, ScreenshotTargetArg,
+    SizeChange, Transform, WorkspaceReferenceArg,
+};
 use smithay::input::keyboard::Keysym;
 use smithay::input::keyboard::keysyms::KEY_NoSymbol;
 use smithay::input::keyboard::xkb::{KEYSYM_CASE_INSENSITIVE, KEYSYM_NO_FLAGS, keysym_from_name};
 
 use crate::recent_windows::{MruDirection, MruFilter, MruScope};
-use crate::utils::{MergeWith, expect_only_children};
+use crate::utils::{FloatOrInt, MergeWith, expect_only_children};
 
 #[derive(Debug, Default, PartialEq)]
 pub struct Binds(pub Vec<Bind>);
@@ -23,6 +26,8 @@ pub struct Bind {
     pub action: Action,
     pub repeat: bool,
     pub cooldown: Option<Duration>,
+    /// How long the key must be held before the action fires.
+    pub hold: Option<Duration>,
     pub allow_when_locked: bool,
     pub allow_inhibiting: bool,
     pub hotkey_overlay_title: Option<Option<String>>,
@@ -52,6 +57,23 @@ pub enum Trigger {
     TouchpadScrollRight,
 }
 
+impl Trigger {
+    /// Whether this is a mouse wheel or touchpad scroll trigger.
+    pub const fn is_scroll(&self) -> bool {
+        matches!(
+            self,
+            Self::WheelScrollDown
+                | Self::WheelScrollUp
+                | Self::WheelScrollLeft
+                | Self::WheelScrollRight
+                | Self::TouchpadScrollDown
+                | Self::TouchpadScrollUp
+                | Self::TouchpadScrollLeft
+                | Self::TouchpadScrollRight
+        )
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, DecodeScalar, Hash)]
 pub enum WindowMoveDirection {
     Up,
@@ -112,11 +134,24 @@ pub enum Action {
     Suspend,
     PowerOffMonitors,
     PowerOnMonitors,
+    PowerOffMonitor(#[knuffel(argument)] String),
+    PowerOnMonitor(#[knuffel(argument)] String),
+    SetOutputScale(#[knuffel(argument)] FloatOrInt<0, 10>),
+    CycleOutputScale(#[knuffel(arguments)] Vec<FloatOrInt<0, 10>>),
+    RotateOutput(#[knuffel(argument, str)] Transform),
+    CycleOutputTransform,
+    ChangeBacklight(#[knuffel(argument)] i32),
+    SetBacklight(#[knuffel(argument)] u32),
     ToggleDebugTint,
     DebugToggleOpaqueRegions,
     DebugToggleDamage,
+    DebugToggleRenderHeatmap,
     Spawn(#[knuffel(arguments)] Vec<String>),
     SpawnSh(#[knuffel(argument)] String),
+    SpawnOnWorkspace(
+        #[knuffel(arguments)] Vec<String>,
+        #[knuffel(property(name = "workspace"))] String,
+    ),
     DoScreenTransition(#[knuffel(property(name = "delay-ms"))] Option<u16>),
     #[knuffel(skip)]
     ConfirmScreenshot {
@@ -137,6 +172,12 @@ pub enum Action {
         // Path; not settable from knuffel
         Option<String>,
     ),
+    ScreenshotEachOutput(
+        #[knuffel(property(name = "write-to-disk"), default = true)] bool,
+        #[knuffel(property(name = "show-pointer"), default = true)] bool,
+        // Path template; not settable from knuffel
+        Option<String>,
+    ),
     ScreenshotWindow(
         #[knuffel(property(name = "write-to-disk"), default = true)] bool,
         // Path; not settable from knuffel
@@ -148,6 +189,11 @@ pub enum Action {
         write_to_disk: bool,
         path: Option<String>,
     },
+    ScreenshotToCommand(
+        #[knuffel(arguments)] Vec<String>,
+        #[knuffel(property(name = "target"), str, default)] ScreenshotTargetArg,
+        #[knuffel(property(name = "show-pointer"), default = true)] bool,
+    ),
     ToggleKeyboardShortcutsInhibit,
     CloseWindow,
     #[knuffel(skip)]
@@ -156,6 +202,8 @@ pub enum Action {
     MoveWindowIntoOrOutOfGroup(#[knuffel(argument)] WindowMoveDirection),
     FocusNextWindow,
     FocusPreviousWindow,
+    SetGroupDisplayMode(#[knuffel(argument, str)] GroupDisplayMode),
+    ToggleGroupDisplayMode,
     FullscreenWindow,
     #[knuffel(skip)]
     FullscreenWindowById(u64),
@@ -172,6 +220,8 @@ pub enum Action {
     FocusColumnRight,
     #[knuffel(skip)]
     FocusColumnRightUnderMouse,
+    #[knuffel(skip)]
+    FocusColumnUnderMouse,
     FocusColumnFirst,
     FocusColumnLast,
     FocusColumnRightOrFirst,
@@ -193,6 +243,7 @@ pub enum Action {
     FocusWindowBottom,
     FocusWindowDownOrTop,
     FocusWindowUpOrBottom,
+    FocusSpatial(#[knuffel(argument, str)] Direction),
     MoveColumnLeft,
     MoveColumnRight,
     MoveColumnToFirst,
@@ -200,6 +251,7 @@ pub enum Action {
     MoveColumnLeftOrToMonitorLeft,
     MoveColumnRightOrToMonitorRight,
     MoveColumnToIndex(#[knuffel(argument)] usize),
+    MoveColumnByOffset(#[knuffel(argument)] i32),
     MoveWindowDown,
     MoveWindowUp,
     MoveWindowDownOrToWorkspaceDown,
@@ -212,20 +264,31 @@ pub enum Action {
     ConsumeOrExpelWindowRightById(u64),
     ConsumeWindowIntoColumn,
     ExpelWindowFromColumn,
+    ExpelWindowToColumnLeft,
+    ExpelWindowToColumnRight,
+    ConsumeWindowFromMonitorLeft,
+    ConsumeWindowFromMonitorRight,
     SwapWindowLeft,
     SwapWindowRight,
     CenterColumn,
     CenterWindow,
     #[knuffel(skip)]
     CenterWindowById(u64),
+    CenterWindowVertically,
+    #[knuffel(skip)]
+    CenterWindowVerticallyById(u64),
     CenterVisibleColumns,
+    DistributeColumnsEvenly,
     FocusWorkspaceDown,
     #[knuffel(skip)]
     FocusWorkspaceDownUnderMouse,
     FocusWorkspaceUp,
     #[knuffel(skip)]
     FocusWorkspaceUpUnderMouse,
-    FocusWorkspace(#[knuffel(argument)] WorkspaceReference),
+    FocusWorkspace(
+        #[knuffel(argument)] WorkspaceReference,
+        #[knuffel(property(name = "create-if-missing"), default)] bool,
+    ),
     FocusWorkspacePrevious,
     MoveWindowToWorkspaceDown(#[knuffel(property(name = "focus"), default = true)] bool),
     MoveWindowToWorkspaceUp(#[knuffel(property(name = "focus"), default = true)] bool),
@@ -239,19 +302,25 @@ pub enum Action {
         reference: WorkspaceReference,
         focus: bool,
     },
+    MoveWindowToWorkspacePrevious(#[knuffel(property(name = "focus"), default = true)] bool),
     MoveColumnToWorkspaceDown(#[knuffel(property(name = "focus"), default = true)] bool),
     MoveColumnToWorkspaceUp(#[knuffel(property(name = "focus"), default = true)] bool),
     MoveColumnToWorkspace(
         #[knuffel(argument)] WorkspaceReference,
         #[knuffel(property(name = "focus"), default = true)] bool,
     ),
+    MoveColumnToWorkspacePrevious(#[knuffel(property(name = "focus"), default = true)] bool),
     MoveWorkspaceDown,
     MoveWorkspaceUp,
-    MoveWorkspaceToIndex(#[knuffel(argument)] usize),
+    MoveWorkspaceToIndex(
+        #[knuffel(argument)] usize,
+        #[knuffel(property(name = "compact"), default)] bool,
+    ),
     #[knuffel(skip)]
     MoveWorkspaceToIndexByRef {
         new_idx: usize,
         reference: WorkspaceReference,
+        compact: bool,
     },
     #[knuffel(skip)]
     MoveWorkspaceToMonitorByRef {
@@ -274,7 +343,12 @@ pub enum Action {
     FocusMonitorUp,
     FocusMonitorPrevious,
     FocusMonitorNext,
+    FocusMonitorLeftWrap,
+    FocusMonitorRightWrap,
+    FocusMonitorDownWrap,
+    FocusMonitorUpWrap,
     FocusMonitor(#[knuffel(argument)] String),
+    FocusMonitorByWorkspace(#[knuffel(argument)] String),
     MoveWindowToMonitorLeft,
     MoveWindowToMonitorRight,
     MoveWindowToMonitorDown,
@@ -287,6 +361,15 @@ pub enum Action {
         id: u64,
         output: String,
     },
+    MoveWindowToLastOutput,
+    #[knuffel(skip)]
+    MoveWindowToLastOutputById(u64),
+    MoveWindowToMonitorWorkspace(
+        #[knuffel(argument)] String,
+        #[knuffel(argument)] WorkspaceReference,
+        #[knuffel(property(name = "focus"), default = true)] bool,
+        #[knuffel(property(name = "create-if-missing"), default)] bool,
+    ),
     MoveColumnToMonitorLeft,
     MoveColumnToMonitorRight,
     MoveColumnToMonitorDown,
@@ -294,6 +377,8 @@ pub enum Action {
     MoveColumnToMonitorPrevious,
     MoveColumnToMonitorNext,
     MoveColumnToMonitor(#[knuffel(argument)] String),
+    SwapColumnToMonitorLeft,
+    SwapColumnToMonitorRight,
     SetWindowWidth(#[knuffel(argument, str)] SizeChange),
     #[knuffel(skip)]
     SetWindowWidthById {
@@ -309,6 +394,9 @@ pub enum Action {
     ResetWindowHeight,
     #[knuffel(skip)]
     ResetWindowHeightById(u64),
+    FillWindowHeightInColumn,
+    #[knuffel(skip)]
+    FillWindowHeightInColumnById(u64),
     SwitchPresetColumnWidth,
     SwitchPresetColumnWidthBack,
     SwitchPresetWindowWidth,
@@ -324,12 +412,21 @@ pub enum Action {
     #[knuffel(skip)]
     SwitchPresetWindowHeightBackById(u64),
     MaximizeColumn,
+    ToggleMaximizeColumnWithGroup,
     MaximizeWindowToEdges,
     #[knuffel(skip)]
     MaximizeWindowToEdgesById(u64),
+    ToggleMaximizeWindowToEdges,
+    #[knuffel(skip)]
+    ToggleMaximizeWindowToEdgesById(u64),
     SetColumnWidth(#[knuffel(argument, str)] SizeChange),
     ExpandColumnToAvailableWidth,
+    ToggleColumnExpandedWidth,
+    AlignColumnWidthToNeighborLeft,
+    AlignColumnWidthToNeighborRight,
+    ToggleZeroGaps,
     SwitchLayout(#[knuffel(argument, str)] LayoutSwitchTarget),
+    ToggleNumLock,
     ShowHotkeyOverlay,
     MoveWorkspaceToMonitorLeft,
     MoveWorkspaceToMonitorRight,
@@ -337,6 +434,13 @@ pub enum Action {
     MoveWorkspaceToMonitorUp,
     MoveWorkspaceToMonitorPrevious,
     MoveWorkspaceToMonitorNext,
+    MoveWorkspaceToMonitorUnderMouse,
+    ToggleMirrorToMonitor(#[knuffel(argument)] String),
+    #[knuffel(skip)]
+    MirrorMonitor {
+        source: String,
+        target: String,
+    },
     ToggleWindowFloating,
     #[knuffel(skip)]
     ToggleWindowFloatingById(u64),
@@ -348,7 +452,20 @@ pub enum Action {
     MoveWindowToTilingById(u64),
     FocusFloating,
     FocusTiling,
+    FocusNextFloatingWindow,
+    FocusPreviousFloatingWindow,
     SwitchFocusBetweenFloatingAndTiling,
+    FocusFloatingOrTilingUnderMouse,
+    ToggleFloatingResizeMode,
+    #[knuffel(skip)]
+    FloatingResizeModeAdjust {
+        width: i32,
+        height: i32,
+    },
+    #[knuffel(skip)]
+    FloatingResizeModeConfirm,
+    #[knuffel(skip)]
+    FloatingResizeModeCancel,
     #[knuffel(skip)]
     MoveFloatingWindowById {
         id: Option<u64>,
@@ -358,14 +475,25 @@ pub enum Action {
     ToggleWindowRuleOpacity,
     #[knuffel(skip)]
     ToggleWindowRuleOpacityById(u64),
+    SetWindowOpacity(#[knuffel(argument)] f32),
+    #[knuffel(skip)]
+    SetWindowOpacityById {
+        id: u64,
+        opacity: f32,
+    },
+    ToggleServerDecorations,
+    #[knuffel(skip)]
+    ToggleServerDecorationsById(u64),
     SetDynamicCastWindow,
     #[knuffel(skip)]
     SetDynamicCastWindowById(u64),
     SetDynamicCastMonitor(#[knuffel(argument)] Option<String>),
     ClearDynamicCastTarget,
     ToggleOverview,
+    ToggleOverviewFocusedMonitor,
     OpenOverview,
     CloseOverview,
+    ToggleFocusedWindowUrgent,
     #[knuffel(skip)]
     ToggleWindowUrgent(u64),
     #[knuffel(skip)]
@@ -374,6 +502,8 @@ pub enum Action {
     UnsetWindowUrgent(u64),
     #[knuffel(skip)]
     LoadConfigFile,
+    ReloadBinds,
+    RestoreClipboardHistoryEntry(#[knuffel(argument)] usize),
     #[knuffel(skip)]
     MruAdvance {
         direction: MruDirection,
@@ -396,6 +526,104 @@ pub enum Action {
     MruCycleScope,
 }
 
+impl Action {
+    /// Whether this action runs while the session is locked.
+    ///
+    /// Actions that manipulate windows or the layout stay forbidden, since they would let anyone
+    /// at the keyboard interfere with the session behind the lock screen.
+    pub const fn allowed_when_locked(&self) -> bool {
+        matches!(
+            self,
+            Self::Quit(_)
+                | Self::ChangeVt(_)
+                | Self::Suspend
+                | Self::PowerOffMonitors
+                | Self::PowerOnMonitors
+                | Self::PowerOffMonitor(_)
+                | Self::PowerOnMonitor(_)
+                | Self::ChangeBacklight(_)
+                | Self::SetBacklight(_)
+                | Self::SwitchLayout(_)
+                | Self::ToggleNumLock
+                | Self::ToggleKeyboardShortcutsInhibit
+        )
+    }
+
+    /// Whether a bind with this action repeats when `repeat` isn't set explicitly.
+    ///
+    /// Toggles and one-shot actions don't repeat, since holding the key would make them flip back
+    /// and forth or fire many times. Movement and resizing actions repeat.
+    pub const fn default_repeat(&self) -> bool {
+        !matches!(
+            self,
+            Self::Quit(_)
+                | Self::ChangeVt(_)
+                | Self::Suspend
+                | Self::PowerOffMonitors
+                | Self::PowerOnMonitors
+                | Self::PowerOffMonitor(_)
+                | Self::PowerOnMonitor(_)
+                | Self::CycleOutputScale(_)
+                | Self::CycleOutputTransform
+                | Self::ToggleDebugTint
+                | Self::DebugToggleOpaqueRegions
+                | Self::DebugToggleDamage
+                | Self::DebugToggleRenderHeatmap
+                | Self::DoScreenTransition(_)
+                | Self::ConfirmScreenshot { .. }
+                | Self::CancelScreenshot
+                | Self::ScreenshotTogglePointer
+                | Self::Screenshot(..)
+                | Self::ScreenshotScreen(..)
+                | Self::ScreenshotEachOutput(..)
+                | Self::ScreenshotWindow(..)
+                | Self::ScreenshotWindowById { .. }
+                | Self::ScreenshotToCommand(..)
+                | Self::ToggleKeyboardShortcutsInhibit
+                | Self::CloseWindow
+                | Self::CloseWindowById(_)
+                | Self::ToggleGroup
+                | Self::FullscreenWindow
+                | Self::FullscreenWindowById(_)
+                | Self::ToggleWindowedFullscreen
+                | Self::ToggleWindowedFullscreenById(_)
+                | Self::FocusWindowPrevious
+                | Self::FocusWorkspacePrevious
+                | Self::MoveWindowToWorkspacePrevious(_)
+                | Self::MoveColumnToWorkspacePrevious(_)
+                | Self::FocusMonitorPrevious
+                | Self::MaximizeColumn
+                | Self::ToggleMaximizeColumnWithGroup
+                | Self::ToggleGroupDisplayMode
+                | Self::MaximizeWindowToEdges
+                | Self::MaximizeWindowToEdgesById(_)
+                | Self::ToggleMaximizeWindowToEdges
+                | Self::ToggleMaximizeWindowToEdgesById(_)
+                | Self::ToggleColumnExpandedWidth
+                | Self::ToggleZeroGaps
+                | Self::ToggleNumLock
+                | Self::ShowHotkeyOverlay
+                | Self::ToggleMirrorToMonitor(_)
+                | Self::ToggleWindowFloating
+                | Self::ToggleWindowFloatingById(_)
+                | Self::SwitchFocusBetweenFloatingAndTiling
+                | Self::FocusFloatingOrTilingUnderMouse
+                | Self::ToggleFloatingResizeMode
+                | Self::ToggleWindowRuleOpacity
+                | Self::ToggleWindowRuleOpacityById(_)
+                | Self::ToggleServerDecorations
+                | Self::ToggleServerDecorationsById(_)
+                | Self::ToggleOverview
+                | Self::ToggleOverviewFocusedMonitor
+                | Self::ToggleFocusedWindowUrgent
+                | Self::ToggleWindowUrgent(_)
+                | Self::LoadConfigFile
+                | Self::ReloadBinds
+                | Self::RestoreClipboardHistoryEntry(_)
+        )
+    }
+}
+
 // TODO: macro, bruh
 impl From<niri_ipc::Action> for Action {
     fn from(value: niri_ipc::Action) -> Self {
@@ -403,8 +631,21 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::Quit { skip_confirmation } => Self::Quit(skip_confirmation),
             niri_ipc::Action::PowerOffMonitors {} => Self::PowerOffMonitors,
             niri_ipc::Action::PowerOnMonitors {} => Self::PowerOnMonitors,
+            niri_ipc::Action::PowerOffMonitor { output } => Self::PowerOffMonitor(output),
+            niri_ipc::Action::PowerOnMonitor { output } => Self::PowerOnMonitor(output),
+            niri_ipc::Action::SetOutputScale { scale } => Self::SetOutputScale(FloatOrInt(scale)),
+            niri_ipc::Action::CycleOutputScale { scales } => {
+                Self::CycleOutputScale(scales.into_iter().map(FloatOrInt).collect())
+            }
+            niri_ipc::Action::RotateOutput { transform } => Self::RotateOutput(transform),
+            niri_ipc::Action::CycleOutputTransform {} => Self::CycleOutputTransform,
+            niri_ipc::Action::ChangeBacklight { delta } => Self::ChangeBacklight(delta),
+            niri_ipc::Action::SetBacklight { percent } => Self::SetBacklight(percent),
             niri_ipc::Action::Spawn { command } => Self::Spawn(command),
             niri_ipc::Action::SpawnSh { command } => Self::SpawnSh(command),
+            niri_ipc::Action::SpawnOnWorkspace { workspace, command } => {
+                Self::SpawnOnWorkspace(command, workspace)
+            }
             niri_ipc::Action::DoScreenTransition { delay_ms } => Self::DoScreenTransition(delay_ms),
             niri_ipc::Action::Screenshot { show_pointer, path } => {
                 Self::Screenshot(show_pointer, path)
@@ -414,6 +655,11 @@ impl From<niri_ipc::Action> for Action {
                 show_pointer,
                 path,
             } => Self::ScreenshotScreen(write_to_disk, show_pointer, path),
+            niri_ipc::Action::ScreenshotEachOutput {
+                write_to_disk,
+                show_pointer,
+                path_template,
+            } => Self::ScreenshotEachOutput(write_to_disk, show_pointer, path_template),
             niri_ipc::Action::ScreenshotWindow {
                 id: None,
                 write_to_disk,
@@ -428,6 +674,11 @@ impl From<niri_ipc::Action> for Action {
                 write_to_disk,
                 path,
             },
+            niri_ipc::Action::ScreenshotToCommand {
+                command,
+                target,
+                show_pointer,
+            } => Self::ScreenshotToCommand(command, target, show_pointer),
             niri_ipc::Action::ToggleKeyboardShortcutsInhibit {} => {
                 Self::ToggleKeyboardShortcutsInhibit
             }
@@ -467,11 +718,13 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::FocusWindowBottom {} => Self::FocusWindowBottom,
             niri_ipc::Action::FocusWindowDownOrTop {} => Self::FocusWindowDownOrTop,
             niri_ipc::Action::FocusWindowUpOrBottom {} => Self::FocusWindowUpOrBottom,
+            niri_ipc::Action::FocusSpatial { direction } => Self::FocusSpatial(direction),
             niri_ipc::Action::MoveColumnLeft {} => Self::MoveColumnLeft,
             niri_ipc::Action::MoveColumnRight {} => Self::MoveColumnRight,
             niri_ipc::Action::MoveColumnToFirst {} => Self::MoveColumnToFirst,
             niri_ipc::Action::MoveColumnToLast {} => Self::MoveColumnToLast,
             niri_ipc::Action::MoveColumnToIndex { index } => Self::MoveColumnToIndex(index),
+            niri_ipc::Action::MoveColumnByOffset { offset } => Self::MoveColumnByOffset(offset),
             niri_ipc::Action::MoveColumnLeftOrToMonitorLeft {} => {
                 Self::MoveColumnLeftOrToMonitorLeft
             }
@@ -498,17 +751,29 @@ impl From<niri_ipc::Action> for Action {
             }
             niri_ipc::Action::ConsumeWindowIntoColumn {} => Self::ConsumeWindowIntoColumn,
             niri_ipc::Action::ExpelWindowFromColumn {} => Self::ExpelWindowFromColumn,
+            niri_ipc::Action::ExpelWindowToColumnLeft {} => Self::ExpelWindowToColumnLeft,
+            niri_ipc::Action::ExpelWindowToColumnRight {} => Self::ExpelWindowToColumnRight,
+            niri_ipc::Action::ConsumeWindowFromMonitorLeft {} => Self::ConsumeWindowFromMonitorLeft,
+            niri_ipc::Action::ConsumeWindowFromMonitorRight {} => {
+                Self::ConsumeWindowFromMonitorRight
+            }
             niri_ipc::Action::SwapWindowRight {} => Self::SwapWindowRight,
             niri_ipc::Action::SwapWindowLeft {} => Self::SwapWindowLeft,
             niri_ipc::Action::CenterColumn {} => Self::CenterColumn,
             niri_ipc::Action::CenterWindow { id: None } => Self::CenterWindow,
             niri_ipc::Action::CenterWindow { id: Some(id) } => Self::CenterWindowById(id),
+            niri_ipc::Action::CenterWindowVertically { id: None } => Self::CenterWindowVertically,
+            niri_ipc::Action::CenterWindowVertically { id: Some(id) } => {
+                Self::CenterWindowVerticallyById(id)
+            }
             niri_ipc::Action::CenterVisibleColumns {} => Self::CenterVisibleColumns,
+            niri_ipc::Action::DistributeColumnsEvenly {} => Self::DistributeColumnsEvenly,
             niri_ipc::Action::FocusWorkspaceDown {} => Self::FocusWorkspaceDown,
             niri_ipc::Action::FocusWorkspaceUp {} => Self::FocusWorkspaceUp,
-            niri_ipc::Action::FocusWorkspace { reference } => {
-                Self::FocusWorkspace(WorkspaceReference::from(reference))
-            }
+            niri_ipc::Action::FocusWorkspace {
+                reference,
+                create_if_missing,
+            } => Self::FocusWorkspace(WorkspaceReference::from(reference), create_if_missing),
             niri_ipc::Action::FocusWorkspacePrevious {} => Self::FocusWorkspacePrevious,
             niri_ipc::Action::MoveWindowToWorkspaceDown { focus } => {
                 Self::MoveWindowToWorkspaceDown(focus)
@@ -530,6 +795,9 @@ impl From<niri_ipc::Action> for Action {
                 reference: WorkspaceReference::from(reference),
                 focus,
             },
+            niri_ipc::Action::MoveWindowToWorkspacePrevious { focus } => {
+                Self::MoveWindowToWorkspacePrevious(focus)
+            }
             niri_ipc::Action::MoveColumnToWorkspaceDown { focus } => {
                 Self::MoveColumnToWorkspaceDown(focus)
             }
@@ -539,6 +807,9 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::MoveColumnToWorkspace { reference, focus } => {
                 Self::MoveColumnToWorkspace(WorkspaceReference::from(reference), focus)
             }
+            niri_ipc::Action::MoveColumnToWorkspacePrevious { focus } => {
+                Self::MoveColumnToWorkspacePrevious(focus)
+            }
             niri_ipc::Action::MoveWorkspaceDown {} => Self::MoveWorkspaceDown,
             niri_ipc::Action::MoveWorkspaceUp {} => Self::MoveWorkspaceUp,
             niri_ipc::Action::SetWorkspaceName {
@@ -562,7 +833,14 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::FocusMonitorUp {} => Self::FocusMonitorUp,
             niri_ipc::Action::FocusMonitorPrevious {} => Self::FocusMonitorPrevious,
             niri_ipc::Action::FocusMonitorNext {} => Self::FocusMonitorNext,
+            niri_ipc::Action::FocusMonitorLeftWrap {} => Self::FocusMonitorLeftWrap,
+            niri_ipc::Action::FocusMonitorRightWrap {} => Self::FocusMonitorRightWrap,
+            niri_ipc::Action::FocusMonitorDownWrap {} => Self::FocusMonitorDownWrap,
+            niri_ipc::Action::FocusMonitorUpWrap {} => Self::FocusMonitorUpWrap,
             niri_ipc::Action::FocusMonitor { output } => Self::FocusMonitor(output),
+            niri_ipc::Action::FocusMonitorByWorkspace { workspace } => {
+                Self::FocusMonitorByWorkspace(workspace)
+            }
             niri_ipc::Action::MoveWindowToMonitorLeft {} => Self::MoveWindowToMonitorLeft,
             niri_ipc::Action::MoveWindowToMonitorRight {} => Self::MoveWindowToMonitorRight,
             niri_ipc::Action::MoveWindowToMonitorDown {} => Self::MoveWindowToMonitorDown,
@@ -576,6 +854,21 @@ impl From<niri_ipc::Action> for Action {
                 id: Some(id),
                 output,
             } => Self::MoveWindowToMonitorById { id, output },
+            niri_ipc::Action::MoveWindowToLastOutput { id: None } => Self::MoveWindowToLastOutput,
+            niri_ipc::Action::MoveWindowToLastOutput { id: Some(id) } => {
+                Self::MoveWindowToLastOutputById(id)
+            }
+            niri_ipc::Action::MoveWindowToMonitorWorkspace {
+                output,
+                workspace,
+                focus,
+                create_if_missing,
+            } => Self::MoveWindowToMonitorWorkspace(
+                output,
+                WorkspaceReference::from(workspace),
+                focus,
+                create_if_missing,
+            ),
             niri_ipc::Action::MoveColumnToMonitorLeft {} => Self::MoveColumnToMonitorLeft,
             niri_ipc::Action::MoveColumnToMonitorRight {} => Self::MoveColumnToMonitorRight,
             niri_ipc::Action::MoveColumnToMonitorDown {} => Self::MoveColumnToMonitorDown,
@@ -583,6 +876,8 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::MoveColumnToMonitorPrevious {} => Self::MoveColumnToMonitorPrevious,
             niri_ipc::Action::MoveColumnToMonitorNext {} => Self::MoveColumnToMonitorNext,
             niri_ipc::Action::MoveColumnToMonitor { output } => Self::MoveColumnToMonitor(output),
+            niri_ipc::Action::SwapColumnToMonitorLeft {} => Self::SwapColumnToMonitorLeft,
+            niri_ipc::Action::SwapColumnToMonitorRight {} => Self::SwapColumnToMonitorRight,
             niri_ipc::Action::SetWindowWidth { id: None, change } => Self::SetWindowWidth(change),
             niri_ipc::Action::SetWindowWidth {
                 id: Some(id),
@@ -595,6 +890,12 @@ impl From<niri_ipc::Action> for Action {
             } => Self::SetWindowHeightById { id, change },
             niri_ipc::Action::ResetWindowHeight { id: None } => Self::ResetWindowHeight,
             niri_ipc::Action::ResetWindowHeight { id: Some(id) } => Self::ResetWindowHeightById(id),
+            niri_ipc::Action::FillWindowHeightInColumn { id: None } => {
+                Self::FillWindowHeightInColumn
+            }
+            niri_ipc::Action::FillWindowHeightInColumn { id: Some(id) } => {
+                Self::FillWindowHeightInColumnById(id)
+            }
             niri_ipc::Action::SwitchPresetColumnWidth {} => Self::SwitchPresetColumnWidth,
             niri_ipc::Action::SwitchPresetColumnWidthBack {} => Self::SwitchPresetColumnWidthBack,
             niri_ipc::Action::SwitchPresetWindowWidth { id: None } => Self::SwitchPresetWindowWidth,
@@ -620,13 +921,33 @@ impl From<niri_ipc::Action> for Action {
                 Self::SwitchPresetWindowHeightBackById(id)
             }
             niri_ipc::Action::MaximizeColumn {} => Self::MaximizeColumn,
+            niri_ipc::Action::ToggleMaximizeColumnWithGroup {} => {
+                Self::ToggleMaximizeColumnWithGroup
+            }
+            niri_ipc::Action::SetGroupDisplayMode { mode } => Self::SetGroupDisplayMode(mode),
+            niri_ipc::Action::ToggleGroupDisplayMode {} => Self::ToggleGroupDisplayMode,
             niri_ipc::Action::MaximizeWindowToEdges { id: None } => Self::MaximizeWindowToEdges,
             niri_ipc::Action::MaximizeWindowToEdges { id: Some(id) } => {
                 Self::MaximizeWindowToEdgesById(id)
             }
+            niri_ipc::Action::ToggleMaximizeWindowToEdges { id: None } => {
+                Self::ToggleMaximizeWindowToEdges
+            }
+            niri_ipc::Action::ToggleMaximizeWindowToEdges { id: Some(id) } => {
+                Self::ToggleMaximizeWindowToEdgesById(id)
+            }
             niri_ipc::Action::SetColumnWidth { change } => Self::SetColumnWidth(change),
             niri_ipc::Action::ExpandColumnToAvailableWidth {} => Self::ExpandColumnToAvailableWidth,
+            niri_ipc::Action::ToggleColumnExpandedWidth {} => Self::ToggleColumnExpandedWidth,
+            niri_ipc::Action::AlignColumnWidthToNeighborLeft {} => {
+                Self::AlignColumnWidthToNeighborLeft
+            }
+            niri_ipc::Action::AlignColumnWidthToNeighborRight {} => {
+                Self::AlignColumnWidthToNeighborRight
+            }
+            niri_ipc::Action::ToggleZeroGaps {} => Self::ToggleZeroGaps,
             niri_ipc::Action::SwitchLayout { layout } => Self::SwitchLayout(layout),
+            niri_ipc::Action::ToggleNumLock {} => Self::ToggleNumLock,
             niri_ipc::Action::ShowHotkeyOverlay {} => Self::ShowHotkeyOverlay,
             niri_ipc::Action::MoveWorkspaceToMonitorLeft {} => Self::MoveWorkspaceToMonitorLeft,
             niri_ipc::Action::MoveWorkspaceToMonitorRight {} => Self::MoveWorkspaceToMonitorRight,
@@ -638,14 +959,17 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::MoveWorkspaceToIndex {
                 index,
                 reference: Some(reference),
+                compact,
             } => Self::MoveWorkspaceToIndexByRef {
                 new_idx: index,
                 reference: WorkspaceReference::from(reference),
+                compact,
             },
             niri_ipc::Action::MoveWorkspaceToIndex {
                 index,
                 reference: None,
-            } => Self::MoveWorkspaceToIndex(index),
+                compact,
+            } => Self::MoveWorkspaceToIndex(index, compact),
             niri_ipc::Action::MoveWorkspaceToMonitor {
                 output,
                 reference: Some(reference),
@@ -658,9 +982,19 @@ impl From<niri_ipc::Action> for Action {
                 reference: None,
             } => Self::MoveWorkspaceToMonitor(output),
             niri_ipc::Action::MoveWorkspaceToMonitorNext {} => Self::MoveWorkspaceToMonitorNext,
+            niri_ipc::Action::MoveWorkspaceToMonitorUnderMouse {} => {
+                Self::MoveWorkspaceToMonitorUnderMouse
+            }
+            niri_ipc::Action::ToggleMirrorToMonitor { output } => {
+                Self::ToggleMirrorToMonitor(output)
+            }
+            niri_ipc::Action::MirrorMonitor { source, target } => {
+                Self::MirrorMonitor { source, target }
+            }
             niri_ipc::Action::ToggleDebugTint {} => Self::ToggleDebugTint,
             niri_ipc::Action::DebugToggleOpaqueRegions {} => Self::DebugToggleOpaqueRegions,
             niri_ipc::Action::DebugToggleDamage {} => Self::DebugToggleDamage,
+            niri_ipc::Action::DebugToggleRenderHeatmap {} => Self::DebugToggleRenderHeatmap,
             niri_ipc::Action::ToggleWindowFloating { id: None } => Self::ToggleWindowFloating,
             niri_ipc::Action::ToggleWindowFloating { id: Some(id) } => {
                 Self::ToggleWindowFloatingById(id)
@@ -674,10 +1008,16 @@ impl From<niri_ipc::Action> for Action {
                 Self::MoveWindowToTilingById(id)
             }
             niri_ipc::Action::FocusFloating {} => Self::FocusFloating,
+            niri_ipc::Action::FocusNextFloatingWindow {} => Self::FocusNextFloatingWindow,
+            niri_ipc::Action::FocusPreviousFloatingWindow {} => Self::FocusPreviousFloatingWindow,
             niri_ipc::Action::FocusTiling {} => Self::FocusTiling,
             niri_ipc::Action::SwitchFocusBetweenFloatingAndTiling {} => {
                 Self::SwitchFocusBetweenFloatingAndTiling
             }
+            niri_ipc::Action::FocusFloatingOrTilingUnderMouse {} => {
+                Self::FocusFloatingOrTilingUnderMouse
+            }
+            niri_ipc::Action::ToggleFloatingResizeMode {} => Self::ToggleFloatingResizeMode,
             niri_ipc::Action::MoveFloatingWindow { id, x, y } => {
                 Self::MoveFloatingWindowById { id, x, y }
             }
@@ -685,6 +1025,17 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::ToggleWindowRuleOpacity { id: Some(id) } => {
                 Self::ToggleWindowRuleOpacityById(id)
             }
+            niri_ipc::Action::SetWindowOpacity { id: None, opacity } => {
+                Self::SetWindowOpacity(opacity)
+            }
+            niri_ipc::Action::SetWindowOpacity {
+                id: Some(id),
+                opacity,
+            } => Self::SetWindowOpacityById { id, opacity },
+            niri_ipc::Action::ToggleServerDecorations { id: None } => Self::ToggleServerDecorations,
+            niri_ipc::Action::ToggleServerDecorations { id: Some(id) } => {
+                Self::ToggleServerDecorationsById(id)
+            }
             niri_ipc::Action::SetDynamicCastWindow { id: None } => Self::SetDynamicCastWindow,
             niri_ipc::Action::SetDynamicCastWindow { id: Some(id) } => {
                 Self::SetDynamicCastWindowById(id)
@@ -694,12 +1045,18 @@ impl From<niri_ipc::Action> for Action {
             }
             niri_ipc::Action::ClearDynamicCastTarget {} => Self::ClearDynamicCastTarget,
             niri_ipc::Action::ToggleOverview {} => Self::ToggleOverview,
+            niri_ipc::Action::ToggleOverviewFocusedMonitor {} => Self::ToggleOverviewFocusedMonitor,
             niri_ipc::Action::OpenOverview {} => Self::OpenOverview,
             niri_ipc::Action::CloseOverview {} => Self::CloseOverview,
             niri_ipc::Action::ToggleWindowUrgent { id } => Self::ToggleWindowUrgent(id),
+            niri_ipc::Action::ToggleFocusedWindowUrgent {} => Self::ToggleFocusedWindowUrgent,
             niri_ipc::Action::SetWindowUrgent { id } => Self::SetWindowUrgent(id),
             niri_ipc::Action::UnsetWindowUrgent { id } => Self::UnsetWindowUrgent(id),
             niri_ipc::Action::LoadConfigFile {} => Self::LoadConfigFile,
+            niri_ipc::Action::ReloadBinds {} => Self::ReloadBinds,
+            niri_ipc::Action::RestoreClipboardHistoryEntry { index } => {
+                Self::RestoreClipboardHistoryEntry(index)
+            }
         }
     }
 }
@@ -709,6 +1066,7 @@ pub enum WorkspaceReference {
     Id(u64),
     Index(u8),
     Name(String),
+    Uuid(String),
 }
 
 impl From<WorkspaceReferenceArg> for WorkspaceReference {
@@ -717,6 +1075,7 @@ impl From<WorkspaceReferenceArg> for WorkspaceReference {
             WorkspaceReferenceArg::Id(id) => Self::Id(id),
             WorkspaceReferenceArg::Index(i) => Self::Index(i),
             WorkspaceReferenceArg::Name(n) => Self::Name(n),
+            WorkspaceReferenceArg::Uuid(uuid) => Self::Uuid(uuid),
         }
     }
 }
@@ -850,8 +1209,10 @@ where
             .parse::<Key>()
             .map_err(|e| DecodeError::conversion(&node.node_name, e.wrap_err("invalid keybind")))?;
 
-        let mut repeat = true;
+        let mut repeat = None;
         let mut cooldown = None;
+        let mut hold = None;
+        let mut hold_node = None;
         let mut allow_when_locked = false;
         let mut allow_when_locked_node = None;
         let mut allow_inhibiting = true;
@@ -859,13 +1220,19 @@ where
         for (name, val) in &node.properties {
             match &***name {
                 "repeat" => {
-                    repeat = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+                    repeat = Some(knuffel::traits::DecodeScalar::decode(val, ctx)?);
                 }
                 "cooldown-ms" => {
                     cooldown = Some(Duration::from_millis(
                         knuffel::traits::DecodeScalar::decode(val, ctx)?,
                     ));
                 }
+                "hold-ms" => {
+                    hold = Some(Duration::from_millis(
+                        knuffel::traits::DecodeScalar::decode(val, ctx)?,
+                    ));
+                    hold_node = Some(name);
+                }
                 "allow-when-locked" => {
                     allow_when_locked = knuffel::traits::DecodeScalar::decode(val, ctx)?;
                     allow_when_locked_node = Some(name);
@@ -886,6 +1253,17 @@ where
             }
         }
 
+        // Holding is only tracked for keyboard keys.
+        if let Some(node) = hold_node
+            && !matches!(key.trigger, Trigger::Keysym(_))
+        {
+            ctx.emit_error(DecodeError::unexpected(
+                node,
+                "property",
+                "hold-ms can only be set on keyboard binds",
+            ));
+        }
+
         let mut children = node.children();
 
         // If the action is invalid but the key is fine, we still want to return something.
@@ -896,6 +1274,7 @@ where
             action: Action::Spawn(vec![]),
             repeat: true,
             cooldown: None,
+            hold: None,
             allow_when_locked: false,
             allow_inhibiting: true,
             hotkey_overlay_title: None,
@@ -912,12 +1291,14 @@ where
             match Action::decode_node(child, ctx) {
                 Ok(action) => {
                     if !matches!(action, Action::Spawn(_) | Action::SpawnSh(_))
+                        && !action.allowed_when_locked()
                         && let Some(node) = allow_when_locked_node
                     {
                         ctx.emit_error(DecodeError::unexpected(
                             node,
                             "property",
-                            "allow-when-locked can only be set on spawn binds",
+                            "allow-when-locked can only be set on spawn binds and actions \
+                             that are always allowed when locked",
                         ));
                     }
 
@@ -927,11 +1308,16 @@ where
                         allow_inhibiting = false;
                     }
 
+                    // Only keyboard keys auto-repeat, scroll ticks and mouse clicks are discrete.
+                    let repeat = matches!(key.trigger, Trigger::Keysym(_))
+                        && repeat.unwrap_or_else(|| action.default_repeat());
+
                     Ok(Self {
                         key,
-                        action,
                         repeat,
+                        action,
                         cooldown,
+                        hold,
                         allow_when_locked,
                         allow_inhibiting,
                         hotkey_overlay_title,
@@ -1054,6 +1440,15 @@ impl FromStr for Key {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Config;
+
+    #[track_caller]
+    fn parse_action(action: &str) -> Action {
+        let config = Config::parse_mem(&format!("binds {{ Mod+T {{ {action}; }} }}"))
+            .map_err(miette::Report::new)
+            .unwrap();
+        config.binds.0[0].action.clone()
+    }
 
     #[test]
     fn parse_xf86_screensaver() {
@@ -1112,4 +1507,386 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn parse_move_to_workspace_previous() {
+        assert_eq!(
+            parse_action("move-window-to-workspace-previous"),
+            Action::MoveWindowToWorkspacePrevious(true),
+        );
+        assert_eq!(
+            parse_action("move-window-to-workspace-previous focus=false"),
+            Action::MoveWindowToWorkspacePrevious(false),
+        );
+        assert_eq!(
+            parse_action("move-column-to-workspace-previous"),
+            Action::MoveColumnToWorkspacePrevious(true),
+        );
+        assert_eq!(
+            parse_action("move-column-to-workspace-previous focus=false"),
+            Action::MoveColumnToWorkspacePrevious(false),
+        );
+    }
+
+    #[test]
+    fn parse_toggle_focused_window_urgent() {
+        assert_eq!(
+            parse_action("toggle-focused-window-urgent"),
+            Action::ToggleFocusedWindowUrgent,
+        );
+    }
+
+    #[test]
+    fn allow_when_locked_on_allowed_action() {
+        let config =
+            Config::parse_mem("binds { Mod+P allow-when-locked=true { power-off-monitors; } }")
+                .map_err(miette::Report::new)
+                .unwrap();
+        let bind = &config.binds.0[0];
+        assert_eq!(bind.action, Action::PowerOffMonitors);
+        assert!(bind.allow_when_locked);
+    }
+
+    #[test]
+    fn allow_when_locked_on_forbidden_action() {
+        let result = Config::parse_mem("binds { Mod+Q allow-when-locked=true { close-window; } }");
+        assert!(result.is_err());
+    }
+
+    fn parse_repeat(bind: &str) -> bool {
+        let config = Config::parse_mem(&format!("binds {{ {bind} }}"))
+            .map_err(miette::Report::new)
+            .unwrap();
+        config.binds.0[0].repeat
+    }
+
+    #[test]
+    fn toggle_actions_default_to_no_repeat() {
+        assert!(!parse_repeat("Mod+O { toggle-overview; }"));
+        assert!(!parse_repeat("Mod+F { fullscreen-window; }"));
+        assert!(!parse_repeat("Mod+V { toggle-window-floating; }"));
+        assert!(!parse_repeat("Mod+Q { close-window; }"));
+    }
+
+    #[test]
+    fn movement_actions_default_to_repeat() {
+        assert!(parse_repeat("Mod+H { focus-column-left; }"));
+        assert!(parse_repeat("Mod+Ctrl+H { move-column-left; }"));
+        assert!(parse_repeat(r#"Mod+Minus { set-column-width "-10%"; }"#));
+    }
+
+    #[test]
+    fn explicit_repeat_overrides_default() {
+        assert!(parse_repeat("Mod+O repeat=true { toggle-overview; }"));
+        assert!(!parse_repeat("Mod+H repeat=false { focus-column-left; }"));
+    }
+
+    #[test]
+    fn parse_focus_workspace_create_if_missing() {
+        assert_eq!(
+            parse_action(r#"focus-workspace "email""#),
+            Action::FocusWorkspace(WorkspaceReference::Name("email".into()), false),
+        );
+        assert_eq!(
+            parse_action(r#"focus-workspace "email" create-if-missing=true"#),
+            Action::FocusWorkspace(WorkspaceReference::Name("email".into()), true),
+        );
+        assert_eq!(
+            parse_action("focus-workspace 2 create-if-missing=true"),
+            Action::FocusWorkspace(WorkspaceReference::Index(2), true),
+        );
+    }
+
+    #[test]
+    fn parse_output_scale_actions() {
+        assert_eq!(
+            parse_action("set-output-scale 1.5"),
+            Action::SetOutputScale(FloatOrInt(1.5)),
+        );
+        assert_eq!(
+            parse_action("set-output-scale 2"),
+            Action::SetOutputScale(FloatOrInt(2.)),
+        );
+        assert_eq!(
+            parse_action("cycle-output-scale 1 1.25 1.5"),
+            Action::CycleOutputScale(vec![FloatOrInt(1.), FloatOrInt(1.25), FloatOrInt(1.5)]),
+        );
+    }
+
+    #[test]
+    fn parse_output_transform_actions() {
+        assert_eq!(
+            parse_action("rotate-output \"90\""),
+            Action::RotateOutput(Transform::_90),
+        );
+        assert_eq!(
+            parse_action("rotate-output \"flipped-270\""),
+            Action::RotateOutput(Transform::Flipped270),
+        );
+        assert_eq!(
+            parse_action("cycle-output-transform"),
+            Action::CycleOutputTransform,
+        );
+    }
+
+    #[test]
+    fn parse_screenshot_to_command() {
+        assert_eq!(
+            parse_action(r#"screenshot-to-command "tesseract" "-" "-""#),
+            Action::ScreenshotToCommand(
+                vec!["tesseract".into(), "-".into(), "-".into()],
+                ScreenshotTargetArg::Screen,
+                true,
+            ),
+        );
+        assert_eq!(
+            parse_action(r#"screenshot-to-command "uploader" target="window" show-pointer=false"#),
+            Action::ScreenshotToCommand(
+                vec!["uploader".into()],
+                ScreenshotTargetArg::Window,
+                false
+            ),
+        );
+    }
+
+    #[test]
+    fn parse_backlight_actions() {
+        assert_eq!(
+            parse_action("change-backlight -10"),
+            Action::ChangeBacklight(-10),
+        );
+        assert_eq!(parse_action("set-backlight 50"), Action::SetBacklight(50));
+    }
+
+    #[test]
+    fn parse_set_window_opacity() {
+        assert_eq!(
+            parse_action("set-window-opacity 0.8"),
+            Action::SetWindowOpacity(0.8),
+        );
+        assert_eq!(
+            Action::from(niri_ipc::Action::SetWindowOpacity {
+                id: Some(3),
+                opacity: 0.5,
+            }),
+            Action::SetWindowOpacityById {
+                id: 3,
+                opacity: 0.5,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_toggle_num_lock() {
+        assert_eq!(parse_action("toggle-num-lock"), Action::ToggleNumLock);
+        assert!(Action::ToggleNumLock.allowed_when_locked());
+        assert!(!Action::ToggleNumLock.default_repeat());
+    }
+
+    #[test]
+    fn parse_move_column_by_offset() {
+        assert_eq!(
+            parse_action("move-column-by-offset -2"),
+            Action::MoveColumnByOffset(-2)
+        );
+    }
+
+    #[test]
+    fn parse_fill_window_height_in_column() {
+        assert_eq!(
+            parse_action("fill-window-height-in-column"),
+            Action::FillWindowHeightInColumn
+        );
+    }
+
+    #[test]
+    fn parse_spawn_on_workspace() {
+        assert_eq!(
+            parse_action(r#"spawn-on-workspace "alacritty" "-e" "htop" workspace="monitoring""#),
+            Action::SpawnOnWorkspace(
+                vec![
+                    String::from("alacritty"),
+                    String::from("-e"),
+                    String::from("htop"),
+                ],
+                String::from("monitoring"),
+            )
+        );
+
+        assert!(
+            Config::parse_mem(r#"binds { Mod+T { spawn-on-workspace "alacritty"; } }"#).is_err()
+        );
+    }
+
+    #[test]
+    fn parse_move_workspace_to_index_compact() {
+        assert_eq!(
+            parse_action("move-workspace-to-index 2"),
+            Action::MoveWorkspaceToIndex(2, false),
+        );
+        assert_eq!(
+            parse_action("move-workspace-to-index 2 compact=true"),
+            Action::MoveWorkspaceToIndex(2, true),
+        );
+    }
+
+    #[test]
+    fn parse_focus_monitor_by_workspace() {
+        assert_eq!(
+            parse_action(r#"focus-monitor-by-workspace "chat""#),
+            Action::FocusMonitorByWorkspace(String::from("chat")),
+        );
+    }
+
+    #[test]
+    fn parse_toggle_overview_focused_monitor() {
+        assert_eq!(
+            parse_action("toggle-overview-focused-monitor"),
+            Action::ToggleOverviewFocusedMonitor,
+        );
+        assert!(!Action::ToggleOverviewFocusedMonitor.default_repeat());
+    }
+
+    #[test]
+    fn parse_power_off_on_monitor() {
+        assert_eq!(
+            parse_action(r#"power-off-monitor "HDMI-A-1""#),
+            Action::PowerOffMonitor(String::from("HDMI-A-1")),
+        );
+        assert_eq!(
+            parse_action(r#"power-on-monitor "HDMI-A-1""#),
+            Action::PowerOnMonitor(String::from("HDMI-A-1")),
+        );
+    }
+
+    #[test]
+    fn parse_distribute_columns_evenly() {
+        assert_eq!(
+            parse_action("distribute-columns-evenly"),
+            Action::DistributeColumnsEvenly,
+        );
+    }
+
+    #[test]
+    fn parse_hold_ms() {
+        let config = Config::parse_mem("binds { Mod+Shift+E hold-ms=500 { quit; } }").unwrap();
+        let bind = &config.binds.0[0];
+        assert_eq!(bind.hold, Some(Duration::from_millis(500)));
+        assert_eq!(bind.cooldown, None);
+
+        assert!(Config::parse_mem("binds { Mod+WheelScrollDown hold-ms=500 { quit; } }").is_err());
+    }
+
+    #[test]
+    fn parse_screenshot_each_output() {
+        assert_eq!(
+            parse_action("screenshot-each-output"),
+            Action::ScreenshotEachOutput(true, true, None),
+        );
+        assert_eq!(
+            parse_action("screenshot-each-output show-pointer=false"),
+            Action::ScreenshotEachOutput(true, false, None),
+        );
+    }
+
+    #[test]
+    fn parse_move_window_to_monitor_workspace() {
+        assert_eq!(
+            parse_action(r#"move-window-to-monitor-workspace "HDMI-A-1" "chat""#),
+            Action::MoveWindowToMonitorWorkspace(
+                String::from("HDMI-A-1"),
+                WorkspaceReference::Name(String::from("chat")),
+                true,
+                false,
+            ),
+        );
+        assert_eq!(
+            parse_action(
+                r#"move-window-to-monitor-workspace "DP-2" 3 focus=false create-if-missing=true"#
+            ),
+            Action::MoveWindowToMonitorWorkspace(
+                String::from("DP-2"),
+                WorkspaceReference::Index(3),
+                false,
+                true,
+            ),
+        );
+    }
+
+    #[test]
+    fn parse_focus_spatial() {
+        assert_eq!(
+            parse_action(r#"focus-spatial "left""#),
+            Action::FocusSpatial(Direction::Left),
+        );
+        assert_eq!(
+            parse_action(r#"focus-spatial "down""#),
+            Action::FocusSpatial(Direction::Down),
+        );
+    }
+
+    #[test]
+    fn parse_toggle_maximize_column_with_group() {
+        assert_eq!(
+            parse_action("toggle-maximize-column-with-group"),
+            Action::ToggleMaximizeColumnWithGroup,
+        );
+    }
+
+    #[test]
+    fn parse_group_display_mode() {
+        assert_eq!(
+            parse_action(r#"set-group-display-mode "stacked""#),
+            Action::SetGroupDisplayMode(GroupDisplayMode::Stacked),
+        );
+        assert_eq!(
+            parse_action("toggle-group-display-mode"),
+            Action::ToggleGroupDisplayMode,
+        );
+    }
+
+    #[test]
+    fn scroll_binds_ignore_repeat() {
+        assert!(parse_repeat("Mod+H repeat=true { focus-column-left; }"));
+        assert!(!parse_repeat(
+            "Mod+WheelScrollDown { focus-workspace-down; }"
+        ));
+        assert!(!parse_repeat(
+            "Mod+TouchpadScrollUp repeat=true { focus-workspace-up; }"
+        ));
+        assert!(!parse_repeat(
+            "Mod+MouseMiddle repeat=true { focus-column-left; }"
+        ));
+
+        let config = Config::parse_mem(
+            "binds { Mod+WheelScrollUp cooldown-ms=150 { focus-workspace-up; } }",
+        )
+        .unwrap();
+        assert_eq!(config.binds.0[0].cooldown, Some(Duration::from_millis(150)));
+    }
+
+    #[test]
+    fn parse_move_workspace_to_monitor_under_mouse() {
+        assert_eq!(
+            parse_action("move-workspace-to-monitor-under-mouse"),
+            Action::MoveWorkspaceToMonitorUnderMouse,
+        );
+    }
+
+    #[test]
+    fn parse_focus_floating_or_tiling_under_mouse() {
+        assert_eq!(
+            parse_action("focus-floating-or-tiling-under-mouse"),
+            Action::FocusFloatingOrTilingUnderMouse,
+        );
+        // Mouse binds run the action as written.
+        let config = Config::parse_mem(
+            "binds { Mod+MouseMiddle { switch-focus-between-floating-and-tiling; } }",
+        )
+        .unwrap();
+        assert_eq!(
+            config.binds.0[0].action,
+            Action::SwitchFocusBetweenFloatingAndTiling,
+        );
+    }
 }
@@ -1,4 +1,7 @@
 use std::path::PathBuf;
+use std::str::FromStr;
+
+use miette::miette;
 
 use crate::utils::{Flag, MergeWith};
 
@@ -25,6 +28,7 @@ pub struct Debug {
     pub honor_xdg_activation_with_invalid_serial: bool,
     pub deactivate_unfocused_windows: bool,
     pub skip_cursor_only_updates_during_vrr: bool,
+    pub disabled_dbus_interfaces: Vec<DbusInterfaceName>,
 }
 
 #[derive(knuffel::Decode, Debug, Default, PartialEq, Eq)]
@@ -71,6 +75,8 @@ pub struct DebugPart {
     pub deactivate_unfocused_windows: Option<Flag>,
     #[knuffel(child)]
     pub skip_cursor_only_updates_during_vrr: Option<Flag>,
+    #[knuffel(children(name = "disable-dbus-interface"), unwrap(argument, str))]
+    pub disabled_dbus_interfaces: Vec<DbusInterfaceName>,
 }
 
 impl MergeWith<DebugPart> for Debug {
@@ -101,6 +107,8 @@ impl MergeWith<DebugPart> for Debug {
 
         self.ignored_drm_devices
             .extend(part.ignored_drm_devices.iter().cloned());
+        self.disabled_dbus_interfaces
+            .extend(part.disabled_dbus_interfaces.iter().copied());
     }
 }
 
@@ -109,3 +117,36 @@ pub enum PreviewRender {
     Screencast,
     ScreenCapture,
 }
+
+/// D-Bus interface that niri can serve, identified by its well-known bus name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbusInterfaceName {
+    ServiceChannel,
+    DisplayConfig,
+    ScreenSaver,
+    Introspect,
+    ScreenCast,
+    KeyboardMonitor,
+    KwinScreenshot2,
+    KwinColorPicker,
+    NiriConfig,
+}
+
+impl FromStr for DbusInterfaceName {
+    type Err = miette::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "org.gnome.Mutter.ServiceChannel" => Ok(Self::ServiceChannel),
+            "org.gnome.Mutter.DisplayConfig" => Ok(Self::DisplayConfig),
+            "org.freedesktop.ScreenSaver" => Ok(Self::ScreenSaver),
+            "org.gnome.Shell.Introspect" => Ok(Self::Introspect),
+            "org.gnome.Mutter.ScreenCast" => Ok(Self::ScreenCast),
+            "org.freedesktop.a11y.Manager" => Ok(Self::KeyboardMonitor),
+            "org.kde.KWin.ScreenShot2" => Ok(Self::KwinScreenshot2),
+            "org.kde.kwin.ColorPicker" => Ok(Self::KwinColorPicker),
+            "org.niri.Config" => Ok(Self::NiriConfig),
+            _ => Err(miette!("unknown D-Bus interface: {s}")),
+        }
+    }
+}
@@ -1,21 +1,29 @@
-use crate::FloatOrInt;
-use crate::utils::MergeWith;
+use knuffel::errors::DecodeError;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+use crate::utils::{MergeWith, expect_only_children};
+use crate::{Action, FloatOrInt};
+
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Gestures {
     pub dnd_edge_view_scroll: DndEdgeViewScroll,
     pub dnd_edge_workspace_switch: DndEdgeWorkspaceSwitch,
+    pub workspace_switch: WorkspaceSwitchGesture,
     pub hot_corners: HotCorners,
+    pub swipe: SwipeBinds,
 }
 
-#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
 pub struct GesturesPart {
     #[knuffel(child)]
     pub dnd_edge_view_scroll: Option<DndEdgeViewScrollPart>,
     #[knuffel(child)]
     pub dnd_edge_workspace_switch: Option<DndEdgeWorkspaceSwitchPart>,
     #[knuffel(child)]
+    pub workspace_switch: Option<WorkspaceSwitchGesturePart>,
+    #[knuffel(child)]
     pub hot_corners: Option<HotCorners>,
+    #[knuffel(child)]
+    pub swipe: Option<SwipeBinds>,
 }
 
 impl MergeWith<GesturesPart> for Gestures {
@@ -24,8 +32,13 @@ impl MergeWith<GesturesPart> for Gestures {
             (self, part),
             dnd_edge_view_scroll,
             dnd_edge_workspace_switch,
+            workspace_switch,
         );
         merge_clone!((self, part), hot_corners);
+
+        if let Some(swipe) = &part.swipe {
+            self.swipe.merge_with(swipe);
+        }
     }
 }
 
@@ -97,6 +110,35 @@ impl MergeWith<DndEdgeWorkspaceSwitchPart> for DndEdgeWorkspaceSwitch {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorkspaceSwitchGesture {
+    pub sensitivity: f64,
+    pub min_delta: f64,
+}
+
+impl Default for WorkspaceSwitchGesture {
+    fn default() -> Self {
+        Self {
+            sensitivity: 1.,
+            min_delta: 0.5,
+        }
+    }
+}
+
+#[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
+pub struct WorkspaceSwitchGesturePart {
+    #[knuffel(child, unwrap(argument))]
+    pub sensitivity: Option<FloatOrInt<0, 10>>,
+    #[knuffel(child, unwrap(argument))]
+    pub min_delta: Option<FloatOrInt<0, 1>>,
+}
+
+impl MergeWith<WorkspaceSwitchGesturePart> for WorkspaceSwitchGesture {
+    fn merge_with(&mut self, part: &WorkspaceSwitchGesturePart) {
+        merge!((self, part), sensitivity, min_delta);
+    }
+}
+
 #[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct HotCorners {
     #[knuffel(child)]
@@ -110,3 +152,124 @@ pub struct HotCorners {
     #[knuffel(child)]
     pub bottom_right: bool,
 }
+
+/// Actions bound to touchpad swipes with three or four fingers.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct SwipeBinds {
+    #[knuffel(child)]
+    pub three_finger_up: Option<SwipeAction>,
+    #[knuffel(child)]
+    pub three_finger_down: Option<SwipeAction>,
+    #[knuffel(child)]
+    pub three_finger_left: Option<SwipeAction>,
+    #[knuffel(child)]
+    pub three_finger_right: Option<SwipeAction>,
+    #[knuffel(child)]
+    pub four_finger_up: Option<SwipeAction>,
+    #[knuffel(child)]
+    pub four_finger_down: Option<SwipeAction>,
+    #[knuffel(child)]
+    pub four_finger_left: Option<SwipeAction>,
+    #[knuffel(child)]
+    pub four_finger_right: Option<SwipeAction>,
+}
+
+impl MergeWith<Self> for SwipeBinds {
+    fn merge_with(&mut self, part: &Self) {
+        merge_clone_opt!(
+            (self, part),
+            three_finger_up,
+            three_finger_down,
+            three_finger_left,
+            three_finger_right,
+            four_finger_up,
+            four_finger_down,
+            four_finger_left,
+            four_finger_right,
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl SwipeBinds {
+    /// Returns whether any swipe with this many fingers is bound.
+    pub fn has_any(&self, fingers: u32) -> bool {
+        [
+            SwipeDirection::Up,
+            SwipeDirection::Down,
+            SwipeDirection::Left,
+            SwipeDirection::Right,
+        ]
+        .into_iter()
+        .any(|direction| self.action(fingers, direction).is_some())
+    }
+
+    pub fn action(&self, fingers: u32, direction: SwipeDirection) -> Option<&Action> {
+        let action = match (fingers, direction) {
+            (3, SwipeDirection::Up) => &self.three_finger_up,
+            (3, SwipeDirection::Down) => &self.three_finger_down,
+            (3, SwipeDirection::Left) => &self.three_finger_left,
+            (3, SwipeDirection::Right) => &self.three_finger_right,
+            (4, SwipeDirection::Up) => &self.four_finger_up,
+            (4, SwipeDirection::Down) => &self.four_finger_down,
+            (4, SwipeDirection::Left) => &self.four_finger_left,
+            (4, SwipeDirection::Right) => &self.four_finger_right,
+            _ => return None,
+        };
+        action.as_ref().map(|action| &action.0)
+    }
+}
+
+impl SwipeDirection {
+    /// Returns the direction of a swipe that moved by this much in total.
+    ///
+    /// The deltas are in the direction that the fingers moved, so negative `dy` is up.
+    pub fn from_delta(dx: f64, dy: f64) -> Self {
+        if dx.abs() > dy.abs() {
+            if dx < 0. { Self::Left } else { Self::Right }
+        } else if dy < 0. {
+            Self::Up
+        } else {
+            Self::Down
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwipeAction(pub Action);
+
+impl<S> knuffel::Decode<S> for SwipeAction
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        expect_only_children(node, ctx);
+
+        let mut children = node.children();
+        let Some(child) = children.next() else {
+            return Err(DecodeError::missing(
+                node,
+                "expected an action for this swipe",
+            ));
+        };
+        for unwanted_child in children {
+            ctx.emit_error(DecodeError::unexpected(
+                unwanted_child,
+                "node",
+                "only one action is allowed per swipe",
+            ));
+        }
+
+        Action::decode_node(child, ctx).map(Self)
+    }
+}
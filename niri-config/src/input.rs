@@ -1,11 +1,13 @@
 use std::str::FromStr;
+use std::time::Duration;
 
+use knuffel::errors::DecodeError;
 use miette::miette;
 use smithay::input::keyboard::XkbConfig;
 use smithay::reexports::input;
 
 use crate::FloatOrInt;
-use crate::binds::Modifiers;
+use crate::binds::{Action, Modifiers};
 use crate::utils::{Flag, MergeWith, Percent};
 
 #[derive(Debug, Default, PartialEq)]
@@ -21,8 +23,10 @@ pub struct Input {
     pub warp_mouse_to_focus: Option<WarpMouseToFocus>,
     pub focus_follows_mouse: Option<FocusFollowsMouse>,
     pub workspace_auto_back_and_forth: bool,
+    pub focus_column_on_gap_click: bool,
     pub mod_key: Option<ModKey>,
     pub mod_key_nested: Option<ModKey>,
+    pub mod_double_tap_action: Option<ModDoubleTapAction>,
 }
 
 #[derive(knuffel::Decode, Debug, Default, PartialEq)]
@@ -49,10 +53,14 @@ pub struct InputPart {
     pub focus_follows_mouse: Option<FocusFollowsMouse>,
     #[knuffel(child)]
     pub workspace_auto_back_and_forth: Option<Flag>,
+    #[knuffel(child)]
+    pub focus_column_on_gap_click: Option<Flag>,
     #[knuffel(child, unwrap(argument, str))]
     pub mod_key: Option<ModKey>,
     #[knuffel(child, unwrap(argument, str))]
     pub mod_key_nested: Option<ModKey>,
+    #[knuffel(child)]
+    pub mod_double_tap_action: Option<ModDoubleTapAction>,
 }
 
 impl MergeWith<InputPart> for Input {
@@ -62,6 +70,7 @@ impl MergeWith<InputPart> for Input {
             keyboard,
             disable_power_key_handling,
             workspace_auto_back_and_forth,
+            focus_column_on_gap_click,
         );
 
         merge_clone!(
@@ -80,6 +89,7 @@ impl MergeWith<InputPart> for Input {
             focus_follows_mouse,
             mod_key,
             mod_key_nested,
+            mod_double_tap_action,
         );
     }
 }
@@ -90,7 +100,12 @@ pub struct Keyboard {
     pub repeat_delay: u16,
     pub repeat_rate: u8,
     pub track_layout: TrackLayout,
+    pub track_layout_per_app: bool,
     pub numlock: bool,
+    pub floating_resize_step: u16,
+    pub disabled_on_external_keyboard: bool,
+    /// Names of the keyboards that count as external, instead of guessing.
+    pub external_keyboards: Vec<String>,
 }
 
 impl Default for Keyboard {
@@ -101,7 +116,11 @@ impl Default for Keyboard {
             repeat_delay: 600,
             repeat_rate: 25,
             track_layout: Default::default(),
+            track_layout_per_app: false,
             numlock: Default::default(),
+            floating_resize_step: 20,
+            disabled_on_external_keyboard: false,
+            external_keyboards: Vec::new(),
         }
     }
 }
@@ -117,13 +136,36 @@ pub struct KeyboardPart {
     #[knuffel(child, unwrap(argument))]
     pub track_layout: Option<TrackLayout>,
     #[knuffel(child)]
+    pub track_layout_per_app: Option<Flag>,
+    #[knuffel(child)]
     pub numlock: Option<Flag>,
+    #[knuffel(child, unwrap(argument))]
+    pub floating_resize_step: Option<u16>,
+    #[knuffel(child)]
+    pub disabled_on_external_keyboard: Option<Flag>,
+    #[knuffel(children(name = "external-keyboard"), unwrap(argument))]
+    pub external_keyboards: Vec<String>,
 }
 
 impl MergeWith<KeyboardPart> for Keyboard {
     fn merge_with(&mut self, part: &KeyboardPart) {
-        merge_clone!((self, part), xkb, repeat_delay, repeat_rate, track_layout);
-        merge!((self, part), numlock);
+        merge_clone!(
+            (self, part),
+            xkb,
+            repeat_delay,
+            repeat_rate,
+            track_layout,
+            floating_resize_step,
+        );
+        merge!(
+            (self, part),
+            track_layout_per_app,
+            numlock,
+            disabled_on_external_keyboard,
+        );
+
+        self.external_keyboards
+            .extend(part.external_keyboards.iter().cloned());
     }
 }
 
@@ -205,6 +247,8 @@ pub struct Touchpad {
     pub accel_speed: FloatOrInt<-1, 1>,
     #[knuffel(child, unwrap(argument, str))]
     pub accel_profile: Option<AccelProfile>,
+    #[knuffel(child)]
+    pub custom_accel: Option<CustomAccel>,
     #[knuffel(child, unwrap(argument, str))]
     pub scroll_method: Option<ScrollMethod>,
     #[knuffel(child, unwrap(argument))]
@@ -218,6 +262,8 @@ pub struct Touchpad {
     #[knuffel(child)]
     pub disabled_on_external_mouse: bool,
     #[knuffel(child)]
+    pub disabled_on_external_keyboard: bool,
+    #[knuffel(child)]
     pub middle_emulation: bool,
     #[knuffel(child)]
     pub scroll_factor: Option<ScrollFactor>,
@@ -233,6 +279,8 @@ pub struct Mouse {
     pub accel_speed: FloatOrInt<-1, 1>,
     #[knuffel(child, unwrap(argument, str))]
     pub accel_profile: Option<AccelProfile>,
+    #[knuffel(child)]
+    pub custom_accel: Option<CustomAccel>,
     #[knuffel(child, unwrap(argument, str))]
     pub scroll_method: Option<ScrollMethod>,
     #[knuffel(child, unwrap(argument))]
@@ -257,12 +305,16 @@ pub struct Trackpoint {
     pub accel_speed: FloatOrInt<-1, 1>,
     #[knuffel(child, unwrap(argument, str))]
     pub accel_profile: Option<AccelProfile>,
+    #[knuffel(child)]
+    pub custom_accel: Option<CustomAccel>,
     #[knuffel(child, unwrap(argument, str))]
     pub scroll_method: Option<ScrollMethod>,
     #[knuffel(child, unwrap(argument))]
     pub scroll_button: Option<u32>,
     #[knuffel(child)]
     pub scroll_button_lock: bool,
+    #[knuffel(child, unwrap(argument))]
+    pub scroll_button_speed: Option<FloatOrInt<0, 100>>,
     #[knuffel(child)]
     pub left_handed: bool,
     #[knuffel(child)]
@@ -279,6 +331,8 @@ pub struct Trackball {
     pub accel_speed: FloatOrInt<-1, 1>,
     #[knuffel(child, unwrap(argument, str))]
     pub accel_profile: Option<AccelProfile>,
+    #[knuffel(child)]
+    pub custom_accel: Option<CustomAccel>,
     #[knuffel(child, unwrap(argument, str))]
     pub scroll_method: Option<ScrollMethod>,
     #[knuffel(child, unwrap(argument))]
@@ -289,6 +343,8 @@ pub struct Trackball {
     pub left_handed: bool,
     #[knuffel(child)]
     pub middle_emulation: bool,
+    #[knuffel(child, unwrap(argument))]
+    pub rotation: Option<u16>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -310,14 +366,79 @@ impl From<ClickMethod> for input::ClickMethod {
 pub enum AccelProfile {
     Adaptive,
     Flat,
+    Custom,
 }
 
-impl From<AccelProfile> for input::AccelProfile {
-    fn from(value: AccelProfile) -> Self {
-        match value {
-            AccelProfile::Adaptive => Self::Adaptive,
-            AccelProfile::Flat => Self::Flat,
+impl AccelProfile {
+    /// Returns the corresponding libinput profile.
+    ///
+    /// The custom profile is applied together with its curve, so it has no plain counterpart.
+    pub fn to_libinput(self) -> Option<input::AccelProfile> {
+        match self {
+            AccelProfile::Adaptive => Some(input::AccelProfile::Adaptive),
+            AccelProfile::Flat => Some(input::AccelProfile::Flat),
+            AccelProfile::Custom => None,
+        }
+    }
+}
+
+/// Custom pointer acceleration curve.
+///
+/// The points are the output speeds for input speeds of `0`, `step`, `2 * step`, and so on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomAccel {
+    pub step: f64,
+    pub points: Vec<f64>,
+}
+
+// Limits from libinput.
+const CUSTOM_ACCEL_MAX_POINTS: usize = 64;
+
+#[derive(knuffel::Decode)]
+struct CustomAccelRaw {
+    #[knuffel(property)]
+    step: FloatOrInt<0, 10000>,
+    #[knuffel(arguments)]
+    points: Vec<FloatOrInt<0, 10000>>,
+}
+
+impl<S: knuffel::traits::ErrorSpan> knuffel::Decode<S> for CustomAccel {
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        let raw = CustomAccelRaw::decode_node(node, ctx)?;
+
+        if raw.step.0 <= 0. {
+            ctx.emit_error(DecodeError::unexpected(
+                node,
+                "node",
+                "custom-accel step must be greater than 0",
+            ));
+        }
+
+        if !(2..=CUSTOM_ACCEL_MAX_POINTS).contains(&raw.points.len()) {
+            ctx.emit_error(DecodeError::unexpected(
+                node,
+                "node",
+                format!("custom-accel must have between 2 and {CUSTOM_ACCEL_MAX_POINTS} points"),
+            ));
         }
+
+        for (i, pair) in raw.points.windows(2).enumerate() {
+            if pair[1].0 < pair[0].0 {
+                ctx.emit_error(DecodeError::conversion(
+                    &node.arguments[i + 1].literal,
+                    "custom-accel points must be sorted in non-decreasing order",
+                ));
+                break;
+            }
+        }
+
+        Ok(Self {
+            step: raw.step.0,
+            points: raw.points.into_iter().map(|p| p.0).collect(),
+        })
     }
 }
 
@@ -432,6 +553,75 @@ impl ModKey {
     }
 }
 
+/// Action triggered by tapping the Mod key twice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModDoubleTapAction {
+    pub action: Action,
+    /// Maximum time between the first press and the second release.
+    pub timeout: Duration,
+}
+
+impl<S> knuffel::Decode<S> for ModDoubleTapAction
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        for val in node.arguments.iter() {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "no arguments expected for this node",
+            ));
+        }
+
+        let mut timeout = Duration::from_millis(300);
+        for (name, val) in &node.properties {
+            match &***name {
+                "timeout-ms" => {
+                    timeout =
+                        Duration::from_millis(knuffel::traits::DecodeScalar::decode(val, ctx)?);
+                }
+                name_str => {
+                    ctx.emit_error(DecodeError::unexpected(
+                        name,
+                        "property",
+                        format!("unexpected property `{}`", name_str.escape_default()),
+                    ));
+                }
+            }
+        }
+
+        let mut children = node.children();
+        let Some(child) = children.next() else {
+            return Err(DecodeError::missing(
+                node,
+                "expected an action for the Mod double tap",
+            ));
+        };
+        for unwanted_child in children {
+            ctx.emit_error(DecodeError::unexpected(
+                unwanted_child,
+                "node",
+                "only one action is allowed for the Mod double tap",
+            ));
+        }
+
+        let action = Action::decode_node(child, ctx)?;
+        Ok(Self { action, timeout })
+    }
+}
+
 impl FromStr for ModKey {
     type Err = miette::Error;
 
@@ -469,8 +659,9 @@ impl FromStr for AccelProfile {
         match s {
             "adaptive" => Ok(Self::Adaptive),
             "flat" => Ok(Self::Flat),
+            "custom" => Ok(Self::Custom),
             _ => Err(miette!(
-                r#"invalid accel profile, can be "adaptive" or "flat""#
+                r#"invalid accel profile, can be "adaptive", "flat" or "custom""#
             )),
         }
     }
@@ -742,4 +933,154 @@ mod tests {
         )
         ");
     }
+
+    #[test]
+    fn parse_trackpoint_scroll_button_speed() {
+        let parsed = do_parse(
+            r#"
+            trackpoint {
+                scroll-method "on-button-down"
+                scroll-button-speed 0.5
+            }
+            "#,
+        );
+        assert_eq!(parsed.trackpoint.scroll_button_speed, Some(FloatOrInt(0.5)));
+
+        let parsed = do_parse("trackpoint {}");
+        assert_eq!(parsed.trackpoint.scroll_button_speed, None);
+    }
+
+    #[test]
+    fn parse_trackball_rotation() {
+        let parsed = do_parse(
+            r#"
+            trackball {
+                rotation 90
+            }
+            "#,
+        );
+        assert_eq!(parsed.trackball.rotation, Some(90));
+
+        let parsed = do_parse("trackball {}");
+        assert_eq!(parsed.trackball.rotation, None);
+    }
+
+    #[test]
+    fn parse_track_layout_per_app() {
+        let parsed = do_parse(
+            r#"
+            keyboard {
+                track-layout "window"
+                track-layout-per-app
+            }
+            "#,
+        );
+        assert_eq!(parsed.keyboard.track_layout, TrackLayout::Window);
+        assert!(parsed.keyboard.track_layout_per_app);
+    }
+
+    #[test]
+    fn parse_custom_accel() {
+        let parsed = do_parse(
+            r#"
+            mouse {
+                accel-profile "custom"
+                custom-accel 0 0.5 1.5 3 step=2.5
+            }
+            "#,
+        );
+
+        assert_eq!(parsed.mouse.accel_profile, Some(AccelProfile::Custom));
+        assert_debug_snapshot!(parsed.mouse.custom_accel, @r"
+        Some(
+            CustomAccel {
+                step: 2.5,
+                points: [
+                    0.0,
+                    0.5,
+                    1.5,
+                    3.0,
+                ],
+            },
+        )
+        ");
+        assert_eq!(parsed.touchpad.custom_accel, None);
+    }
+
+    #[test]
+    fn parse_custom_accel_trackpoint_trackball() {
+        let parsed = do_parse(
+            r#"
+            trackpoint {
+                accel-profile "custom"
+                custom-accel 0 1 2 step=1
+            }
+            trackball {
+                accel-profile "custom"
+                custom-accel 0 2 step=0.5
+            }
+            "#,
+        );
+
+        assert_eq!(parsed.trackpoint.accel_profile, Some(AccelProfile::Custom));
+        let custom_accel = parsed.trackpoint.custom_accel.unwrap();
+        assert_eq!(custom_accel.points, [0., 1., 2.]);
+
+        assert_eq!(parsed.trackball.accel_profile, Some(AccelProfile::Custom));
+        let custom_accel = parsed.trackball.custom_accel.unwrap();
+        assert_eq!(custom_accel.step, 0.5);
+    }
+
+    #[test]
+    fn parse_custom_accel_unsorted() {
+        let result = knuffel::parse::<InputPart>(
+            "test.kdl",
+            r#"
+            touchpad {
+                custom-accel 0 2 1 step=1
+            }
+            "#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_custom_accel_too_few_points() {
+        let result = knuffel::parse::<InputPart>(
+            "test.kdl",
+            r#"
+            mouse {
+                custom-accel 1 step=1
+            }
+            "#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_disabled_on_external_keyboard() {
+        let parsed = do_parse(
+            r#"
+            keyboard {
+                disabled-on-external-keyboard
+                external-keyboard "Keychron K2"
+                external-keyboard "ZSA Moonlander"
+            }
+            touchpad {
+                disabled-on-external-keyboard
+            }
+            "#,
+        );
+        assert!(parsed.keyboard.disabled_on_external_keyboard);
+        assert_eq!(
+            parsed.keyboard.external_keyboards,
+            ["Keychron K2", "ZSA Moonlander"]
+        );
+        assert!(parsed.touchpad.disabled_on_external_keyboard);
+        assert!(!parsed.touchpad.disabled_on_external_mouse);
+
+        let parsed = do_parse("keyboard {}\ntouchpad {}");
+        assert!(!parsed.keyboard.disabled_on_external_keyboard);
+        assert!(!parsed.touchpad.disabled_on_external_keyboard);
+    }
 }
@@ -23,6 +23,11 @@ pub struct Layout {
     pub center_focused_column: CenterFocusedColumn,
     pub always_center_single_column: bool,
     pub empty_workspace_above_first: bool,
+    pub on_empty_workspace: OnEmptyWorkspace,
+    pub focus_after_close: FocusAfterClose,
+    pub remember_column_width_per_app: bool,
+    pub keep_floating_on_screen: bool,
+    pub overscroll: Overscroll,
     pub gaps: f64,
     pub struts: Struts,
     pub background_color: Color,
@@ -46,6 +51,11 @@ impl Default for Layout {
             center_focused_column: CenterFocusedColumn::Never,
             always_center_single_column: false,
             empty_workspace_above_first: false,
+            on_empty_workspace: OnEmptyWorkspace::Keep,
+            focus_after_close: FocusAfterClose::Neighbor,
+            remember_column_width_per_app: false,
+            keep_floating_on_screen: false,
+            overscroll: Overscroll::default(),
             gaps: 16.,
             struts: Struts::default(),
             preset_window_heights: vec![
@@ -70,6 +80,9 @@ impl MergeWith<LayoutPart> for Layout {
             insert_hint,
             always_center_single_column,
             empty_workspace_above_first,
+            remember_column_width_per_app,
+            keep_floating_on_screen,
+            overscroll,
             gaps,
         );
 
@@ -78,6 +91,8 @@ impl MergeWith<LayoutPart> for Layout {
             preset_column_widths,
             preset_window_heights,
             center_focused_column,
+            on_empty_workspace,
+            focus_after_close,
             struts,
             background_color,
         );
@@ -123,6 +138,16 @@ pub struct LayoutPart {
     #[knuffel(child)]
     pub empty_workspace_above_first: Option<Flag>,
     #[knuffel(child, unwrap(argument))]
+    pub on_empty_workspace: Option<OnEmptyWorkspace>,
+    #[knuffel(child, unwrap(argument))]
+    pub focus_after_close: Option<FocusAfterClose>,
+    #[knuffel(child)]
+    pub remember_column_width_per_app: Option<Flag>,
+    #[knuffel(child)]
+    pub keep_floating_on_screen: Option<Flag>,
+    #[knuffel(child)]
+    pub overscroll: Option<OverscrollPart>,
+    #[knuffel(child, unwrap(argument))]
     pub gaps: Option<FloatOrInt<0, 65535>>,
     #[knuffel(child)]
     pub struts: Option<Struts>,
@@ -160,6 +185,42 @@ pub struct Struts {
     pub bottom: FloatOrInt<-65535, 65535>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Overscroll {
+    pub on: bool,
+    pub resistance: f64,
+}
+
+impl Default for Overscroll {
+    fn default() -> Self {
+        Self {
+            on: false,
+            resistance: 0.5,
+        }
+    }
+}
+
+impl MergeWith<OverscrollPart> for Overscroll {
+    fn merge_with(&mut self, part: &OverscrollPart) {
+        self.on |= part.on;
+        if part.off {
+            self.on = false;
+        }
+
+        merge!((self, part), resistance);
+    }
+}
+
+#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
+pub struct OverscrollPart {
+    #[knuffel(child)]
+    pub off: bool,
+    #[knuffel(child)]
+    pub on: bool,
+    #[knuffel(child, unwrap(argument))]
+    pub resistance: Option<FloatOrInt<0, 1>>,
+}
+
 #[derive(knuffel::DecodeScalar, Debug, Default, PartialEq, Eq, Clone, Copy)]
 pub enum CenterFocusedColumn {
     /// Focusing a column will not center the column.
@@ -172,6 +233,28 @@ pub enum CenterFocusedColumn {
     OnOverflow,
 }
 
+/// What happens to a workspace when its last window closes.
+#[derive(knuffel::DecodeScalar, Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum OnEmptyWorkspace {
+    /// Keep the workspace until it's no longer focused, and keep named workspaces indefinitely.
+    #[default]
+    Keep,
+    /// Remove the workspace right away, even if it's focused or named.
+    Remove,
+    /// Remove unnamed workspaces right away, even if they're focused.
+    RemoveIfUnnamed,
+}
+
+/// Which workspace gets focused when the focused workspace is removed after its last window closes.
+#[derive(knuffel::DecodeScalar, Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum FocusAfterClose {
+    /// Focus the workspace next to the removed one.
+    #[default]
+    Neighbor,
+    /// Focus the workspace with the most recently focused window on the same monitor.
+    Mru,
+}
+
 impl<S> knuffel::Decode<S> for DefaultPresetSize
 where
     S: knuffel::traits::ErrorSpan,
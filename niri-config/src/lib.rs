@@ -23,7 +23,7 @@ use std::rc::Rc;
 
 use knuffel::Decode as _;
 use knuffel::errors::DecodeError;
-use miette::{Context as _, IntoDiagnostic as _, miette};
+use miette::{Context as _, GraphicalReportHandler, GraphicalTheme, IntoDiagnostic as _, miette};
 
 #[macro_use]
 pub mod macros;
@@ -44,17 +44,20 @@ pub mod utils;
 pub mod window_rule;
 pub mod workspace;
 
+use crate::animations::AnimationsPart;
 pub use crate::animations::{Animation, Animations};
 pub use crate::appearance::*;
 pub use crate::binds::*;
 pub use crate::debug::Debug;
 pub use crate::error::{ConfigIncludeError, ConfigParseResult};
 pub use crate::gestures::Gestures;
-pub use crate::input::{Input, ModKey, ScrollMethod, TrackLayout, WarpMouseToFocusMode, Xkb};
+pub use crate::input::{
+    AccelProfile, CustomAccel, Input, ModKey, ScrollMethod, TrackLayout, WarpMouseToFocusMode, Xkb,
+};
 pub use crate::layer_rule::LayerRule;
 pub use crate::layout::*;
 pub use crate::misc::*;
-pub use crate::output::{Output, OutputName, Outputs, Position, Vrr};
+pub use crate::output::{Output, OutputName, Outputs, Position, ScaleRounding, Vrr};
 use crate::recent_windows::RecentWindowsPart;
 pub use crate::recent_windows::{MruDirection, MruFilter, MruPreviews, MruScope, RecentWindows};
 pub use crate::utils::FloatOrInt;
@@ -72,15 +75,18 @@ pub struct Config {
     pub spawn_sh_at_startup: Vec<SpawnShAtStartup>,
     pub layout: Layout,
     pub prefer_no_csd: bool,
+    pub block_out_all_from_screencast: bool,
     pub cursor: Cursor,
     pub screenshot_path: ScreenshotPath,
     pub clipboard: Clipboard,
     pub hotkey_overlay: HotkeyOverlay,
     pub config_notification: ConfigNotification,
+    pub ipc: Ipc,
     pub animations: Animations,
     pub gestures: Gestures,
     pub overview: Overview,
     pub environment: Environment,
+    pub app_id_map: AppIdMap,
     pub xwayland_satellite: XwaylandSatellite,
     pub window_rules: Vec<WindowRule>,
     pub layer_rules: Vec<LayerRule>,
@@ -193,7 +199,7 @@ where
                 "clipboard" => m_merge!(clipboard),
                 "hotkey-overlay" => m_merge!(hotkey_overlay),
                 "config-notification" => m_merge!(config_notification),
-                "animations" => m_merge!(animations),
+                "ipc" => m_merge!(ipc),
                 "gestures" => m_merge!(gestures),
                 "overview" => m_merge!(overview),
                 "xwayland-satellite" => m_merge!(xwayland_satellite),
@@ -229,16 +235,39 @@ where
                     let part = Environment::decode_node(node, ctx)?;
                     config.borrow_mut().environment.0.extend(part.0);
                 }
+                "app-id-map" => {
+                    let part = AppIdMap::decode_node(node, ctx)?;
+                    config.borrow_mut().app_id_map.0.extend(part.0);
+                }
 
                 "prefer-no-csd" => {
                     config.borrow_mut().prefer_no_csd = Flag::decode_node(node, ctx)?.0
                 }
 
+                "block-out-all-from-screencast" => {
+                    config.borrow_mut().block_out_all_from_screencast =
+                        Flag::decode_node(node, ctx)?.0
+                }
+
                 "screenshot-path" => {
                     let part = knuffel::Decode::decode_node(node, ctx)?;
                     config.borrow_mut().screenshot_path = part;
                 }
 
+                "animations" => {
+                    let mut part = AnimationsPart::decode_node(node, ctx)?;
+
+                    let mut config = config.borrow_mut();
+                    if let Err(name) = part.resolve_presets(&config.animations.presets) {
+                        ctx.emit_error(DecodeError::missing(
+                            node,
+                            format!("unknown animation preset `{}`", name.escape_default()),
+                        ));
+                    }
+
+                    config.animations.merge_with(&part);
+                }
+
                 "layout" => {
                     let mut part = LayoutPart::decode_node(node, ctx)?;
 
@@ -481,6 +510,23 @@ impl ConfigPath {
         .map_config_res(|res| res.context("error loading config"))
     }
 
+    /// Loads the config only to check it for errors, without colors in the formatted error.
+    pub fn validate(&self) -> Result<(), String> {
+        self.load_uncolored().map(|_| ())
+    }
+
+    /// Loads the config, without colors in the formatted error.
+    pub fn load_uncolored(&self) -> Result<Config, String> {
+        self.load().config.map_err(|err| {
+            let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+            let mut error = String::new();
+            if handler.render_report(&mut error, &*err).is_err() {
+                error = err.to_string();
+            }
+            error
+        })
+    }
+
     /// Loads the config, or creates it if it doesn't exist.
     ///
     /// Returns a tuple containing the path that was created, if any, and the loaded config.
@@ -580,6 +626,393 @@ mod tests {
         assert_eq!(config.input.keyboard.repeat_rate, 25);
     }
 
+    #[test]
+    fn parse_overview_columns() {
+        let config = Config::parse_mem("overview { columns 3; }").unwrap();
+        assert_eq!(config.overview.columns, Some(3));
+
+        let config = Config::parse_mem("").unwrap();
+        assert_eq!(config.overview.columns, None);
+    }
+
+    #[test]
+    fn parse_workspace_shadow_motion() {
+        let config = Config::parse_mem("overview { workspace-shadow { motion; } }").unwrap();
+        assert!(config.overview.workspace_shadow.motion);
+
+        let config = Config::parse_mem("overview { workspace-shadow { motion false; } }").unwrap();
+        assert!(!config.overview.workspace_shadow.motion);
+    }
+
+    #[test]
+    fn parse_window_rule_prefer_server_decorations() {
+        let config = Config::parse_mem(
+            r#"
+            window-rule {
+                match app-id="^foot$"
+                prefer-server-decorations true
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.window_rules[0].prefer_server_decorations, Some(true));
+
+        let config = Config::parse_mem("window-rule { prefer-server-decorations false; }").unwrap();
+        assert_eq!(
+            config.window_rules[0].prefer_server_decorations,
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn parse_gestures_workspace_switch() {
+        let config =
+            Config::parse_mem("gestures { workspace-switch { sensitivity 0.5; min-delta 0.8; } }")
+                .unwrap();
+        let c = config.gestures.workspace_switch;
+        assert_eq!(c.sensitivity, 0.5);
+        assert_eq!(c.min_delta, 0.8);
+
+        let config = Config::parse_mem("gestures { workspace-switch { sensitivity 2; } }").unwrap();
+        let c = config.gestures.workspace_switch;
+        assert_eq!(c.sensitivity, 2.);
+        assert_eq!(c.min_delta, 0.5);
+
+        assert!(Config::parse_mem("gestures { workspace-switch { min-delta 1.5; } }").is_err());
+    }
+
+    #[test]
+    fn parse_window_rule_force_floating() {
+        let config = Config::parse_mem(
+            r#"
+            window-rule {
+                match app-id="mpv"
+                force-floating true
+            }
+            window-rule {
+                force-floating false
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.window_rules[0].force_floating, Some(true));
+        assert_eq!(config.window_rules[1].force_floating, Some(false));
+    }
+
+    #[test]
+    fn parse_window_rule_is_fullscreen() {
+        let config = Config::parse_mem(
+            r#"
+            window-rule {
+                match is-fullscreen=true
+                exclude app-id="mpv" is-fullscreen=false
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.window_rules[0].matches[0].is_fullscreen, Some(true));
+        assert_eq!(
+            config.window_rules[0].excludes[0].is_fullscreen,
+            Some(false)
+        );
+        assert_eq!(config.window_rules[0].excludes[0].is_floating, None);
+    }
+
+    #[test]
+    fn parse_gestures_swipe() {
+        use crate::gestures::SwipeDirection;
+
+        let config = Config::parse_mem(
+            "gestures { swipe { four-finger-up { toggle-overview; }; three-finger-left { focus-workspace-down; }; } }",
+        )
+        .unwrap();
+        let swipe = &config.gestures.swipe;
+        assert_eq!(
+            swipe.action(4, SwipeDirection::Up),
+            Some(&Action::ToggleOverview)
+        );
+        assert_eq!(
+            swipe.action(3, SwipeDirection::Left),
+            Some(&Action::FocusWorkspaceDown)
+        );
+        assert_eq!(swipe.action(3, SwipeDirection::Right), None);
+        assert!(swipe.has_any(3));
+        assert!(!swipe.has_any(5));
+
+        assert!(Config::parse_mem("gestures { swipe { four-finger-up; } }").is_err());
+    }
+
+    #[test]
+    fn parse_cursor_reveal_motion_threshold() {
+        let config = Config::parse_mem("cursor { reveal-motion-threshold 20; }").unwrap();
+        assert_eq!(config.cursor.reveal_motion_threshold, 20);
+
+        let config = Config::parse_mem("cursor { hide-when-typing; }").unwrap();
+        assert_eq!(config.cursor.reveal_motion_threshold, 0);
+    }
+
+    #[test]
+    fn focus_column_on_gap_click_is_off_by_default() {
+        let config = Config::parse_mem("").unwrap();
+        assert!(!config.input.focus_column_on_gap_click);
+
+        let config = Config::parse_mem("input { focus-column-on-gap-click; }").unwrap();
+        assert!(config.input.focus_column_on_gap_click);
+    }
+
+    #[test]
+    fn parse_vrr_on_demand_idle_ms() {
+        let config = Config::parse_mem(
+            r#"output "DP-1" { variable-refresh-rate on-demand=true on-demand-idle-ms=250; }"#,
+        )
+        .unwrap();
+        let output = &config.outputs.0[0];
+        assert!(output.is_vrr_on_demand());
+        assert_eq!(
+            output
+                .variable_refresh_rate
+                .as_ref()
+                .unwrap()
+                .on_demand_idle_ms,
+            Some(250)
+        );
+    }
+
+    #[test]
+    fn parse_output_max_fps() {
+        let config = Config::parse_mem(r#"output "eDP-1" { max-fps 30; }"#).unwrap();
+        assert_eq!(config.outputs.0[0].max_fps, Some(FloatOrInt(30.)));
+
+        assert!(Config::parse_mem(r#"output "eDP-1" { max-fps 0; }"#).is_err());
+    }
+
+    #[test]
+    fn parse_layout_overscroll() {
+        let config = Config::parse_mem("layout { overscroll { on; resistance 0.8; }; }").unwrap();
+        assert!(config.layout.overscroll.on);
+        assert_eq!(config.layout.overscroll.resistance, 0.8);
+
+        let config = Config::parse_mem(
+            r#"
+            layout { overscroll { on; }; }
+            layout { overscroll { off; }; }
+            "#,
+        )
+        .unwrap();
+        assert!(!config.layout.overscroll.on);
+
+        assert!(Config::parse_mem("layout { overscroll { resistance 2; }; }").is_err());
+    }
+
+    #[test]
+    fn parse_layout_on_empty_workspace() {
+        let config = Config::parse_mem("layout {}").unwrap();
+        assert_eq!(config.layout.on_empty_workspace, OnEmptyWorkspace::Keep);
+
+        let config =
+            Config::parse_mem(r#"layout { on-empty-workspace "remove-if-unnamed"; }"#).unwrap();
+        assert_eq!(
+            config.layout.on_empty_workspace,
+            OnEmptyWorkspace::RemoveIfUnnamed
+        );
+
+        let config = Config::parse_mem(r#"layout { on-empty-workspace "remove"; }"#).unwrap();
+        assert_eq!(config.layout.on_empty_workspace, OnEmptyWorkspace::Remove);
+
+        assert!(Config::parse_mem(r#"layout { on-empty-workspace "close"; }"#).is_err());
+    }
+
+    #[test]
+    fn parse_layout_focus_after_close() {
+        let config = Config::parse_mem("layout {}").unwrap();
+        assert_eq!(config.layout.focus_after_close, FocusAfterClose::Neighbor);
+
+        let config = Config::parse_mem(r#"layout { focus-after-close "mru"; }"#).unwrap();
+        assert_eq!(config.layout.focus_after_close, FocusAfterClose::Mru);
+
+        let config = Config::parse_mem(r#"layout { focus-after-close "neighbor"; }"#).unwrap();
+        assert_eq!(config.layout.focus_after_close, FocusAfterClose::Neighbor);
+
+        assert!(Config::parse_mem(r#"layout { focus-after-close "last"; }"#).is_err());
+    }
+
+    #[test]
+    fn parse_output_scale_rounding() {
+        let config = Config::parse_mem(r#"output "eDP-1" { scale 1.5; }"#).unwrap();
+        assert_eq!(config.outputs.0[0].scale_rounding, ScaleRounding::Exact);
+
+        let config =
+            Config::parse_mem(r#"output "eDP-1" { scale-rounding "nearest-int"; }"#).unwrap();
+        assert_eq!(
+            config.outputs.0[0].scale_rounding,
+            ScaleRounding::NearestInt
+        );
+
+        let config = Config::parse_mem(r#"output "eDP-1" { scale-rounding "floor"; }"#).unwrap();
+        assert_eq!(config.outputs.0[0].scale_rounding, ScaleRounding::Floor);
+
+        assert!(Config::parse_mem(r#"output "eDP-1" { scale-rounding "ceil"; }"#).is_err());
+    }
+
+    #[test]
+    fn parse_animation_presets() {
+        let with_presets = Config::parse_mem(
+            r#"
+            animations {
+                window-open {
+                    preset "snappy"
+                    custom-shader "shader"
+                }
+                window-close { preset "slow"; off; }
+
+                animation-preset "snappy" {
+                    spring damping-ratio=0.8 stiffness=1200 epsilon=0.001
+                }
+                animation-preset "slow" {
+                    duration-ms 400
+                }
+            }
+
+            animations {
+                workspace-switch { preset "snappy"; }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let inline = Config::parse_mem(
+            r#"
+            animations {
+                window-open {
+                    spring damping-ratio=0.8 stiffness=1200 epsilon=0.001
+                    custom-shader "shader"
+                }
+                window-close {
+                    duration-ms 400
+                    curve "ease-out-cubic"
+                    off
+                }
+                workspace-switch {
+                    spring damping-ratio=0.8 stiffness=1200 epsilon=0.001
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let a = &with_presets.animations;
+        let b = &inline.animations;
+        assert_eq!(a.window_open, b.window_open);
+        assert_eq!(a.window_close, b.window_close);
+        assert_eq!(a.workspace_switch, b.workspace_switch);
+        assert_eq!(a.presets.len(), 2);
+    }
+
+    #[test]
+    fn parse_animation_presets_errors() {
+        // Unknown preset.
+        assert!(Config::parse_mem(r#"animations { window-open { preset "nope"; } }"#).is_err());
+        // Preset together with inline parameters.
+        assert!(
+            Config::parse_mem(
+                r#"
+                animations {
+                    animation-preset "a" { duration-ms 100; }
+                    window-open { preset "a"; duration-ms 200; }
+                }
+                "#
+            )
+            .is_err()
+        );
+        // Preset referring to another preset.
+        assert!(
+            Config::parse_mem(
+                r#"
+                animations {
+                    animation-preset "a" { duration-ms 100; }
+                    animation-preset "b" { preset "a"; }
+                }
+                "#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_mod_double_tap_action() {
+        let config =
+            Config::parse_mem("input { mod-double-tap-action { toggle-overview; } }").unwrap();
+        let tap = config.input.mod_double_tap_action.unwrap();
+        assert_eq!(tap.action, Action::ToggleOverview);
+        assert_eq!(tap.timeout, std::time::Duration::from_millis(300));
+
+        let config = Config::parse_mem(
+            "input { mod-double-tap-action timeout-ms=200 { spawn \"fuzzel\"; } }",
+        )
+        .unwrap();
+        let tap = config.input.mod_double_tap_action.unwrap();
+        assert_eq!(tap.action, Action::Spawn(vec!["fuzzel".to_owned()]));
+        assert_eq!(tap.timeout, std::time::Duration::from_millis(200));
+
+        assert!(Config::parse_mem("input { mod-double-tap-action; }").is_err());
+        assert!(
+            Config::parse_mem("input { mod-double-tap-action { toggle-overview; quit; } }")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_disable_dbus_interface() {
+        use crate::debug::DbusInterfaceName;
+
+        let config = Config::parse_mem(
+            r#"
+            debug {
+                disable-dbus-interface "org.gnome.Shell.Introspect"
+                disable-dbus-interface "org.kde.KWin.ScreenShot2"
+                disable-dbus-interface "org.niri.Config"
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.debug.disabled_dbus_interfaces,
+            [
+                DbusInterfaceName::Introspect,
+                DbusInterfaceName::KwinScreenshot2,
+                DbusInterfaceName::NiriConfig,
+            ]
+        );
+
+        assert!(
+            Config::parse_mem(r#"debug { disable-dbus-interface "org.example.Foo"; }"#).is_err()
+        );
+    }
+
+    #[test]
+    fn parse_app_id_map() {
+        let config = Config::parse_mem(
+            r#"
+            app-id-map {
+                map "^code-url-handler$" "code"
+                map "^org\\.example\\." "example.desktop"
+            }
+            app-id-map {
+                map "^foot" "foot-server"
+            }
+            "#,
+        )
+        .unwrap();
+        let map = &config.app_id_map;
+        assert_eq!(map.0.len(), 3);
+        assert_eq!(map.desktop_id("code-url-handler"), "code");
+        assert_eq!(map.desktop_id("org.example.App"), "example");
+        assert_eq!(map.desktop_id("footclient"), "foot-server");
+        assert_eq!(map.desktop_id("firefox"), "firefox");
+
+        assert!(Config::parse_mem(r#"app-id-map { map "(" "code"; }"#).is_err());
+    }
+
     #[track_caller]
     fn do_parse(text: &str) -> Config {
         Config::parse_mem(text)
@@ -647,6 +1080,7 @@ mod tests {
                     scroll-button-lock
                     left-handed
                     middle-emulation
+                    rotation 90
                 }
 
                 tablet {
@@ -676,6 +1110,7 @@ mod tests {
                 position x=10 y=20
                 mode "1920x1080@144"
                 variable-refresh-rate on-demand=true
+                color-profile "~/.local/share/icc/monitor.icc"
                 background-color "rgba(25, 25, 102, 1.0)"
                 hot-corners {
                     off
@@ -714,6 +1149,11 @@ mod tests {
                 tab-indicator {
                     width 10
                     position "top"
+
+                    label-shadow {
+                        on
+                        softness 4
+                    }
                 }
 
                 preset-column-widths {
@@ -745,6 +1185,7 @@ mod tests {
                 insert-hint {
                     color "rgb(255, 200, 127)"
                     gradient from="rgba(10, 20, 30, 1.0)" to="#0080ffff" relative-to="workspace-view"
+                    animation "pulse"
                 }
             }
 
@@ -752,6 +1193,7 @@ mod tests {
             spawn-sh-at-startup "qs -c ~/source/qs/MyAwesomeShell"
 
             prefer-no-csd
+            block-out-all-from-screencast
 
             cursor {
                 xcursor-theme "breeze_cursors"
@@ -764,6 +1206,7 @@ mod tests {
 
             clipboard {
                 disable-primary
+                history-size 20
             }
 
             hotkey-overlay {
@@ -806,7 +1249,7 @@ mod tests {
             }
 
             window-rule {
-                match app-id=".*alacritty"
+                match app-id=".*alacritty" process="^alacritty$"
                 exclude title="~"
                 exclude is-active=true is-focused=false
 
@@ -831,6 +1274,12 @@ mod tests {
                 tab-indicator {
                     active-color "#f00"
                 }
+
+                insert-hint {
+                    color "#00f8"
+                }
+
+                allow-in-screencast true
             }
 
             layer-rule {
@@ -875,6 +1324,7 @@ mod tests {
 
             recent-windows {
                 off
+                focus-history-depth 4
 
                 highlight {
                     padding 15
@@ -911,7 +1361,11 @@ mod tests {
                     repeat_delay: 600,
                     repeat_rate: 25,
                     track_layout: Window,
+                    track_layout_per_app: false,
                     numlock: false,
+                    floating_resize_step: 20,
+                    disabled_on_external_keyboard: false,
+                    external_keyboards: [],
                 },
                 touchpad: Touchpad {
                     off: false,
@@ -932,6 +1386,7 @@ mod tests {
                     accel_profile: Some(
                         Flat,
                     ),
+                    custom_accel: None,
                     scroll_method: Some(
                         TwoFinger,
                     ),
@@ -944,6 +1399,7 @@ mod tests {
                     ),
                     left_handed: false,
                     disabled_on_external_mouse: true,
+                    disabled_on_external_keyboard: false,
                     middle_emulation: false,
                     scroll_factor: Some(
                         ScrollFactor {
@@ -966,6 +1422,7 @@ mod tests {
                     accel_profile: Some(
                         Flat,
                     ),
+                    custom_accel: None,
                     scroll_method: Some(
                         NoScroll,
                     ),
@@ -996,6 +1453,7 @@ mod tests {
                     accel_profile: Some(
                         Flat,
                     ),
+                    custom_accel: None,
                     scroll_method: Some(
                         OnButtonDown,
                     ),
@@ -1003,6 +1461,7 @@ mod tests {
                         274,
                     ),
                     scroll_button_lock: false,
+                    scroll_button_speed: None,
                     left_handed: false,
                     middle_emulation: false,
                 },
@@ -1015,6 +1474,7 @@ mod tests {
                     accel_profile: Some(
                         Flat,
                     ),
+                    custom_accel: None,
                     scroll_method: Some(
                         Edge,
                     ),
@@ -1024,6 +1484,9 @@ mod tests {
                     scroll_button_lock: true,
                     left_handed: true,
                     middle_emulation: true,
+                    rotation: Some(
+                        90,
+                    ),
                 },
                 tablet: Tablet {
                     off: false,
@@ -1061,12 +1524,14 @@ mod tests {
                     },
                 ),
                 workspace_auto_back_and_forth: true,
+                focus_column_on_gap_click: false,
                 mod_key: Some(
                     IsoLevel3Shift,
                 ),
                 mod_key_nested: Some(
                     Super,
                 ),
+                mod_double_tap_action: None,
             },
             outputs: Outputs(
                 [
@@ -1078,6 +1543,7 @@ mod tests {
                                 2.0,
                             ),
                         ),
+                        scale_rounding: Exact,
                         transform: Flipped90,
                         position: Some(
                             Position {
@@ -1101,9 +1567,15 @@ mod tests {
                         variable_refresh_rate: Some(
                             Vrr {
                                 on_demand: true,
+                                on_demand_idle_ms: None,
                             },
                         ),
+                        color_profile: Some(
+                            "~/.local/share/icc/monitor.icc",
+                        ),
+                        max_fps: None,
                         focus_at_startup: true,
+                        default_workspace: None,
                         background_color: Some(
                             Color {
                                 r: 0.09803922,
@@ -1128,6 +1600,7 @@ mod tests {
                         off: false,
                         name: "eDP-2",
                         scale: None,
+                        scale_rounding: Exact,
                         transform: Normal,
                         position: None,
                         mode: Some(
@@ -1144,7 +1617,10 @@ mod tests {
                         ),
                         modeline: None,
                         variable_refresh_rate: None,
+                        color_profile: None,
+                        max_fps: None,
                         focus_at_startup: false,
+                        default_workspace: None,
                         background_color: None,
                         backdrop_color: None,
                         hot_corners: None,
@@ -1154,6 +1630,7 @@ mod tests {
                         off: false,
                         name: "eDP-3",
                         scale: None,
+                        scale_rounding: Exact,
                         transform: Normal,
                         position: None,
                         mode: None,
@@ -1173,7 +1650,10 @@ mod tests {
                             },
                         ),
                         variable_refresh_rate: None,
+                        color_profile: None,
+                        max_fps: None,
                         focus_at_startup: false,
+                        default_workspace: None,
                         background_color: None,
                         backdrop_color: None,
                         hot_corners: None,
@@ -1335,6 +1815,27 @@ mod tests {
                     active_gradient: None,
                     inactive_gradient: None,
                     urgent_gradient: None,
+                    label_shadow: Shadow {
+                        on: true,
+                        offset: ShadowOffset {
+                            x: FloatOrInt(
+                                0.0,
+                            ),
+                            y: FloatOrInt(
+                                1.0,
+                            ),
+                        },
+                        softness: 4.0,
+                        spread: 0.0,
+                        draw_behind_window: false,
+                        color: Color {
+                            r: 0.0,
+                            g: 0.0,
+                            b: 0.0,
+                            a: 0.6666667,
+                        },
+                        inactive_color: None,
+                    },
                 },
                 insert_hint: InsertHint {
                     off: false,
@@ -1366,6 +1867,7 @@ mod tests {
                             },
                         },
                     ),
+                    animation: Pulse,
                 },
                 preset_column_widths: [
                     Proportion(
@@ -1403,6 +1905,14 @@ mod tests {
                 center_focused_column: OnOverflow,
                 always_center_single_column: false,
                 empty_workspace_above_first: false,
+                on_empty_workspace: Keep,
+                focus_after_close: Neighbor,
+                remember_column_width_per_app: false,
+                keep_floating_on_screen: false,
+                overscroll: Overscroll {
+                    on: false,
+                    resistance: 0.5,
+                },
                 gaps: 8.0,
                 struts: Struts {
                     left: FloatOrInt(
@@ -1426,6 +1936,7 @@ mod tests {
                 },
             },
             prefer_no_csd: true,
+            block_out_all_from_screencast: true,
             cursor: Cursor {
                 xcursor_theme: "breeze_cursors",
                 xcursor_size: 16,
@@ -1433,6 +1944,7 @@ mod tests {
                 hide_after_inactive_ms: Some(
                     3000,
                 ),
+                reveal_motion_threshold: 0,
             },
             screenshot_path: ScreenshotPath(
                 Some(
@@ -1441,6 +1953,7 @@ mod tests {
             ),
             clipboard: Clipboard {
                 disable_primary: true,
+                history_size: 20,
             },
             hotkey_overlay: HotkeyOverlay {
                 skip_at_startup: true,
@@ -1449,6 +1962,9 @@ mod tests {
             config_notification: ConfigNotification {
                 disable_failed: false,
             },
+            ipc: Ipc {
+                pointer_window_events: false,
+            },
             animations: Animations {
                 off: false,
                 slowdown: 2.0,
@@ -1610,6 +2126,7 @@ mod tests {
                         ),
                     },
                 ),
+                presets: [],
             },
             gestures: Gestures {
                 dnd_edge_view_scroll: DndEdgeViewScroll {
@@ -1622,6 +2139,10 @@ mod tests {
                     delay_ms: 100,
                     max_speed: 1500.0,
                 },
+                workspace_switch: WorkspaceSwitchGesture {
+                    sensitivity: 1.0,
+                    min_delta: 0.5,
+                },
                 hot_corners: HotCorners {
                     off: false,
                     top_left: false,
@@ -1629,6 +2150,16 @@ mod tests {
                     bottom_left: false,
                     bottom_right: false,
                 },
+                swipe: SwipeBinds {
+                    three_finger_up: None,
+                    three_finger_down: None,
+                    three_finger_left: None,
+                    three_finger_right: None,
+                    four_finger_up: None,
+                    four_finger_down: None,
+                    four_finger_left: None,
+                    four_finger_right: None,
+                },
             },
             overview: Overview {
                 zoom: 0.5,
@@ -1656,7 +2187,9 @@ mod tests {
                         b: 0.0,
                         a: 0.3137255,
                     },
+                    motion: false,
                 },
+                columns: None,
             },
             environment: Environment(
                 [
@@ -1672,6 +2205,9 @@ mod tests {
                     },
                 ],
             ),
+            app_id_map: AppIdMap(
+                [],
+            ),
             xwayland_satellite: XwaylandSatellite {
                 off: false,
                 path: "xwayland-satellite",
@@ -1688,10 +2224,19 @@ mod tests {
                                 ),
                             ),
                             title: None,
+                            process: Some(
+                                RegexEq(
+                                    Regex(
+                                        "^alacritty$",
+                                    ),
+                                ),
+                            ),
+                            pid: None,
                             is_active: None,
                             is_focused: None,
                             is_active_in_column: None,
                             is_floating: None,
+                            is_fullscreen: None,
                             is_window_cast_target: None,
                             is_urgent: None,
                             at_startup: None,
@@ -1707,10 +2252,13 @@ mod tests {
                                     ),
                                 ),
                             ),
+                            process: None,
+                            pid: None,
                             is_active: None,
                             is_focused: None,
                             is_active_in_column: None,
                             is_floating: None,
+                            is_fullscreen: None,
                             is_window_cast_target: None,
                             is_urgent: None,
                             at_startup: None,
@@ -1718,6 +2266,8 @@ mod tests {
                         Match {
                             app_id: None,
                             title: None,
+                            process: None,
+                            pid: None,
                             is_active: Some(
                                 true,
                             ),
@@ -1726,6 +2276,7 @@ mod tests {
                             ),
                             is_active_in_column: None,
                             is_floating: None,
+                            is_fullscreen: None,
                             is_window_cast_target: None,
                             is_urgent: None,
                             at_startup: None,
@@ -1758,6 +2309,7 @@ mod tests {
                     open_focused: Some(
                         true,
                     ),
+                    force_floating: None,
                     min_width: None,
                     min_height: None,
                     max_width: None,
@@ -1830,12 +2382,26 @@ mod tests {
                         inactive_gradient: None,
                         urgent_gradient: None,
                     },
+                    insert_hint: InsertHintRule {
+                        color: Some(
+                            Color {
+                                r: 0.0,
+                                g: 0.0,
+                                b: 1.0,
+                                a: 0.53333336,
+                            },
+                        ),
+                        gradient: None,
+                    },
                     draw_border_with_background: None,
                     opacity: None,
                     geometry_corner_radius: None,
                     clip_to_geometry: None,
                     baba_is_float: None,
                     block_out_from: None,
+                    allow_in_screencast: Some(
+                        true,
+                    ),
                     variable_refresh_rate: None,
                     default_floating_position: Some(
                         FloatingPosition {
@@ -1850,6 +2416,7 @@ mod tests {
                     ),
                     scroll_factor: None,
                     tiled_state: None,
+                    prefer_server_decorations: None,
                 },
             ],
             layer_rules: [
@@ -1871,6 +2438,7 @@ mod tests {
                     block_out_from: Some(
                         Screencast,
                     ),
+                    allow_in_screencast: None,
                     shadow: ShadowRule {
                         off: false,
                         on: false,
@@ -1911,8 +2479,9 @@ mod tests {
                             ),
                         },
                         action: ToggleKeyboardShortcutsInhibit,
-                        repeat: true,
+                        repeat: false,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: false,
                         hotkey_overlay_title: Some(
@@ -1931,8 +2500,9 @@ mod tests {
                             ),
                         },
                         action: ToggleKeyboardShortcutsInhibit,
-                        repeat: true,
+                        repeat: false,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: false,
                         hotkey_overlay_title: None,
@@ -1953,6 +2523,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: true,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -1967,8 +2538,9 @@ mod tests {
                             ),
                         },
                         action: CloseWindow,
-                        repeat: true,
+                        repeat: false,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: Some(
@@ -1987,6 +2559,7 @@ mod tests {
                         action: FocusMonitorLeft,
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2005,6 +2578,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2021,6 +2595,7 @@ mod tests {
                         action: MoveWindowToMonitorRight,
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2039,6 +2614,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2057,6 +2633,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2073,6 +2650,7 @@ mod tests {
                         action: ConsumeWindowIntoColumn,
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2090,9 +2668,11 @@ mod tests {
                             Index(
                                 1,
                             ),
+                            false,
                         ),
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2110,9 +2690,11 @@ mod tests {
                             Name(
                                 "workspace-1",
                             ),
+                            false,
                         ),
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2129,8 +2711,9 @@ mod tests {
                         action: Quit(
                             true,
                         ),
-                        repeat: true,
+                        repeat: false,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: false,
                         hotkey_overlay_title: None,
@@ -2143,10 +2726,11 @@ mod tests {
                             ),
                         },
                         action: FocusWorkspaceDown,
-                        repeat: true,
+                        repeat: false,
                         cooldown: Some(
                             150ms,
                         ),
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2165,6 +2749,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: true,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2220,6 +2805,7 @@ mod tests {
                 honor_xdg_activation_with_invalid_serial: false,
                 deactivate_unfocused_windows: false,
                 skip_cursor_only_updates_during_vrr: false,
+                disabled_dbus_interfaces: [],
             },
             workspaces: [
                 Workspace {
@@ -2250,6 +2836,7 @@ mod tests {
                 on: false,
                 debounce_ms: 750,
                 open_delay_ms: 150,
+                focus_history_depth: 4,
                 highlight: MruHighlight {
                     active_color: Color {
                         r: 0.0,
@@ -2289,6 +2876,7 @@ mod tests {
                         },
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2311,6 +2899,7 @@ mod tests {
                         },
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2335,6 +2924,7 @@ mod tests {
                         },
                         repeat: true,
                         cooldown: None,
+                        hold: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
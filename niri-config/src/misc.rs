@@ -1,6 +1,6 @@
 use crate::FloatOrInt;
 use crate::appearance::{Color, DEFAULT_BACKDROP_COLOR, WorkspaceShadow, WorkspaceShadowPart};
-use crate::utils::{Flag, MergeWith};
+use crate::utils::{Flag, MergeWith, RegexEq};
 
 #[derive(knuffel::Decode, Debug, Clone, PartialEq, Eq)]
 pub struct SpawnAtStartup {
@@ -20,6 +20,7 @@ pub struct Cursor {
     pub xcursor_size: u8,
     pub hide_when_typing: bool,
     pub hide_after_inactive_ms: Option<u32>,
+    pub reveal_motion_threshold: u16,
 }
 
 impl Default for Cursor {
@@ -29,6 +30,7 @@ impl Default for Cursor {
             xcursor_size: 24,
             hide_when_typing: false,
             hide_after_inactive_ms: None,
+            reveal_motion_threshold: 0,
         }
     }
 }
@@ -43,11 +45,18 @@ pub struct CursorPart {
     pub hide_when_typing: Option<Flag>,
     #[knuffel(child, unwrap(argument))]
     pub hide_after_inactive_ms: Option<u32>,
+    #[knuffel(child, unwrap(argument))]
+    pub reveal_motion_threshold: Option<u16>,
 }
 
 impl MergeWith<CursorPart> for Cursor {
     fn merge_with(&mut self, part: &CursorPart) {
-        merge_clone!((self, part), xcursor_theme, xcursor_size);
+        merge_clone!(
+            (self, part),
+            xcursor_theme,
+            xcursor_size,
+            reveal_motion_threshold
+        );
         merge!((self, part), hide_when_typing);
         merge_clone_opt!((self, part), hide_after_inactive_ms);
     }
@@ -101,20 +110,42 @@ impl MergeWith<ConfigNotificationPart> for ConfigNotification {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Ipc {
+    pub pointer_window_events: bool,
+}
+
+#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IpcPart {
+    #[knuffel(child)]
+    pub pointer_window_events: Option<Flag>,
+}
+
+impl MergeWith<IpcPart> for Ipc {
+    fn merge_with(&mut self, part: &IpcPart) {
+        merge!((self, part), pointer_window_events);
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Clipboard {
     pub disable_primary: bool,
+    /// Number of clipboard selections to remember, 0 disables the history.
+    pub history_size: u16,
 }
 
 #[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct ClipboardPart {
     #[knuffel(child)]
     pub disable_primary: Option<Flag>,
+    #[knuffel(child, unwrap(argument))]
+    pub history_size: Option<u16>,
 }
 
 impl MergeWith<ClipboardPart> for Clipboard {
     fn merge_with(&mut self, part: &ClipboardPart) {
         merge!((self, part), disable_primary);
+        merge_clone!((self, part), history_size);
     }
 }
 
@@ -123,6 +154,8 @@ pub struct Overview {
     pub zoom: f64,
     pub backdrop_color: Color,
     pub workspace_shadow: WorkspaceShadow,
+    /// Number of columns to arrange the workspaces in, `None` for a vertical strip.
+    pub columns: Option<u8>,
 }
 
 impl Default for Overview {
@@ -131,6 +164,7 @@ impl Default for Overview {
             zoom: 0.5,
             backdrop_color: DEFAULT_BACKDROP_COLOR,
             workspace_shadow: WorkspaceShadow::default(),
+            columns: None,
         }
     }
 }
@@ -143,12 +177,15 @@ pub struct OverviewPart {
     pub backdrop_color: Option<Color>,
     #[knuffel(child)]
     pub workspace_shadow: Option<WorkspaceShadowPart>,
+    #[knuffel(child, unwrap(argument))]
+    pub columns: Option<u8>,
 }
 
 impl MergeWith<OverviewPart> for Overview {
     fn merge_with(&mut self, part: &OverviewPart) {
         merge!((self, part), zoom, workspace_shadow);
         merge_clone!((self, part), backdrop_color);
+        merge_clone_opt!((self, part), columns);
     }
 }
 
@@ -163,6 +200,32 @@ pub struct EnvironmentVariable {
     pub value: Option<String>,
 }
 
+/// Mapping of Wayland app IDs to .desktop file IDs.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq, Eq)]
+pub struct AppIdMap(#[knuffel(children(name = "map"))] pub Vec<AppIdMapping>);
+
+#[derive(knuffel::Decode, Debug, Clone, PartialEq, Eq)]
+pub struct AppIdMapping {
+    #[knuffel(argument, str)]
+    pub app_id: RegexEq,
+    #[knuffel(argument)]
+    pub desktop_id: String,
+}
+
+impl AppIdMap {
+    /// Returns the .desktop file ID for the app ID, without the `.desktop` suffix.
+    ///
+    /// Falls back to the app ID itself when no mapping matches.
+    pub fn desktop_id<'a>(&'a self, app_id: &'a str) -> &'a str {
+        let Some(mapping) = self.0.iter().find(|m| m.app_id.0.is_match(app_id)) else {
+            return app_id;
+        };
+
+        let id = &mapping.desktop_id;
+        id.strip_suffix(".desktop").unwrap_or(id)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct XwaylandSatellite {
     pub off: bool,
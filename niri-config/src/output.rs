@@ -13,6 +13,18 @@ use crate::{Color, FloatOrInt, LayoutPart};
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Outputs(pub Vec<Output>);
 
+/// How to round the scale that clients on an output render at.
+#[derive(knuffel::DecodeScalar, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleRounding {
+    /// Use the scale as is.
+    #[default]
+    Exact,
+    /// Round the scale to the nearest integer.
+    NearestInt,
+    /// Round the scale down to an integer.
+    Floor,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Mode {
     pub custom: bool,
@@ -55,6 +67,8 @@ pub struct Output {
     pub name: String,
     #[knuffel(child, unwrap(argument))]
     pub scale: Option<FloatOrInt<0, 10>>,
+    #[knuffel(child, unwrap(argument), default)]
+    pub scale_rounding: ScaleRounding,
     #[knuffel(child, unwrap(argument, str), default = Transform::Normal)]
     pub transform: Transform,
     #[knuffel(child)]
@@ -65,8 +79,14 @@ pub struct Output {
     pub modeline: Option<Modeline>,
     #[knuffel(child)]
     pub variable_refresh_rate: Option<Vrr>,
+    #[knuffel(child, unwrap(argument))]
+    pub color_profile: Option<String>,
+    #[knuffel(child, unwrap(argument))]
+    pub max_fps: Option<FloatOrInt<1, 1000>>,
     #[knuffel(child)]
     pub focus_at_startup: bool,
+    #[knuffel(child, unwrap(argument))]
+    pub default_workspace: Option<String>,
     // Deprecated; use layout.background_color.
     #[knuffel(child)]
     pub background_color: Option<Color>,
@@ -80,11 +100,15 @@ pub struct Output {
 
 impl Output {
     pub fn is_vrr_always_on(&self) -> bool {
-        self.variable_refresh_rate == Some(Vrr { on_demand: false })
+        self.variable_refresh_rate
+            .as_ref()
+            .is_some_and(|vrr| !vrr.on_demand)
     }
 
     pub fn is_vrr_on_demand(&self) -> bool {
-        self.variable_refresh_rate == Some(Vrr { on_demand: true })
+        self.variable_refresh_rate
+            .as_ref()
+            .is_some_and(|vrr| vrr.on_demand)
     }
 
     pub const fn is_vrr_always_off(&self) -> bool {
@@ -97,13 +121,17 @@ impl Default for Output {
         Self {
             off: false,
             focus_at_startup: false,
+            default_workspace: None,
             name: String::new(),
             scale: None,
+            scale_rounding: ScaleRounding::Exact,
             transform: Transform::Normal,
             position: None,
             mode: None,
             modeline: None,
             variable_refresh_rate: None,
+            color_profile: None,
+            max_fps: None,
             background_color: None,
             backdrop_color: None,
             hot_corners: None,
@@ -132,6 +160,11 @@ pub struct Position {
 pub struct Vrr {
     #[knuffel(property, default = false)]
     pub on_demand: bool,
+    /// Only enable on-demand VRR while the window has committed within this many milliseconds.
+    ///
+    /// `None` or `Some(0)` keeps VRR enabled regardless of whether the window is updating.
+    #[knuffel(property)]
+    pub on_demand_idle_ms: Option<u32>,
 }
 
 impl FromIterator<Output> for Outputs {
@@ -236,6 +269,20 @@ impl OutputName {
         true
     }
 
+    /// Matches by "make model", ignoring the connector and the serial.
+    ///
+    /// Useful for things like input device mapping, where the same monitor model may show up on a
+    /// different connector depending on the dock.
+    pub fn matches_make_model(&self, target: &str) -> bool {
+        if self.make.is_none() && self.model.is_none() {
+            return false;
+        }
+
+        let make = self.make.as_deref().unwrap_or("Unknown");
+        let model = self.model.as_deref().unwrap_or("Unknown");
+        target.eq_ignore_ascii_case(&format!("{make} {model}"))
+    }
+
     // Similar in spirit to Ord, but I don't want to derive Eq to avoid mistakes (you should use
     // `Self::match`, not Eq).
     pub fn compare(&self, other: &Self) -> std::cmp::Ordering {
@@ -525,6 +572,24 @@ mod tests {
         assert!("1920x1080@60Hz".parse::<ConfiguredMode>().is_err());
     }
 
+    #[test]
+    fn parse_default_workspace() {
+        let config = crate::Config::parse_mem(
+            r#"
+            output "DP-1" {
+                default-workspace "chat"
+            }
+
+            output "DP-2" {}
+            "#,
+        )
+        .unwrap();
+
+        let outputs = &config.outputs.0;
+        assert_eq!(outputs[0].default_workspace.as_deref(), Some("chat"));
+        assert_eq!(outputs[1].default_workspace, None);
+    }
+
     fn make_output_name(
         connector: &str,
         make: Option<&str>,
@@ -600,6 +665,20 @@ mod tests {
         assert!(!check("unknown unknown unknown", "DP-2", None, None, None));
     }
 
+    #[test]
+    fn test_output_name_match_make_model() {
+        let name = make_output_name("DP-2", Some("Some Company"), Some("Some Monitor"), None);
+        assert!(name.matches_make_model("some company some monitor"));
+        assert!(!name.matches_make_model("some company some monitor unknown"));
+        assert!(!name.matches_make_model("DP-2"));
+
+        let name = make_output_name("DP-2", None, Some("Model"), Some("1234"));
+        assert!(name.matches_make_model("unknown model"));
+
+        let name = make_output_name("DP-2", None, None, Some("1234"));
+        assert!(!name.matches_make_model("unknown unknown"));
+    }
+
     #[test]
     fn test_output_name_sorting() {
         let mut names = vec![
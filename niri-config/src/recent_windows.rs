@@ -11,6 +11,7 @@ pub struct RecentWindows {
     pub on: bool,
     pub debounce_ms: u16,
     pub open_delay_ms: u16,
+    pub focus_history_depth: u8,
     pub highlight: MruHighlight,
     pub previews: MruPreviews,
     pub binds: Vec<Bind>,
@@ -22,6 +23,7 @@ impl Default for RecentWindows {
             on: true,
             debounce_ms: 750,
             open_delay_ms: 150,
+            focus_history_depth: 2,
             highlight: MruHighlight::default(),
             previews: MruPreviews::default(),
             binds: default_binds(),
@@ -39,6 +41,8 @@ pub struct RecentWindowsPart {
     pub debounce_ms: Option<u16>,
     #[knuffel(child, unwrap(argument))]
     pub open_delay_ms: Option<u16>,
+    #[knuffel(child, unwrap(argument))]
+    pub focus_history_depth: Option<u8>,
     #[knuffel(child)]
     pub highlight: Option<MruHighlightPart>,
     #[knuffel(child)]
@@ -54,7 +58,12 @@ impl MergeWith<RecentWindowsPart> for RecentWindows {
             self.on = false;
         }
 
-        merge_clone!((self, part), debounce_ms, open_delay_ms);
+        merge_clone!(
+            (self, part),
+            debounce_ms,
+            open_delay_ms,
+            focus_history_depth
+        );
         merge!((self, part), highlight, previews);
 
         if let Some(part) = &part.binds {
@@ -151,6 +160,7 @@ impl From<MruBind> for Bind {
             action: Action::from(x.action),
             repeat: true,
             cooldown: None,
+            hold: None,
             allow_when_locked: false,
             allow_inhibiting: x.allow_inhibiting,
             hotkey_overlay_title: x.hotkey_overlay_title,
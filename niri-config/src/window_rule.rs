@@ -1,4 +1,6 @@
-use crate::appearance::{BlockOutFrom, BorderRule, CornerRadius, ShadowRule, TabIndicatorRule};
+use crate::appearance::{
+    BlockOutFrom, BorderRule, CornerRadius, InsertHintRule, ShadowRule, TabIndicatorRule,
+};
 use crate::layout::DefaultPresetSize;
 use crate::utils::RegexEq;
 use crate::{BlurRule, FloatOrInt};
@@ -32,6 +34,8 @@ pub struct WindowRule {
 
     // Rules applied dynamically.
     #[knuffel(child, unwrap(argument))]
+    pub force_floating: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
     pub min_width: Option<u16>,
     #[knuffel(child, unwrap(argument))]
     pub min_height: Option<u16>,
@@ -50,6 +54,8 @@ pub struct WindowRule {
     pub shadow: ShadowRule,
     #[knuffel(child, default)]
     pub tab_indicator: TabIndicatorRule,
+    #[knuffel(child, default)]
+    pub insert_hint: InsertHintRule,
     #[knuffel(child, unwrap(argument))]
     pub draw_border_with_background: Option<bool>,
     #[knuffel(child, unwrap(argument))]
@@ -63,6 +69,8 @@ pub struct WindowRule {
     #[knuffel(child, unwrap(argument))]
     pub block_out_from: Option<BlockOutFrom>,
     #[knuffel(child, unwrap(argument))]
+    pub allow_in_screencast: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
     pub variable_refresh_rate: Option<bool>,
     #[knuffel(child)]
     pub default_floating_position: Option<FloatingPosition>,
@@ -70,6 +78,8 @@ pub struct WindowRule {
     pub scroll_factor: Option<FloatOrInt<0, 100>>,
     #[knuffel(child, unwrap(argument))]
     pub tiled_state: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub prefer_server_decorations: Option<bool>,
 }
 
 #[derive(knuffel::Decode, Debug, Default, Clone, PartialEq, Eq)]
@@ -78,6 +88,10 @@ pub struct Match {
     pub app_id: Option<RegexEq>,
     #[knuffel(property, str)]
     pub title: Option<RegexEq>,
+    #[knuffel(property, str)]
+    pub process: Option<RegexEq>,
+    #[knuffel(property)]
+    pub pid: Option<i32>,
     #[knuffel(property)]
     pub is_active: Option<bool>,
     #[knuffel(property)]
@@ -87,6 +101,8 @@ pub struct Match {
     #[knuffel(property)]
     pub is_floating: Option<bool>,
     #[knuffel(property)]
+    pub is_fullscreen: Option<bool>,
+    #[knuffel(property)]
     pub is_window_cast_target: Option<bool>,
     #[knuffel(property)]
     pub is_urgent: Option<bool>,
@@ -117,6 +117,28 @@ pub enum Request {
     ReturnError,
     /// Request information about the overview.
     OverviewState,
+    /// Check a config file for errors without applying it.
+    ValidateConfig {
+        /// Absolute path to the config file to check.
+        ///
+        /// If `None`, checks the config file currently used by niri.
+        path: Option<String>,
+    },
+    /// Request the clipboard history.
+    ///
+    /// Only available when the clipboard history is enabled in the config.
+    ClipboardHistory,
+    /// Request detailed information about a window, useful for debugging window rules.
+    WindowInfo {
+        /// Id of the window to get information about.
+        ///
+        /// If `None`, uses the focused window.
+        id: Option<u64>,
+    },
+    /// Request the binds shown in the hotkey overlay.
+    ///
+    /// The list reflects the currently loaded config.
+    HotkeyOverlay,
 }
 
 /// Reply from niri to client.
@@ -161,6 +183,14 @@ pub enum Response {
     OutputConfigChanged(OutputConfigChanged),
     /// Information about the overview.
     OverviewState(Overview),
+    /// Config file validation result.
+    ConfigValidated(ConfigValidation),
+    /// Clipboard history, most recent entry first.
+    ClipboardHistory(Vec<ClipboardHistoryEntry>),
+    /// Detailed information about the requested window.
+    WindowInfo(Option<WindowInfo>),
+    /// Binds shown in the hotkey overlay, in the order that they appear there.
+    HotkeyOverlay(Vec<HotkeyOverlayEntry>),
 }
 
 /// Overview information.
@@ -198,6 +228,62 @@ pub enum Action {
     PowerOffMonitors {},
     /// Power on all monitors via DPMS.
     PowerOnMonitors {},
+    /// Power off a single monitor via DPMS.
+    PowerOffMonitor {
+        /// Name of the output to power off.
+        #[cfg_attr(feature = "clap", arg())]
+        output: String,
+    },
+    /// Power on a single monitor via DPMS.
+    PowerOnMonitor {
+        /// Name of the output to power on.
+        #[cfg_attr(feature = "clap", arg())]
+        output: String,
+    },
+    /// Set the scale of the focused monitor.
+    ///
+    /// The scale is clamped to the 0.5 to 4 range. Like `niri msg output`, the change is
+    /// transient and is lost on config reload.
+    SetOutputScale {
+        /// Scale to set.
+        #[cfg_attr(feature = "clap", arg())]
+        scale: f64,
+    },
+    /// Cycle the scale of the focused monitor through a list of scales.
+    ///
+    /// Switches to the scale after the current one, or to the first scale if the current one is
+    /// not in the list.
+    CycleOutputScale {
+        /// Scales to cycle through.
+        #[cfg_attr(feature = "clap", arg(required = true))]
+        scales: Vec<f64>,
+    },
+    /// Set the transform of the focused monitor.
+    ///
+    /// Like `niri msg output`, the change is transient and is lost on config reload.
+    RotateOutput {
+        /// Transform to set, counter-clockwise.
+        #[cfg_attr(feature = "clap", arg())]
+        transform: Transform,
+    },
+    /// Rotate the focused monitor by 90° counter-clockwise, keeping it flipped if it was.
+    CycleOutputTransform {},
+    /// Change the display backlight brightness by a percentage.
+    ///
+    /// The result is clamped to the 0 to 100 range. Only works on a TTY.
+    ChangeBacklight {
+        /// Percentage to add to the brightness, can be negative.
+        #[cfg_attr(feature = "clap", arg(allow_hyphen_values = true))]
+        delta: i32,
+    },
+    /// Set the display backlight brightness to a percentage.
+    ///
+    /// The brightness is clamped to 100. Only works on a TTY.
+    SetBacklight {
+        /// Brightness percentage to set.
+        #[cfg_attr(feature = "clap", arg())]
+        percent: u32,
+    },
     /// Spawn a command.
     Spawn {
         /// Command to spawn.
@@ -210,6 +296,16 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(last = true, required = true))]
         command: String,
     },
+    /// Spawn a command and open its first window on a named workspace.
+    SpawnOnWorkspace {
+        /// Name of the workspace to open the window on.
+        #[cfg_attr(feature = "clap", arg())]
+        workspace: String,
+
+        /// Command to spawn.
+        #[cfg_attr(feature = "clap", arg(last = true, required = true))]
+        command: Vec<String>,
+    },
     /// Do a screen transition.
     DoScreenTransition {
         /// Delay in milliseconds for the screen to freeze before starting the transition.
@@ -250,6 +346,31 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set))]
         path: Option<String>,
     },
+    /// Screenshot each output into a separate file.
+    ScreenshotEachOutput {
+        /// Write the screenshots to disk.
+        ///
+        /// If `false`, only the focused output is screenshotted and put in your clipboard, since
+        /// the clipboard can only hold one image.
+        #[cfg_attr(feature = "clap", arg(short = 'd', long, action = clap::ArgAction::Set, default_value_t = true))]
+        write_to_disk: bool,
+
+        /// Whether to include the mouse pointer in the screenshots.
+        #[cfg_attr(feature = "clap", arg(short = 'p', long, action = clap::ArgAction::Set, default_value_t = true))]
+        show_pointer: bool,
+
+        /// Path template to save the screenshots to.
+        ///
+        /// The path must be absolute, otherwise an error is returned. It is formatted with
+        /// `strftime()` like the `screenshot-path` config setting, and `{output}` is replaced
+        /// with the output name. Without `{output}`, the output name is appended to the file
+        /// name.
+        ///
+        /// If `None`, the screenshots are saved according to the `screenshot-path` config
+        /// setting.
+        #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set))]
+        path_template: Option<String>,
+    },
     /// Screenshot a window.
     #[cfg_attr(feature = "clap", clap(about = "Screenshot the focused window"))]
     ScreenshotWindow {
@@ -272,6 +393,23 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set))]
         path: Option<String>,
     },
+    /// Take a screenshot and pipe it into a command as PNG.
+    ///
+    /// The command receives the image on its stdin. The screenshot is not saved to disk or put in
+    /// your clipboard.
+    ScreenshotToCommand {
+        /// Command to run.
+        #[cfg_attr(feature = "clap", arg(last = true, required = true))]
+        command: Vec<String>,
+
+        /// What to screenshot.
+        #[cfg_attr(feature = "clap", arg(short, long, value_enum, default_value_t = ScreenshotTargetArg::Screen))]
+        target: ScreenshotTargetArg,
+
+        /// Whether to include the mouse pointer in the screenshot.
+        #[cfg_attr(feature = "clap", arg(short = 'p', long, action = clap::ArgAction::Set, default_value_t = true))]
+        show_pointer: bool,
+    },
     /// Enable or disable the keyboard shortcuts inhibitor (if any) for the focused surface.
     ToggleKeyboardShortcutsInhibit {},
     /// Close a window.
@@ -375,6 +513,14 @@ pub enum Action {
     FocusWindowDownOrTop {},
     /// Focus the window above or the bottommost window.
     FocusWindowUpOrBottom {},
+    /// Focus the nearest window in a direction by on-screen position.
+    ///
+    /// Considers both tiled and floating windows on the focused workspace.
+    FocusSpatial {
+        /// Direction to look for a window in.
+        #[cfg_attr(feature = "clap", arg(value_enum))]
+        direction: Direction,
+    },
     /// Move the focused column to the left.
     MoveColumnLeft {},
     /// Move the focused column to the right.
@@ -395,6 +541,14 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg())]
         index: usize,
     },
+    /// Move the focused column by a number of positions on its workspace.
+    ///
+    /// Stops at the first or last column.
+    MoveColumnByOffset {
+        /// Number of positions to move the column by, negative to move to the left.
+        #[cfg_attr(feature = "clap", arg(allow_hyphen_values = true))]
+        offset: i32,
+    },
     /// Move the focused window down in a column.
     MoveWindowDown {},
     /// Move the focused window up in a column.
@@ -431,6 +585,18 @@ pub enum Action {
     ConsumeWindowIntoColumn {},
     /// Expel the focused window from the column.
     ExpelWindowFromColumn {},
+    /// Expel the focused window into a new column to the left of its column.
+    ///
+    /// Focus follows the expelled window.
+    ExpelWindowToColumnLeft {},
+    /// Expel the focused window into a new column to the right of its column.
+    ///
+    /// Focus follows the expelled window.
+    ExpelWindowToColumnRight {},
+    /// Consume the focused window of the monitor to the left into the focused column.
+    ConsumeWindowFromMonitorLeft {},
+    /// Consume the focused window of the monitor to the right into the focused column.
+    ConsumeWindowFromMonitorRight {},
     /// Swap focused window with one to the right.
     SwapWindowRight {},
     /// Swap focused window with one to the left.
@@ -449,17 +615,37 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long))]
         id: Option<u64>,
     },
+    /// Center a window vertically within its column.
+    #[cfg_attr(
+        feature = "clap",
+        clap(about = "Center the focused window vertically within its column")
+    )]
+    CenterWindowVertically {
+        /// Id of the window to center.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
     /// Center all fully visible columns on the screen.
     CenterVisibleColumns {},
+    /// Resize all fully visible columns to equal widths filling the screen, then center them.
+    DistributeColumnsEvenly {},
     /// Focus the workspace below.
     FocusWorkspaceDown {},
     /// Focus the workspace above.
     FocusWorkspaceUp {},
-    /// Focus a workspace by reference (index or name).
+    /// Focus a workspace by reference (index, name or UUID).
     FocusWorkspace {
-        /// Reference (index or name) of the workspace to focus.
+        /// Reference (index, name or UUID) of the workspace to focus.
         #[cfg_attr(feature = "clap", arg())]
         reference: WorkspaceReferenceArg,
+
+        /// Create a workspace with this name on the focused monitor if it doesn't exist.
+        ///
+        /// Ignored for index and id references.
+        #[cfg_attr(feature = "clap", arg(long))]
+        create_if_missing: bool,
     },
     /// Focus the previous workspace.
     FocusWorkspacePrevious {},
@@ -484,7 +670,7 @@ pub enum Action {
     /// Move a window to a workspace.
     #[cfg_attr(
         feature = "clap",
-        clap(about = "Move the focused window to a workspace by reference (index or name)")
+        clap(about = "Move the focused window to a workspace by reference (index, name or UUID)")
     )]
     MoveWindowToWorkspace {
         /// Id of the window to move.
@@ -493,7 +679,7 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long))]
         window_id: Option<u64>,
 
-        /// Reference (index or name) of the workspace to move the window to.
+        /// Reference (index, name or UUID) of the workspace to move the window to.
         #[cfg_attr(feature = "clap", arg())]
         reference: WorkspaceReferenceArg,
 
@@ -505,6 +691,15 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set, default_value_t = true))]
         focus: bool,
     },
+    /// Move the focused window to the previous workspace.
+    MoveWindowToWorkspacePrevious {
+        /// Whether the focus should follow the target workspace.
+        ///
+        /// If `true` (the default), the focus will follow the window to the new workspace. If
+        /// `false`, the focus will remain on the original workspace.
+        #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set, default_value_t = true))]
+        focus: bool,
+    },
     /// Move the focused column to the workspace below.
     MoveColumnToWorkspaceDown {
         /// Whether the focus should follow the target workspace.
@@ -523,9 +718,9 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set, default_value_t = true))]
         focus: bool,
     },
-    /// Move the focused column to a workspace by reference (index or name).
+    /// Move the focused column to a workspace by reference (index, name or UUID).
     MoveColumnToWorkspace {
-        /// Reference (index or name) of the workspace to move the column to.
+        /// Reference (index, name or UUID) of the workspace to move the column to.
         #[cfg_attr(feature = "clap", arg())]
         reference: WorkspaceReferenceArg,
 
@@ -536,6 +731,15 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set, default_value_t = true))]
         focus: bool,
     },
+    /// Move the focused column to the previous workspace.
+    MoveColumnToWorkspacePrevious {
+        /// Whether the focus should follow the target workspace.
+        ///
+        /// If `true` (the default), the focus will follow the column to the new workspace. If
+        /// `false`, the focus will remain on the original workspace.
+        #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set, default_value_t = true))]
+        focus: bool,
+    },
     /// Move the focused workspace down.
     MoveWorkspaceDown {},
     /// Move the focused workspace up.
@@ -550,11 +754,15 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg())]
         index: usize,
 
-        /// Reference (index or name) of the workspace to move.
+        /// Reference (index, name or UUID) of the workspace to move.
         ///
         /// If `None`, uses the focused workspace.
         #[cfg_attr(feature = "clap", arg(long))]
         reference: Option<WorkspaceReferenceArg>,
+
+        /// Remove empty unnamed workspaces on the monitor after the move.
+        #[cfg_attr(feature = "clap", arg(long))]
+        compact: bool,
     },
     /// Set the name of a workspace.
     #[cfg_attr(
@@ -566,7 +774,7 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg())]
         name: String,
 
-        /// Reference (index or name) of the workspace to name.
+        /// Reference (index, name or UUID) of the workspace to name.
         ///
         /// If `None`, uses the focused workspace.
         #[cfg_attr(feature = "clap", arg(long))]
@@ -578,7 +786,7 @@ pub enum Action {
         clap(about = "Unset the name of the focused workspace")
     )]
     UnsetWorkspaceName {
-        /// Reference (index or name) of the workspace to unname.
+        /// Reference (index, name or UUID) of the workspace to unname.
         ///
         /// If `None`, uses the focused workspace.
         #[cfg_attr(feature = "clap", arg())]
@@ -596,12 +804,26 @@ pub enum Action {
     FocusMonitorPrevious {},
     /// Focus the next monitor.
     FocusMonitorNext {},
+    /// Focus the monitor to the left, wrapping around to the rightmost one.
+    FocusMonitorLeftWrap {},
+    /// Focus the monitor to the right, wrapping around to the leftmost one.
+    FocusMonitorRightWrap {},
+    /// Focus the monitor below, wrapping around to the topmost one.
+    FocusMonitorDownWrap {},
+    /// Focus the monitor above, wrapping around to the bottommost one.
+    FocusMonitorUpWrap {},
     /// Focus a monitor by name.
     FocusMonitor {
         /// Name of the output to focus.
         #[cfg_attr(feature = "clap", arg())]
         output: String,
     },
+    /// Focus the monitor that has a named workspace.
+    FocusMonitorByWorkspace {
+        /// Name of the workspace.
+        #[cfg_attr(feature = "clap", arg())]
+        workspace: String,
+    },
     /// Move the focused window to the monitor to the left.
     MoveWindowToMonitorLeft {},
     /// Move the focused window to the monitor to the right.
@@ -630,6 +852,48 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg())]
         output: String,
     },
+    /// Move a window back to the output it was on before that output got disconnected.
+    #[cfg_attr(
+        feature = "clap",
+        clap(about = "Move the focused window back to the output it was on before a disconnect")
+    )]
+    MoveWindowToLastOutput {
+        /// Id of the window to move.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Move the focused window to a workspace on a specific monitor.
+    #[cfg_attr(
+        feature = "clap",
+        clap(about = "Move the focused window to a workspace on a specific monitor")
+    )]
+    MoveWindowToMonitorWorkspace {
+        /// The target output name.
+        #[cfg_attr(feature = "clap", arg())]
+        output: String,
+
+        /// Reference (index, name or UUID) of the workspace to move the window to.
+        ///
+        /// Indices count the workspaces of the target monitor. Name, UUID and id references must
+        /// point to a workspace that is on the target monitor.
+        #[cfg_attr(feature = "clap", arg())]
+        workspace: WorkspaceReferenceArg,
+
+        /// Whether the focus should follow the moved window.
+        ///
+        /// If `true` (the default), the focus will follow the window to the target monitor. If
+        /// `false`, the focus will remain on the original workspace.
+        #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set, default_value_t = true))]
+        focus: bool,
+
+        /// Create a workspace with this name on the target monitor if it doesn't exist.
+        ///
+        /// Only applies to name references.
+        #[cfg_attr(feature = "clap", arg(long))]
+        create_if_missing: bool,
+    },
     /// Move the focused column to the monitor to the left.
     MoveColumnToMonitorLeft {},
     /// Move the focused column to the monitor to the right.
@@ -648,6 +912,10 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg())]
         output: String,
     },
+    /// Swap the focused column with the focused column of the monitor to the left.
+    SwapColumnToMonitorLeft {},
+    /// Swap the focused column with the focused column of the monitor to the right.
+    SwapColumnToMonitorRight {},
     /// Change the width of a window.
     #[cfg_attr(
         feature = "clap",
@@ -692,6 +960,18 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long))]
         id: Option<u64>,
     },
+    /// Make a window fill the height left over by the other windows in its column.
+    #[cfg_attr(
+        feature = "clap",
+        clap(about = "Make the focused window fill the leftover height in its column")
+    )]
+    FillWindowHeightInColumn {
+        /// Id of the window that should fill the column.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
     /// Switch between preset column widths.
     SwitchPresetColumnWidth {},
     /// Switch between preset column widths backwards.
@@ -730,6 +1010,17 @@ pub enum Action {
     },
     /// Toggle the maximized state of the focused column.
     MaximizeColumn {},
+    /// Toggle the focused column between full width with all its windows in one group, and its
+    /// previous state.
+    ToggleMaximizeColumnWithGroup {},
+    /// Set the display mode of the focused window group.
+    SetGroupDisplayMode {
+        /// Display mode to set.
+        #[cfg_attr(feature = "clap", arg(value_enum))]
+        mode: GroupDisplayMode,
+    },
+    /// Toggle the focused window group between tabbed and stacked display.
+    ToggleGroupDisplayMode {},
     /// Toggle the maximized-to-edges state of the focused window.
     MaximizeWindowToEdges {
         /// Id of the window to maximize.
@@ -738,6 +1029,15 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long))]
         id: Option<u64>,
     },
+    /// Toggle the maximized-to-edges state of the focused window, putting it back into its
+    /// original column when unmaximizing.
+    ToggleMaximizeWindowToEdges {
+        /// Id of the window to maximize.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
     /// Change the width of the focused column.
     SetColumnWidth {
         /// How to change the width.
@@ -746,12 +1046,30 @@ pub enum Action {
     },
     /// Expand the focused column to space not taken up by other fully visible columns.
     ExpandColumnToAvailableWidth {},
+    /// Toggle between expanding the focused column to available width and its previous width.
+    ToggleColumnExpandedWidth {},
+    /// Resize the focused column so that its left neighbor lines up with the left edge of the
+    /// working area.
+    ///
+    /// Does nothing if there's no column to the left.
+    AlignColumnWidthToNeighborLeft {},
+    /// Resize the focused column so that its right neighbor lines up with the right edge of the
+    /// working area.
+    ///
+    /// Does nothing if there's no column to the right.
+    AlignColumnWidthToNeighborRight {},
+    /// Toggle removing all gaps and struts.
+    ///
+    /// This lasts until toggled back or until the config is reloaded.
+    ToggleZeroGaps {},
     /// Switch between keyboard layouts.
     SwitchLayout {
         /// Layout to switch to.
         #[cfg_attr(feature = "clap", arg())]
         layout: LayoutSwitchTarget,
     },
+    /// Toggle the num lock state of the keyboard.
+    ToggleNumLock {},
     /// Show the hotkey overlay.
     ShowHotkeyOverlay {},
     /// Move the focused workspace to the monitor to the left.
@@ -766,6 +1084,8 @@ pub enum Action {
     MoveWorkspaceToMonitorPrevious {},
     /// Move the focused workspace to the next monitor.
     MoveWorkspaceToMonitorNext {},
+    /// Move the focused workspace to the monitor under the mouse cursor.
+    MoveWorkspaceToMonitorUnderMouse {},
     /// Move a workspace to a specific monitor.
     #[cfg_attr(
         feature = "clap",
@@ -776,18 +1096,36 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg())]
         output: String,
 
-        // Reference (index or name) of the workspace to move.
+        // Reference (index, name or UUID) of the workspace to move.
         ///
         /// If `None`, uses the focused workspace.
         #[cfg_attr(feature = "clap", arg(long))]
         reference: Option<WorkspaceReferenceArg>,
     },
+    /// Toggle mirroring the focused monitor onto another monitor.
+    ToggleMirrorToMonitor {
+        /// Name of the output to show the focused monitor on.
+        #[cfg_attr(feature = "clap", arg())]
+        output: String,
+    },
+    /// Mirror one monitor onto another.
+    MirrorMonitor {
+        /// Name of the output to mirror.
+        #[cfg_attr(feature = "clap", arg(long))]
+        source: String,
+
+        /// Name of the output to show the mirrored contents on.
+        #[cfg_attr(feature = "clap", arg(long))]
+        target: String,
+    },
     /// Toggle a debug tint on windows.
     ToggleDebugTint {},
     /// Toggle visualization of render element opaque regions.
     DebugToggleOpaqueRegions {},
     /// Toggle visualization of output damage.
     DebugToggleDamage {},
+    /// Toggle visualization of render element draw times.
+    DebugToggleRenderHeatmap {},
     /// Move the focused window between the floating and the tiling layout.
     ToggleWindowFloating {
         /// Id of the window to move.
@@ -816,8 +1154,23 @@ pub enum Action {
     FocusFloating {},
     /// Switches focus to the tiling layout.
     FocusTiling {},
+    /// Focus the next floating window on the workspace in stacking order, and raise it.
+    ///
+    /// Wraps around at the bottom of the stack.
+    FocusNextFloatingWindow {},
+    /// Focus the previous floating window on the workspace in stacking order, and raise it.
+    ///
+    /// Wraps around at the top of the stack.
+    FocusPreviousFloatingWindow {},
     /// Toggles the focus between the floating and the tiling layout.
     SwitchFocusBetweenFloatingAndTiling {},
+    /// Focus the window under the mouse cursor, switching to its floating or tiling layout.
+    FocusFloatingOrTilingUnderMouse {},
+    /// Toggle the keyboard resize mode for the focused floating window.
+    ///
+    /// While the mode is active, the arrow keys resize the window, Return confirms the new size
+    /// and Escape restores the original size.
+    ToggleFloatingResizeMode {},
     /// Move a floating window on screen.
     #[cfg_attr(feature = "clap", clap(about = "Move the floating window on screen"))]
     MoveFloatingWindow {
@@ -853,6 +1206,34 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long))]
         id: Option<u64>,
     },
+    /// Set the opacity of a window, overriding the opacity window rule.
+    ///
+    /// The opacity is clamped between 0.05 and 1.
+    #[cfg_attr(
+        feature = "clap",
+        clap(about = "Set the opacity of the focused window")
+    )]
+    SetWindowOpacity {
+        /// Id of the window.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+
+        /// Opacity to set, from 0 to 1.
+        #[cfg_attr(feature = "clap", arg())]
+        opacity: f32,
+    },
+    /// Toggle between requesting server-side and client-side decorations from a window.
+    ///
+    /// Only works for windows that support the xdg-decoration protocol.
+    ToggleServerDecorations {
+        /// Id of the window.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
     /// Set the dynamic cast target to a window.
     #[cfg_attr(
         feature = "clap",
@@ -881,6 +1262,8 @@ pub enum Action {
     ClearDynamicCastTarget {},
     /// Toggle (open/close) the Overview.
     ToggleOverview {},
+    /// Toggle (open/close) the Overview only on the focused monitor.
+    ToggleOverviewFocusedMonitor {},
     /// Open the Overview.
     OpenOverview {},
     /// Close the Overview.
@@ -891,6 +1274,10 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long))]
         id: u64,
     },
+    /// Toggle urgent status of the focused window.
+    ///
+    /// The window stays urgent until it's focused again.
+    ToggleFocusedWindowUrgent {},
     /// Set urgent status of a window.
     SetWindowUrgent {
         /// Id of the window to set urgent.
@@ -908,6 +1295,17 @@ pub enum Action {
     /// Can be useful for scripts changing the config file, to avoid waiting the small duration for
     /// niri's config file watcher to notice the changes.
     LoadConfigFile {},
+    /// Reload only the binds from the config file.
+    ///
+    /// Other config sections are left untouched, so layout and animation state is not disrupted.
+    /// If the layout or input sections changed, the whole config is reloaded instead.
+    ReloadBinds {},
+    /// Set an entry from the clipboard history as the current clipboard selection.
+    RestoreClipboardHistoryEntry {
+        /// Index of the entry, 0 being the most recent one.
+        #[cfg_attr(feature = "clap", arg())]
+        index: usize,
+    },
 }
 
 /// Change in window or column size.
@@ -918,10 +1316,19 @@ pub enum SizeChange {
     SetFixed(i32),
     /// Set the size as a proportion of the working area.
     SetProportion(f64),
+    /// Set the size as a proportion of the full output size.
+    ///
+    /// Unlike [`SizeChange::SetProportion`], this ignores gaps and struts, so e.g. 50% is
+    /// literally half of the monitor. The result is rounded to physical pixels.
+    SetOutputProportion(f64),
     /// Add or subtract to the current size in logical pixels.
     AdjustFixed(i32),
     /// Add or subtract to the current size as a proportion of the working area.
     AdjustProportion(f64),
+    /// Set the size to whatever is left over by the other columns.
+    ///
+    /// For column widths, this keeps following the other columns until the column is resized.
+    SetRest,
 }
 
 /// Change in floating window position.
@@ -938,7 +1345,7 @@ pub enum PositionChange {
     AdjustProportion(f64),
 }
 
-/// Workspace reference (id, index or name) to operate on.
+/// Workspace reference (id, index, name or UUID) to operate on.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum WorkspaceReferenceArg {
@@ -948,6 +1355,11 @@ pub enum WorkspaceReferenceArg {
     Index(u8),
     /// Name of the workspace.
     Name(String),
+    /// Stable UUID of a named workspace.
+    ///
+    /// When parsing from a string, UUIDs need the `uuid:` prefix, so that workspace names that
+    /// look like a UUID keep working.
+    Uuid(String),
 }
 
 /// Layout to switch to.
@@ -962,6 +1374,47 @@ pub enum LayoutSwitchTarget {
     Index(u8),
 }
 
+/// What to take a screenshot of.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum ScreenshotTargetArg {
+    /// The focused screen.
+    #[default]
+    Screen,
+    /// The focused window.
+    Window,
+    /// All screens together.
+    AllOutputs,
+}
+
+/// Direction on the screen.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum Direction {
+    /// To the left.
+    Left,
+    /// To the right.
+    Right,
+    /// Upwards.
+    Up,
+    /// Downwards.
+    Down,
+}
+
+/// How a group of windows in a tile is displayed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum GroupDisplayMode {
+    /// Show only the active window, with a tab indicator for the others.
+    #[default]
+    Tabbed,
+    /// Show all windows at once, split vertically.
+    Stacked,
+}
+
 /// Output actions that niri can perform.
 // Variants in this enum should match the spelling of the ones in niri-config. Most thigs from
 // niri-config should be present here.
@@ -1291,6 +1744,38 @@ pub struct Window {
     pub focus_timestamp: Option<Timestamp>,
 }
 
+/// Detailed information about a window.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct WindowInfo {
+    /// Same information as in the window list.
+    pub window: Window,
+    /// Whether this window is currently fullscreen.
+    pub is_fullscreen: bool,
+    /// Indices of the window rules that matched this window.
+    ///
+    /// The indices are 0-based and follow the order of the `window-rule` sections in the config,
+    /// including the ones from included config files.
+    pub matched_rules: Vec<usize>,
+}
+
+/// Bind shown in the hotkey overlay.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct HotkeyOverlayEntry {
+    /// Key combination of the bind, formatted for display, for example `Super + T`.
+    ///
+    /// `None` if the action is shown in the overlay without being bound.
+    pub key: Option<String>,
+    /// Title shown for the bind.
+    ///
+    /// This is either the `hotkey-overlay-title` from the config, or the default title of the
+    /// action. Pango markup is removed.
+    pub title: String,
+    /// Name of the bound action as written in the config, for example `focus-column-left`.
+    pub action: String,
+}
+
 /// A moment in time.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
@@ -1357,6 +1842,31 @@ pub enum OutputConfigChanged {
     OutputWasMissing,
 }
 
+/// Config file validation result.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum ConfigValidation {
+    /// The config file parsed successfully.
+    Valid,
+    /// The config file failed to parse.
+    Invalid {
+        /// Formatted error, including the diagnostics pointing at the problematic config lines.
+        error: String,
+    },
+}
+
+/// Entry in the clipboard history.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ClipboardHistoryEntry {
+    /// Mime type of the recorded data.
+    pub mime_type: String,
+    /// Size of the recorded data in bytes.
+    pub size: usize,
+    /// Recorded text, if this is a text entry.
+    pub text: Option<String>,
+}
+
 /// A workspace.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
@@ -1380,6 +1890,12 @@ pub struct Workspace {
     pub idx: u8,
     /// Optional name of the workspace.
     pub name: Option<String>,
+    /// Stable UUID of the workspace.
+    ///
+    /// Only named workspaces have a UUID. Unlike [`Self::id`], it stays the same across niri
+    /// restarts, as long as the workspace keeps its name.
+    #[serde(default)]
+    pub uuid: Option<String>,
     /// Name of the output that the workspace is on.
     ///
     /// Can be `None` if no outputs are currently connected.
@@ -1525,6 +2041,14 @@ pub enum Event {
         /// The new focus timestamp.
         focus_timestamp: Option<Timestamp>,
     },
+    /// The window under the pointer changed.
+    ///
+    /// Only sent when `ipc { pointer-window-events; }` is set in the niri config. Rapid changes are
+    /// debounced, so intermediate windows that the pointer merely passed over may be skipped.
+    WindowUnderPointerChanged {
+        /// Id of the window now under the pointer, or `None` if there's no window under it.
+        id: Option<u64>,
+    },
     /// Window urgency changed.
     WindowUrgencyChanged {
         /// Id of the window.
@@ -1597,6 +2121,12 @@ impl FromStr for WorkspaceReferenceArg {
             } else {
                 return Err("workspace index must be between 0 and 255");
             }
+        } else if let Some(uuid) = s.strip_prefix("uuid:") {
+            if Self::is_uuid(uuid) {
+                Self::Uuid(uuid.to_string())
+            } else {
+                return Err("workspace UUID must be in the 8-4-4-4-12 hexadecimal form");
+            }
         } else {
             Self::Name(s.to_string())
         };
@@ -1605,12 +2135,36 @@ impl FromStr for WorkspaceReferenceArg {
     }
 }
 
+impl WorkspaceReferenceArg {
+    /// Returns whether the string is a UUID in the canonical hyphenated form.
+    pub fn is_uuid(s: &str) -> bool {
+        s.len() == 36
+            && s.char_indices().all(|(i, c)| match i {
+                8 | 13 | 18 | 23 => c == '-',
+                _ => c.is_ascii_hexdigit(),
+            })
+    }
+}
+
 impl FromStr for SizeChange {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((value, empty)) = s.split_once('%') {
-            if !empty.is_empty() {
+        if s == "rest" {
+            Ok(Self::SetRest)
+        } else if let Some((value, suffix)) = s.split_once('%') {
+            if suffix == "out" {
+                return match value.bytes().next() {
+                    Some(b'-' | b'+') => Err("output proportion cannot be adjusted, only set"),
+                    Some(_) => {
+                        let value = value.parse().map_err(|_| "error parsing value")?;
+                        Ok(Self::SetOutputProportion(value))
+                    }
+                    None => Err("value is missing"),
+                };
+            }
+
+            if !suffix.is_empty() {
                 return Err("trailing characters after '%' are not allowed");
             }
 
@@ -1694,6 +2248,45 @@ impl FromStr for LayoutSwitchTarget {
     }
 }
 
+impl FromStr for ScreenshotTargetArg {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "screen" => Ok(Self::Screen),
+            "window" => Ok(Self::Window),
+            "all-outputs" => Ok(Self::AllOutputs),
+            _ => Err(r#"invalid screenshot target, can be "screen", "window" or "all-outputs""#),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "up" => Ok(Self::Up),
+            "down" => Ok(Self::Down),
+            _ => Err(r#"invalid direction, can be "left", "right", "up" or "down""#),
+        }
+    }
+}
+
+impl FromStr for GroupDisplayMode {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tabbed" => Ok(Self::Tabbed),
+            "stacked" => Ok(Self::Stacked),
+            _ => Err(r#"invalid group display mode, can be "tabbed" or "stacked""#),
+        }
+    }
+}
+
 impl FromStr for Transform {
     type Err = &'static str;
 
@@ -1905,8 +2498,20 @@ mod tests {
             SizeChange::AdjustProportion(-10.),
         );
 
+        assert_eq!(
+            "50%out".parse::<SizeChange>().unwrap(),
+            SizeChange::SetOutputProportion(50.),
+        );
+        assert_eq!("rest".parse::<SizeChange>().unwrap(), SizeChange::SetRest);
+
         assert!("-".parse::<SizeChange>().is_err());
         assert!("10% ".parse::<SizeChange>().is_err());
+        assert!("rest%".parse::<SizeChange>().is_err());
+        assert!("+rest".parse::<SizeChange>().is_err());
+        assert!("+10%out".parse::<SizeChange>().is_err());
+        assert!("%out".parse::<SizeChange>().is_err());
+        assert!("10%outs".parse::<SizeChange>().is_err());
+        assert!(" rest".parse::<SizeChange>().is_err());
     }
 
     #[test]
@@ -1939,4 +2544,32 @@ mod tests {
         assert!("-".parse::<PositionChange>().is_err());
         assert!("10% ".parse::<PositionChange>().is_err());
     }
+
+    #[test]
+    fn parse_workspace_reference() {
+        let uuid = "0b6f3d7e-4c1a-4f2e-9a55-6e2f0c3b8d41";
+
+        assert_eq!(
+            "3".parse::<WorkspaceReferenceArg>().unwrap(),
+            WorkspaceReferenceArg::Index(3),
+        );
+        assert_eq!(
+            "chat".parse::<WorkspaceReferenceArg>().unwrap(),
+            WorkspaceReferenceArg::Name(String::from("chat")),
+        );
+        // A name that looks like a UUID stays a name.
+        assert_eq!(
+            uuid.parse::<WorkspaceReferenceArg>().unwrap(),
+            WorkspaceReferenceArg::Name(String::from(uuid)),
+        );
+        assert_eq!(
+            format!("uuid:{uuid}")
+                .parse::<WorkspaceReferenceArg>()
+                .unwrap(),
+            WorkspaceReferenceArg::Uuid(String::from(uuid)),
+        );
+
+        assert!("256".parse::<WorkspaceReferenceArg>().is_err());
+        assert!("uuid:chat".parse::<WorkspaceReferenceArg>().is_err());
+    }
 }
@@ -46,6 +46,9 @@ pub struct EventStreamState {
 
     /// State of the config.
     pub config: ConfigState,
+
+    /// State of the window under the pointer.
+    pub pointer_window: PointerWindowState,
 }
 
 /// The workspaces state communicated over the event stream.
@@ -83,6 +86,16 @@ pub struct ConfigState {
     pub failed: bool,
 }
 
+/// The window under the pointer state communicated over the event stream.
+#[derive(Debug, Default)]
+pub struct PointerWindowState {
+    /// Id of the window under the pointer.
+    ///
+    /// This is `None` until niri sends the first [`Event::WindowUnderPointerChanged`], which only
+    /// happens when pointer window events are enabled in the config.
+    pub window_id: Option<Option<u64>>,
+}
+
 impl EventStreamStatePart for EventStreamState {
     fn replicate(&self) -> Vec<Event> {
         let mut events = Vec::new();
@@ -91,6 +104,7 @@ impl EventStreamStatePart for EventStreamState {
         events.extend(self.keyboard_layouts.replicate());
         events.extend(self.overview.replicate());
         events.extend(self.config.replicate());
+        events.extend(self.pointer_window.replicate());
         events
     }
 
@@ -100,6 +114,7 @@ impl EventStreamStatePart for EventStreamState {
         let event = self.keyboard_layouts.apply(event)?;
         let event = self.overview.apply(event)?;
         let event = self.config.apply(event)?;
+        let event = self.pointer_window.apply(event)?;
         Some(event)
     }
 }
@@ -285,3 +300,23 @@ impl EventStreamStatePart for ConfigState {
         None
     }
 }
+
+impl EventStreamStatePart for PointerWindowState {
+    fn replicate(&self) -> Vec<Event> {
+        if let Some(id) = self.window_id {
+            vec![Event::WindowUnderPointerChanged { id }]
+        } else {
+            vec![]
+        }
+    }
+
+    fn apply(&mut self, event: Event) -> Option<Event> {
+        match event {
+            Event::WindowUnderPointerChanged { id } => {
+                self.window_id = Some(id);
+            }
+            event => return Some(event),
+        }
+        None
+    }
+}
@@ -125,6 +125,22 @@ impl Backend {
         }
     }
 
+    pub fn change_backlight(&mut self, delta: i32) {
+        match self {
+            Self::Tty(tty) => tty.change_backlight(delta),
+            Self::Winit(_) => (),
+            Self::Headless(_) => (),
+        }
+    }
+
+    pub fn set_backlight(&mut self, percent: u32) {
+        match self {
+            Self::Tty(tty) => tty.set_backlight(percent),
+            Self::Winit(_) => (),
+            Self::Headless(_) => (),
+        }
+    }
+
     pub fn toggle_debug_tint(&mut self) {
         match self {
             Self::Tty(tty) => tty.toggle_debug_tint(),
@@ -177,6 +193,14 @@ impl Backend {
         }
     }
 
+    pub fn set_output_active(&mut self, output: &Output, active: bool) {
+        match self {
+            Self::Tty(tty) => tty.set_output_active(output, active),
+            Self::Winit(_) => (),
+            Self::Headless(_) => (),
+        }
+    }
+
     pub fn set_output_on_demand_vrr(&mut self, niri: &mut Niri, output: &Output, enable_vrr: bool) {
         match self {
             Self::Tty(tty) => tty.set_output_on_demand_vrr(niri, output, enable_vrr),
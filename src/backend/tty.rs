@@ -4,11 +4,11 @@ use std::fmt::Write;
 use std::iter::zip;
 use std::num::NonZeroU64;
 use std::os::fd::{AsFd, OwnedFd};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use std::{io, mem};
+use std::{fs, io, mem};
 
 use anyhow::{Context, anyhow, bail, ensure};
 use bytemuck::cast_slice_mut;
@@ -70,6 +70,7 @@ use crate::render_helpers::debug::draw_damage;
 use crate::render_helpers::render_data::RendererData;
 use crate::render_helpers::renderer::AsGlesRenderer;
 use crate::render_helpers::{RenderTarget, resources, shaders};
+use crate::utils::color_profile;
 use crate::utils::{PanelOrientation, get_monotonic_time, is_laptop_panel, logical_output};
 
 const SUPPORTED_COLOR_FORMATS: [Fourcc; 4] = [
@@ -79,6 +80,11 @@ const SUPPORTED_COLOR_FORMATS: [Fourcc; 4] = [
     Fourcc::Abgr8888,
 ];
 
+const BACKLIGHT_DIR: &str = "/sys/class/backlight";
+
+/// Minimum interval between backlight changes.
+const BACKLIGHT_DEBOUNCE: Duration = Duration::from_millis(50);
+
 pub struct Tty {
     config: Rc<RefCell<Config>>,
     session: LibSeatSession,
@@ -104,6 +110,10 @@ pub struct Tty {
     update_ignored_nodes_on_resume: bool,
     // Whether the debug tinting is enabled.
     debug_tint: bool,
+    // Time of the last backlight change, used to debounce rapid presses.
+    last_backlight_change: Option<Duration>,
+    // Backlight device, found on first use.
+    backlight: Option<Backlight>,
     ipc_outputs: Arc<Mutex<IpcOutputMap>>,
 }
 
@@ -383,6 +393,14 @@ struct Surface {
     gamma_props: Option<GammaProps>,
     /// Gamma change to apply upon session resume.
     pending_gamma_change: Option<Option<Vec<u16>>>,
+    /// Path to the configured color profile.
+    color_profile: Option<String>,
+    /// Gamma ramp from the color profile, applied while no client controls the gamma.
+    color_profile_ramp: Option<Vec<u16>>,
+    /// Configured frame rate cap.
+    max_fps: Option<f64>,
+    /// Target presentation time of the last frame queued to the DRM.
+    last_queued_frame_time: Option<Duration>,
     /// Tracy frame that goes from vblank to vblank.
     vblank_frame: Option<tracy_client::Frame>,
     /// Frame name for the VBlank frame.
@@ -508,6 +526,8 @@ impl Tty {
             update_output_config_on_resume: false,
             update_ignored_nodes_on_resume: false,
             debug_tint: false,
+            last_backlight_change: None,
+            backlight: None,
             ipc_outputs: Arc::new(Mutex::new(HashMap::new())),
         })
     }
@@ -716,9 +736,8 @@ impl Tty {
                 self.refresh_ipc_outputs(niri);
 
                 niri.notify_activity();
-                niri.monitors_active = true;
+                niri.resume_monitors();
                 self.set_monitors_active(true);
-                niri.queue_redraw_all();
             }
         }
     }
@@ -1287,11 +1306,18 @@ impl Tty {
             .map_err(|err| debug!("couldn't get gamma properties: {err:?}"))
             .ok();
 
+        let color_profile_ramp = load_color_profile(
+            config.color_profile.as_deref(),
+            || surface_gamma_size(&device.drm, crtc, gamma_props.as_ref()),
+            &connector_name,
+        );
+
         // Reset gamma in case it was set before.
+        let ramp = color_profile_ramp.as_deref();
         let res = if let Some(gamma_props) = &mut gamma_props {
-            gamma_props.set_gamma(&device.drm, None)
+            gamma_props.set_gamma(&device.drm, ramp)
         } else {
-            set_gamma_for_crtc(&device.drm, crtc, None)
+            set_gamma_for_crtc(&device.drm, crtc, ramp)
         };
         if let Err(err) = res {
             debug!("couldn't reset gamma: {err:?}");
@@ -1497,6 +1523,10 @@ impl Tty {
             dmabuf_feedback,
             gamma_props,
             pending_gamma_change: None,
+            color_profile: config.color_profile.clone(),
+            color_profile_ramp,
+            max_fps: config.max_fps.map(|fps| fps.0),
+            last_queued_frame_time: None,
             vblank_frame: None,
             vblank_frame_name,
             time_since_presentation_plot_name,
@@ -1508,6 +1538,7 @@ impl Tty {
         assert!(res.is_none(), "crtc must not have already existed");
 
         niri.add_output(output.clone(), Some(refresh_interval(mode)), vrr_enabled);
+        niri.activate_output_default_workspace(&output);
 
         let mut renderer = self.gpu_manager.single_renderer(&render_node)?;
         EffectsFramebuffers::init_for_output(&output, &mut renderer, None);
@@ -1837,6 +1868,32 @@ impl Tty {
             return rv;
         }
 
+        // Skip frames that come too soon after the last one to honor the frame rate cap. The cap
+        // doesn't apply with VRR active, and while locking so as to not delay the locked frame.
+        if let Some(max_fps) = surface.max_fps
+            && let Some(last) = surface.last_queued_frame_time
+            && !niri.is_locked()
+        {
+            let output_state = niri.output_state.get_mut(output).unwrap();
+            let refresh_interval = output_state.frame_clock.refresh_interval();
+            if !output_state.frame_clock.vrr()
+                && !should_render_capped_frame(
+                    max_fps,
+                    refresh_interval,
+                    last,
+                    target_presentation_time,
+                )
+            {
+                // Make sure we try again at the next estimated vblank, even if nothing else
+                // queues a redraw until then.
+                output_state.unfinished_animations_remain = true;
+
+                drop(surface.vblank_frame.take());
+                queue_estimated_vblank_timer(niri, output.clone(), target_presentation_time);
+                return RenderResult::NoDamage;
+            }
+        }
+
         let mut renderer = match self.gpu_manager.renderer(
             &self.primary_render_node,
             &device.render_node.unwrap_or(self.primary_render_node),
@@ -1850,8 +1907,11 @@ impl Tty {
         };
 
         // Render the elements.
-        let mut elements =
-            niri.render::<TtyRenderer>(&mut renderer, output, true, RenderTarget::Output);
+        let mut elements = if let Some(source) = niri.mirrored_outputs.get(output) {
+            niri.render_mirror::<TtyRenderer>(&mut renderer, output, source)
+        } else {
+            niri.render::<TtyRenderer>(&mut renderer, output, true, RenderTarget::Output)
+        };
 
         // Visualize the damage, if enabled.
         if niri.debug_draw_damage {
@@ -1940,6 +2000,8 @@ impl Tty {
                             output_state.frame_callback_sequence =
                                 output_state.frame_callback_sequence.wrapping_add(1);
 
+                            surface.last_queued_frame_time = Some(target_presentation_time);
+
                             return RenderResult::Submitted;
                         }
                         Err(err) => {
@@ -1978,6 +2040,44 @@ impl Tty {
         }
     }
 
+    pub fn change_backlight(&mut self, delta: i32) {
+        self.update_backlight(|current| current.saturating_add_signed(delta));
+    }
+
+    pub fn set_backlight(&mut self, percent: u32) {
+        self.update_backlight(|_| percent);
+    }
+
+    fn update_backlight(&mut self, f: impl FnOnce(u32) -> u32) {
+        let now = get_monotonic_time();
+        if let Some(last) = self.last_backlight_change {
+            if now.saturating_sub(last) < BACKLIGHT_DEBOUNCE {
+                return;
+            }
+        }
+        self.last_backlight_change = Some(now);
+
+        if self.backlight.is_none() {
+            match Backlight::find() {
+                Ok(backlight) => self.backlight = Some(backlight),
+                Err(err) => {
+                    warn!("error finding backlight device: {err:?}");
+                    return;
+                }
+            }
+        }
+        let backlight = self.backlight.as_ref().unwrap();
+
+        let res = backlight
+            .percent()
+            .and_then(|current| backlight.set_percent(f(current).min(100)));
+        if let Err(err) = res {
+            warn!("error changing backlight: {err:?}");
+            // The device may have gone away, so look it up again next time.
+            self.backlight = None;
+        }
+    }
+
     pub fn toggle_debug_tint(&mut self) {
         self.debug_tint = !self.debug_tint;
 
@@ -2033,15 +2133,7 @@ impl Tty {
             .context("missing device")?;
 
         let surface = device.surfaces.get(&crtc).context("missing surface")?;
-        if let Some(gamma_props) = &surface.gamma_props {
-            gamma_props.gamma_size(&device.drm)
-        } else {
-            let info = device
-                .drm
-                .get_crtc(crtc)
-                .context("error getting crtc info")?;
-            Ok(info.gamma_length())
-        }
+        surface_gamma_size(&device.drm, crtc, surface.gamma_props.as_ref())
     }
 
     pub fn set_gamma(&mut self, output: &Output, ramp: Option<Vec<u16>>) -> anyhow::Result<()> {
@@ -2054,6 +2146,9 @@ impl Tty {
             .context("missing device")?;
         let surface = device.surfaces.get_mut(&crtc).context("missing surface")?;
 
+        // Fall back to the color profile when the client resets the gamma.
+        let ramp = ramp.or_else(|| surface.color_profile_ramp.clone());
+
         // Cannot change properties while the device is inactive.
         if !self.session.is_active() {
             surface.pending_gamma_change = Some(ramp);
@@ -2215,6 +2310,28 @@ impl Tty {
         }
     }
 
+    pub fn set_output_active(&mut self, output: &Output, active: bool) {
+        // Same as set_monitors_active(): the next queued frame will activate the CRTC again with
+        // its existing mode.
+        if active {
+            return;
+        }
+
+        let Some(tty_state) = output.user_data().get::<TtyOutputState>() else {
+            return;
+        };
+        let Some(device) = self.devices.get_mut(&tty_state.node) else {
+            return;
+        };
+        let Some(surface) = device.surfaces.get_mut(&tty_state.crtc) else {
+            return;
+        };
+
+        if let Err(err) = surface.compositor.clear() {
+            warn!("error clearing drm surface: {err:?}");
+        }
+    }
+
     pub fn set_output_on_demand_vrr(&mut self, niri: &mut Niri, output: &Output, enable_vrr: bool) {
         let _span = tracy_client::span!("Tty::set_output_on_demand_vrr");
 
@@ -2349,6 +2466,39 @@ impl Tty {
                     continue;
                 }
 
+                surface.max_fps = config.max_fps.map(|fps| fps.0);
+
+                if surface.color_profile != config.color_profile {
+                    surface.color_profile_ramp = load_color_profile(
+                        config.color_profile.as_deref(),
+                        || surface_gamma_size(&device.drm, crtc, surface.gamma_props.as_ref()),
+                        &surface.name.connector,
+                    );
+                    surface.color_profile = config.color_profile.clone();
+
+                    // Clients controlling the gamma take precedence over the color profile.
+                    let has_gamma_control = niri.global_space.outputs().any(|output| {
+                        let tty_state: &TtyOutputState = output.user_data().get().unwrap();
+                        tty_state.node == node
+                            && tty_state.crtc == crtc
+                            && niri.gamma_control_manager_state.has_gamma_control(output)
+                    });
+                    if !has_gamma_control {
+                        let ramp = surface.color_profile_ramp.as_deref();
+                        let res = if let Some(gamma_props) = &mut surface.gamma_props {
+                            gamma_props.set_gamma(&device.drm, ramp)
+                        } else {
+                            set_gamma_for_crtc(&device.drm, crtc, ramp)
+                        };
+                        if let Err(err) = res {
+                            warn!(
+                                "output {:?}: error applying color profile: {err:?}",
+                                surface.name.connector
+                            );
+                        }
+                    }
+                }
+
                 // Check if we need to change the mode.
                 let Some(connector) = device.drm_scanner.connectors().get(&surface.connector)
                 else {
@@ -2889,6 +3039,116 @@ fn refresh_interval(mode: DrmMode) -> Duration {
     Duration::from_nanos(refresh_interval)
 }
 
+/// Backlight device in `/sys/class/backlight`.
+struct Backlight {
+    name: String,
+    path: PathBuf,
+    max_brightness: u32,
+    /// System bus connection for setting the brightness through logind.
+    #[cfg(feature = "dbus")]
+    logind: Option<zbus::blocking::Connection>,
+}
+
+impl Backlight {
+    /// Finds the backlight device to control.
+    ///
+    /// Prefers firmware interfaces over platform ones, and those over raw driver interfaces,
+    /// following the kernel recommendation for choosing among several backlight devices.
+    fn find() -> anyhow::Result<Self> {
+        let entries = fs::read_dir(BACKLIGHT_DIR).context("error reading backlight directory")?;
+
+        let mut best = None;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(ty) = fs::read_to_string(path.join("type")) else {
+                continue;
+            };
+            let priority = match ty.trim() {
+                "firmware" => 0,
+                "platform" => 1,
+                "raw" => 2,
+                _ => continue,
+            };
+
+            if best.as_ref().is_none_or(|(p, _)| priority < *p) {
+                best = Some((priority, path));
+            }
+        }
+
+        let (_, path) = best.context("no backlight device found")?;
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .context("invalid backlight device name")?
+            .to_owned();
+        let max_brightness = read_u32(&path.join("max_brightness"))?;
+        ensure!(max_brightness > 0, "backlight max_brightness is zero");
+
+        #[cfg(feature = "dbus")]
+        let logind = zbus::blocking::Connection::system()
+            .map_err(|err| debug!("error connecting to system bus: {err:?}"))
+            .ok();
+
+        Ok(Self {
+            name,
+            path,
+            max_brightness,
+            #[cfg(feature = "dbus")]
+            logind,
+        })
+    }
+
+    fn percent(&self) -> anyhow::Result<u32> {
+        let brightness = read_u32(&self.path.join("brightness"))?;
+        let percent = (brightness as f64 / self.max_brightness as f64 * 100.).round();
+        Ok(percent as u32)
+    }
+
+    fn set_percent(&self, percent: u32) -> anyhow::Result<()> {
+        let brightness = (percent as f64 / 100. * self.max_brightness as f64).round() as u32;
+        debug!("setting backlight {} to {brightness}", self.name);
+
+        // Writing to sysfs requires write access to the brightness file, which regular users
+        // usually don't have. logind lets the session owner set the brightness instead.
+        #[cfg(feature = "dbus")]
+        if let Some(conn) = &self.logind {
+            match set_brightness_logind(conn, &self.name, brightness) {
+                Ok(()) => return Ok(()),
+                Err(err) => debug!("error setting backlight through logind: {err:?}"),
+            }
+        }
+
+        fs::write(self.path.join("brightness"), brightness.to_string())
+            .context("error writing backlight brightness")
+    }
+}
+
+fn read_u32(path: &Path) -> anyhow::Result<u32> {
+    let value = fs::read_to_string(path).with_context(|| format!("error reading {path:?}"))?;
+    value
+        .trim()
+        .parse()
+        .with_context(|| format!("error parsing {path:?}"))
+}
+
+#[cfg(feature = "dbus")]
+fn set_brightness_logind(
+    conn: &zbus::blocking::Connection,
+    name: &str,
+    brightness: u32,
+) -> anyhow::Result<()> {
+    conn.call_method(
+        Some("org.freedesktop.login1"),
+        "/org/freedesktop/login1/session/auto",
+        Some("org.freedesktop.login1.Session"),
+        "SetBrightness",
+        &("backlight", name, brightness),
+    )
+    .context("error calling SetBrightness")?;
+
+    Ok(())
+}
+
 #[cfg(feature = "dbus")]
 fn suspend() -> anyhow::Result<()> {
     let conn = zbus::blocking::Connection::system().context("error connecting to system bus")?;
@@ -2951,6 +3211,32 @@ fn queue_estimated_vblank_timer(
     output_state.redraw_state = RedrawState::WaitingForEstimatedVBlank(token);
 }
 
+/// Returns whether a frame targeting `target` should be rendered under the `max_fps` cap, given
+/// the target time of the last queued frame.
+///
+/// The cap is rounded to a whole number of refresh cycles so that frames are spaced evenly.
+fn should_render_capped_frame(
+    max_fps: f64,
+    refresh_interval: Option<Duration>,
+    last: Duration,
+    target: Duration,
+) -> bool {
+    let min_interval = Duration::from_secs_f64(1. / max_fps);
+    let elapsed = target.saturating_sub(last);
+
+    let Some(refresh_interval) = refresh_interval else {
+        return elapsed >= min_interval;
+    };
+
+    let cycles = (min_interval.as_secs_f64() / refresh_interval.as_secs_f64())
+        .round()
+        .max(1.);
+    let min_interval = refresh_interval.mul_f64(cycles);
+
+    // Leave half a refresh cycle of slack for presentation time jitter.
+    elapsed + refresh_interval / 2 >= min_interval
+}
+
 pub fn calculate_drm_mode_from_modeline(modeline: &Modeline) -> anyhow::Result<DrmMode> {
     ensure!(
         modeline.hdisplay < modeline.hsync_start,
@@ -3291,6 +3577,40 @@ fn get_panel_orientation(props: &ConnectorProperties) -> anyhow::Result<Transfor
     }
 }
 
+fn surface_gamma_size(
+    device: &DrmDevice,
+    crtc: crtc::Handle,
+    gamma_props: Option<&GammaProps>,
+) -> anyhow::Result<u32> {
+    if let Some(gamma_props) = gamma_props {
+        gamma_props.gamma_size(device)
+    } else {
+        let info = device.get_crtc(crtc).context("error getting crtc info")?;
+        Ok(info.gamma_length())
+    }
+}
+
+/// Loads the gamma ramp from the color profile, logging and skipping malformed profiles.
+fn load_color_profile(
+    path: Option<&str>,
+    gamma_size: impl FnOnce() -> anyhow::Result<u32>,
+    connector: &str,
+) -> Option<Vec<u16>> {
+    let path = path?;
+
+    let res = gamma_size().and_then(|gamma_size| {
+        ensure!(gamma_size != 0, "setting gamma is not supported");
+        color_profile::load_gamma_ramp(Path::new(path), gamma_size)
+    });
+    match res {
+        Ok(ramp) => Some(ramp),
+        Err(err) => {
+            warn!("output {connector:?}: error loading color profile {path:?}: {err:?}");
+            None
+        }
+    }
+}
+
 pub fn set_gamma_for_crtc(
     device: &DrmDevice,
     crtc: crtc::Handle,
@@ -3406,11 +3726,35 @@ unsafe fn init_libinput_plugin_system(libinput: &Libinput) {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use insta::assert_debug_snapshot;
     use niri_config::output::Modeline;
     use niri_ipc::{HSyncPolarity, VSyncPolarity};
 
-    use crate::backend::tty::{calculate_drm_mode_from_modeline, calculate_mode_cvt};
+    use crate::backend::tty::{
+        calculate_drm_mode_from_modeline, calculate_mode_cvt, should_render_capped_frame,
+    };
+
+    #[test]
+    fn test_should_render_capped_frame() {
+        let refresh = Some(Duration::from_micros(16_667));
+        let last = Duration::from_secs(1);
+        let frame = |n: u32| last + Duration::from_micros(16_667) * n;
+
+        // 30 fps on a 60 Hz output renders every other frame.
+        assert!(!should_render_capped_frame(30., refresh, last, frame(1)));
+        assert!(should_render_capped_frame(30., refresh, last, frame(2)));
+
+        // A cap above the refresh rate renders every frame.
+        assert!(should_render_capped_frame(120., refresh, last, frame(1)));
+
+        // Unknown refresh interval uses the cap directly.
+        let target = last + Duration::from_millis(20);
+        assert!(!should_render_capped_frame(30., None, last, target));
+        let target = last + Duration::from_millis(34);
+        assert!(should_render_capped_frame(30., None, last, target));
+    }
 
     #[test]
     fn test_calculate_drmmode_from_modeline() {
@@ -107,6 +107,26 @@ pub enum Msg {
     RequestError,
     /// Print the overview state.
     OverviewState,
+    /// Check a config file for errors without applying it.
+    ValidateConfig {
+        /// Path to the config file to check.
+        ///
+        /// Defaults to the config file used by the running niri instance.
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+    /// List the clipboard history, most recent entry first.
+    ClipboardHistory,
+    /// Print detailed information about a window, including the window rules that matched it.
+    WindowInfo {
+        /// Id of the window to print information about.
+        ///
+        /// If not specified, uses the focused window.
+        #[arg(short, long)]
+        id: Option<u64>,
+    },
+    /// List the binds shown in the hotkey overlay.
+    HotkeyOverlay,
 }
 
 #[derive(Clone, Debug, clap::ValueEnum)]
@@ -0,0 +1,330 @@
+//! In-memory history of clipboard selections.
+
+use std::collections::VecDeque;
+use std::io::{self, PipeReader, Read};
+use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
+
+use smithay::reexports::calloop::generic::Generic;
+use smithay::reexports::calloop::timer::{TimeoutAction, Timer};
+use smithay::reexports::calloop::{Interest, Mode, PostAction, RegistrationToken};
+use smithay::reexports::rustix::fs::{OFlags, fcntl_setfl};
+use smithay::wayland::selection::SelectionSource;
+use smithay::wayland::selection::data_device::{
+    request_data_device_client_selection, set_data_device_selection,
+};
+
+use crate::niri::State;
+
+/// Maximum size of a single recorded selection.
+///
+/// Bigger selections (usually large images) are not recorded to keep memory usage in check.
+const MAX_ENTRY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// How long the selection owner has to send the selection contents.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Text mime types, in order of preference.
+///
+/// Restored text entries are offered with all of these.
+const TEXT_MIME_TYPES: [&str; 5] = [
+    "text/plain;charset=utf-8",
+    "text/plain",
+    "UTF8_STRING",
+    "STRING",
+    "TEXT",
+];
+
+/// Mime type that password managers use to mark selections that shouldn't be recorded.
+const PASSWORD_HINT_MIME_TYPE: &str = "x-kde-passwordManagerHint";
+
+#[derive(Debug, Clone)]
+pub struct ClipboardEntry {
+    /// Mime type of the recorded data.
+    pub mime_type: String,
+    /// Recorded data.
+    pub data: Arc<[u8]>,
+}
+
+/// Recent clipboard selections, most recent first.
+#[derive(Debug, Default)]
+pub struct ClipboardHistory {
+    entries: VecDeque<ClipboardEntry>,
+    /// Event loop sources of the selection being read: the pipe and its timeout.
+    pending_read: Option<(RegistrationToken, RegistrationToken)>,
+}
+
+/// Progress of reading the selection contents.
+#[derive(Debug, PartialEq, Eq)]
+enum ReadProgress {
+    /// The selection owner hasn't finished writing yet.
+    Pending,
+    /// The selection owner closed the pipe.
+    Done,
+    /// The selection exceeds [`MAX_ENTRY_BYTES`].
+    TooBig,
+}
+
+impl ClipboardEntry {
+    pub fn is_text(&self) -> bool {
+        TEXT_MIME_TYPES.contains(&self.mime_type.as_str())
+    }
+
+    pub fn text(&self) -> Option<String> {
+        if !self.is_text() {
+            return None;
+        }
+
+        Some(String::from_utf8_lossy(&self.data).into_owned())
+    }
+
+    /// Mime types to offer when restoring this entry.
+    fn offered_mime_types(&self) -> Vec<String> {
+        if self.is_text() {
+            TEXT_MIME_TYPES.map(String::from).to_vec()
+        } else {
+            vec![self.mime_type.clone()]
+        }
+    }
+}
+
+impl ClipboardHistory {
+    /// Adds an entry to the front of the history.
+    ///
+    /// An older entry with the same contents is removed, so that copying the same thing again
+    /// moves it to the front instead of duplicating it.
+    pub fn push(&mut self, entry: ClipboardEntry, max_len: usize) {
+        self.entries
+            .retain(|e| e.mime_type != entry.mime_type || e.data != entry.data);
+        self.entries.push_front(entry);
+        self.entries.truncate(max_len);
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&ClipboardEntry> {
+        self.entries.get(idx)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &ClipboardEntry> {
+        self.entries.iter()
+    }
+
+    pub fn truncate(&mut self, max_len: usize) {
+        self.entries.truncate(max_len);
+    }
+}
+
+/// Picks the mime type to record out of the ones offered by a selection.
+fn pick_mime_type(offered: &[String]) -> Option<&str> {
+    if offered.iter().any(|m| m == PASSWORD_HINT_MIME_TYPE) {
+        return None;
+    }
+
+    let has = |mime_type: &str| offered.iter().any(|m| m == mime_type);
+    if let Some(text) = TEXT_MIME_TYPES.into_iter().find(|m| has(m)) {
+        return Some(text);
+    }
+
+    offered
+        .iter()
+        .find(|m| m.starts_with("image/"))
+        .map(String::as_str)
+}
+
+/// Reads the currently available selection contents into `data`.
+fn read_available(mut read: impl Read, data: &mut Vec<u8>) -> io::Result<ReadProgress> {
+    let mut buf = [0; 8192];
+    loop {
+        match read.read(&mut buf) {
+            Ok(0) => return Ok(ReadProgress::Done),
+            Ok(n) => {
+                data.extend_from_slice(&buf[..n]);
+                if data.len() as u64 > MAX_ENTRY_BYTES {
+                    return Ok(ReadProgress::TooBig);
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                return Ok(ReadProgress::Pending);
+            }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+impl State {
+    pub fn clipboard_history_on_new_selection(&mut self, source: Option<SelectionSource>) {
+        let max_len = usize::from(self.niri.config.borrow().clipboard.history_size);
+        if max_len == 0 {
+            return;
+        }
+
+        // Only one selection is read at a time.
+        self.clipboard_history_cancel_read();
+
+        // A cleared selection, for example because its owner exited, isn't recorded.
+        let Some(source) = source else {
+            return;
+        };
+
+        let offered = source.mime_types();
+        let Some(mime_type) = pick_mime_type(&offered) else {
+            return;
+        };
+        let mime_type = mime_type.to_owned();
+
+        let (read, write) = match io::pipe() {
+            Ok(x) => x,
+            Err(err) => {
+                warn!("error creating a pipe for the clipboard history: {err:?}");
+                return;
+            }
+        };
+
+        if let Err(err) =
+            request_data_device_client_selection(&self.niri.seat, mime_type.clone(), write.into())
+        {
+            warn!("error requesting the selection for the clipboard history: {err:?}");
+            return;
+        }
+
+        // The client may take its time writing, so read from the event loop as data arrives.
+        if let Err(err) = fcntl_setfl(&read, OFlags::NONBLOCK) {
+            warn!("error setting the clipboard history pipe to non-blocking: {err:?}");
+            return;
+        }
+
+        let mut data = Vec::new();
+        let source = Generic::new(read, Interest::READ, Mode::Level);
+        let read_token = self
+            .niri
+            .event_loop
+            .insert_source(source, move |_, read, state| {
+                let read: &PipeReader = read;
+                match read_available(read, &mut data) {
+                    Ok(ReadProgress::Pending) => return Ok(PostAction::Continue),
+                    Ok(ReadProgress::Done) => {
+                        let max_len =
+                            usize::from(state.niri.config.borrow().clipboard.history_size);
+                        let entry = ClipboardEntry {
+                            mime_type: mime_type.clone(),
+                            data: Arc::from(mem::take(&mut data)),
+                        };
+                        state.niri.clipboard_history.push(entry, max_len);
+                    }
+                    Ok(ReadProgress::TooBig) => {
+                        debug!("selection is too big for the clipboard history");
+                    }
+                    Err(err) => {
+                        warn!("error reading the selection for the clipboard history: {err:?}");
+                    }
+                }
+
+                if let Some((_, timer_token)) = state.niri.clipboard_history.pending_read.take() {
+                    state.niri.event_loop.remove(timer_token);
+                }
+                Ok(PostAction::Remove)
+            })
+            .unwrap();
+
+        let timer = Timer::from_duration(READ_TIMEOUT);
+        let timer_token = self
+            .niri
+            .event_loop
+            .insert_source(timer, |_, _, state| {
+                debug!("timed out reading the selection for the clipboard history");
+                if let Some((read_token, _)) = state.niri.clipboard_history.pending_read.take() {
+                    state.niri.event_loop.remove(read_token);
+                }
+                TimeoutAction::Drop
+            })
+            .unwrap();
+
+        self.niri.clipboard_history.pending_read = Some((read_token, timer_token));
+    }
+
+    fn clipboard_history_cancel_read(&mut self) {
+        if let Some((read_token, timer_token)) = self.niri.clipboard_history.pending_read.take() {
+            self.niri.event_loop.remove(read_token);
+            self.niri.event_loop.remove(timer_token);
+        }
+    }
+
+    pub fn restore_clipboard_history_entry(&mut self, idx: usize) {
+        let Some(entry) = self.niri.clipboard_history.get(idx).cloned() else {
+            return;
+        };
+
+        set_data_device_selection(
+            &self.niri.display_handle,
+            &self.niri.seat,
+            entry.offered_mime_types(),
+            entry.data.clone(),
+        );
+
+        let max_len = usize::from(self.niri.config.borrow().clipboard.history_size);
+        self.niri.clipboard_history.push(entry, max_len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(mime_type: &str, data: &[u8]) -> ClipboardEntry {
+        ClipboardEntry {
+            mime_type: mime_type.to_owned(),
+            data: Arc::from(data),
+        }
+    }
+
+    fn mime_types(list: &[&str]) -> Vec<String> {
+        list.iter().map(|m| String::from(*m)).collect()
+    }
+
+    #[test]
+    fn picks_text_over_image() {
+        let offered = mime_types(&["image/png", "text/plain", "UTF8_STRING"]);
+        assert_eq!(pick_mime_type(&offered), Some("text/plain"));
+
+        let offered = mime_types(&["image/png", "application/x-foo"]);
+        assert_eq!(pick_mime_type(&offered), Some("image/png"));
+
+        let offered = mime_types(&["application/x-foo"]);
+        assert_eq!(pick_mime_type(&offered), None);
+    }
+
+    #[test]
+    fn skips_passwords() {
+        let offered = mime_types(&["text/plain", PASSWORD_HINT_MIME_TYPE]);
+        assert_eq!(pick_mime_type(&offered), None);
+    }
+
+    #[test]
+    fn read_stops_at_max_size() {
+        let mut data = Vec::new();
+        let progress = read_available(&b"text"[..], &mut data).unwrap();
+        assert_eq!(progress, ReadProgress::Done);
+        assert_eq!(data, b"text");
+
+        let big = vec![0; MAX_ENTRY_BYTES as usize + 1];
+        let mut data = Vec::new();
+        let progress = read_available(&big[..], &mut data).unwrap();
+        assert_eq!(progress, ReadProgress::TooBig);
+    }
+
+    #[test]
+    fn push_dedups_and_truncates() {
+        let mut history = ClipboardHistory::default();
+        history.push(entry("text/plain", b"a"), 2);
+        history.push(entry("text/plain", b"b"), 2);
+        history.push(entry("text/plain", b"a"), 2);
+
+        let data: Vec<_> = history.entries().map(|e| &*e.data).collect();
+        assert_eq!(data, [b"a", b"b"]);
+
+        history.push(entry("text/plain", b"c"), 2);
+        let data: Vec<_> = history.entries().map(|e| &*e.data).collect();
+        assert_eq!(data, [b"c", b"a"]);
+    }
+}
@@ -23,8 +23,8 @@ pub struct WindowProperties {
     /// Window app ID.
     ///
     /// This is actually the name of the .desktop file, and Shell does internal tracking to match
-    /// Wayland app IDs to desktop files. We don't do that yet, which is the reason why
-    /// xdg-desktop-portal-gnome's window list is missing icons.
+    /// Wayland app IDs to desktop files. We don't do that, so we take the app ID as is unless the
+    /// config maps it to a different .desktop file with `app-id-map`.
     #[zvariant(rename = "app-id")]
     pub app_id: String,
 }
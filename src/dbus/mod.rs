@@ -1,4 +1,5 @@
 use calloop::channel::Event;
+use niri_config::debug::DbusInterfaceName;
 use zbus::blocking::Connection;
 use zbus::object_server::Interface;
 
@@ -14,6 +15,7 @@ pub mod kwin_colorpicker;
 pub mod kwin_screenshot2;
 pub mod mutter_display_config;
 pub mod mutter_service_channel;
+pub mod niri_config_reload;
 
 #[cfg(feature = "xdp-gnome-screencast")]
 pub mod mutter_screen_cast;
@@ -26,6 +28,7 @@ use self::gnome_shell_introspect::Introspect;
 use self::kwin_screenshot2::KwinScreenshot2;
 use self::mutter_display_config::DisplayConfig;
 use self::mutter_service_channel::ServiceChannel;
+use self::niri_config_reload::ConfigLoader;
 
 trait DbusInterface: Sized + Interface {
     type InitArgs;
@@ -54,6 +57,7 @@ pub struct DBusServers {
     pub conn_keyboard_monitor: Option<Connection>,
     pub conn_kwin_screenshot2: Option<Connection>,
     pub conn_kwin_colorpicker: Option<Connection>,
+    pub conn_config_loader: Option<Connection>,
 }
 
 impl DBusServers {
@@ -65,7 +69,16 @@ impl DBusServers {
 
         let mut dbus = Self::default();
 
-        if is_session_instance {
+        let disabled = niri.config.borrow().debug.disabled_dbus_interfaces.clone();
+        let enabled = |name| {
+            let enabled = !disabled.contains(&name);
+            if !enabled {
+                debug!("not starting disabled D-Bus interface {name:?}");
+            }
+            enabled
+        };
+
+        if is_session_instance && enabled(DbusInterfaceName::ServiceChannel) {
             dbus.conn_service_channel = start_interface::<ServiceChannel>(niri, ()).unwrap();
         }
 
@@ -76,28 +89,44 @@ impl DBusServers {
                 .debug
                 .dbus_interfaces_in_non_session_instances
         {
-            dbus.conn_kwin_colorpicker = start_interface::<KwinColorpicker>(niri, ()).unwrap();
+            if enabled(DbusInterfaceName::KwinColorPicker) {
+                dbus.conn_kwin_colorpicker = start_interface::<KwinColorpicker>(niri, ()).unwrap();
+            }
 
-            dbus.conn_kwin_screenshot2 = start_interface::<KwinScreenshot2>(niri, ()).unwrap();
+            if enabled(DbusInterfaceName::KwinScreenshot2) {
+                dbus.conn_kwin_screenshot2 = start_interface::<KwinScreenshot2>(niri, ()).unwrap();
+            }
 
-            dbus.conn_display_config =
-                start_interface::<DisplayConfig>(niri, backend.ipc_outputs()).unwrap();
+            if enabled(DbusInterfaceName::NiriConfig) {
+                dbus.conn_config_loader = start_interface::<ConfigLoader>(niri, ()).unwrap();
+            }
 
-            dbus.conn_screen_saver =
-                try_start(ScreenSaver::new(niri.is_fdo_idle_inhibited.clone()));
+            if enabled(DbusInterfaceName::DisplayConfig) {
+                dbus.conn_display_config =
+                    start_interface::<DisplayConfig>(niri, backend.ipc_outputs()).unwrap();
+            }
+
+            if enabled(DbusInterfaceName::ScreenSaver) {
+                dbus.conn_screen_saver =
+                    try_start(ScreenSaver::new(niri.is_fdo_idle_inhibited.clone()));
+            }
 
-            dbus.conn_introspect = start_interface::<Introspect>(niri, ()).unwrap();
+            if enabled(DbusInterfaceName::Introspect) {
+                dbus.conn_introspect = start_interface::<Introspect>(niri, ()).unwrap();
+            }
 
             #[cfg(feature = "xdp-gnome-screencast")]
-            {
+            if enabled(DbusInterfaceName::ScreenCast) {
                 dbus.conn_screen_cast =
                     start_interface::<ScreenCast>(niri, backend.ipc_outputs()).unwrap();
             }
 
-            let keyboard_monitor = KeyboardMonitor::new();
-            if let Some(x) = try_start(keyboard_monitor.clone()) {
-                dbus.conn_keyboard_monitor = Some(x);
-                niri.a11y_keyboard_monitor = Some(keyboard_monitor);
+            if enabled(DbusInterfaceName::KeyboardMonitor) {
+                let keyboard_monitor = KeyboardMonitor::new();
+                if let Some(x) = try_start(keyboard_monitor.clone()) {
+                    dbus.conn_keyboard_monitor = Some(x);
+                    niri.a11y_keyboard_monitor = Some(keyboard_monitor);
+                }
             }
         }
 
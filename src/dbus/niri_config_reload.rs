@@ -0,0 +1,116 @@
+use std::path::{Path, PathBuf};
+
+use niri_config::{Config, ConfigPath};
+use zbus::fdo::RequestNameFlags;
+use zbus::{DBusError, interface};
+
+use crate::dbus::DbusInterface;
+
+pub enum ConfigLoaderToNiri {
+    GetConfigPath(tokio::sync::oneshot::Sender<Option<ConfigPath>>),
+    LoadConfig(Result<Config, ()>, tokio::sync::oneshot::Sender<()>),
+}
+
+#[derive(Debug, DBusError)]
+#[zbus(prefix = "org.niri.Config.Error")]
+pub enum ConfigLoaderError {
+    #[zbus(error)]
+    ZBus(zbus::Error),
+    /// The requested path cannot be loaded, or niri is not using a config file.
+    InvalidPath(String),
+    /// The config file failed to parse. Contains the formatted diagnostics.
+    ParseFailed(String),
+    /// Internal error while talking to the compositor.
+    Failed(String),
+}
+
+pub struct ConfigLoader {
+    to_niri: calloop::channel::Sender<ConfigLoaderToNiri>,
+}
+
+#[interface(name = "org.niri.Config")]
+impl ConfigLoader {
+    /// Loads and applies the config file at `path`, or the current config file if `path` is empty.
+    ///
+    /// Loading a different file does not change which file niri watches for changes.
+    async fn load_config_file(&self, path: String) -> Result<(), ConfigLoaderError> {
+        let config_path = if path.is_empty() {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            self.to_niri
+                .send(ConfigLoaderToNiri::GetConfigPath(tx))
+                .map_err(|e| {
+                    ConfigLoaderError::Failed(format!("error getting config path: {e}"))
+                })?;
+            let path = rx.await.map_err(|e| {
+                ConfigLoaderError::Failed(format!("error getting config path: {e}"))
+            })?;
+            path.ok_or_else(|| {
+                ConfigLoaderError::InvalidPath(String::from("niri is not using a config file"))
+            })?
+        } else {
+            // Relative paths would be resolved against the compositor's working directory.
+            if !Path::new(&path).is_absolute() {
+                return Err(ConfigLoaderError::InvalidPath(format!(
+                    "path must be absolute: {path}"
+                )));
+            }
+            ConfigPath::Explicit(PathBuf::from(path))
+        };
+
+        // This runs on the D-Bus thread, so parsing doesn't block the event loop.
+        let (config, error) = match config_path.load_uncolored() {
+            Ok(config) => (Ok(config), None),
+            Err(error) => (Err(()), Some(error)),
+        };
+
+        // Also send failed loads, so that niri shows the config error notification.
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.to_niri
+            .send(ConfigLoaderToNiri::LoadConfig(config, tx))
+            .map_err(|e| ConfigLoaderError::Failed(format!("error applying config: {e}")))?;
+        rx.await
+            .map_err(|e| ConfigLoaderError::Failed(format!("error applying config: {e}")))?;
+
+        match error {
+            Some(error) => Err(ConfigLoaderError::ParseFailed(error)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl DbusInterface for ConfigLoader {
+    type Message = ConfigLoaderToNiri;
+    type InitArgs = ();
+
+    fn start(self) -> anyhow::Result<zbus::blocking::Connection> {
+        let conn = zbus::blocking::Connection::session()?;
+
+        let flags = RequestNameFlags::AllowReplacement
+            | RequestNameFlags::ReplaceExisting
+            | RequestNameFlags::DoNotQueue;
+
+        conn.object_server().at("/org/niri/Config", self)?;
+        conn.request_name_with_flags("org.niri.Config", flags)?;
+
+        Ok(conn)
+    }
+
+    fn init_interface(to_niri: calloop::channel::Sender<Self::Message>, _: Self::InitArgs) -> Self {
+        Self { to_niri }
+    }
+
+    fn on_callback(msg: Self::Message, state: &mut crate::niri::State) {
+        match msg {
+            ConfigLoaderToNiri::GetConfigPath(sender) => {
+                let watcher = state.niri.config_file_watcher.as_ref();
+                let _ = sender.send(watcher.map(|w| w.path().clone()));
+            }
+            ConfigLoaderToNiri::LoadConfig(config, sender) => {
+                let failed = config.is_err();
+                state.reload_config(config);
+                state.ipc_config_loaded(failed);
+                let _ = sender.send(());
+            }
+        }
+    }
+}
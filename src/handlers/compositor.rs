@@ -23,7 +23,7 @@ use crate::handlers::XDG_ACTIVATION_TOKEN_TIMEOUT;
 use crate::layout::{ActivateWindow, AddWindowTarget, LayoutElement as _};
 use crate::niri::{CastTarget, ClientState, LockState, State};
 use crate::utils::transaction::Transaction;
-use crate::utils::{is_mapped, send_scale_transform};
+use crate::utils::{client_scale, get_monotonic_time, is_mapped, send_scale_transform};
 use crate::window::{InitialConfigureState, Mapped, ResolvedWindowRules, Unmapped};
 
 impl CompositorHandler for State {
@@ -42,7 +42,7 @@ impl CompositorHandler for State {
         }
 
         if let Some(output) = self.niri.output_for_root(&root) {
-            let scale = output.current_scale();
+            let scale = client_scale(output);
             let transform = output.current_transform();
             with_states(surface, |data| {
                 send_scale_transform(surface, data, scale, transform);
@@ -151,6 +151,9 @@ impl CompositorHandler for State {
                     let is_floating = rules.compute_open_floating(toplevel);
 
                     // Figure out if we should activate the window.
+                    //
+                    // This also decides whether opening on another workspace (for example, through
+                    // open-on-workspace) switches to it: only ActivateWindow::Yes does.
                     let activate = rules.open_focused.map(|focus| {
                         if focus {
                             ActivateWindow::Yes
@@ -256,6 +259,7 @@ impl CompositorHandler for State {
                 let output = output.cloned();
 
                 let id = mapped.id();
+                mapped.last_commit_time().set(Some(get_monotonic_time()));
 
                 // This is a commit of a previously-mapped toplevel.
                 let is_mapped = is_mapped(surface);
@@ -367,6 +371,7 @@ impl CompositorHandler for State {
         if let Some((mapped, output)) = root_window_output {
             let window = mapped.window.clone();
             let output = output.cloned();
+            mapped.last_commit_time().set(Some(get_monotonic_time()));
             window.on_commit();
             self.niri
                 .window_mru_ui
@@ -13,7 +13,7 @@ use smithay::wayland::shell::xdg::PopupSurface;
 use crate::layer::{MappedLayer, ResolvedLayerRules};
 use crate::niri::State;
 use crate::render_helpers::blur::EffectsFramebuffers;
-use crate::utils::{is_mapped, output_size, send_scale_transform};
+use crate::utils::{client_scale, is_mapped, output_size, send_scale_transform};
 
 impl WlrLayerShellHandler for State {
     fn shell_state(&mut self) -> &mut WlrLayerShellState {
@@ -142,8 +142,12 @@ impl State {
             if was_unmapped {
                 let config = self.niri.config.borrow();
 
-                let rules = &config.layer_rules;
-                let rules = ResolvedLayerRules::compute(rules, layer, self.niri.is_at_startup);
+                let rules = ResolvedLayerRules::compute(
+                    &config.layer_rules,
+                    layer,
+                    self.niri.is_at_startup,
+                    config.block_out_all_from_screencast,
+                );
 
                 let output_size = output_size(&output);
                 let scale = output.current_scale().fractional_scale();
@@ -207,7 +211,7 @@ impl State {
                         .initial_configure_sent
                 });
                 if !initial_configure_sent {
-                    let scale = output.current_scale();
+                    let scale = client_scale(&output);
                     let transform = output.current_transform();
                     with_states(surface, |data| {
                         send_scale_transform(surface, data, scale, transform);
@@ -4,6 +4,7 @@ mod xdg_shell;
 
 use std::fs::File;
 use std::io::Write;
+use std::mem;
 use std::os::fd::OwnedFd;
 use std::sync::Arc;
 use std::thread;
@@ -18,6 +19,7 @@ use smithay::input::pointer::{CursorIcon, CursorImageStatus, Focus, PointerHandl
 use smithay::input::{Seat, SeatHandler, SeatState, keyboard};
 use smithay::output::Output;
 use smithay::reexports::rustix::fs::{OFlags, fcntl_setfl};
+use smithay::reexports::wayland_protocols_wlr::output_management::v1::server::zwlr_output_configuration_v1::ZwlrOutputConfigurationV1;
 use smithay::reexports::wayland_protocols_wlr::screencopy::v1::server::zwlr_screencopy_manager_v1::ZwlrScreencopyManagerV1;
 use smithay::reexports::wayland_server::Resource;
 use smithay::reexports::wayland_server::protocol::wl_output::WlOutput;
@@ -52,7 +54,7 @@ use smithay::wayland::selection::primary_selection::{
 use smithay::wayland::selection::wlr_data_control::{
     DataControlHandler as WlrDataControlHandler, DataControlState as WlrDataControlState,
 };
-use smithay::wayland::selection::{SelectionHandler, SelectionTarget};
+use smithay::wayland::selection::{SelectionHandler, SelectionSource, SelectionTarget};
 use smithay::wayland::session_lock::{
     LockSurface, SessionLockHandler, SessionLockManagerState, SessionLocker,
 };
@@ -85,7 +87,9 @@ use crate::protocols::gamma_control::{GammaControlHandler, GammaControlManagerSt
 use crate::protocols::kde_blur::OrgKdeKwinBlurManagerHandler;
 use crate::protocols::kde_output_order::KdeOutputOrderV1Handler;
 use crate::protocols::mutter_x11_interop::MutterX11InteropHandler;
-use crate::protocols::output_management::{OutputManagementHandler, OutputManagementManagerState};
+use crate::protocols::output_management::{
+    OutputManagementHandler, OutputManagementManagerState, PendingOutputConfiguration,
+};
 use crate::protocols::screencopy::{Screencopy, ScreencopyHandler, ScreencopyManagerState};
 use crate::protocols::virtual_pointer::{
     VirtualPointerAxisEvent, VirtualPointerButtonEvent, VirtualPointerHandler,
@@ -93,7 +97,7 @@ use crate::protocols::virtual_pointer::{
     VirtualPointerMotionEvent,
 };
 use crate::utils::region::Region;
-use crate::utils::{output_size, send_scale_transform};
+use crate::utils::{client_scale, output_size, send_scale_transform};
 use crate::{
     delegate_ext_background_effect, delegate_ext_workspace, delegate_foreign_toplevel,
     delegate_gamma_control, delegate_kde_output_order_v1, delegate_mutter_x11_interop,
@@ -228,7 +232,7 @@ impl InputMethodHandler for State {
     fn new_popup(&mut self, surface: PopupSurface) {
         let popup = PopupKind::InputMethod(surface);
         if let Some(output) = self.output_for_popup(&popup) {
-            let scale = output.current_scale();
+            let scale = client_scale(output);
             let transform = output.current_transform();
             let wl_surface = popup.wl_surface();
             with_states(wl_surface, |data| {
@@ -289,6 +293,17 @@ delegate_keyboard_shortcuts_inhibit!(State);
 impl SelectionHandler for State {
     type SelectionUserData = Arc<[u8]>;
 
+    fn new_selection(
+        &mut self,
+        ty: SelectionTarget,
+        source: Option<SelectionSource>,
+        _seat: Seat<Self>,
+    ) {
+        if matches!(ty, SelectionTarget::Clipboard) {
+            self.clipboard_history_on_new_selection(source);
+        }
+    }
+
     fn send_selection(
         &mut self,
         _ty: SelectionTarget,
@@ -498,7 +513,7 @@ pub fn configure_lock_surface(surface: &LockSurface, output: &Output) {
         let size = output_size(output);
         states.size = Some(size.to_i32_round());
     });
-    let scale = output.current_scale();
+    let scale = client_scale(output);
     let transform = output.current_transform();
     let wl_surface = surface.wl_surface();
     with_states(wl_surface, |data| {
@@ -517,6 +532,7 @@ impl SecurityContextHandler for State {
                     client,
                     restricted: true,
                     credentials_unknown: false,
+                    activation_token: None,
                 });
             })
             .unwrap();
@@ -856,9 +872,32 @@ impl OutputManagementHandler for State {
         &mut self.niri.output_management_state
     }
 
-    fn apply_output_config(&mut self, config: niri_config::Outputs) {
-        self.niri.config.borrow_mut().outputs = config;
+    fn apply_output_config(
+        &mut self,
+        configs: Vec<niri_config::Output>,
+        conf: ZwlrOutputConfigurationV1,
+    ) {
+        let old_outputs = self.niri.config.borrow().outputs.clone();
+
+        for config in &configs {
+            self.modify_output_config(&config.name, |c| {
+                // Keep the name that the existing section matches the output by.
+                let name = mem::take(&mut c.name);
+                *c = config.clone();
+                c.name = name;
+            });
+        }
         self.reload_output_config();
+
+        // Mode changes take effect with the next frame, so the result is checked after redrawing.
+        self.niri
+            .pending_output_configurations
+            .push(PendingOutputConfiguration {
+                conf,
+                configs,
+                old_outputs,
+            });
+        self.niri.queue_redraw_all();
     }
 }
 delegate_output_management!(State);
@@ -918,9 +957,11 @@ impl OrgKdeKwinBlurManagerHandler for State {
             .values_mut()
             .find(|l| l.surface().wl_surface() == surface)
         {
-            layer.set_blur_region(
-                region.map(|region| Region::from_region_attributes(get_region_attributes(&region))),
-            );
+            layer.set_blur_region(region.map(|region| {
+                let mut region = Region::from_region_attributes(get_region_attributes(&region));
+                region.normalize();
+                region
+            }));
         } else {
             trace!(
                 "tried to set blur region on un-mapped or unsupported surface: {}",
@@ -47,7 +47,8 @@ use crate::layout::ActivateWindow;
 use crate::niri::{CastTarget, PopupGrabState, State};
 use crate::utils::transaction::Transaction;
 use crate::utils::{
-    ResizeEdge, get_monotonic_time, output_matches_name, send_scale_transform, update_tiled_state,
+    ResizeEdge, client_scale, get_monotonic_time, output_matches_name, send_scale_transform,
+    set_decoration_mode, update_tiled_state, with_toplevel_role,
 };
 use crate::window::{InitialConfigureState, ResolvedWindowRules, Unmapped, WindowRef};
 
@@ -1014,18 +1015,37 @@ impl State {
     pub fn send_initial_configure(&mut self, toplevel: &ToplevelSurface) {
         let _span = tracy_client::span!("State::send_initial_configure");
 
-        let Some(unmapped) = self.niri.unmapped_windows.get_mut(toplevel.wl_surface()) else {
+        if !self
+            .niri
+            .unmapped_windows
+            .contains_key(toplevel.wl_surface())
+        {
             error!("window must be present in unmapped_windows in send_initial_configure()");
             return;
-        };
+        }
+
+        let spawn_workspace = self
+            .niri
+            .take_spawn_on_workspace_target(toplevel.wl_surface());
+        let unmapped = self
+            .niri
+            .unmapped_windows
+            .get_mut(toplevel.wl_surface())
+            .unwrap();
 
         let config = self.niri.config.borrow();
-        let rules = ResolvedWindowRules::compute(
+        let mut rules = ResolvedWindowRules::compute(
             &config.window_rules,
             WindowRef::Unmapped(unmapped),
             self.niri.is_at_startup,
+            config.block_out_all_from_screencast,
         );
 
+        // A pending spawn-on-workspace takes precedence over the window rules.
+        if let Some(name) = spawn_workspace {
+            rules.open_on_workspace = Some(name);
+        }
+
         let Unmapped { window, state, .. } = unmapped;
 
         let InitialConfigureState::NotConfigured {
@@ -1127,6 +1147,14 @@ impl State {
             }
 
             width = ws.resolve_default_width(rules.default_width, false);
+            if rules.default_width.is_none() {
+                let remembered = with_toplevel_role(toplevel, |role| role.app_id.clone())
+                    .and_then(|app_id| self.niri.preset_column_width_by_app_id.get(&app_id))
+                    .copied();
+                if let Some(remembered) = ws.resolve_remembered_width(remembered) {
+                    width = Some(remembered);
+                }
+            }
             floating_width = ws.resolve_default_width(rules.default_width, true);
             height = ws.resolve_default_height(rules.default_height, false);
             floating_height = ws.resolve_default_height(rules.default_height, true);
@@ -1148,6 +1176,12 @@ impl State {
             );
         }
 
+        // Request the decoration mode from the window rules. This does nothing if the client
+        // doesn't use xdg-decoration.
+        if let Some(server_side) = rules.prefer_server_decorations {
+            set_decoration_mode(toplevel, server_side);
+        }
+
         // Set the tiled state for the initial configure.
         update_tiled_state(toplevel, config.prefer_no_csd, rules.tiled_state);
 
@@ -1194,7 +1228,7 @@ impl State {
                     if !popup.is_initial_configure_sent() {
                         if let Some(output) = self.output_for_popup(&PopupKind::Xdg(popup.clone()))
                         {
-                            let scale = output.current_scale();
+                            let scale = client_scale(output);
                             let transform = output.current_transform();
                             with_states(surface, |data| {
                                 send_scale_transform(surface, data, scale, transform);
@@ -1362,6 +1396,7 @@ impl State {
                 window_rules,
                 WindowRef::Unmapped(unmapped),
                 self.niri.is_at_startup,
+                config.block_out_all_from_screencast,
             );
             if let InitialConfigureState::Configured { rules, .. } = &mut unmapped.state {
                 *rules = new_rules;
@@ -1370,7 +1405,11 @@ impl State {
             .niri
             .layout
             .find_window_and_output_mut(toplevel.wl_surface())
-            && mapped.recompute_window_rules(window_rules, self.niri.is_at_startup)
+            && mapped.recompute_window_rules(
+                window_rules,
+                self.niri.is_at_startup,
+                config.block_out_all_from_screencast,
+            )
         {
             drop(config);
             let output = output.cloned();
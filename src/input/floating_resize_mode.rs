@@ -0,0 +1,20 @@
+use smithay::desktop::Window;
+use smithay::utils::{Logical, Size};
+
+/// State of the keyboard floating window resize mode.
+#[derive(Debug)]
+pub struct FloatingResizeMode {
+    /// Floating window being resized.
+    pub window: Window,
+    /// Window size when entering the mode, restored upon cancelling.
+    pub original_size: Size<i32, Logical>,
+}
+
+impl FloatingResizeMode {
+    pub const fn new(window: Window, original_size: Size<i32, Logical>) -> Self {
+        Self {
+            window,
+            original_size,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+/// Walks back through the recently focused windows with repeated `focus-window-previous`.
+///
+/// The walk takes a snapshot of the focus history when it starts, so that the windows it focuses
+/// on the way don't reorder the history in the middle of the walk.
+#[derive(Debug)]
+pub struct FocusHistoryWalk<Id> {
+    /// Windows to walk through, starting with the one focused when the walk began.
+    stack: Vec<Id>,
+    /// Index into `stack` of the window that the walk focused last.
+    pos: usize,
+}
+
+impl<Id> Default for FocusHistoryWalk<Id> {
+    fn default() -> Self {
+        Self {
+            stack: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl<Id: PartialEq + Clone> FocusHistoryWalk<Id> {
+    /// Returns the window to focus next.
+    ///
+    /// `recent` lists the windows most recent first, and is only called when starting a new walk.
+    /// The walk continues as long as the focus stays on the window that it focused last;
+    /// otherwise, a new walk starts from the focused window. After `depth` windows, the walk
+    /// wraps around to the window it started from.
+    pub fn next(
+        &mut self,
+        focused: Option<&Id>,
+        recent: impl FnOnce() -> Vec<Id>,
+        depth: usize,
+    ) -> Option<Id> {
+        let next = if self.is_continuing(focused) {
+            (self.pos + 1) % self.stack.len()
+        } else {
+            self.stack.clear();
+            self.stack.extend(focused.cloned());
+            self.stack
+                .extend(recent().into_iter().filter(|id| Some(id) != focused));
+            self.stack.truncate(depth.max(2));
+
+            // Without a focused window, start right from the most recent one.
+            usize::from(focused.is_some())
+        };
+
+        let Some(id) = self.stack.get(next).cloned() else {
+            self.reset();
+            return None;
+        };
+        self.pos = next;
+        Some(id)
+    }
+
+    /// Returns whether the next step continues the current walk rather than starting a new one.
+    pub fn is_continuing(&self, focused: Option<&Id>) -> bool {
+        focused.is_some() && focused == self.stack.get(self.pos)
+    }
+
+    pub fn reset(&mut self) {
+        self.stack.clear();
+        self.pos = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Focus history, most recent first.
+    const RECENT: [u32; 4] = [1, 2, 3, 4];
+
+    fn walk(depth: usize, focused: &[Option<u32>]) -> Vec<Option<u32>> {
+        let mut walk = FocusHistoryWalk::default();
+        focused
+            .iter()
+            .map(|focused| walk.next(focused.as_ref(), || RECENT.to_vec(), depth))
+            .collect()
+    }
+
+    #[test]
+    fn depth_two_ping_pongs() {
+        let res = walk(2, &[Some(1), Some(2), Some(1), Some(2)]);
+        assert_eq!(res, [Some(2), Some(1), Some(2), Some(1)]);
+    }
+
+    #[test]
+    fn walks_the_stack_and_wraps_around() {
+        let res = walk(3, &[Some(1), Some(2), Some(3), Some(1)]);
+        assert_eq!(res, [Some(2), Some(3), Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn focus_change_restarts_the_walk() {
+        // Focus moved to window 4 by other means after the first step.
+        let res = walk(4, &[Some(1), Some(4), Some(1)]);
+        assert_eq!(res, [Some(2), Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn reset_restarts_the_walk() {
+        let mut walk = FocusHistoryWalk::default();
+        let recent = || RECENT.to_vec();
+        assert_eq!(walk.next(Some(&1), recent, 4), Some(2));
+        assert_eq!(walk.next(Some(&2), recent, 4), Some(3));
+
+        walk.reset();
+        // Window 3 is now the most recent one.
+        let recent = || vec![3, 1, 2, 4];
+        assert_eq!(walk.next(Some(&3), recent, 4), Some(1));
+    }
+
+    #[test]
+    fn no_focus_starts_at_most_recent() {
+        let res = walk(3, &[None]);
+        assert_eq!(res, [Some(1)]);
+    }
+
+    #[test]
+    fn single_window() {
+        let mut walk = FocusHistoryWalk::default();
+        assert_eq!(walk.next(Some(&1), || vec![1], 3), None);
+    }
+}
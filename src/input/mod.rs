@@ -1,15 +1,17 @@
 use std::any::Any;
-use std::cmp::min;
+use std::cmp::{Reverse, min};
 use std::collections::HashSet;
-use std::collections::hash_map::Entry;
+use std::mem;
 use std::time::Duration;
 
 use calloop::timer::{TimeoutAction, Timer};
 use input::event::gesture::GestureEventCoordinates as _;
+use niri_config::gestures::SwipeDirection;
 use niri_config::{
-    Action, Bind, Binds, Config, Key, ModKey, Modifiers, MruDirection, SwitchBinds, Trigger, Xkb,
+    Action, Bind, Binds, Config, Key, ModKey, Modifiers, MruDirection, OutputName, SwitchBinds,
+    Trigger, WorkspaceReference, Xkb,
 };
-use niri_ipc::LayoutSwitchTarget;
+use niri_ipc::{LayoutSwitchTarget, ScreenshotTargetArg, SizeChange};
 use smithay::backend::input::{
     AbsolutePositionEvent, Axis, AxisSource, ButtonState, Device, DeviceCapability, Event,
     GestureBeginEvent, GestureEndEvent, GesturePinchUpdateEvent as _, GestureSwipeUpdateEvent as _,
@@ -19,6 +21,7 @@ use smithay::backend::input::{
     TabletToolTipState, TouchEvent,
 };
 use smithay::backend::libinput::LibinputInputBackend;
+use smithay::desktop::Window;
 use smithay::input::SeatHandler;
 use smithay::input::dnd::DnDGrab;
 use smithay::input::keyboard::{FilterResult, Keysym, Layout, ModifiersState, keysyms};
@@ -40,6 +43,8 @@ use smithay::wayland::pointer_constraints::{PointerConstraint, with_pointer_cons
 use smithay::wayland::tablet_manager::{TabletDescriptor, TabletSeatTrait};
 use touch_overview_grab::TouchOverviewGrab;
 
+use self::floating_resize_mode::FloatingResizeMode;
+use self::mod_double_tap::is_mod_keysym;
 use self::move_grab::MoveGrab;
 use self::resize_grab::ResizeGrab;
 use self::spatial_movement_grab::SpatialMovementGrab;
@@ -47,15 +52,24 @@ use self::spatial_movement_grab::SpatialMovementGrab;
 use crate::dbus::freedesktop_a11y::KbMonBlock;
 use crate::layout::scrolling::ScrollDirection;
 use crate::layout::{ActivateWindow, LayoutElement as _};
-use crate::niri::{CastTarget, LegacyScreenshotOutput, PointerVisibility, ScreenshotTarget, State};
+use crate::niri::{
+    CastTarget, CommandScreenshotOutput, LegacyScreenshotOutput, PointerVisibility,
+    ScreenshotTarget, State,
+};
 use crate::protocols::virtual_keyboard::VirtualKeyboard;
 use crate::ui::mru::{WindowMru, WindowMruUi};
 use crate::ui::screenshot_ui::ScreenshotUi;
+use crate::utils::scale::next_scale_in_cycle;
 use crate::utils::spawning::{spawn, spawn_sh};
-use crate::utils::{ResizeEdge, center, get_monotonic_time};
+use crate::utils::{
+    ResizeEdge, center, get_monotonic_time, next_transform_in_cycle, with_toplevel_role,
+};
 use crate::window::mapped::MappedId;
 
 pub mod backend_ext;
+pub mod floating_resize_mode;
+pub mod focus_history;
+pub mod mod_double_tap;
 pub mod move_grab;
 pub mod pick_color_grab;
 pub mod pick_output_grab;
@@ -230,17 +244,46 @@ impl State {
                     self.niri.touch.insert(device.clone());
                 }
 
-                apply_libinput_settings(&self.niri.config.borrow().input, device);
+                if is_trackpoint(device) {
+                    self.niri.trackpoints.insert(device.clone());
+                }
+
+                let config = self.niri.config.borrow();
+                if is_external_keyboard(&config.input.keyboard, device) {
+                    drop(config);
+                    // Internal devices may need to disable themselves.
+                    self.reapply_libinput_settings();
+                } else {
+                    let has_external_keyboard =
+                        has_external_keyboard(&config.input.keyboard, &self.niri.devices);
+                    apply_libinput_settings(&config.input, device, has_external_keyboard);
+                }
             }
             InputEvent::DeviceRemoved { device } => {
                 self.niri.touch.remove(device);
+                self.niri.trackpoints.remove(device);
                 self.niri.tablets.remove(device);
                 self.niri.devices.remove(device);
+
+                if is_external_keyboard(&self.niri.config.borrow().input.keyboard, device) {
+                    // Internal devices may need to enable themselves back.
+                    self.reapply_libinput_settings();
+                }
             }
             _ => (),
         }
     }
 
+    /// Applies the libinput settings to all connected devices.
+    pub fn reapply_libinput_settings(&self) {
+        let config = self.niri.config.borrow();
+        let has_external_keyboard =
+            has_external_keyboard(&config.input.keyboard, &self.niri.devices);
+        for mut device in self.niri.devices.iter().cloned() {
+            apply_libinput_settings(&config.input, &mut device, has_external_keyboard);
+        }
+    }
+
     fn on_device_added(&mut self, device: impl Device) {
         if device.has_capability(DeviceCapability::TabletTool) {
             let tablet_seat = self.niri.seat.tablet_seat();
@@ -407,6 +450,15 @@ impl State {
             self.niri.event_loop.remove(token);
         }
 
+        // Releasing a hold bind's key before the hold time has passed cancels the bind.
+        if !pressed
+            && let Some((key_code, token)) = self.niri.bind_hold_timer
+            && key_code == event.key_code()
+        {
+            self.niri.bind_hold_timer = None;
+            self.niri.event_loop.remove(token);
+        }
+
         if pressed {
             self.hide_cursor_if_needed();
         }
@@ -526,6 +578,39 @@ impl State {
                     return FilterResult::Intercept(None);
                 }
 
+                if pressed && this.niri.floating_resize_mode_window().is_some() {
+                    let step = this
+                        .niri
+                        .config
+                        .borrow()
+                        .input
+                        .keyboard
+                        .floating_resize_step;
+                    if let Some(bind) =
+                        raw.and_then(|raw| hardcoded_floating_resize_bind(raw, *mods, step))
+                    {
+                        this.niri.suppressed_keys.insert(key_code);
+                        return FilterResult::Intercept(Some(bind));
+                    }
+                }
+
+                if !is_inhibiting_shortcuts {
+                    let config = this.niri.config.borrow();
+                    let action = config.input.mod_double_tap_action.as_ref().and_then(|tap| {
+                        let is_mod = is_mod_keysym(mod_key, modified);
+                        let time = Duration::from_millis(u64::from(time));
+                        this.niri
+                            .mod_double_tap
+                            .on_key(is_mod, pressed, time, tap.timeout)
+                            .then(|| tap.action.clone())
+                    });
+                    drop(config);
+
+                    if let Some(action) = action {
+                        this.do_action(action, false);
+                    }
+                }
+
                 if raw == Some(Keysym::space) {
                     this.niri.screenshot_ui.set_space_down(pressed);
                 }
@@ -575,11 +660,37 @@ impl State {
             return;
         }
 
+        if let Some(hold) = bind.hold {
+            self.start_bind_hold(event.key_code(), bind, hold);
+            return;
+        }
+
         self.handle_bind(bind.clone());
 
         self.start_key_repeat(bind);
     }
 
+    fn start_bind_hold(&mut self, key_code: Keycode, bind: Bind, hold: Duration) {
+        // Stop the previous hold if any.
+        if let Some((_, token)) = self.niri.bind_hold_timer.take() {
+            self.niri.event_loop.remove(token);
+        }
+
+        let timer = Timer::from_duration(hold);
+        let token = self
+            .niri
+            .event_loop
+            .insert_source(timer, move |_, _, state| {
+                state.niri.bind_hold_timer = None;
+                state.handle_bind(bind.clone());
+                state.start_key_repeat(bind.clone());
+                TimeoutAction::Drop
+            })
+            .unwrap();
+
+        self.niri.bind_hold_timer = Some((key_code, token));
+    }
+
     fn start_key_repeat(&mut self, bind: Bind) {
         if !bind.repeat {
             return;
@@ -633,44 +744,151 @@ impl State {
         }
 
         self.niri.pointer_visibility = PointerVisibility::Hidden;
+        self.niri.pointer_motion_since_typing_hide = Some(0.);
         self.niri.queue_redraw_all();
     }
 
+    /// Returns whether pointer motion by `delta` should show the pointer.
+    ///
+    /// After hiding the pointer while typing, small motions are accumulated until they exceed the
+    /// reveal-motion-threshold, so that accidentally brushing the touchpad doesn't show it.
+    fn pointer_motion_reveals_pointer(&mut self, delta: Point<f64, Logical>) -> bool {
+        let Some(motion) = &mut self.niri.pointer_motion_since_typing_hide else {
+            return true;
+        };
+
+        if !self.niri.pointer_visibility.is_visible() {
+            *motion += delta.x.hypot(delta.y);
+
+            let threshold = self.niri.config.borrow().cursor.reveal_motion_threshold;
+            if *motion < f64::from(threshold) {
+                return false;
+            }
+        }
+
+        self.niri.pointer_motion_since_typing_hide = None;
+        true
+    }
+
+    /// Remembers the preset width of the active column for the app of its focused window.
+    fn remember_preset_column_width(&mut self) {
+        let layout = &self.niri.layout;
+        let Some(idx) = layout
+            .active_workspace()
+            .and_then(|ws| ws.active_column_preset_width_idx())
+        else {
+            return;
+        };
+        let Some(mapped) = layout.focus() else {
+            return;
+        };
+
+        if let Some(app_id) = with_toplevel_role(mapped.toplevel(), |role| role.app_id.clone()) {
+            self.niri.preset_column_width_by_app_id.insert(app_id, idx);
+        }
+    }
+
+    /// Returns the window in the floating resize mode.
+    ///
+    /// Leaves the mode if that window is no longer the focused floating window.
+    fn floating_resize_mode_window(&mut self) -> Option<Window> {
+        let window = self.niri.floating_resize_mode_window().cloned();
+        if window.is_none() {
+            self.niri.floating_resize_mode = None;
+        }
+        window
+    }
+
+    /// Moves the window back to the output it was on before that output got disconnected.
+    ///
+    /// Returns the output if the window was moved.
+    fn move_window_to_last_output(&mut self, window: &Window) -> Option<Output> {
+        let (mon, mapped) = self
+            .niri
+            .layout
+            .windows()
+            .find(|(_, m)| m.window == *window)?;
+        let name = mapped.last_output()?;
+        let output = self.niri.output_by_name_match(name).cloned()?;
+        let moved = mon.is_none_or(|mon| *mon.output() != output);
+
+        if moved {
+            self.niri
+                .layout
+                .move_to_output(Some(window), &output, None, ActivateWindow::Smart);
+        }
+
+        // The window is back where it was, so there's nothing to return to anymore.
+        self.niri.layout.with_windows_mut(|mapped, _| {
+            if mapped.window == *window {
+                mapped.set_last_output(None);
+            }
+        });
+
+        moved.then_some(output)
+    }
+
     pub fn handle_bind(&mut self, bind: Bind) {
+        let debounce = bind.key.trigger.is_scroll();
+        self.handle_bind_with_cooldown(bind, debounce);
+    }
+
+    /// Handles a bind whose cooldown limits the rate rather than debounces, even for scroll.
+    fn handle_rate_limited_bind(&mut self, bind: Bind) {
+        self.handle_bind_with_cooldown(bind, false);
+    }
+
+    fn handle_bind_with_cooldown(&mut self, bind: Bind, debounce: bool) {
         let Some(cooldown) = bind.cooldown else {
             self.do_action(bind.action, bind.allow_when_locked);
             return;
         };
 
         // Check this first so that it doesn't trigger the cooldown.
-        if self.niri.is_locked() && !(bind.allow_when_locked || allowed_when_locked(&bind.action)) {
+        if self.niri.is_locked() && !(bind.allow_when_locked || bind.action.allowed_when_locked()) {
             return;
         }
 
-        match self.niri.bind_cooldown_timers.entry(bind.key) {
-            // The bind is on cooldown.
-            Entry::Occupied(_) => (),
-            Entry::Vacant(entry) => {
-                let timer = Timer::from_duration(cooldown);
-                let token = self
-                    .niri
-                    .event_loop
-                    .insert_source(timer, move |_, _, state| {
-                        if state.niri.bind_cooldown_timers.remove(&bind.key).is_none() {
-                            error!("bind cooldown timer entry disappeared");
-                        }
-                        TimeoutAction::Drop
-                    })
-                    .unwrap();
-                entry.insert(token);
-
-                self.do_action(bind.action, bind.allow_when_locked);
+        let on_cooldown = if debounce {
+            // Scroll ticks come in bursts, so for them the cooldown is a debounce: every tick
+            // restarts it, and the bind fires again only after scrolling pauses for the cooldown.
+            let token = self.niri.bind_cooldown_timers.remove(&bind.key);
+            if let Some(token) = token {
+                self.niri.event_loop.remove(token);
+            }
+            self.start_bind_cooldown(bind.key, cooldown);
+            token.is_some()
+        } else {
+            // Otherwise, the cooldown limits how often the bind fires, including key repeat.
+            let on_cooldown = self.niri.bind_cooldown_timers.contains_key(&bind.key);
+            if !on_cooldown {
+                self.start_bind_cooldown(bind.key, cooldown);
             }
+            on_cooldown
+        };
+
+        if !on_cooldown {
+            self.do_action(bind.action, bind.allow_when_locked);
         }
     }
 
+    fn start_bind_cooldown(&mut self, key: Key, cooldown: Duration) {
+        let timer = Timer::from_duration(cooldown);
+        let token = self
+            .niri
+            .event_loop
+            .insert_source(timer, move |_, _, state| {
+                if state.niri.bind_cooldown_timers.remove(&key).is_none() {
+                    error!("bind cooldown timer entry disappeared");
+                }
+                TimeoutAction::Drop
+            })
+            .unwrap();
+        self.niri.bind_cooldown_timers.insert(key, token);
+    }
+
     pub fn do_action(&mut self, action: Action, allow_when_locked: bool) {
-        if self.niri.is_locked() && !(allow_when_locked || allowed_when_locked(&action)) {
+        if self.niri.is_locked() && !(allow_when_locked || action.allowed_when_locked()) {
             return;
         }
 
@@ -678,6 +896,10 @@ impl State {
             touch.cancel(self);
         }
 
+        if !matches!(action, Action::FocusWindowPrevious) {
+            self.niri.focus_history_walk.reset();
+        }
+
         match action {
             Action::Quit(skip_confirmation) => {
                 if !skip_confirmation && self.niri.exit_confirm_dialog.show() {
@@ -703,6 +925,58 @@ impl State {
             }
             Action::PowerOnMonitors => {
                 self.niri.activate_monitors(&mut self.backend);
+
+                let outputs: Vec<_> = self.niri.output_state.keys().cloned().collect();
+                for output in outputs {
+                    self.niri.activate_monitor(&mut self.backend, &output);
+                }
+            }
+            Action::PowerOffMonitor(output) => {
+                if let Some(output) = self.niri.output_by_name_match(&output).cloned() {
+                    self.niri.deactivate_monitor(&mut self.backend, &output);
+                }
+            }
+            Action::PowerOnMonitor(output) => {
+                if let Some(output) = self.niri.output_by_name_match(&output).cloned() {
+                    self.niri.activate_monitor(&mut self.backend, &output);
+                }
+            }
+            Action::SetOutputScale(scale) => {
+                if let Some(output) = self.niri.layout.active_output().cloned() {
+                    self.set_output_scale(&output, scale.0);
+                }
+            }
+            Action::CycleOutputScale(scales) => {
+                if let Some(output) = self.niri.layout.active_output().cloned() {
+                    let current = output.current_scale().fractional_scale();
+                    let scales: Vec<f64> = scales.iter().map(|scale| scale.0).collect();
+                    if let Some(scale) = next_scale_in_cycle(current, &scales) {
+                        self.set_output_scale(&output, scale);
+                    }
+                }
+            }
+            Action::RotateOutput(transform) => {
+                if let Some(output) = self.niri.layout.active_output().cloned() {
+                    self.set_output_transform(&output, transform);
+                }
+            }
+            Action::CycleOutputTransform => {
+                if let Some(output) = self.niri.layout.active_output().cloned() {
+                    let current = self
+                        .niri
+                        .config
+                        .borrow()
+                        .outputs
+                        .find(output.user_data().get::<OutputName>().unwrap())
+                        .map_or(niri_ipc::Transform::Normal, |config| config.transform);
+                    self.set_output_transform(&output, next_transform_in_cycle(current));
+                }
+            }
+            Action::ChangeBacklight(delta) => {
+                self.backend.change_backlight(delta);
+            }
+            Action::SetBacklight(percent) => {
+                self.backend.set_backlight(percent);
             }
             Action::ToggleDebugTint => {
                 self.backend.toggle_debug_tint();
@@ -715,6 +989,9 @@ impl State {
             Action::DebugToggleDamage => {
                 self.niri.debug_toggle_damage();
             }
+            Action::DebugToggleRenderHeatmap => {
+                self.niri.debug_toggle_render_heatmap();
+            }
             Action::Spawn(command) => {
                 let (token, _) = self.niri.activation_state.create_external_token(None);
                 spawn(command, Some(token.clone()));
@@ -723,6 +1000,13 @@ impl State {
                 let (token, _) = self.niri.activation_state.create_external_token(None);
                 spawn_sh(command, Some(token.clone()));
             }
+            Action::SpawnOnWorkspace(command, workspace) => {
+                let (token, _) = self.niri.activation_state.create_external_token(None);
+                let token = token.clone();
+                self.niri
+                    .add_pending_spawn_on_workspace(token.clone(), workspace);
+                spawn(command, Some(token));
+            }
             Action::DoScreenTransition(delay_ms) => {
                 self.backend.with_primary_renderer(|renderer| {
                     self.niri.do_screen_transition(renderer, delay_ms);
@@ -733,6 +1017,26 @@ impl State {
                 show_pointer,
                 LegacyScreenshotOutput::new(write_to_disk, path),
             ),
+            Action::ScreenshotEachOutput(write_to_disk, show_pointer, path_template) => {
+                if !write_to_disk {
+                    // The clipboard can only hold one of the screenshots anyway.
+                    self.handle_screenshot(
+                        ScreenshotTarget::CurrentOutput,
+                        show_pointer,
+                        LegacyScreenshotOutput::new(false, None),
+                    );
+                } else {
+                    let names: Vec<_> =
+                        self.niri.global_space.outputs().map(|o| o.name()).collect();
+                    for name in names {
+                        self.handle_screenshot(
+                            ScreenshotTarget::Output(name.clone()),
+                            show_pointer,
+                            LegacyScreenshotOutput::for_output(path_template.clone(), name),
+                        );
+                    }
+                }
+            }
             Action::ConfirmScreenshot { write_to_disk } => {
                 self.confirm_screenshot(write_to_disk);
             }
@@ -772,6 +1076,14 @@ impl State {
                     LegacyScreenshotOutput::new(write_to_disk, path),
                 )
             }
+            Action::ScreenshotToCommand(command, target, show_pointer) => {
+                let target = match target {
+                    ScreenshotTargetArg::Screen => ScreenshotTarget::CurrentOutput,
+                    ScreenshotTargetArg::Window => ScreenshotTarget::CurrentWindow,
+                    ScreenshotTargetArg::AllOutputs => ScreenshotTarget::AllOutputs,
+                };
+                self.handle_screenshot(target, show_pointer, CommandScreenshotOutput::new(command))
+            }
             Action::ToggleKeyboardShortcutsInhibit => {
                 if let Some(inhibitor) = self.niri.keyboard_focus.surface().and_then(|surface| {
                     self.niri
@@ -812,6 +1124,14 @@ impl State {
             Action::FocusPreviousWindow => {
                 self.niri.layout.focus_prev();
             }
+            Action::SetGroupDisplayMode(mode) => {
+                self.niri.layout.set_group_display_mode(mode);
+                self.niri.queue_redraw_all();
+            }
+            Action::ToggleGroupDisplayMode => {
+                self.niri.layout.toggle_group_display_mode();
+                self.niri.queue_redraw_all();
+            }
             Action::FullscreenWindow => {
                 let focus = self.niri.layout.focus().map(|m| m.window.clone());
                 if let Some(window) = focus {
@@ -861,19 +1181,29 @@ impl State {
                 self.niri.queue_redraw_all();
             }
             Action::FocusWindowPrevious => {
-                let current = self.niri.layout.focus().map(|win| win.id());
-                if let Some(window) = self
-                    .niri
-                    .layout
-                    .windows()
-                    .map(|(_, win)| win)
-                    .filter(|win| Some(win.id()) != current)
-                    .max_by_key(|win| win.get_focus_timestamp())
-                    .map(|win| win.window.clone())
-                {
-                    // Commit current focus so repeated focus-window-previous works as expected.
+                let current = self.niri.layout.focus().map(|win| win.window.clone());
+                let depth = self.niri.config.borrow().recent_windows.focus_history_depth;
+
+                // Commit current focus so that a new walk starts from it. Windows focused in the
+                // middle of a walk shouldn't reorder the history though.
+                if self.niri.focus_history_walk.is_continuing(current.as_ref()) {
+                    self.niri.mru_cancel_keyboard_commit();
+                } else {
                     self.niri.mru_apply_keyboard_commit();
+                }
 
+                let layout = &self.niri.layout;
+                let recent = || {
+                    let mut windows: Vec<_> = layout.windows().map(|(_, win)| win).collect();
+                    windows.sort_by_key(|win| Reverse(win.get_focus_timestamp()));
+                    windows.into_iter().map(|win| win.window.clone()).collect()
+                };
+
+                if let Some(window) =
+                    self.niri
+                        .focus_history_walk
+                        .next(current.as_ref(), recent, usize::from(depth))
+                {
                     self.focus_window(&window);
                 }
             }
@@ -892,6 +1222,15 @@ impl State {
                     }
                 });
             }
+            Action::ToggleNumLock => {
+                // Num lock isn't tracked per window, so this changes it for all of them. Setting
+                // the modifier state sends the new modifiers to the focused client and updates
+                // the keyboard LEDs.
+                let keyboard = self.niri.seat.get_keyboard().unwrap();
+                let mut mods_state = keyboard.modifier_state();
+                mods_state.num_lock = !mods_state.num_lock;
+                keyboard.set_modifier_state(mods_state);
+            }
             Action::MoveColumnLeft => {
                 if self.niri.screenshot_ui.is_open() {
                     self.niri.screenshot_ui.move_left();
@@ -1090,6 +1429,19 @@ impl State {
                     self.niri.queue_redraw(&output);
                 }
             }
+            Action::FocusColumnUnderMouse => {
+                if let Some((output, ws_id, idx)) = self.niri.column_under_cursor() {
+                    let ws = {
+                        let mut workspaces = self.niri.layout.workspaces_mut();
+                        workspaces.find(|ws| ws.id() == ws_id).unwrap()
+                    };
+                    // Column indices in focus_column() start from 1.
+                    ws.focus_column(idx + 1);
+                    self.niri.layout.focus_output(&output);
+                    self.niri.layer_shell_on_demand_focus = None;
+                    self.niri.queue_redraw(&output);
+                }
+            }
             Action::FocusColumnFirst => {
                 self.niri.layout.focus_column_first();
                 self.maybe_warp_cursor_to_focus();
@@ -1293,6 +1645,13 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::FocusSpatial(direction) => {
+                self.niri.layout.focus_spatial(direction);
+                self.maybe_warp_cursor_to_focus();
+                self.niri.layer_shell_on_demand_focus = None;
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::MoveWindowToWorkspaceDown(focus) => {
                 self.niri.layout.move_to_workspace_down(focus);
                 self.maybe_warp_cursor_to_focus();
@@ -1438,12 +1797,30 @@ impl State {
                     self.niri.queue_redraw_all();
                 }
             }
+            Action::MoveWindowToWorkspacePrevious(focus) => {
+                self.niri.layout.move_to_workspace_previous(focus);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::MoveColumnToWorkspacePrevious(focus) => {
+                self.niri.layout.move_column_to_workspace_previous(focus);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::MoveColumnToIndex(idx) => {
                 self.niri.layout.move_column_to_index(idx);
                 self.maybe_warp_cursor_to_focus();
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::MoveColumnByOffset(offset) => {
+                self.niri.layout.move_column_by_offset(offset);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::FocusWorkspaceDown => {
                 self.niri.layout.switch_workspace_down();
                 self.maybe_warp_cursor_to_focus();
@@ -1478,8 +1855,22 @@ impl State {
                     self.niri.queue_redraw(&output);
                 }
             }
-            Action::FocusWorkspace(reference) => {
-                if let Some((mut output, index)) =
+            Action::FocusWorkspace(reference, create_if_missing) => {
+                if create_if_missing
+                    && let WorkspaceReference::Name(name) = &reference
+                    && self.niri.layout.find_workspace_by_name(name).is_none()
+                {
+                    if let Some(output) = self.niri.layout.active_output().cloned() {
+                        self.niri
+                            .layout
+                            .activate_named_workspace_on_output(&output, name);
+                        self.maybe_warp_cursor_to_focus();
+                        self.niri.layer_shell_on_demand_focus = None;
+
+                        // FIXME: granular
+                        self.niri.queue_redraw_all();
+                    }
+                } else if let Some((mut output, index)) =
                     self.niri.find_output_and_workspace_index(reference)
                 {
                     if let Some(active) = self.niri.layout.active_output()
@@ -1526,16 +1917,24 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
-            Action::MoveWorkspaceToIndex(new_idx) => {
+            Action::MoveWorkspaceToIndex(new_idx, compact) => {
                 let new_idx = new_idx.saturating_sub(1);
-                self.niri.layout.move_workspace_to_idx(None, new_idx);
+                self.niri
+                    .layout
+                    .move_workspace_to_idx(None, new_idx, compact);
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
-            Action::MoveWorkspaceToIndexByRef { new_idx, reference } => {
+            Action::MoveWorkspaceToIndexByRef {
+                new_idx,
+                reference,
+                compact,
+            } => {
                 if let Some(res) = self.niri.find_output_and_workspace_index(reference) {
                     let new_idx = new_idx.saturating_sub(1);
-                    self.niri.layout.move_workspace_to_idx(Some(res), new_idx);
+                    self.niri
+                        .layout
+                        .move_workspace_to_idx(Some(res), new_idx, compact);
                     // FIXME: granular
                     self.niri.queue_redraw_all();
                 }
@@ -1559,12 +1958,38 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::ConsumeWindowFromMonitorLeft => {
+                if let Some(output) = self.niri.output_left() {
+                    self.niri.layout.consume_window_from_output(&output);
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::ConsumeWindowFromMonitorRight => {
+                if let Some(output) = self.niri.output_right() {
+                    self.niri.layout.consume_window_from_output(&output);
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
             Action::ExpelWindowFromColumn => {
                 self.niri.layout.expel_from_column();
                 self.maybe_warp_cursor_to_focus();
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::ExpelWindowToColumnLeft => {
+                self.niri.layout.expel_window_to_column(false);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::ExpelWindowToColumnRight => {
+                self.niri.layout.expel_window_to_column(true);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::SwapWindowRight => {
                 self.niri
                     .layout
@@ -1583,9 +2008,11 @@ impl State {
             }
             Action::SwitchPresetColumnWidth => {
                 self.niri.layout.toggle_width(true);
+                self.remember_preset_column_width();
             }
             Action::SwitchPresetColumnWidthBack => {
                 self.niri.layout.toggle_width(false);
+                self.remember_preset_column_width();
             }
             Action::SwitchPresetWindowWidth => {
                 self.niri.layout.toggle_window_width(None, true);
@@ -1646,14 +2073,37 @@ impl State {
                     self.niri.queue_redraw_all();
                 }
             }
+            Action::CenterWindowVertically => {
+                self.niri.layout.center_window_vertically(None);
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::CenterWindowVerticallyById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.layout.center_window_vertically(Some(&window));
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
             Action::CenterVisibleColumns => {
                 self.niri.layout.center_visible_columns();
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::DistributeColumnsEvenly => {
+                self.niri.layout.distribute_columns_evenly();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::MaximizeColumn => {
                 self.niri.layout.toggle_full_width();
             }
+            Action::ToggleMaximizeColumnWithGroup => {
+                self.niri.layout.toggle_maximize_column_with_group();
+                self.niri.queue_redraw_all();
+            }
             Action::MaximizeWindowToEdges => {
                 let focus = self.niri.layout.focus().map(|m| m.window.clone());
                 if let Some(window) = focus {
@@ -1671,6 +2121,23 @@ impl State {
                     self.niri.queue_redraw_all();
                 }
             }
+            Action::ToggleMaximizeWindowToEdges => {
+                let focus = self.niri.layout.focus().map(|m| m.window.clone());
+                if let Some(window) = focus {
+                    self.niri.layout.toggle_maximized_with_restore(&window);
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::ToggleMaximizeWindowToEdgesById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.layout.toggle_maximized_with_restore(&window);
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
             Action::FocusMonitorLeft => {
                 if let Some(output) = self.niri.output_left() {
                     self.niri.layout.focus_output(&output);
@@ -1725,6 +2192,42 @@ impl State {
                     self.niri.layer_shell_on_demand_focus = None;
                 }
             }
+            Action::FocusMonitorLeftWrap => {
+                if let Some(output) = self.niri.output_left_wrapping() {
+                    self.niri.layout.focus_output(&output);
+                    if !self.maybe_warp_cursor_to_focus_centered() {
+                        self.move_cursor_to_output(&output);
+                    }
+                    self.niri.layer_shell_on_demand_focus = None;
+                }
+            }
+            Action::FocusMonitorRightWrap => {
+                if let Some(output) = self.niri.output_right_wrapping() {
+                    self.niri.layout.focus_output(&output);
+                    if !self.maybe_warp_cursor_to_focus_centered() {
+                        self.move_cursor_to_output(&output);
+                    }
+                    self.niri.layer_shell_on_demand_focus = None;
+                }
+            }
+            Action::FocusMonitorDownWrap => {
+                if let Some(output) = self.niri.output_down_wrapping() {
+                    self.niri.layout.focus_output(&output);
+                    if !self.maybe_warp_cursor_to_focus_centered() {
+                        self.move_cursor_to_output(&output);
+                    }
+                    self.niri.layer_shell_on_demand_focus = None;
+                }
+            }
+            Action::FocusMonitorUpWrap => {
+                if let Some(output) = self.niri.output_up_wrapping() {
+                    self.niri.layout.focus_output(&output);
+                    if !self.maybe_warp_cursor_to_focus_centered() {
+                        self.move_cursor_to_output(&output);
+                    }
+                    self.niri.layer_shell_on_demand_focus = None;
+                }
+            }
             Action::FocusMonitor(output) => {
                 if let Some(output) = self.niri.output_by_name_match(&output).cloned() {
                     self.niri.layout.focus_output(&output);
@@ -1734,6 +2237,20 @@ impl State {
                     self.niri.layer_shell_on_demand_focus = None;
                 }
             }
+            Action::FocusMonitorByWorkspace(name) => {
+                let output = self
+                    .niri
+                    .layout
+                    .find_workspace_by_name(&name)
+                    .and_then(|(_, ws)| ws.current_output().cloned());
+                if let Some(output) = output {
+                    self.niri.layout.focus_output(&output);
+                    if !self.maybe_warp_cursor_to_focus_centered() {
+                        self.move_cursor_to_output(&output);
+                    }
+                    self.niri.layer_shell_on_demand_focus = None;
+                }
+            }
             Action::MoveWindowToMonitorLeft => {
                 if let Some(current_output) = self.niri.screenshot_ui.selection_output() {
                     if let Some(target_output) = self.niri.output_left_of(current_output) {
@@ -1875,6 +2392,81 @@ impl State {
                     }
                 }
             }
+            Action::MoveWindowToMonitorWorkspace(output, reference, focus, create_if_missing) => {
+                if let Some(output) = self.niri.output_by_name_match(&output).cloned() {
+                    let index = match reference {
+                        WorkspaceReference::Index(index) => Some(index.saturating_sub(1) as usize),
+                        WorkspaceReference::Name(name)
+                            if create_if_missing
+                                && self.niri.layout.find_workspace_by_name(&name).is_none() =>
+                        {
+                            self.niri
+                                .layout
+                                .put_named_workspace_on_output(&output, &name, false)
+                        }
+                        reference => self
+                            .niri
+                            .find_output_and_workspace_index(reference)
+                            .filter(|(ws_output, _)| ws_output.as_ref() == Some(&output))
+                            .map(|(_, index)| index),
+                    };
+
+                    if let Some(index) = index {
+                        let activate = if focus {
+                            ActivateWindow::Smart
+                        } else {
+                            ActivateWindow::No
+                        };
+
+                        if self.niri.layout.active_output() == Some(&output) {
+                            self.niri.layout.move_to_workspace(None, index, activate);
+                            self.maybe_warp_cursor_to_focus();
+                        } else {
+                            self.niri
+                                .layout
+                                .move_to_output(None, &output, Some(index), activate);
+
+                            if focus {
+                                if !self.maybe_warp_cursor_to_focus_centered() {
+                                    self.move_cursor_to_output(&output);
+                                }
+                            } else {
+                                self.maybe_warp_cursor_to_focus();
+                            }
+                        }
+
+                        // FIXME: granular
+                        self.niri.queue_redraw_all();
+                    }
+                }
+            }
+            Action::MoveWindowToLastOutput => {
+                let window = self.niri.layout.focus().map(|m| m.window.clone());
+                if let Some(window) = window
+                    && let Some(output) = self.move_window_to_last_output(&window)
+                {
+                    self.niri.layout.focus_output(&output);
+                    if !self.maybe_warp_cursor_to_focus_centered() {
+                        self.move_cursor_to_output(&output);
+                    }
+                }
+            }
+            Action::MoveWindowToLastOutputById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+
+                if let Some(window) = window {
+                    let active = self.niri.layout.active_output().cloned();
+                    if let Some(output) = self.move_window_to_last_output(&window)
+                        // If the active output changed (window was moved and focused).
+                        && active.as_ref() != Some(&output)
+                        && self.niri.layout.active_output() == Some(&output)
+                        && !self.maybe_warp_cursor_to_focus_centered()
+                    {
+                        self.move_cursor_to_output(&output);
+                    }
+                }
+            }
             Action::MoveColumnToMonitorLeft => {
                 if let Some(current_output) = self.niri.screenshot_ui.selection_output() {
                     if let Some(target_output) = self.niri.output_left_of(current_output) {
@@ -1973,6 +2565,22 @@ impl State {
                     }
                 }
             }
+            Action::SwapColumnToMonitorLeft => {
+                if let Some(output) = self.niri.output_left()
+                    && self.niri.layout.swap_column_with_output(&output)
+                    && !self.maybe_warp_cursor_to_focus_centered()
+                {
+                    self.move_cursor_to_output(&output);
+                }
+            }
+            Action::SwapColumnToMonitorRight => {
+                if let Some(output) = self.niri.output_right()
+                    && self.niri.layout.swap_column_with_output(&output)
+                    && !self.maybe_warp_cursor_to_focus_centered()
+                {
+                    self.move_cursor_to_output(&output);
+                }
+            }
             Action::SetColumnWidth(change) => {
                 if self.niri.screenshot_ui.is_open() {
                     self.niri.screenshot_ui.set_width(change);
@@ -2027,9 +2635,33 @@ impl State {
                     self.niri.layout.reset_window_height(Some(&window));
                 }
             }
+            Action::FillWindowHeightInColumn => {
+                self.niri.layout.fill_window_height_in_column(None);
+            }
+            Action::FillWindowHeightInColumnById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.layout.fill_window_height_in_column(Some(&window));
+                }
+            }
             Action::ExpandColumnToAvailableWidth => {
                 self.niri.layout.expand_column_to_available_width();
             }
+            Action::ToggleColumnExpandedWidth => {
+                self.niri.layout.toggle_column_expanded_width();
+            }
+            Action::AlignColumnWidthToNeighborLeft => {
+                self.niri.layout.align_column_width_to_neighbor(false);
+            }
+            Action::AlignColumnWidthToNeighborRight => {
+                self.niri.layout.align_column_width_to_neighbor(true);
+            }
+            Action::ToggleZeroGaps => {
+                self.niri.layout.toggle_zero_gaps();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::ShowHotkeyOverlay => {
                 if self.niri.hotkey_overlay.show() {
                     self.niri.queue_redraw_all();
@@ -2086,6 +2718,29 @@ impl State {
                     }
                 }
             }
+            Action::MoveWorkspaceToMonitorUnderMouse => {
+                if let Some(output) = self.niri.output_under_cursor()
+                    && self.niri.layout.active_output() != Some(&output)
+                    && self.niri.layout.move_workspace_to_output(&output)
+                {
+                    self.maybe_warp_cursor_to_focus_centered();
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::ToggleMirrorToMonitor(target) => {
+                if let Some(source) = self.niri.layout.active_output().cloned()
+                    && let Some(target) = self.niri.output_by_name_match(&target).cloned()
+                {
+                    self.niri.toggle_output_mirror(&source, &target);
+                }
+            }
+            Action::MirrorMonitor { source, target } => {
+                if let Some(source) = self.niri.output_by_name_match(&source).cloned()
+                    && let Some(target) = self.niri.output_by_name_match(&target).cloned()
+                {
+                    self.niri.mirror_output(&source, &target);
+                }
+            }
             Action::MoveWorkspaceToMonitor(new_output) => {
                 if let Some(new_output) = self.niri.output_by_name_match(&new_output).cloned()
                     && self.niri.layout.move_workspace_to_output(&new_output)
@@ -2126,6 +2781,42 @@ impl State {
                     self.niri.queue_redraw_all();
                 }
             }
+            Action::ToggleFloatingResizeMode => {
+                if self.niri.floating_resize_mode.take().is_none()
+                    && self.niri.layout.is_focus_floating()
+                    && let Some(mapped) = self.niri.layout.focus()
+                {
+                    let size = mapped.expected_size().unwrap_or_else(|| mapped.size());
+                    let mode = FloatingResizeMode::new(mapped.window.clone(), size);
+                    self.niri.floating_resize_mode = Some(mode);
+                }
+            }
+            Action::FloatingResizeModeAdjust { width, height } => {
+                if let Some(window) = self.floating_resize_mode_window() {
+                    if width != 0 {
+                        let change = SizeChange::AdjustFixed(width);
+                        self.niri.layout.set_window_width(Some(&window), change);
+                    }
+                    if height != 0 {
+                        let change = SizeChange::AdjustFixed(height);
+                        self.niri.layout.set_window_height(Some(&window), change);
+                    }
+                }
+            }
+            Action::FloatingResizeModeConfirm => {
+                self.niri.floating_resize_mode = None;
+            }
+            Action::FloatingResizeModeCancel => {
+                if let Some(window) = self.floating_resize_mode_window()
+                    && let Some(mode) = self.niri.floating_resize_mode.take()
+                {
+                    let size = mode.original_size;
+                    let layout = &mut self.niri.layout;
+                    layout.set_window_width(Some(&window), SizeChange::SetFixed(size.w));
+                    layout.set_window_height(Some(&window), SizeChange::SetFixed(size.h));
+                }
+                self.niri.floating_resize_mode = None;
+            }
             Action::MoveWindowToFloating => {
                 self.niri.layout.set_window_floating(None, true);
                 // FIXME: granular
@@ -2160,6 +2851,18 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::FocusNextFloatingWindow => {
+                self.niri.layout.focus_floating_window_in_stack(true);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::FocusPreviousFloatingWindow => {
+                self.niri.layout.focus_floating_window_in_stack(false);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::FocusTiling => {
                 self.niri.layout.focus_tiling();
                 self.maybe_warp_cursor_to_focus();
@@ -2172,6 +2875,16 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::FocusFloatingOrTilingUnderMouse => {
+                // Activating the window also moves the focus between floating and tiling.
+                if let Some(mapped) = self.niri.window_under_cursor() {
+                    let window = mapped.window.clone();
+                    self.niri.layout.activate_window(&window);
+                    self.niri.layer_shell_on_demand_focus = None;
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
             Action::MoveFloatingWindowById { id, x, y } => {
                 let window = if let Some(id) = id {
                     let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
@@ -2197,7 +2910,7 @@ impl State {
                     .active_workspace_mut()
                     .and_then(|ws| ws.active_window_mut());
                 if let Some(window) = active_window
-                    && window.rules().opacity.is_some_and(|o| o != 1.)
+                    && window.opacity().is_some_and(|o| o != 1.)
                 {
                     window.toggle_ignore_opacity_window_rule();
                     // FIXME: granular
@@ -2211,13 +2924,63 @@ impl State {
                     .workspaces_mut()
                     .find_map(|ws| ws.windows_mut().find(|w| w.id().get() == id));
                 if let Some(window) = window
-                    && window.rules().opacity.is_some_and(|o| o != 1.)
+                    && window.opacity().is_some_and(|o| o != 1.)
                 {
                     window.toggle_ignore_opacity_window_rule();
                     // FIXME: granular
                     self.niri.queue_redraw_all();
                 }
             }
+            Action::SetWindowOpacity(opacity) => {
+                let active_window = self
+                    .niri
+                    .layout
+                    .active_workspace_mut()
+                    .and_then(|ws| ws.active_window_mut());
+                if let Some(window) = active_window {
+                    window.set_opacity(opacity);
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::SetWindowOpacityById { id, opacity } => {
+                let window = self
+                    .niri
+                    .layout
+                    .workspaces_mut()
+                    .find_map(|ws| ws.windows_mut().find(|w| w.id().get() == id));
+                if let Some(window) = window {
+                    window.set_opacity(opacity);
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::ToggleServerDecorations => {
+                let prefer_no_csd = self.niri.config.borrow().prefer_no_csd;
+                let active_window = self
+                    .niri
+                    .layout
+                    .active_workspace_mut()
+                    .and_then(|ws| ws.active_window_mut());
+                if let Some(window) = active_window
+                    && !window.toggle_server_decorations(prefer_no_csd)
+                {
+                    debug!("window doesn't support xdg-decoration, cannot toggle decorations");
+                }
+            }
+            Action::ToggleServerDecorationsById(id) => {
+                let prefer_no_csd = self.niri.config.borrow().prefer_no_csd;
+                let window = self
+                    .niri
+                    .layout
+                    .workspaces_mut()
+                    .find_map(|ws| ws.windows_mut().find(|w| w.id().get() == id));
+                if let Some(window) = window
+                    && !window.toggle_server_decorations(prefer_no_csd)
+                {
+                    debug!("window doesn't support xdg-decoration, cannot toggle decorations");
+                }
+            }
             Action::SetDynamicCastWindow => {
                 let id = self
                     .niri
@@ -2252,6 +3015,10 @@ impl State {
                 self.niri.layout.toggle_overview();
                 self.niri.queue_redraw_all();
             }
+            Action::ToggleOverviewFocusedMonitor => {
+                self.niri.layout.toggle_overview_on_active_output();
+                self.niri.queue_redraw_all();
+            }
             Action::OpenOverview => {
                 if self.niri.layout.open_overview() {
                     self.niri.queue_redraw_all();
@@ -2262,6 +3029,12 @@ impl State {
                     self.niri.queue_redraw_all();
                 }
             }
+            Action::ToggleFocusedWindowUrgent => {
+                if let Some(mapped) = self.niri.layout.focus() {
+                    let id = mapped.id().get();
+                    self.do_action(Action::ToggleWindowUrgent(id), allow_when_locked);
+                }
+            }
             Action::ToggleWindowUrgent(id) => {
                 let window = self
                     .niri
@@ -2270,7 +3043,7 @@ impl State {
                     .find_map(|ws| ws.windows_mut().find(|w| w.id().get() == id));
                 if let Some(window) = window {
                     let urgent = window.is_urgent();
-                    window.set_urgent(!urgent);
+                    window.set_urgent_by_user(!urgent);
                 }
                 self.niri.queue_redraw_all();
             }
@@ -2301,6 +3074,14 @@ impl State {
                     watcher.load_config();
                 }
             }
+            Action::ReloadBinds => {
+                if let Some(watcher) = &self.niri.config_file_watcher {
+                    watcher.reload_binds();
+                }
+            }
+            Action::RestoreClipboardHistoryEntry(idx) => {
+                self.restore_clipboard_history_entry(idx);
+            }
             Action::MruConfirm => {
                 self.confirm_mru();
             }
@@ -2406,7 +3187,9 @@ impl State {
         let mut new_pos = pos + event.delta();
 
         // We received an event for the regular pointer, so show it now.
-        self.niri.pointer_visibility = PointerVisibility::Visible;
+        if self.pointer_motion_reveals_pointer(event.delta()) {
+            self.niri.pointer_visibility = PointerVisibility::Visible;
+        }
         self.niri.tablet_cursor_location = None;
 
         // Check if we have an active pointer constraint.
@@ -2754,6 +3537,9 @@ impl State {
         }
 
         if ButtonState::Pressed == button_state {
+            // Mod+click is not a Mod tap.
+            self.niri.mod_double_tap.reset();
+
             let mods = self.niri.seat.get_keyboard().unwrap().modifier_state();
             let modifiers = modifiers_from_state(mods);
 
@@ -2804,7 +3590,7 @@ impl State {
             self.niri.pointer_visibility = PointerVisibility::Visible;
             self.niri.tablet_cursor_location = None;
 
-            let is_overview_open = self.niri.layout.is_overview_open();
+            let is_overview_open = self.niri.is_overview_open_under_cursor();
 
             if is_overview_open
                 && !pointer.is_grabbed()
@@ -3007,6 +3793,15 @@ impl State {
                         self.niri.queue_redraw_all();
                     }
                     _ => {
+                        // Clicking into the gaps around a column focuses the column.
+                        if !is_overview_open
+                            && button == Some(MouseButton::Left)
+                            && !pointer.is_grabbed()
+                            && self.niri.config.borrow().input.focus_column_on_gap_click
+                        {
+                            self.do_action(Action::FocusColumnUnderMouse, false);
+                        }
+
                         if let Some(output) = self.niri.output_under_cursor() {
                             self.niri.layout.focus_output(&output);
 
@@ -3066,7 +3861,10 @@ impl State {
         pointer.frame(self);
     }
 
-    fn on_pointer_axis<I: InputBackend>(&mut self, event: I::PointerAxisEvent) {
+    fn on_pointer_axis<I: InputBackend>(&mut self, event: I::PointerAxisEvent)
+    where
+        I::Device: 'static,
+    {
         let pointer = &self.niri.seat.get_pointer().unwrap();
 
         let source = event.source();
@@ -3084,7 +3882,7 @@ impl State {
         let horizontal_amount_v120 = event.amount_v120(Axis::Horizontal);
         let vertical_amount_v120 = event.amount_v120(Axis::Vertical);
 
-        let is_overview_open = self.niri.layout.is_overview_open();
+        let is_overview_open = self.niri.is_overview_open_under_cursor();
 
         // We should only handle scrolling in the overview if the pointer is not over a (top or
         // overlay) layer surface.
@@ -3129,8 +3927,9 @@ impl State {
                                     modifiers: Modifiers::empty(),
                                 },
                                 action: Action::FocusColumnLeftUnderMouse,
-                                repeat: true,
+                                repeat: false,
                                 cooldown: None,
+                                hold: None,
                                 allow_when_locked: false,
                                 allow_inhibiting: false,
                                 hotkey_overlay_title: None,
@@ -3141,8 +3940,9 @@ impl State {
                                     modifiers: Modifiers::empty(),
                                 },
                                 action: Action::FocusColumnRightUnderMouse,
-                                repeat: true,
+                                repeat: false,
                                 cooldown: None,
+                                hold: None,
                                 allow_when_locked: false,
                                 allow_inhibiting: false,
                                 hotkey_overlay_title: None,
@@ -3182,6 +3982,10 @@ impl State {
                 let vertical = vertical_amount_v120.unwrap_or(0.);
                 let ticks = self.niri.vertical_wheel_tracker.accumulate(vertical);
                 if ticks != 0 {
+                    // The built-in overview binds keep scrolling through workspaces and columns
+                    // while the wheel spins.
+                    let is_overview_bind = should_handle_in_overview
+                        && (modifiers.is_empty() || modifiers == Modifiers::SHIFT);
                     let (bind_up, bind_down) = if should_handle_in_overview && modifiers.is_empty()
                     {
                         let bind_up = Some(Bind {
@@ -3190,8 +3994,9 @@ impl State {
                                 modifiers: Modifiers::empty(),
                             },
                             action: Action::FocusWorkspaceUpUnderMouse,
-                            repeat: true,
+                            repeat: false,
                             cooldown: Some(Duration::from_millis(50)),
+                            hold: None,
                             allow_when_locked: false,
                             allow_inhibiting: false,
                             hotkey_overlay_title: None,
@@ -3202,8 +4007,9 @@ impl State {
                                 modifiers: Modifiers::empty(),
                             },
                             action: Action::FocusWorkspaceDownUnderMouse,
-                            repeat: true,
+                            repeat: false,
                             cooldown: Some(Duration::from_millis(50)),
+                            hold: None,
                             allow_when_locked: false,
                             allow_inhibiting: false,
                             hotkey_overlay_title: None,
@@ -3216,8 +4022,9 @@ impl State {
                                 modifiers: Modifiers::empty(),
                             },
                             action: Action::FocusColumnLeftUnderMouse,
-                            repeat: true,
+                            repeat: false,
                             cooldown: Some(Duration::from_millis(50)),
+                            hold: None,
                             allow_when_locked: false,
                             allow_inhibiting: false,
                             hotkey_overlay_title: None,
@@ -3228,8 +4035,9 @@ impl State {
                                 modifiers: Modifiers::empty(),
                             },
                             action: Action::FocusColumnRightUnderMouse,
-                            repeat: true,
+                            repeat: false,
                             cooldown: Some(Duration::from_millis(50)),
+                            hold: None,
                             allow_when_locked: false,
                             allow_inhibiting: false,
                             hotkey_overlay_title: None,
@@ -3250,14 +4058,19 @@ impl State {
                         (bind_up, bind_down)
                     };
 
+                    let handle_bind = if is_overview_bind {
+                        Self::handle_rate_limited_bind
+                    } else {
+                        Self::handle_bind
+                    };
                     if let Some(down) = bind_down {
                         for _ in 0..ticks {
-                            self.handle_bind(down.clone());
+                            handle_bind(self, down.clone());
                         }
                     }
                     if let Some(up) = bind_up {
                         for _ in ticks..0 {
-                            self.handle_bind(up.clone());
+                            handle_bind(self, up.clone());
                         }
                     }
                 }
@@ -3442,31 +4255,36 @@ impl State {
 
         self.update_pointer_contents();
 
+        let device = event.device();
+        let is_trackpoint = (&device as &dyn Any)
+            .downcast_ref::<input::Device>()
+            .is_some_and(|device| self.niri.trackpoints.contains(device));
+
         let device_scroll_factor = {
             let config = self.niri.config.borrow();
             match source {
-                AxisSource::Wheel => config.input.mouse.scroll_factor,
-                AxisSource::Finger => config.input.touchpad.scroll_factor,
+                AxisSource::Wheel => config.input.mouse.scroll_factor.map(|x| x.h_v_factors()),
+                AxisSource::Finger => config.input.touchpad.scroll_factor.map(|x| x.h_v_factors()),
+                // Trackpoints generate continuous scroll events with the on-button-down method.
+                AxisSource::Continuous if is_trackpoint => config
+                    .input
+                    .trackpoint
+                    .scroll_button_speed
+                    .map(|x| (x.0, x.0)),
                 _ => None,
             }
         };
 
-        // Get window-specific scroll factor
+        // Get the scroll factor of the window that receives the events. Scroll binds were handled
+        // above, so this only affects scrolling inside the window, not the layout.
         let window_scroll_factor = pointer
             .current_focus()
             .map(|focused| self.niri.find_root_shell_surface(&focused))
             .and_then(|root| self.niri.layout.find_window_and_output(&root).unzip().0)
-            .and_then(|window| window.rules().scroll_factor)
-            .unwrap_or(1.);
-
-        // Determine final scroll factors based on configuration
-        let (horizontal_factor, vertical_factor) = device_scroll_factor
-            .map(|x| x.h_v_factors())
-            .unwrap_or((1.0, 1.0));
-        let (horizontal_factor, vertical_factor) = (
-            horizontal_factor * window_scroll_factor,
-            vertical_factor * window_scroll_factor,
-        );
+            .and_then(|window| window.rules().scroll_factor);
+
+        let (horizontal_factor, vertical_factor) =
+            combine_scroll_factors(device_scroll_factor, window_scroll_factor);
 
         let horizontal_amount = horizontal_amount.unwrap_or_else(|| {
             // Winit backend, discrete scrolling.
@@ -3592,7 +4410,11 @@ impl State {
         };
         let tip_state = event.tip_state();
 
-        let is_overview_open = self.niri.layout.is_overview_open();
+        let is_overview_open = self
+            .niri
+            .tablet_cursor_location
+            .and_then(|pos| self.niri.output_under(pos))
+            .is_some_and(|(output, _)| self.niri.layout.is_overview_open_on(output));
 
         match tip_state {
             TabletToolTipState::Down => {
@@ -3759,6 +4581,16 @@ impl State {
             return;
         }
 
+        let fingers = event.fingers();
+        if self.niri.config.borrow().gestures.swipe.has_any(fingers) {
+            // Wait until the swipe direction is known to pick between the bind and the built-in
+            // gesture.
+            self.niri.gesture_swipe_bind_cumulative = Some((fingers, 0., 0.));
+
+            // We handled this event.
+            return;
+        }
+
         if event.fingers() == 3 {
             self.niri.gesture_swipe_3f_cumulative = Some((0., 0.));
 
@@ -3805,6 +4637,7 @@ impl State {
             delta_y = libinput_event.dy_unaccelerated();
         }
 
+        let uninverted_delta_x = delta_x;
         let uninverted_delta_y = delta_y;
 
         let device = event.device();
@@ -3815,7 +4648,47 @@ impl State {
             delta_y = -delta_y;
         }
 
-        let is_overview_open = self.niri.layout.is_overview_open();
+        if self.niri.gesture_swipe_bind_triggered {
+            // The rest of the swipe belongs to the bind that it triggered.
+            return;
+        }
+
+        if let Some((fingers, cx, cy)) = &mut self.niri.gesture_swipe_bind_cumulative {
+            // Swipe binds follow the fingers regardless of natural scrolling.
+            *cx += uninverted_delta_x;
+            *cy += uninverted_delta_y;
+
+            let (fingers, cx, cy) = (*fingers, *cx, *cy);
+            if cx.mul_add(cx, cy * cy) < 16. * 16. {
+                return;
+            }
+            self.niri.gesture_swipe_bind_cumulative = None;
+
+            let direction = SwipeDirection::from_delta(cx, cy);
+            let action = self
+                .niri
+                .config
+                .borrow()
+                .gestures
+                .swipe
+                .action(fingers, direction)
+                .cloned();
+            if let Some(action) = action {
+                self.niri.gesture_swipe_bind_triggered = true;
+                self.do_action(action, false);
+                return;
+            }
+
+            // No bind for this direction, fall back to the built-in gesture.
+            if fingers == 3 {
+                self.niri.gesture_swipe_3f_cumulative = Some((0., 0.));
+            } else if fingers == 4 {
+                self.niri.layout.overview_gesture_begin();
+                self.niri.queue_redraw_all();
+            }
+        }
+
+        let is_overview_open = self.niri.is_overview_open_under_cursor();
 
         if let Some((cx, cy)) = &mut self.niri.gesture_swipe_3f_cumulative {
             *cx += delta_x;
@@ -3913,7 +4786,10 @@ impl State {
     fn on_gesture_swipe_end<I: InputBackend>(&mut self, event: I::GestureSwipeEndEvent) {
         self.niri.gesture_swipe_3f_cumulative = None;
 
-        let mut handled = false;
+        // The swipe began as a potential swipe bind, so clients never saw it.
+        let mut handled = self.niri.gesture_swipe_bind_cumulative.take().is_some();
+        handled |= mem::take(&mut self.niri.gesture_swipe_bind_triggered);
+
         let res = self.niri.layout.workspace_switch_gesture_end(Some(true));
         if let Some(output) = res {
             self.niri.queue_redraw(&output);
@@ -4124,10 +5000,12 @@ impl State {
             let mods = modifiers_from_state(mods);
             let mod_down = mods.contains(mod_key.to_modifiers());
 
-            if self.niri.layout.is_overview_open()
+            if under
+                .output
+                .as_ref()
+                .is_some_and(|output| self.niri.layout.is_overview_open_on(output))
                 && !mod_down
                 && under.layer.is_none()
-                && under.output.is_some()
             {
                 let (output, pos_within_output) = self.niri.output_under(pos).unwrap();
                 let output = output.clone();
@@ -4361,6 +5239,7 @@ fn should_intercept_key<'a>(
                 action,
                 repeat: true,
                 cooldown: None,
+                hold: None,
                 allow_when_locked: false,
                 // The screenshot UI owns the focus anyway, so this doesn't really matter.
                 // But logically, nothing can inhibit its actions. Only opening it can be
@@ -4424,6 +5303,7 @@ fn find_bind<'a>(
             action,
             repeat: true,
             cooldown: None,
+            hold: None,
             allow_when_locked: false,
             // In a worst-case scenario, the user has no way to unlock the compositor and a
             // misbehaving client has a keyboard shortcuts inhibitor, "jailing" the user.
@@ -4490,6 +5370,15 @@ fn find_configured_switch_action(
         .map(|switch_action| Action::Spawn(switch_action.spawn.clone()))
 }
 
+/// Combines the input device scroll factor with the window rule scroll factor.
+///
+/// The device factor is applied first, then the window factor scales both axes on top of it.
+fn combine_scroll_factors(device: Option<(f64, f64)>, window: Option<f64>) -> (f64, f64) {
+    let (horizontal, vertical) = device.unwrap_or((1., 1.));
+    let window = window.unwrap_or(1.);
+    (horizontal * window, vertical * window)
+}
+
 fn modifiers_from_state(mods: ModifiersState) -> Modifiers {
     let mut modifiers = Modifiers::empty();
     if mods.ctrl {
@@ -4585,19 +5474,6 @@ const fn should_reset_pointer_inactivity_timer<I: InputBackend>(event: &InputEve
     )
 }
 
-const fn allowed_when_locked(action: &Action) -> bool {
-    matches!(
-        action,
-        Action::Quit(_)
-            | Action::ChangeVt(_)
-            | Action::Suspend
-            | Action::PowerOffMonitors
-            | Action::PowerOnMonitors
-            | Action::SwitchLayout(_)
-            | Action::ToggleKeyboardShortcutsInhibit
-    )
-}
-
 const fn allowed_during_screenshot(action: &Action) -> bool {
     matches!(
         action,
@@ -4606,6 +5482,8 @@ const fn allowed_during_screenshot(action: &Action) -> bool {
             | Action::Suspend
             | Action::PowerOffMonitors
             | Action::PowerOnMonitors
+            | Action::PowerOffMonitor(_)
+            | Action::PowerOnMonitor(_)
             // The screenshot UI can handle these.
             | Action::MoveColumnLeft
             | Action::MoveColumnLeftOrToMonitorLeft
@@ -4664,19 +5542,258 @@ fn hardcoded_overview_bind(raw: Keysym, mods: ModifiersState) -> Option<Bind> {
         action,
         repeat,
         cooldown: None,
+        hold: None,
+        allow_when_locked: false,
+        allow_inhibiting: false,
+        hotkey_overlay_title: None,
+    })
+}
+
+fn hardcoded_floating_resize_bind(raw: Keysym, mods: ModifiersState, step: u16) -> Option<Bind> {
+    let mods = modifiers_from_state(mods);
+    if !mods.is_empty() {
+        return None;
+    }
+
+    let step = i32::from(step);
+    let mut repeat = true;
+    let action = match raw {
+        Keysym::Escape => {
+            repeat = false;
+            Action::FloatingResizeModeCancel
+        }
+        Keysym::Return => {
+            repeat = false;
+            Action::FloatingResizeModeConfirm
+        }
+        Keysym::Left => Action::FloatingResizeModeAdjust {
+            width: -step,
+            height: 0,
+        },
+        Keysym::Right => Action::FloatingResizeModeAdjust {
+            width: step,
+            height: 0,
+        },
+        Keysym::Up => Action::FloatingResizeModeAdjust {
+            width: 0,
+            height: -step,
+        },
+        Keysym::Down => Action::FloatingResizeModeAdjust {
+            width: 0,
+            height: step,
+        },
+        _ => {
+            return None;
+        }
+    };
+
+    Some(Bind {
+        key: Key {
+            trigger: Trigger::Keysym(raw),
+            modifiers: Modifiers::empty(),
+        },
+        action,
+        repeat,
+        cooldown: None,
+        hold: None,
         allow_when_locked: false,
         allow_inhibiting: false,
         hotkey_overlay_title: None,
     })
 }
 
-pub fn apply_libinput_settings(config: &niri_config::Input, device: &mut input::Device) {
+fn is_trackpoint(device: &input::Device) -> bool {
+    unsafe { device.udev_device() }.is_some_and(|udev_device| {
+        udev_device
+            .property_value("ID_INPUT_POINTINGSTICK")
+            .is_some()
+    })
+}
+
+/// Whether the device is connected through an external bus, such as USB or Bluetooth.
+fn is_external(device: &input::Device) -> bool {
+    unsafe { device.udev_device() }.is_some_and(|udev_device| {
+        udev_device
+            .property_value("ID_BUS")
+            .is_some_and(|bus| bus == "usb" || bus == "bluetooth")
+    })
+}
+
+/// Whether the device is a full keyboard, as opposed to e.g. a power button.
+fn is_keyboard(device: &input::Device) -> bool {
+    device.has_capability(input::DeviceCapability::Keyboard)
+        && unsafe { device.udev_device() }
+            .is_some_and(|udev_device| udev_device.property_value("ID_INPUT_KEYBOARD").is_some())
+        && has_alphanumeric_keys(|key| device.keyboard_has_key(key).unwrap_or(false))
+}
+
+/// Whether all letter and digit keys are present.
+///
+/// Mice with extra buttons and security keys often claim to be keyboards, but most of them lack
+/// some of these keys.
+fn has_alphanumeric_keys(has_key: impl Fn(u32) -> bool) -> bool {
+    // Evdev codes of the 1 to 0, Q to P, A to L and Z to M key rows.
+    let mut keys = (2..=11).chain(16..=25).chain(30..=38).chain(44..=50);
+    keys.all(has_key)
+}
+
+fn is_external_keyboard(config: &niri_config::Keyboard, device: &input::Device) -> bool {
+    device.has_capability(input::DeviceCapability::Keyboard)
+        && counts_as_external_keyboard(config, device.name(), || {
+            is_external(device) && is_keyboard(device)
+        })
+}
+
+/// Whether a keyboard counts as external for `disabled-on-external-keyboard`.
+///
+/// When the config lists external keyboards, exactly those count. Otherwise, it's up to the
+/// heuristic: a full keyboard on an external bus.
+fn counts_as_external_keyboard(
+    config: &niri_config::Keyboard,
+    name: &str,
+    heuristic: impl FnOnce() -> bool,
+) -> bool {
+    if config.external_keyboards.is_empty() {
+        heuristic()
+    } else {
+        config.external_keyboards.iter().any(|x| x == name)
+    }
+}
+
+fn has_external_keyboard(config: &niri_config::Keyboard, devices: &HashSet<input::Device>) -> bool {
+    devices
+        .iter()
+        .any(|device| is_external_keyboard(config, device))
+}
+
+/// Whether a built-in device with `disabled-on-external-keyboard` should be disabled.
+fn is_disabled_by_external_keyboard(
+    disabled_on_external_keyboard: bool,
+    is_built_in: bool,
+    has_external_keyboard: bool,
+) -> bool {
+    disabled_on_external_keyboard && is_built_in && has_external_keyboard
+}
+
+fn apply_accel_profile(
+    device: &mut input::Device,
+    profile: Option<niri_config::AccelProfile>,
+    custom_accel: Option<&niri_config::CustomAccel>,
+) {
+    let profile = match profile {
+        Some(niri_config::AccelProfile::Custom) => {
+            if let Some(custom_accel) = custom_accel {
+                if apply_custom_accel(device, custom_accel) {
+                    return;
+                }
+            } else {
+                warn!("accel-profile \"custom\" requires custom-accel to be set, ignoring");
+            }
+            None
+        }
+        profile => profile.and_then(niri_config::AccelProfile::to_libinput),
+    };
+
+    if let Some(profile) = profile.or_else(|| device.config_accel_default_profile()) {
+        let _ = device.config_accel_set_profile(profile);
+    }
+}
+
+/// Applies a custom acceleration curve to the device, returning whether it succeeded.
+fn apply_custom_accel(device: &mut input::Device, custom_accel: &niri_config::CustomAccel) -> bool {
+    // The input crate doesn't wrap the custom acceleration API, so call into libinput directly.
+    #[cfg(have_libinput_custom_accel)]
+    unsafe {
+        use std::ffi::{c_double, c_int, c_void};
+
+        use input::AsRaw as _;
+        use input::ffi::libinput_device;
+
+        unsafe extern "C" {
+            fn libinput_config_accel_create(profile: c_int) -> *mut c_void;
+            fn libinput_config_accel_destroy(accel_config: *mut c_void);
+            fn libinput_config_accel_set_points(
+                accel_config: *mut c_void,
+                accel_type: c_int,
+                step: c_double,
+                npoints: usize,
+                points: *const c_double,
+            ) -> c_int;
+            fn libinput_device_config_accel_apply(
+                device: *mut libinput_device,
+                accel_config: *mut c_void,
+            ) -> c_int;
+        }
+        const LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM: c_int = 1 << 2;
+        const LIBINPUT_ACCEL_TYPE_FALLBACK: c_int = 0;
+        const LIBINPUT_CONFIG_STATUS_SUCCESS: c_int = 0;
+
+        let accel_config = libinput_config_accel_create(LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM);
+        if accel_config.is_null() {
+            warn!("error creating custom pointer acceleration config");
+            return false;
+        }
+
+        let mut status = libinput_config_accel_set_points(
+            accel_config,
+            LIBINPUT_ACCEL_TYPE_FALLBACK,
+            custom_accel.step,
+            custom_accel.points.len(),
+            custom_accel.points.as_ptr(),
+        );
+        if status == LIBINPUT_CONFIG_STATUS_SUCCESS {
+            status = libinput_device_config_accel_apply(device.as_raw_mut(), accel_config);
+        }
+        libinput_config_accel_destroy(accel_config);
+
+        if status != LIBINPUT_CONFIG_STATUS_SUCCESS {
+            warn!("error applying custom pointer acceleration, status {status}");
+            return false;
+        }
+
+        true
+    }
+    #[cfg(not(have_libinput_custom_accel))]
+    {
+        let _ = (device, custom_accel);
+        warn!("custom pointer acceleration requires libinput 1.23 or newer, ignoring");
+        false
+    }
+}
+
+pub fn apply_libinput_settings(
+    config: &niri_config::Input,
+    device: &mut input::Device,
+    has_external_keyboard: bool,
+) {
+    if is_keyboard(device) {
+        let c = &config.keyboard;
+        let is_built_in = !is_external_keyboard(c, device);
+        let _ = device.config_send_events_set_mode(
+            if is_disabled_by_external_keyboard(
+                c.disabled_on_external_keyboard,
+                is_built_in,
+                has_external_keyboard,
+            ) {
+                input::SendEventsMode::DISABLED
+            } else {
+                input::SendEventsMode::ENABLED
+            },
+        );
+    }
+
     // According to Mutter code, this setting is specific to touchpads.
     let is_touchpad = device.config_tap_finger_count() > 0;
     if is_touchpad {
         let c = &config.touchpad;
         let _ = device.config_send_events_set_mode(if c.off {
             input::SendEventsMode::DISABLED
+        } else if is_disabled_by_external_keyboard(
+            c.disabled_on_external_keyboard,
+            !is_external(device),
+            has_external_keyboard,
+        ) {
+            input::SendEventsMode::DISABLED
         } else if c.disabled_on_external_mouse {
             input::SendEventsMode::DISABLED_ON_EXTERNAL_MOUSE
         } else {
@@ -4698,11 +5815,7 @@ pub fn apply_libinput_settings(config: &niri_config::Input, device: &mut input::
             let _ = device.config_tap_set_drag_enabled(default);
         }
 
-        if let Some(accel_profile) = c.accel_profile {
-            let _ = device.config_accel_set_profile(accel_profile.into());
-        } else if let Some(default) = device.config_accel_default_profile() {
-            let _ = device.config_accel_set_profile(default);
-        }
+        apply_accel_profile(device, c.accel_profile, c.custom_accel.as_ref());
 
         if let Some(method) = c.scroll_method {
             let _ = device.config_scroll_set_method(method.into());
@@ -4746,19 +5859,9 @@ pub fn apply_libinput_settings(config: &niri_config::Input, device: &mut input::
     }
 
     // This is how Mutter tells apart mice.
-    let mut is_trackball = false;
-    let mut is_trackpoint = false;
-    if let Some(udev_device) = unsafe { device.udev_device() } {
-        if udev_device.property_value("ID_INPUT_TRACKBALL").is_some() {
-            is_trackball = true;
-        }
-        if udev_device
-            .property_value("ID_INPUT_POINTINGSTICK")
-            .is_some()
-        {
-            is_trackpoint = true;
-        }
-    }
+    let is_trackball = unsafe { device.udev_device() }
+        .is_some_and(|udev_device| udev_device.property_value("ID_INPUT_TRACKBALL").is_some());
+    let is_trackpoint = is_trackpoint(device);
 
     let is_mouse = device.has_capability(input::DeviceCapability::Pointer)
         && !is_touchpad
@@ -4776,11 +5879,7 @@ pub fn apply_libinput_settings(config: &niri_config::Input, device: &mut input::
         let _ = device.config_left_handed_set(c.left_handed);
         let _ = device.config_middle_emulation_set_enabled(c.middle_emulation);
 
-        if let Some(accel_profile) = c.accel_profile {
-            let _ = device.config_accel_set_profile(accel_profile.into());
-        } else if let Some(default) = device.config_accel_default_profile() {
-            let _ = device.config_accel_set_profile(default);
-        }
+        apply_accel_profile(device, c.accel_profile, c.custom_accel.as_ref());
 
         if let Some(method) = c.scroll_method {
             let _ = device.config_scroll_set_method(method.into());
@@ -4823,12 +5922,17 @@ pub fn apply_libinput_settings(config: &niri_config::Input, device: &mut input::
         let _ = device.config_middle_emulation_set_enabled(c.middle_emulation);
         let _ = device.config_left_handed_set(c.left_handed);
 
-        if let Some(accel_profile) = c.accel_profile {
-            let _ = device.config_accel_set_profile(accel_profile.into());
-        } else if let Some(default) = device.config_accel_default_profile() {
-            let _ = device.config_accel_set_profile(default);
+        if device.config_rotation_is_available() {
+            let angle = c
+                .rotation
+                .map_or_else(|| device.config_rotation_default_angle(), u32::from);
+            let _ = device.config_rotation_set_angle(angle % 360);
+        } else if c.rotation.is_some() {
+            debug!("trackball doesn't support rotation, ignoring the rotation setting");
         }
 
+        apply_accel_profile(device, c.accel_profile, c.custom_accel.as_ref());
+
         if let Some(method) = c.scroll_method {
             let _ = device.config_scroll_set_method(method.into());
 
@@ -4870,11 +5974,7 @@ pub fn apply_libinput_settings(config: &niri_config::Input, device: &mut input::
         let _ = device.config_left_handed_set(c.left_handed);
         let _ = device.config_middle_emulation_set_enabled(c.middle_emulation);
 
-        if let Some(accel_profile) = c.accel_profile {
-            let _ = device.config_accel_set_profile(accel_profile.into());
-        } else if let Some(default) = device.config_accel_default_profile() {
-            let _ = device.config_accel_set_profile(default);
-        }
+        apply_accel_profile(device, c.accel_profile, c.custom_accel.as_ref());
 
         if let Some(method) = c.scroll_method {
             let _ = device.config_scroll_set_method(method.into());
@@ -5081,6 +6181,7 @@ mod tests {
             action: Action::CloseWindow,
             repeat: true,
             cooldown: None,
+            hold: None,
             allow_when_locked: false,
             allow_inhibiting: true,
             hotkey_overlay_title: None,
@@ -5267,6 +6368,7 @@ mod tests {
                 action: Action::CloseWindow,
                 repeat: true,
                 cooldown: None,
+                hold: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
                 hotkey_overlay_title: None,
@@ -5279,6 +6381,7 @@ mod tests {
                 action: Action::FocusColumnLeft,
                 repeat: true,
                 cooldown: None,
+                hold: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
                 hotkey_overlay_title: None,
@@ -5291,6 +6394,7 @@ mod tests {
                 action: Action::FocusWindowDown,
                 repeat: true,
                 cooldown: None,
+                hold: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
                 hotkey_overlay_title: None,
@@ -5303,6 +6407,7 @@ mod tests {
                 action: Action::FocusWindowUp,
                 repeat: true,
                 cooldown: None,
+                hold: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
                 hotkey_overlay_title: None,
@@ -5315,6 +6420,7 @@ mod tests {
                 action: Action::FocusColumnRight,
                 repeat: true,
                 cooldown: None,
+                hold: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
                 hotkey_overlay_title: None,
@@ -5440,4 +6546,71 @@ mod tests {
             None,
         );
     }
+
+    #[test]
+    fn window_scroll_factor_composes_with_device() {
+        assert_eq!(combine_scroll_factors(None, None), (1., 1.));
+        assert_eq!(combine_scroll_factors(Some((2., 0.5)), None), (2., 0.5));
+        assert_eq!(combine_scroll_factors(None, Some(0.75)), (0.75, 0.75));
+        assert_eq!(
+            combine_scroll_factors(Some((2., 0.5)), Some(0.5)),
+            (1., 0.25)
+        );
+    }
+
+    #[test]
+    fn external_keyboard_needs_alphanumeric_keys() {
+        // A full keyboard.
+        assert!(has_alphanumeric_keys(|_| true));
+
+        // A security key that types digits only.
+        assert!(!has_alphanumeric_keys(|key| (2..=11).contains(&key)));
+
+        // A mouse receiver that exposes a handful of media and letter keys.
+        assert!(!has_alphanumeric_keys(|key| key == 30 || key >= 113));
+    }
+
+    #[test]
+    fn external_keyboard_list_overrides_heuristic() {
+        let mut config = niri_config::Keyboard::default();
+        let full_usb_keyboard = || true;
+        let other = || false;
+
+        // With no list, the heuristic decides.
+        assert!(counts_as_external_keyboard(
+            &config,
+            "Keychron K2",
+            full_usb_keyboard
+        ));
+        assert!(!counts_as_external_keyboard(
+            &config,
+            "Yubico YubiKey",
+            other
+        ));
+
+        config.external_keyboards = vec![String::from("Keychron K2")];
+
+        // With a list, only listed names count, whatever the heuristic says.
+        assert!(counts_as_external_keyboard(&config, "Keychron K2", other));
+        assert!(!counts_as_external_keyboard(
+            &config,
+            "Yubico YubiKey",
+            full_usb_keyboard
+        ));
+        assert!(!counts_as_external_keyboard(
+            &config,
+            "USB Laptop Keyboard",
+            full_usb_keyboard
+        ));
+    }
+
+    #[test]
+    fn disabled_by_external_keyboard() {
+        // Only built-in devices with the setting get disabled, and only when an external
+        // keyboard is connected.
+        assert!(is_disabled_by_external_keyboard(true, true, true));
+        assert!(!is_disabled_by_external_keyboard(false, true, true));
+        assert!(!is_disabled_by_external_keyboard(true, false, true));
+        assert!(!is_disabled_by_external_keyboard(true, true, false));
+    }
 }
@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use niri_config::ModKey;
+use smithay::input::keyboard::Keysym;
+
+/// Detects the Mod key being tapped twice in a row.
+#[derive(Debug, Default)]
+pub struct ModDoubleTapTracker {
+    state: State,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+enum State {
+    #[default]
+    Idle,
+    /// The Mod key was pressed for the first time at this time.
+    FirstPress(Duration),
+    /// The Mod key was released after the first press started at this time.
+    FirstRelease(Duration),
+    /// The Mod key was pressed again after the first press started at this time.
+    SecondPress(Duration),
+}
+
+impl ModDoubleTapTracker {
+    /// Processes a key event and returns whether it completed a double tap.
+    ///
+    /// Pressing any other key in between resets the tracker, so that using Mod for regular
+    /// shortcuts doesn't count as a tap.
+    pub fn on_key(
+        &mut self,
+        is_mod: bool,
+        pressed: bool,
+        time: Duration,
+        timeout: Duration,
+    ) -> bool {
+        let within = |start: Duration| time.saturating_sub(start) <= timeout;
+
+        if !is_mod {
+            if pressed {
+                self.reset();
+            }
+            return false;
+        }
+
+        let (state, done) = match (self.state, pressed) {
+            (State::FirstRelease(start), true) if within(start) => {
+                (State::SecondPress(start), false)
+            }
+            (_, true) => (State::FirstPress(time), false),
+            (State::FirstPress(start), false) if within(start) => {
+                (State::FirstRelease(start), false)
+            }
+            (State::SecondPress(start), false) => (State::Idle, within(start)),
+            (_, false) => (State::Idle, false),
+        };
+
+        self.state = state;
+        done
+    }
+
+    pub const fn reset(&mut self) {
+        self.state = State::Idle;
+    }
+}
+
+/// Returns whether the keysym corresponds to the given Mod key.
+pub const fn is_mod_keysym(mod_key: ModKey, keysym: Keysym) -> bool {
+    matches!(
+        (mod_key, keysym),
+        (ModKey::Ctrl, Keysym::Control_L | Keysym::Control_R)
+            | (ModKey::Shift, Keysym::Shift_L | Keysym::Shift_R)
+            | (ModKey::Alt, Keysym::Alt_L | Keysym::Alt_R)
+            | (ModKey::Super, Keysym::Super_L | Keysym::Super_R)
+            | (ModKey::IsoLevel3Shift, Keysym::ISO_Level3_Shift)
+            | (ModKey::IsoLevel5Shift, Keysym::ISO_Level5_Shift)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_millis(300);
+
+    fn feed(tracker: &mut ModDoubleTapTracker, events: &[(bool, bool, u64)]) -> Vec<bool> {
+        events
+            .iter()
+            .map(|&(is_mod, pressed, ms)| {
+                tracker.on_key(is_mod, pressed, Duration::from_millis(ms), TIMEOUT)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn double_tap() {
+        let mut tracker = ModDoubleTapTracker::default();
+        let res = feed(
+            &mut tracker,
+            &[
+                (true, true, 0),
+                (true, false, 50),
+                (true, true, 100),
+                (true, false, 150),
+            ],
+        );
+        assert_eq!(res, [false, false, false, true]);
+    }
+
+    #[test]
+    fn too_slow() {
+        let mut tracker = ModDoubleTapTracker::default();
+        let res = feed(
+            &mut tracker,
+            &[
+                (true, true, 0),
+                (true, false, 50),
+                (true, true, 250),
+                (true, false, 350),
+            ],
+        );
+        assert_eq!(res, [false, false, false, false]);
+    }
+
+    #[test]
+    fn other_key_in_between() {
+        let mut tracker = ModDoubleTapTracker::default();
+        let res = feed(
+            &mut tracker,
+            &[
+                (true, true, 0),
+                (false, true, 20),
+                (false, false, 30),
+                (true, false, 50),
+                (true, true, 100),
+                (true, false, 150),
+            ],
+        );
+        assert_eq!(res, [false; 6]);
+    }
+}
@@ -91,7 +91,9 @@ impl MoveGrab {
                             .any(|w| w.window == self.window)
                             .then(|| (mon.map(|mon| mon.output().clone()), ws_idx))
                     });
-                    if let Some((Some(output), ws_idx)) = res {
+                    if let Some((Some(output), ws_idx)) = res
+                        && layout.is_overview_open_on(&output)
+                    {
                         layout.focus_output(&output);
                         layout.toggle_overview_to_workspace(ws_idx);
                     }
@@ -7,8 +7,8 @@ use anyhow::{Context, anyhow, bail};
 use niri_config::OutputName;
 use niri_ipc::socket::Socket;
 use niri_ipc::{
-    Action, Event, KeyboardLayouts, LogicalOutput, Mode, Output, OutputConfigChanged, Overview,
-    Request, Response, Transform, Window, WindowLayout,
+    Action, ClipboardHistoryEntry, ConfigValidation, Event, KeyboardLayouts, LogicalOutput, Mode,
+    Output, OutputConfigChanged, Overview, Request, Response, Transform, Window, WindowLayout,
 };
 use serde_json::json;
 
@@ -21,12 +21,19 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
         action:
             Action::Screenshot { path, .. }
             | Action::ScreenshotScreen { path, .. }
+            | Action::ScreenshotEachOutput {
+                path_template: path,
+                ..
+            }
             | Action::ScreenshotWindow { path, .. },
     } = &mut msg
         && let Some(path) = path
     {
         ensure_absolute_path(path).context("error making the path absolute")?;
     }
+    if let Msg::ValidateConfig { path: Some(path) } = &mut msg {
+        ensure_absolute_path(path).context("error making the path absolute")?;
+    }
 
     let request = match &msg {
         Msg::Version => Request::Version,
@@ -47,6 +54,10 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
         Msg::EventStream => Request::EventStream,
         Msg::RequestError => Request::ReturnError,
         Msg::OverviewState => Request::OverviewState,
+        Msg::ValidateConfig { path } => Request::ValidateConfig { path: path.clone() },
+        Msg::ClipboardHistory => Request::ClipboardHistory,
+        Msg::WindowInfo { id } => Request::WindowInfo { id: *id },
+        Msg::HotkeyOverlay => Request::HotkeyOverlay,
     };
 
     let mut socket = Socket::connect().context("error connecting to the niri socket")?;
@@ -463,6 +474,9 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                     } => {
                         println!("Window {id}: focus timestamp changed to {focus_timestamp:?}");
                     }
+                    Event::WindowUnderPointerChanged { id } => {
+                        println!("Window under pointer changed: {id:?}");
+                    }
                     Event::WindowUrgencyChanged { id, urgent } => {
                         println!("Window {id}: urgency changed to {urgent}");
                     }
@@ -517,6 +531,110 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                 println!("Overview is closed.");
             }
         }
+        Msg::ValidateConfig { .. } => {
+            let Response::ConfigValidated(response) = response else {
+                bail!("unexpected response: expected ConfigValidated, got {response:?}");
+            };
+
+            if json {
+                let response =
+                    serde_json::to_string(&response).context("error formatting response")?;
+                println!("{response}");
+                return Ok(());
+            }
+
+            match response {
+                ConfigValidation::Valid => println!("Config is valid."),
+                ConfigValidation::Invalid { error } => {
+                    eprintln!("{error}");
+                    bail!("config is invalid");
+                }
+            }
+        }
+        Msg::ClipboardHistory => {
+            let Response::ClipboardHistory(response) = response else {
+                bail!("unexpected response: expected ClipboardHistory, got {response:?}");
+            };
+
+            if json {
+                let response =
+                    serde_json::to_string(&response).context("error formatting response")?;
+                println!("{response}");
+                return Ok(());
+            }
+
+            if response.is_empty() {
+                println!("Clipboard history is empty.");
+            }
+
+            for (idx, entry) in response.into_iter().enumerate() {
+                let ClipboardHistoryEntry {
+                    mime_type,
+                    size,
+                    text,
+                } = entry;
+
+                match text {
+                    Some(text) => {
+                        // Show the first line, shortened to keep the list readable.
+                        let line = text.lines().next().unwrap_or_default();
+                        let mut preview: String = line.chars().take(60).collect();
+                        if preview.len() < text.len() {
+                            preview.push('…');
+                        }
+                        println!("{idx}: {preview:?}");
+                    }
+                    None => println!("{idx}: {mime_type}, {size} bytes"),
+                }
+            }
+        }
+        Msg::WindowInfo { .. } => {
+            let Response::WindowInfo(info) = response else {
+                bail!("unexpected response: expected WindowInfo, got {response:?}");
+            };
+
+            if json {
+                let info = serde_json::to_string(&info).context("error formatting response")?;
+                println!("{info}");
+                return Ok(());
+            }
+
+            let Some(info) = info else {
+                println!("No such window.");
+                return Ok(());
+            };
+
+            print_window(&info.window);
+            println!(
+                "  Is fullscreen: {}",
+                if info.is_fullscreen { "yes" } else { "no" }
+            );
+
+            if info.matched_rules.is_empty() {
+                println!("  Matched window rules: (none)");
+            } else {
+                let rules = info.matched_rules.iter().map(|idx| idx.to_string());
+                let rules = rules.collect::<Vec<_>>().join(", ");
+                println!("  Matched window rules: {rules}");
+            }
+        }
+        Msg::HotkeyOverlay => {
+            let Response::HotkeyOverlay(entries) = response else {
+                bail!("unexpected response: expected HotkeyOverlay, got {response:?}");
+            };
+
+            if json {
+                let entries =
+                    serde_json::to_string(&entries).context("error formatting response")?;
+                println!("{entries}");
+                return Ok(());
+            }
+
+            for entry in entries {
+                let key = entry.key.as_deref().unwrap_or("(not bound)");
+                println!("{key}: {}", entry.title);
+            }
+        }
     }
 
     Ok(())
@@ -5,7 +5,8 @@ use std::os::unix::net::{UnixListener, UnixStream};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
-use std::{env, io, process};
+use std::time::Duration;
+use std::{env, io, process, thread};
 
 use anyhow::Context;
 use async_channel::{Receiver, Sender, TrySendError};
@@ -14,19 +15,21 @@ use calloop::io::Async;
 use directories::BaseDirs;
 use futures_util::io::{AsyncReadExt, BufReader};
 use futures_util::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, FutureExt as _, select_biased};
-use niri_config::OutputName;
+use niri_config::{ConfigPath, OutputName};
 use niri_ipc::state::{EventStreamState, EventStreamStatePart as _};
 use niri_ipc::{
-    Action, Event, KeyboardLayouts, OutputConfigChanged, Overview, Reply, Request, Response,
-    Timestamp, WindowLayout, Workspace,
+    Action, ClipboardHistoryEntry, ConfigValidation, Event, KeyboardLayouts, OutputConfigChanged,
+    Overview, Reply, Request, Response, Timestamp, WindowInfo, WindowLayout, Workspace,
 };
-use smithay::desktop::layer_map_for_output;
+use smithay::desktop::{Window, layer_map_for_output};
 use smithay::reexports::calloop::generic::Generic;
-use smithay::reexports::calloop::{Interest, LoopHandle, Mode, PostAction};
+use smithay::reexports::calloop::timer::{TimeoutAction, Timer};
+use smithay::reexports::calloop::{Interest, LoopHandle, Mode, PostAction, RegistrationToken};
 use smithay::reexports::rustix::fs::unlink;
 use smithay::wayland::shell::wlr_layer::{KeyboardInteractivity, Layer};
 
 use crate::backend::IpcOutputMap;
+use crate::layout::LayoutElement as _;
 use crate::layout::workspace::WorkspaceId;
 use crate::niri::State;
 use crate::utils::{version, with_toplevel_role};
@@ -36,6 +39,10 @@ use crate::window::Mapped;
 // number in our buffer, we drop that event stream client.
 const EVENT_STREAM_BUFFER_SIZE: usize = 64;
 
+// Changes of the window under the pointer are only sent once the pointer stays over the new window
+// for this long, so that sweeping the pointer across the screen doesn't flood the event stream.
+const POINTER_WINDOW_DEBOUNCE: Duration = Duration::from_millis(100);
+
 pub struct IpcServer {
     /// Path to the IPC socket.
     ///
@@ -43,6 +50,14 @@ pub struct IpcServer {
     pub socket_path: Option<PathBuf>,
     event_streams: Rc<RefCell<Vec<EventStreamSender>>>,
     event_stream_state: Rc<RefCell<EventStreamState>>,
+    /// Window under the pointer at the last refresh.
+    ///
+    /// Used to skip looking up the window id when the pointer focus didn't change.
+    pointer_contents_window: Option<Window>,
+    /// Id of the window under the pointer last sent over the event stream.
+    pointer_window: Option<u64>,
+    /// Window under the pointer waiting for the debounce timer to fire.
+    pending_pointer_window: Option<(Option<u64>, RegistrationToken)>,
 }
 
 struct ClientCtx {
@@ -104,6 +119,9 @@ impl IpcServer {
             socket_path,
             event_streams: Rc::new(RefCell::new(Vec::new())),
             event_stream_state: Rc::new(RefCell::new(EventStreamState::default())),
+            pointer_contents_window: None,
+            pointer_window: None,
+            pending_pointer_window: None,
         })
     }
 
@@ -427,6 +445,108 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
             let is_open = state.overview.is_open;
             Response::OverviewState(Overview { is_open })
         }
+        Request::ValidateConfig { path } => {
+            let config_path = match path {
+                Some(path) => {
+                    // Relative paths would be resolved against the compositor's working directory.
+                    if !Path::new(&path).is_absolute() {
+                        return Err(format!("path must be absolute: {path}"));
+                    }
+                    ConfigPath::Explicit(PathBuf::from(path))
+                }
+                None => {
+                    let (tx, rx) = async_channel::bounded(1);
+                    ctx.event_loop.insert_idle(move |state| {
+                        let watcher = state.niri.config_file_watcher.as_ref();
+                        let _ = tx.send_blocking(watcher.map(|w| w.path().clone()));
+                    });
+                    let result = rx.recv().await;
+                    let path = result.map_err(|_| String::from("error getting config path"))?;
+                    path.ok_or_else(|| String::from("niri is not using a config file"))?
+                }
+            };
+
+            // Parsing the config can take a while, so don't block the event loop on it.
+            let (tx, rx) = async_channel::bounded(1);
+            thread::spawn(move || {
+                let _ = tx.send_blocking(config_path.validate());
+            });
+            let result = rx.recv().await;
+            let result = result.map_err(|_| String::from("error validating config"))?;
+            let validation = match result {
+                Ok(()) => ConfigValidation::Valid,
+                Err(error) => ConfigValidation::Invalid { error },
+            };
+            Response::ConfigValidated(validation)
+        }
+        Request::ClipboardHistory => {
+            let (tx, rx) = async_channel::bounded(1);
+            ctx.event_loop.insert_idle(move |state| {
+                let history_size = state.niri.config.borrow().clipboard.history_size;
+                let entries = (history_size > 0).then(|| {
+                    let entries = state.niri.clipboard_history.entries();
+                    entries
+                        .map(|entry| ClipboardHistoryEntry {
+                            mime_type: entry.mime_type.clone(),
+                            size: entry.data.len(),
+                            text: entry.text(),
+                        })
+                        .collect()
+                });
+                let _ = tx.send_blocking(entries);
+            });
+            let result = rx.recv().await;
+            let entries = result.map_err(|_| String::from("error getting clipboard history"))?;
+            let entries = entries.ok_or_else(|| String::from("clipboard history is disabled"))?;
+            Response::ClipboardHistory(entries)
+        }
+        Request::WindowInfo { id } => {
+            let window = {
+                let state = ctx.event_stream_state.borrow();
+                let windows = &state.windows.windows;
+                match id {
+                    Some(id) => windows.get(&id).cloned(),
+                    None => windows.values().find(|win| win.is_focused).cloned(),
+                }
+            };
+            let Some(window) = window else {
+                return Ok(Response::WindowInfo(None));
+            };
+
+            let (tx, rx) = async_channel::bounded(1);
+            let id = window.id;
+            ctx.event_loop.insert_idle(move |state| {
+                let mapped = state
+                    .niri
+                    .layout
+                    .windows()
+                    .find(|(_, m)| m.id().get() == id);
+                let info = mapped.map(|(_, mapped)| {
+                    let is_fullscreen = mapped.sizing_mode().is_fullscreen();
+                    let matched_rules = mapped.rules().matched_rules.clone();
+                    (is_fullscreen, matched_rules)
+                });
+                let _ = tx.send_blocking(info);
+            });
+            let result = rx.recv().await;
+            let info = result.map_err(|_| String::from("error getting window info"))?;
+            let info = info.map(|(is_fullscreen, matched_rules)| WindowInfo {
+                window,
+                is_fullscreen,
+                matched_rules,
+            });
+            Response::WindowInfo(info)
+        }
+        Request::HotkeyOverlay => {
+            let (tx, rx) = async_channel::bounded(1);
+            ctx.event_loop.insert_idle(move |state| {
+                let entries = state.niri.hotkey_overlay.ipc_entries();
+                let _ = tx.send_blocking(entries);
+            });
+            let result = rx.recv().await;
+            let entries = result.map_err(|_| String::from("error getting hotkey overlay"))?;
+            Response::HotkeyOverlay(entries)
+        }
     };
 
     Ok(response)
@@ -435,6 +555,10 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
 fn validate_action(action: &Action) -> Result<(), String> {
     if let Action::Screenshot { path, .. }
     | Action::ScreenshotScreen { path, .. }
+    | Action::ScreenshotEachOutput {
+        path_template: path,
+        ..
+    }
     | Action::ScreenshotWindow { path, .. } = action
         && let Some(path) = path
     {
@@ -547,6 +671,7 @@ impl State {
         self.ipc_refresh_workspaces();
         self.ipc_refresh_windows();
         self.ipc_refresh_overview();
+        self.ipc_refresh_pointer_window();
     }
 
     fn ipc_refresh_workspaces(&mut self) {
@@ -580,6 +705,7 @@ impl State {
             let output_name = mon.map(|mon| mon.output_name());
             if ipc_ws.idx != u8::try_from(ws_idx + 1).unwrap_or(u8::MAX)
                 || ipc_ws.name.as_ref() != ws.name()
+                || ipc_ws.uuid.as_ref() != ws.uuid()
                 || ipc_ws.output.as_ref() != output_name
             {
                 need_workspaces_changed = true;
@@ -630,6 +756,7 @@ impl State {
                         id,
                         idx: u8::try_from(ws_idx + 1).unwrap_or(u8::MAX),
                         name: ws.name().cloned(),
+                        uuid: ws.uuid().cloned(),
                         output: mon.map(|mon| mon.output_name().clone()),
                         is_urgent: ws.is_urgent(),
                         is_active: mon.is_some_and(|mon| mon.active_workspace_idx() == ws_idx),
@@ -769,6 +896,67 @@ impl State {
         server.send_event(event);
     }
 
+    fn ipc_refresh_pointer_window(&mut self) {
+        let Some(server) = &mut self.niri.ipc_server else {
+            return;
+        };
+
+        // This reveals what the user is hovering over, so it's opt-in.
+        let window = if self.niri.config.borrow().ipc.pointer_window_events {
+            self.niri
+                .pointer_contents
+                .window
+                .as_ref()
+                .map(|(window, _)| window.clone())
+        } else {
+            None
+        };
+
+        if server.pointer_contents_window == window {
+            return;
+        }
+
+        let id = window.as_ref().and_then(|window| {
+            self.niri
+                .layout
+                .windows()
+                .find(|(_, mapped)| mapped.window == *window)
+                .map(|(_, mapped)| mapped.id().get())
+        });
+        server.pointer_contents_window = window;
+
+        if let Some((pending, token)) = server.pending_pointer_window {
+            if pending == id {
+                return;
+            }
+
+            server.pending_pointer_window = None;
+            self.niri.event_loop.remove(token);
+        }
+
+        if server.pointer_window == id {
+            return;
+        }
+
+        let timer = Timer::from_duration(POINTER_WINDOW_DEBOUNCE);
+        let token = self
+            .niri
+            .event_loop
+            .insert_source(timer, move |_, _, state| {
+                if let Some(server) = &mut state.niri.ipc_server {
+                    server.pending_pointer_window = None;
+                    server.pointer_window = id;
+
+                    let event = Event::WindowUnderPointerChanged { id };
+                    server.event_stream_state.borrow_mut().apply(event.clone());
+                    server.send_event(event);
+                }
+                TimeoutAction::Drop
+            })
+            .unwrap();
+        server.pending_pointer_window = Some((id, token));
+    }
+
     pub fn ipc_config_loaded(&mut self, failed: bool) {
         let Some(server) = &self.niri.ipc_server else {
             return;
@@ -194,8 +194,18 @@ impl MappedLayer {
     }
 
     /// Recomputes the resolved layer rules and returns whether they changed.
-    pub fn recompute_layer_rules(&mut self, rules: &[LayerRule], is_at_startup: bool) -> bool {
-        let new_rules = ResolvedLayerRules::compute(rules, &self.surface, is_at_startup);
+    pub fn recompute_layer_rules(
+        &mut self,
+        rules: &[LayerRule],
+        is_at_startup: bool,
+        block_out_all_from_screencast: bool,
+    ) -> bool {
+        let new_rules = ResolvedLayerRules::compute(
+            rules,
+            &self.surface,
+            is_at_startup,
+            block_out_all_from_screencast,
+        );
         if new_rules == self.rules {
             return false;
         }
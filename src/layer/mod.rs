@@ -65,10 +65,16 @@ impl ResolvedLayerRules {
         }
     }
 
-    pub fn compute(rules: &[LayerRule], surface: &LayerSurface, is_at_startup: bool) -> Self {
+    pub fn compute(
+        rules: &[LayerRule],
+        surface: &LayerSurface,
+        is_at_startup: bool,
+        block_out_all_from_screencast: bool,
+    ) -> Self {
         let _span = tracy_client::span!("ResolvedLayerRules::compute");
 
         let mut resolved = Self::empty();
+        let mut allow_in_screencast = false;
 
         for rule in rules {
             let matches = |m: &Match| {
@@ -95,6 +101,9 @@ impl ResolvedLayerRules {
             if let Some(x) = rule.block_out_from {
                 resolved.block_out_from = Some(x);
             }
+            if let Some(x) = rule.allow_in_screencast {
+                allow_in_screencast = x;
+            }
             if let Some(x) = rule.geometry_corner_radius {
                 resolved.geometry_corner_radius = Some(x);
             }
@@ -109,6 +118,14 @@ impl ResolvedLayerRules {
             resolved.blur.merge_with(&rule.blur);
         }
 
+        // An explicit block-out-from takes precedence over the global default.
+        if block_out_all_from_screencast
+            && !allow_in_screencast
+            && resolved.block_out_from.is_none()
+        {
+            resolved.block_out_from = Some(BlockOutFrom::Screencast);
+        }
+
         resolved
     }
 }
@@ -26,7 +26,7 @@ use crate::utils::render::{PushRenderElement, Render};
 use crate::utils::transaction::TransactionBlocker;
 use crate::utils::{
     ResizeEdge, center_preferring_top_left_in_area, clamp_preferring_top_left_in_area,
-    ensure_min_max_size, ensure_min_max_size_maybe_zero,
+    ensure_min_max_size, ensure_min_max_size_maybe_zero, round_logical_in_physical,
 };
 use crate::window::ResolvedWindowRules;
 
@@ -243,7 +243,19 @@ impl<W: LayoutElement> FloatingSpace<W> {
         for (tile, data) in zip(&mut self.tiles, &mut self.data) {
             tile.update_config(view_size, scale, options.clone());
             data.update(tile);
+
+            let working_area_changed = data.working_area != working_area;
             data.update_config(working_area);
+
+            // Only clamp when the working area changes, so that windows intentionally moved
+            // partially off-screen stay there on unrelated config changes.
+            if working_area_changed && options.layout.keep_floating_on_screen {
+                let mut rect = Rectangle::new(data.logical_pos, data.size);
+                clamp_preferring_top_left_in_area(working_area, &mut rect);
+                if rect.loc != data.logical_pos {
+                    data.set_logical_pos(rect.loc);
+                }
+            }
         }
 
         self.view_size = view_size;
@@ -457,6 +469,38 @@ impl<W: LayoutElement> FloatingSpace<W> {
         true
     }
 
+    /// Focuses the next or previous window in stacking order, wrapping around.
+    ///
+    /// Rotates the stack so that the newly focused window ends up on top. This way, repeated
+    /// calls cycle through all windows.
+    pub fn focus_window_in_stack(&mut self, next: bool) -> bool {
+        if self.interactive_resize.is_some() || self.tiles.len() < 2 {
+            return false;
+        }
+
+        let active_idx = self
+            .active_window_id
+            .as_ref()
+            .and_then(|id| self.idx_of(id))
+            .unwrap_or(0);
+
+        // Index of the window to focus, which will become the top of the stack.
+        let len = self.tiles.len();
+        let new_idx = if next {
+            (active_idx + 1) % len
+        } else {
+            (active_idx + len - 1) % len
+        };
+
+        self.tiles.rotate_left(new_idx);
+        self.data.rotate_left(new_idx);
+
+        let id = self.tiles[0].focused_window().id().clone();
+        self.activate_window(&id);
+
+        true
+    }
+
     pub fn has_window(&self, id: &W::Id) -> bool {
         self.tiles.iter().any(|tile| tile.has_window(id))
     }
@@ -851,6 +895,11 @@ impl<W: LayoutElement> FloatingSpace<W> {
                 let tile_width = available_size * prop;
                 tile.window_width_for_tile_width(tile_width)
             }
+            SizeChange::SetOutputProportion(prop) => {
+                let prop = (prop / 100.).clamp(0., MAX_F);
+                let tile_width = round_logical_in_physical(self.scale, self.view_size.w * prop);
+                tile.window_width_for_tile_width(tile_width)
+            }
             SizeChange::AdjustFixed(delta) => f64::from(current_window.saturating_add(delta)),
             SizeChange::AdjustProportion(delta) => {
                 let current_prop = current_tile / available_size;
@@ -858,6 +907,8 @@ impl<W: LayoutElement> FloatingSpace<W> {
                 let tile_width = available_size * prop;
                 tile.window_width_for_tile_width(tile_width)
             }
+            // Floating windows have no neighbors, so the whole working area is left over.
+            SizeChange::SetRest => tile.window_width_for_tile_width(available_size),
         };
         let win_width = win_width.round().clamp(1., MAX_PX) as i32;
 
@@ -900,6 +951,11 @@ impl<W: LayoutElement> FloatingSpace<W> {
                 let tile_height = available_size * prop;
                 tile.window_height_for_tile_height(tile_height)
             }
+            SizeChange::SetOutputProportion(prop) => {
+                let prop = (prop / 100.).clamp(0., MAX_F);
+                let tile_height = round_logical_in_physical(self.scale, self.view_size.h * prop);
+                tile.window_height_for_tile_height(tile_height)
+            }
             SizeChange::AdjustFixed(delta) => f64::from(current_window.saturating_add(delta)),
             SizeChange::AdjustProportion(delta) => {
                 let current_prop = current_tile / available_size;
@@ -907,6 +963,8 @@ impl<W: LayoutElement> FloatingSpace<W> {
                 let tile_height = available_size * prop;
                 tile.window_height_for_tile_height(tile_height)
             }
+            // Floating windows have no neighbors, so the whole working area is left over.
+            SizeChange::SetRest => tile.window_height_for_tile_height(available_size),
         };
         let win_height = win_height.round().clamp(1., MAX_PX) as i32;
 
@@ -1099,6 +1157,18 @@ impl<W: LayoutElement> FloatingSpace<W> {
         self.move_to(idx, new_pos, true);
     }
 
+    pub fn center_window_vertically(&mut self, id: Option<&W::Id>) {
+        let Some(id) = id.or(self.active_window_id.as_ref()).cloned() else {
+            return;
+        };
+        let idx = self.idx_of(&id).unwrap();
+
+        let centered = center_preferring_top_left_in_area(self.working_area, self.data[idx].size);
+        let mut new_pos = self.data[idx].logical_pos;
+        new_pos.y = centered.y;
+        self.move_to(idx, new_pos, true);
+    }
+
     pub fn descendants_added(&mut self, id: &W::Id) -> bool {
         let Some(idx) = self.idx_of(id) else {
             return false;
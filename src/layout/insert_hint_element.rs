@@ -53,9 +53,10 @@ impl InsertHintElement {
         view_rect: Rectangle<f64, Logical>,
         radius: CornerRadius,
         scale: f64,
+        alpha: f32,
     ) {
         self.inner
-            .update_render_elements(size, true, false, false, view_rect, radius, scale, 1.);
+            .update_render_elements(size, true, false, false, view_rect, radius, scale, alpha);
     }
 }
 
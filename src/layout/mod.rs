@@ -50,11 +50,12 @@ use std::time::Duration;
 
 use monitor::{InsertHint, InsertPosition, InsertWorkspace, MonitorAddWindowTarget};
 use niri_config::utils::MergeWith as _;
+use niri_config::workspace::WorkspaceName;
 use niri_config::{
-    Config, CornerRadius, LayoutPart, PresetSize, WindowMoveDirection,
+    Config, CornerRadius, LayoutPart, PresetSize, Struts, WindowMoveDirection,
     Workspace as WorkspaceConfig, WorkspaceReference,
 };
-use niri_ipc::{PositionChange, SizeChange, WindowLayout};
+use niri_ipc::{Direction, GroupDisplayMode, PositionChange, SizeChange, WindowLayout};
 use scrolling::{Column, ColumnWidth};
 use smithay::backend::renderer::element::surface::WaylandSurfaceRenderElement;
 use smithay::backend::renderer::element::utils::RescaleRenderElement;
@@ -85,7 +86,7 @@ use crate::utils::region::Region;
 use crate::utils::render::{PushRenderElement, Render};
 use crate::utils::transaction::{Transaction, TransactionBlocker};
 use crate::utils::{
-    ResizeEdge, ensure_min_max_size_maybe_zero, output_matches_name, output_size,
+    ResizeEdge, client_scale, ensure_min_max_size_maybe_zero, output_matches_name, output_size,
     round_logical_in_physical_max1,
 };
 use crate::window::ResolvedWindowRules;
@@ -211,8 +212,18 @@ pub trait LayoutElement {
     fn set_bounds(&self, bounds: Size<i32, Logical>);
     fn is_ignoring_opacity_window_rule(&self) -> bool;
 
+    /// Opacity set at runtime, which takes precedence over the opacity window rule.
+    fn opacity_override(&self) -> Option<f32> {
+        None
+    }
+
     fn is_urgent(&self) -> bool;
 
+    /// When the element was last focused, used to find the most recently used window.
+    fn focus_timestamp(&self) -> Option<Duration> {
+        None
+    }
+
     fn configure_intent(&self) -> ConfigureIntent;
     fn send_pending_configure(&mut self);
 
@@ -345,6 +356,8 @@ pub struct Layout<W: LayoutElement> {
     overview_open: bool,
     /// The overview zoom progress.
     overview_progress: Option<OverviewProgress>,
+    /// Output that the overview is open on, or `None` if it's open on all outputs.
+    overview_output: Option<Output>,
     /// Configurable properties of the layout.
     options: Rc<Options>,
 }
@@ -373,6 +386,8 @@ pub struct Options {
     pub animations: niri_config::Animations,
     pub gestures: niri_config::Gestures,
     pub overview: niri_config::Overview,
+    /// Whether gaps and struts are temporarily removed, overriding the config.
+    pub zero_gaps: bool,
     // Debug flags.
     pub disable_resize_throttling: bool,
     pub disable_transactions: bool,
@@ -635,8 +650,9 @@ impl Options {
         Self {
             layout: config.layout.clone(),
             animations: config.animations.clone(),
-            gestures: config.gestures,
+            gestures: config.gestures.clone(),
             overview: config.overview,
+            zero_gaps: false,
             disable_resize_throttling: config.debug.disable_resize_throttling,
             disable_transactions: config.debug.disable_transactions,
             deactivate_unfocused_windows: config.debug.deactivate_unfocused_windows,
@@ -651,6 +667,13 @@ impl Options {
     }
 
     fn adjusted_for_scale(mut self, scale: f64) -> Self {
+        // This is the last step after merging all layout configs, so the override applies on top
+        // of per-output and per-workspace gaps and struts.
+        if self.zero_gaps {
+            self.layout.gaps = 0.;
+            self.layout.struts = Struts::default();
+        }
+
         self.layout.gaps = round_logical_in_physical_max1(scale, self.layout.gaps);
         self
     }
@@ -686,6 +709,7 @@ impl<W: LayoutElement> Layout<W> {
             update_render_elements_time: Duration::ZERO,
             overview_open: false,
             overview_progress: None,
+            overview_output: None,
             options: Rc::new(options),
         }
     }
@@ -711,6 +735,7 @@ impl<W: LayoutElement> Layout<W> {
             update_render_elements_time: Duration::ZERO,
             overview_open: false,
             overview_progress: None,
+            overview_output: None,
             options: opts,
         }
     }
@@ -790,8 +815,11 @@ impl<W: LayoutElement> Layout<W> {
                     self.options.clone(),
                     layout_config,
                 );
-                monitor.overview_open = self.overview_open;
-                monitor.set_overview_progress(self.overview_progress.as_ref());
+                monitor.set_overview_state(
+                    self.overview_open,
+                    self.overview_progress.as_ref(),
+                    self.overview_output.as_ref(),
+                );
                 monitors.push(monitor);
 
                 MonitorSet::Normal {
@@ -811,8 +839,11 @@ impl<W: LayoutElement> Layout<W> {
                     self.options.clone(),
                     layout_config,
                 );
-                monitor.overview_open = self.overview_open;
-                monitor.set_overview_progress(self.overview_progress.as_ref());
+                monitor.set_overview_state(
+                    self.overview_open,
+                    self.overview_progress.as_ref(),
+                    self.overview_output.as_ref(),
+                );
 
                 MonitorSet::Normal {
                     monitors: vec![monitor],
@@ -897,7 +928,7 @@ impl<W: LayoutElement> Layout<W> {
             panic!()
         };
 
-        monitors[monitor_idx].add_column(workspace_idx, column, activate);
+        monitors[monitor_idx].add_column(workspace_idx, None, column, activate);
 
         if activate {
             *active_monitor_idx = monitor_idx;
@@ -1160,6 +1191,8 @@ impl<W: LayoutElement> Layout<W> {
                                 if idx < mon.active_workspace_idx {
                                     mon.active_workspace_idx -= 1;
                                 }
+                            } else if !remove_tile {
+                                mon.apply_on_empty_workspace(idx);
                             }
 
                             // Special case handling when empty_workspace_above_first is set and all
@@ -1289,6 +1322,12 @@ impl<W: LayoutElement> Layout<W> {
         None
     }
 
+    pub fn find_workspace_by_uuid(&self, uuid: &str) -> Option<(usize, &Workspace<W>)> {
+        self.workspaces()
+            .find(|(_, _, ws)| ws.uuid().is_some_and(|u| u.eq_ignore_ascii_case(uuid)))
+            .map(|(_, idx, ws)| (idx, ws))
+    }
+
     pub fn find_workspace_by_name(&self, workspace_name: &str) -> Option<(usize, &Workspace<W>)> {
         match &self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
@@ -1334,6 +1373,9 @@ impl<W: LayoutElement> Layout<W> {
                     .as_ref()
                     .is_some_and(|name| name.eq_ignore_ascii_case(ref_name)),
                 WorkspaceReference::Id(id) => ws.id().get() == *id,
+                WorkspaceReference::Uuid(ref_uuid) => ws
+                    .uuid()
+                    .is_some_and(|uuid| uuid.eq_ignore_ascii_case(ref_uuid)),
                 WorkspaceReference::Index(_) => unreachable!(),
             })
         }
@@ -1694,12 +1736,9 @@ impl<W: LayoutElement> Layout<W> {
         if let Some(InteractiveMoveState::Moving(move_)) = &self.interactive_move {
             // We don't fill any positions for interactively moved windows.
             let layout = move_.tile.ipc_layout_template();
-            f(
-                move_.tile.focused_window(),
-                Some(&move_.output),
-                None,
-                layout,
-            );
+            for (win, layout) in move_.tile.windows_with_ipc_layouts(layout) {
+                f(win, Some(&move_.output), None, layout);
+            }
         }
 
         match &self.monitor_set {
@@ -1707,12 +1746,9 @@ impl<W: LayoutElement> Layout<W> {
                 for mon in monitors {
                     for ws in &mon.workspaces {
                         for (tile, layout) in ws.tiles_with_ipc_layouts() {
-                            f(
-                                tile.focused_window(),
-                                Some(&mon.output),
-                                Some(ws.id()),
-                                layout,
-                            );
+                            for (win, layout) in tile.windows_with_ipc_layouts(layout) {
+                                f(win, Some(&mon.output), Some(ws.id()), layout);
+                            }
                         }
                     }
                 }
@@ -1720,7 +1756,9 @@ impl<W: LayoutElement> Layout<W> {
             MonitorSet::NoOutputs { workspaces } => {
                 for ws in workspaces {
                     for (tile, layout) in ws.tiles_with_ipc_layouts() {
-                        f(tile.focused_window(), None, Some(ws.id()), layout);
+                        for (win, layout) in tile.windows_with_ipc_layouts(layout) {
+                            f(win, None, Some(ws.id()), layout);
+                        }
                     }
                 }
             }
@@ -1877,6 +1915,13 @@ impl<W: LayoutElement> Layout<W> {
         workspace.move_column_to_index(index);
     }
 
+    pub fn move_column_by_offset(&mut self, offset: i32) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.move_column_by_offset(offset);
+    }
+
     pub fn move_down(&mut self) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -2131,6 +2176,13 @@ impl<W: LayoutElement> Layout<W> {
         workspace.focus_up();
     }
 
+    pub fn focus_spatial(&mut self, direction: Direction) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.focus_spatial(direction);
+    }
+
     pub fn focus_down_or_left(&mut self) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -2302,6 +2354,20 @@ impl<W: LayoutElement> Layout<W> {
         monitor.switch_workspace_previous();
     }
 
+    pub fn move_to_workspace_previous(&mut self, focus: bool) {
+        let Some(monitor) = self.active_monitor() else {
+            return;
+        };
+        monitor.move_to_workspace_previous(focus);
+    }
+
+    pub fn move_column_to_workspace_previous(&mut self, activate: bool) {
+        let Some(monitor) = self.active_monitor() else {
+            return;
+        };
+        monitor.move_column_to_workspace_previous(activate);
+    }
+
     pub fn consume_into_column(&mut self) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -2309,6 +2375,125 @@ impl<W: LayoutElement> Layout<W> {
         workspace.consume_into_column();
     }
 
+    /// Consumes the active window of `output` into the active column of the active output.
+    ///
+    /// Does nothing if `output` is the active output, if it has no active window, or if there is
+    /// no active column to consume into.
+    pub fn consume_window_from_output(&mut self, output: &Output) {
+        if let Some(InteractiveMoveState::Moving(_)) = &self.interactive_move {
+            return;
+        }
+
+        let MonitorSet::Normal {
+            monitors,
+            active_monitor_idx,
+            ..
+        } = &mut self.monitor_set
+        else {
+            return;
+        };
+
+        let Some(source_idx) = monitors.iter().position(|mon| &mon.output == output) else {
+            return;
+        };
+        if source_idx == *active_monitor_idx {
+            return;
+        }
+
+        let target = &monitors[*active_monitor_idx];
+        let target_ws_idx = target.active_workspace_idx;
+        let target_ws = &target.workspaces[target_ws_idx];
+        if target_ws.floating_is_active() || target_ws.scrolling().is_empty() {
+            return;
+        }
+        let column_idx = target_ws.scrolling().active_column_idx();
+
+        let source = &mut monitors[source_idx];
+        let source_ws_idx = source.active_workspace_idx;
+        let Some(mut removed) =
+            source.workspaces[source_ws_idx].remove_active_tile(Transaction::new())
+        else {
+            return;
+        };
+        removed.tile.stop_move_animations();
+
+        if source.workspace_switch.is_none() {
+            source.clean_up_workspaces();
+        }
+
+        // The tile is refitted to the target output's size and scale upon insertion.
+        monitors[*active_monitor_idx].add_tile_to_column(
+            target_ws_idx,
+            column_idx,
+            None,
+            removed.tile,
+            true,
+            false,
+        );
+    }
+
+    /// Exchanges the focused column with the focused column on the active workspace of `output`.
+    ///
+    /// If `output` has no focused column, the focused column just moves there. Focus follows the
+    /// moved column.
+    ///
+    /// Returns `true` if the column moved.
+    pub fn swap_column_with_output(&mut self, output: &Output) -> bool {
+        if let Some(InteractiveMoveState::Moving(_)) = &self.interactive_move {
+            return false;
+        }
+
+        let MonitorSet::Normal {
+            monitors,
+            active_monitor_idx,
+            ..
+        } = &mut self.monitor_set
+        else {
+            return false;
+        };
+
+        let Some(target_idx) = monitors.iter().position(|mon| &mon.output == output) else {
+            return false;
+        };
+        let source_idx = *active_monitor_idx;
+        if target_idx == source_idx {
+            return false;
+        }
+
+        let source = &mut monitors[source_idx];
+        let source_ws_idx = source.active_workspace_idx;
+        let source_ws = &mut source.workspaces[source_ws_idx];
+        let source_col_idx = source_ws.scrolling().active_column_idx();
+        let Some(column) = source_ws.remove_active_column() else {
+            return false;
+        };
+
+        let target = &mut monitors[target_idx];
+        let target_ws_idx = target.active_workspace_idx;
+        let target_ws = &mut target.workspaces[target_ws_idx];
+        let target_col_idx = target_ws.scrolling().active_column_idx();
+        let other = if target_ws.floating_is_active() {
+            None
+        } else {
+            target_ws.remove_active_column()
+        };
+
+        // Put the columns in place of each other. They're refitted to the new output's size and
+        // scale upon insertion.
+        let target_col_idx = other.is_some().then_some(target_col_idx);
+        target.add_column(target_ws_idx, target_col_idx, column, true);
+
+        let source = &mut monitors[source_idx];
+        if let Some(other) = other {
+            source.add_column(source_ws_idx, Some(source_col_idx), other, true);
+        } else if source.workspace_switch.is_none() {
+            source.clean_up_workspaces();
+        }
+
+        *active_monitor_idx = target_idx;
+        true
+    }
+
     pub fn expel_from_column(&mut self) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -2316,6 +2501,13 @@ impl<W: LayoutElement> Layout<W> {
         workspace.expel_from_column();
     }
 
+    pub fn expel_window_to_column(&mut self, right: bool) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.expel_window_to_column(right);
+    }
+
     pub fn swap_window_in_direction(&mut self, direction: ScrollDirection) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -2349,6 +2541,25 @@ impl<W: LayoutElement> Layout<W> {
         workspace.center_window(id);
     }
 
+    pub fn center_window_vertically(&mut self, id: Option<&W::Id>) {
+        if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move
+            && id.is_none_or(|id| id == move_.tile.focused_window().id())
+        {
+            return;
+        }
+
+        let workspace = if let Some(id) = id {
+            Some(self.workspaces_mut().find(|ws| ws.has_window(id)).unwrap())
+        } else {
+            self.active_workspace_mut()
+        };
+
+        let Some(workspace) = workspace else {
+            return;
+        };
+        workspace.center_window_vertically(id);
+    }
+
     pub fn center_visible_columns(&mut self) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -2356,6 +2567,13 @@ impl<W: LayoutElement> Layout<W> {
         workspace.center_visible_columns();
     }
 
+    pub fn distribute_columns_evenly(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.distribute_columns_evenly();
+    }
+
     pub fn focus(&self) -> Option<&W> {
         self.focus_with_output().map(|(win, _out)| win)
     }
@@ -2403,8 +2621,9 @@ impl<W: LayoutElement> Layout<W> {
     ) -> Option<(&W, HitType)> {
         if let Some(InteractiveMoveState::Moving(move_)) = &self.interactive_move {
             if move_.output == *output {
-                if self.overview_progress.is_some() {
-                    let zoom = self.overview_zoom();
+                let mon = self.monitor_for_output(output)?;
+                if mon.overview_progress_value().is_some() {
+                    let zoom = mon.overview_zoom();
                     let tile_pos = move_.tile_render_location(zoom);
                     let pos_within_tile = (pos_within_output - tile_pos).downscale(zoom);
                     // During the overview animation, we cannot do input hits because we cannot
@@ -2434,6 +2653,16 @@ impl<W: LayoutElement> Layout<W> {
         mon.window_under(pos_within_output)
     }
 
+    /// Returns the workspace and the index of the column under the position.
+    pub fn column_under(
+        &self,
+        output: &Output,
+        pos_within_output: Point<f64, Logical>,
+    ) -> Option<(&Workspace<W>, usize)> {
+        let mon = self.monitor_for_output(output)?;
+        mon.column_under(pos_within_output)
+    }
+
     pub fn resize_edges_under(
         &self,
         output: &Output,
@@ -2464,18 +2693,13 @@ impl<W: LayoutElement> Layout<W> {
         }
     }
 
-    pub fn overview_zoom(&self) -> f64 {
-        let progress = self.overview_progress.as_ref().map(|p| p.value());
-        compute_overview_zoom(&self.options, progress)
-    }
-
     #[cfg(test)]
     fn verify_invariants(&self) {
         use std::collections::HashSet;
 
         use approx::assert_abs_diff_eq;
 
-        let zoom = self.overview_zoom();
+        let zoom = self.interactive_move_zoom();
 
         let mut move_win_id = None;
         if let Some(state) = &self.interactive_move {
@@ -2609,9 +2833,19 @@ impl<W: LayoutElement> Layout<W> {
                 "monitor base options must be synchronized with layout"
             );
 
-            assert_eq!(self.overview_open, monitor.overview_open);
+            let overview_applies = self
+                .overview_output
+                .as_ref()
+                .is_none_or(|output| *output == monitor.output);
+            assert_eq!(
+                self.overview_open && overview_applies,
+                monitor.overview_open
+            );
             assert_eq!(
-                self.overview_progress.as_ref().map(|p| p.value()),
+                self.overview_progress
+                    .as_ref()
+                    .filter(|_| overview_applies)
+                    .map(|p| p.value()),
                 monitor.overview_progress_value()
             );
 
@@ -2710,7 +2944,9 @@ impl<W: LayoutElement> Layout<W> {
             }
         }
 
-        let is_overview_open = self.overview_open;
+        let is_overview_open = dnd_scroll
+            .as_ref()
+            .is_some_and(|(output, _, _)| self.is_overview_open_on(output));
 
         // Scroll the view if needed.
         if let Some((output, pos_within_output, is_scrolling)) = dnd_scroll
@@ -2811,13 +3047,18 @@ impl<W: LayoutElement> Layout<W> {
                 self.overview_progress = Some(OverviewProgress::Open);
             } else {
                 self.overview_progress = None;
+                self.overview_output = None;
             }
         }
 
         match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
-                    mon.set_overview_progress(self.overview_progress.as_ref());
+                    mon.set_overview_state(
+                        self.overview_open,
+                        self.overview_progress.as_ref(),
+                        self.overview_output.as_ref(),
+                    );
                     mon.advance_animations();
                 }
             }
@@ -2876,7 +3117,7 @@ impl<W: LayoutElement> Layout<W> {
 
         self.update_render_elements_time = self.clock.now();
 
-        let zoom = self.overview_zoom();
+        let zoom = self.interactive_move_zoom();
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move
             && output.is_none_or(|output| move_.output == *output)
         {
@@ -2903,7 +3144,11 @@ impl<W: LayoutElement> Layout<W> {
                 let is_active = self.is_active
                     && idx == *active_monitor_idx
                     && !matches!(self.interactive_move, Some(InteractiveMoveState::Moving(_)));
-                mon.set_overview_progress(self.overview_progress.as_ref());
+                mon.set_overview_state(
+                    self.overview_open,
+                    self.overview_progress.as_ref(),
+                    self.overview_output.as_ref(),
+                );
                 mon.update_render_elements(is_active);
             }
         }
@@ -2949,6 +3194,7 @@ impl<W: LayoutElement> Layout<W> {
         let _span = tracy_client::span!("Layout::update_insert_hint::update");
 
         if let Some(mon) = self.monitor_for_output_mut(&move_.output) {
+            let rules = move_.tile.focused_window().rules();
             let zoom = mon.overview_zoom();
             let (insert_ws, geo) = mon.insert_position(move_.pointer_pos_within_output);
             match insert_ws {
@@ -2966,7 +3212,6 @@ impl<W: LayoutElement> Layout<W> {
                         ws.scrolling_insert_position(pos_within_workspace)
                     };
 
-                    let rules = move_.tile.focused_window().rules();
                     let border_width = move_.tile.effective_border_width().unwrap_or(0.);
                     let corner_radius = rules
                         .geometry_corner_radius
@@ -2977,6 +3222,7 @@ impl<W: LayoutElement> Layout<W> {
                         workspace: insert_ws,
                         position,
                         corner_radius,
+                        rule: rules.insert_hint,
                     });
                 }
                 InsertWorkspace::NewAt(_) => {
@@ -2989,6 +3235,7 @@ impl<W: LayoutElement> Layout<W> {
                         workspace: insert_ws,
                         position,
                         corner_radius: CornerRadius::default(),
+                        rule: rules.insert_hint,
                     });
                 }
             }
@@ -3039,6 +3286,71 @@ impl<W: LayoutElement> Layout<W> {
         }
     }
 
+    /// Activates the named workspace on the output.
+    ///
+    /// If the workspace is on a different output, it is moved to this output. If it doesn't
+    /// exist, it is created.
+    pub fn activate_named_workspace_on_output(&mut self, output: &Output, name: &str) {
+        self.put_named_workspace_on_output(output, name, true);
+    }
+
+    /// Puts the named workspace on the output and returns its index there.
+    ///
+    /// If the workspace is on a different output, it is moved to this output. If it doesn't
+    /// exist, it is created.
+    pub fn put_named_workspace_on_output(
+        &mut self,
+        output: &Output,
+        name: &str,
+        activate: bool,
+    ) -> Option<usize> {
+        let clock = self.clock.clone();
+        let options = self.options.clone();
+
+        let MonitorSet::Normal { monitors, .. } = &mut self.monitor_set else {
+            return None;
+        };
+
+        let target_idx = monitors.iter().position(|mon| mon.output == *output)?;
+
+        let has_name = |ws: &Workspace<W>| {
+            ws.name
+                .as_ref()
+                .is_some_and(|ws_name| ws_name.eq_ignore_ascii_case(name))
+        };
+
+        let found = monitors.iter().enumerate().find_map(|(mon_idx, mon)| {
+            let ws_idx = mon.workspaces.iter().position(has_name)?;
+            Some((mon_idx, ws_idx))
+        });
+
+        let ws = match found {
+            Some((mon_idx, ws_idx)) if mon_idx == target_idx => {
+                if activate {
+                    monitors[target_idx].activate_workspace(ws_idx);
+                }
+                return Some(ws_idx);
+            }
+            Some((mon_idx, ws_idx)) => {
+                let mut ws = monitors[mon_idx].remove_workspace_by_idx(ws_idx);
+                ws.original_output = OutputId::new(output);
+                ws
+            }
+            None => {
+                let ws_config = WorkspaceConfig {
+                    name: WorkspaceName(name.to_owned()),
+                    open_on_output: None,
+                    layout: None,
+                };
+                Workspace::new_with_config(output.clone(), Some(ws_config), clock, options)
+            }
+        };
+
+        let target = &mut monitors[target_idx];
+        target.insert_workspace(ws, target.active_workspace_idx, activate);
+        target.workspaces.iter().position(has_name)
+    }
+
     pub fn update_config(&mut self, config: &Config) {
         // Update workspace-specific config for all named workspaces.
         for ws in self.workspaces_mut() {
@@ -3080,6 +3392,13 @@ impl<W: LayoutElement> Layout<W> {
         self.options = options;
     }
 
+    /// Toggles removing all gaps and struts until the next config reload.
+    pub fn toggle_zero_gaps(&mut self) {
+        let mut options = Options::clone(&self.options);
+        options.zero_gaps = !options.zero_gaps;
+        self.update_options(options);
+    }
+
     pub fn toggle_width(&mut self, forwards: bool) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -3216,6 +3535,29 @@ impl<W: LayoutElement> Layout<W> {
         workspace.reset_window_height(window);
     }
 
+    pub fn fill_window_height_in_column(&mut self, window: Option<&W::Id>) {
+        if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move
+            && window.is_none_or(|window| window == move_.tile.focused_window().id())
+        {
+            return;
+        }
+
+        let workspace = if let Some(window) = window {
+            Some(
+                self.workspaces_mut()
+                    .find(|ws| ws.has_window(window))
+                    .unwrap(),
+            )
+        } else {
+            self.active_workspace_mut()
+        };
+
+        let Some(workspace) = workspace else {
+            return;
+        };
+        workspace.fill_window_height_in_column(window);
+    }
+
     pub fn expand_column_to_available_width(&mut self) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -3223,10 +3565,50 @@ impl<W: LayoutElement> Layout<W> {
         workspace.expand_column_to_available_width();
     }
 
+    pub fn toggle_column_expanded_width(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.toggle_column_expanded_width();
+    }
+
+    pub fn toggle_maximize_column_with_group(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.toggle_maximize_column_with_group();
+    }
+
+    pub fn set_group_display_mode(&mut self, mode: GroupDisplayMode) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.set_group_display_mode(mode);
+    }
+
+    pub fn toggle_group_display_mode(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.toggle_group_display_mode();
+    }
+
+    pub fn align_column_width_to_neighbor(&mut self, right: bool) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.align_column_width_to_neighbor(right);
+    }
+
     pub fn toggle_window_floating(&mut self, window: Option<&W::Id>) {
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move
             && window.is_none_or(|window| window == move_.tile.focused_window().id())
         {
+            // Window rules may pin the window to its current layer.
+            if move_.tile.focused_window().rules().force_floating == Some(move_.is_floating) {
+                return;
+            }
+
             move_.is_floating = !move_.is_floating;
 
             // When going to floating, restore the floating window size.
@@ -3319,6 +3701,13 @@ impl<W: LayoutElement> Layout<W> {
         workspace.focus_floating();
     }
 
+    pub fn focus_floating_window_in_stack(&mut self, next: bool) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.focus_floating_window_in_stack(next);
+    }
+
     pub fn focus_tiling(&mut self) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -3685,6 +4074,21 @@ impl<W: LayoutElement> Layout<W> {
         }
     }
 
+    pub fn toggle_maximized_with_restore(&mut self, id: &W::Id) {
+        if let Some(InteractiveMoveState::Moving(move_)) = &self.interactive_move
+            && move_.tile.focused_window().id() == id
+        {
+            return;
+        }
+
+        for ws in self.workspaces_mut() {
+            if ws.has_window(id) {
+                ws.toggle_maximized_with_restore(id);
+                return;
+            }
+        }
+    }
+
     pub fn workspace_switch_gesture_begin(&mut self, output: &Output, is_touchpad: bool) {
         let monitors = match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => monitors,
@@ -3775,15 +4179,13 @@ impl<W: LayoutElement> Layout<W> {
         timestamp: Duration,
         is_touchpad: bool,
     ) -> Option<Option<Output>> {
-        let zoom = self.overview_zoom();
-        let delta_x = delta_x / zoom;
-
         let monitors = match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => monitors,
             MonitorSet::NoOutputs { .. } => return None,
         };
 
         for monitor in monitors {
+            let delta_x = delta_x / monitor.overview_zoom();
             for ws in &mut monitor.workspaces {
                 if let Some(refresh) =
                     ws.view_offset_gesture_update(delta_x, timestamp, is_touchpad)
@@ -3818,6 +4220,10 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn overview_gesture_begin(&mut self) {
+        // The gesture continues a single-output overview, but otherwise opens it everywhere.
+        if !self.overview_open {
+            self.overview_output = None;
+        }
         self.overview_open = true;
 
         let value = self.overview_progress.take().map_or(0., |p| p.value());
@@ -3984,7 +4390,7 @@ impl<W: LayoutElement> Layout<W> {
                     return false;
                 }
 
-                let zoom = self.overview_zoom();
+                let zoom = self.overview_zoom_on(&output);
                 let delta = delta.downscale(zoom);
 
                 pointer_delta += delta;
@@ -4077,7 +4483,7 @@ impl<W: LayoutElement> Layout<W> {
                 tile.interactive_move_offset = Point::from((0., 0.));
                 tile.focused_window().output_enter(&output);
                 tile.focused_window().set_preferred_scale_transform(
-                    output.current_scale(),
+                    client_scale(&output),
                     output.current_transform(),
                 );
 
@@ -4152,7 +4558,7 @@ impl<W: LayoutElement> Layout<W> {
                     move_.tile.focused_window().output_leave(&move_.output);
                     move_.tile.focused_window().output_enter(&output);
                     move_.tile.focused_window().set_preferred_scale_transform(
-                        output.current_scale(),
+                        client_scale(&output),
                         output.current_transform(),
                     );
                     move_.output = output.clone();
@@ -4259,7 +4665,7 @@ impl<W: LayoutElement> Layout<W> {
         }
 
         // Dragging in the overview shouldn't switch the workspace and so on.
-        let allow_to_activate_workspace = !self.overview_open;
+        let allow_to_activate_workspace = !self.is_overview_open_on(&move_.output);
 
         match &mut self.monitor_set {
             MonitorSet::Normal {
@@ -4602,6 +5008,7 @@ impl<W: LayoutElement> Layout<W> {
         &mut self,
         reference: Option<(Option<Output>, usize)>,
         new_idx: usize,
+        compact: bool,
     ) {
         let (monitor, old_idx) = if let Some((output, old_idx)) = reference {
             let monitor = if let Some(output) = output {
@@ -4626,7 +5033,7 @@ impl<W: LayoutElement> Layout<W> {
             (monitor, index)
         };
 
-        monitor.move_workspace_to_idx(old_idx, new_idx);
+        monitor.move_workspace_to_idx(old_idx, new_idx, compact);
     }
 
     pub fn set_workspace_name(&mut self, name: String, reference: Option<WorkspaceReference>) {
@@ -4699,13 +5106,19 @@ impl<W: LayoutElement> Layout<W> {
         };
 
         for mon in monitors {
-            mon.overview_open = self.overview_open;
-            mon.set_overview_progress(self.overview_progress.as_ref());
+            mon.set_overview_state(
+                self.overview_open,
+                self.overview_progress.as_ref(),
+                self.overview_output.as_ref(),
+            );
         }
     }
 
     pub fn toggle_overview(&mut self) {
         self.overview_open = !self.overview_open;
+        if self.overview_open {
+            self.overview_output = None;
+        }
 
         let from = self.overview_progress.take().map_or(0., |p| p.value());
         let to = if self.overview_open { 1. } else { 0. };
@@ -4721,6 +5134,26 @@ impl<W: LayoutElement> Layout<W> {
         self.set_monitors_overview_state();
     }
 
+    /// Toggles the overview only on the active output.
+    ///
+    /// Other outputs keep showing their workspaces as usual. If the overview is already open,
+    /// whether on all outputs or on one, this closes it.
+    pub fn toggle_overview_on_active_output(&mut self) {
+        if !self.overview_open {
+            let Some(output) = self.active_output().cloned() else {
+                return;
+            };
+
+            // Other outputs may be in the middle of the closing animation, they will jump to the
+            // closed state.
+            self.toggle_overview();
+            self.overview_output = Some(output);
+            self.set_monitors_overview_state();
+        } else {
+            self.toggle_overview();
+        }
+    }
+
     pub fn open_overview(&mut self) -> bool {
         if self.overview_open {
             return false;
@@ -4831,7 +5264,7 @@ impl<W: LayoutElement> Layout<W> {
     ) {
         let _span = tracy_client::span!("Layout::start_close_animation_for_window");
 
-        let zoom = self.overview_zoom();
+        let zoom = self.interactive_move_zoom();
 
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move
             && move_.tile.focused_window().id() == window
@@ -4902,7 +5335,7 @@ impl<W: LayoutElement> Layout<W> {
             && &move_.output == output
         {
             let scale = Scale::from(move_.output.current_scale().fractional_scale());
-            let zoom = self.overview_zoom();
+            let zoom = self.overview_zoom_on(output);
             let location = move_.tile_render_location(zoom);
             let fx_buffers = EffectsFramebuffers::get_user_data(output);
             move_.tile.render(
@@ -4931,6 +5364,14 @@ impl<W: LayoutElement> Layout<W> {
 
         self.is_active = is_active;
 
+        // A single-output overview closes when the focus moves to a different output.
+        if self.overview_open
+            && let Some(overview_output) = &self.overview_output
+            && self.active_output() != Some(overview_output)
+        {
+            self.toggle_overview();
+        }
+
         let mut ongoing_scrolling_dnd = self.dnd.is_some().then_some(true);
 
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
@@ -4971,10 +5412,10 @@ impl<W: LayoutElement> Layout<W> {
                         && idx == *active_monitor_idx
                         && !matches!(self.interactive_move, Some(InteractiveMoveState::Moving(_)));
 
-                    if ongoing_scrolling_dnd.is_some() && self.overview_open {
+                    if ongoing_scrolling_dnd.is_some() && mon.overview_open {
                         // Begin the scroll on new monitors and when opening the overview.
                         mon.dnd_scroll_gesture_begin();
-                    } else if !self.overview_open {
+                    } else if !mon.overview_open {
                         mon.dnd_scroll_gesture_end();
                     }
 
@@ -4991,7 +5432,7 @@ impl<W: LayoutElement> Layout<W> {
                             }
                         } else {
                             // Cancel the view offset gesture after workspace switches, moves, etc.
-                            if !self.overview_open && ws_idx != mon.active_workspace_idx {
+                            if !mon.overview_open && ws_idx != mon.active_workspace_idx {
                                 ws.view_offset_gesture_end(None);
                             }
                         }
@@ -5094,6 +5535,28 @@ impl<W: LayoutElement> Layout<W> {
     pub const fn is_overview_open(&self) -> bool {
         self.overview_open
     }
+
+    /// Returns whether the overview is open on this output.
+    ///
+    /// Unlike [`Self::is_overview_open()`], this takes a single-output overview into account.
+    pub fn is_overview_open_on(&self, output: &Output) -> bool {
+        self.monitor_for_output(output)
+            .is_some_and(|mon| mon.is_overview_open())
+    }
+
+    /// Returns the overview zoom on this output.
+    fn overview_zoom_on(&self, output: &Output) -> f64 {
+        self.monitor_for_output(output)
+            .map_or(1., |mon| mon.overview_zoom())
+    }
+
+    /// Returns the overview zoom on the output of the interactively moved window.
+    fn interactive_move_zoom(&self) -> f64 {
+        match &self.interactive_move {
+            Some(InteractiveMoveState::Moving(move_)) => self.overview_zoom_on(&move_.output),
+            _ => 1.,
+        }
+    }
 }
 
 impl<W: LayoutElement> Default for MonitorSet<W> {
@@ -5104,7 +5567,14 @@ impl<W: LayoutElement> Default for MonitorSet<W> {
 
 fn compute_overview_zoom(options: &Options, overview_progress: Option<f64>) -> f64 {
     // Clamp to some sane values.
-    let zoom = options.overview.zoom.clamp(0.0001, 0.75);
+    let mut zoom = options.overview.zoom.clamp(0.0001, 0.75);
+
+    // Make sure that a grid of workspaces fits horizontally. The gaps between workspaces are at
+    // most 10% of the workspace width.
+    if let Some(columns) = options.overview.columns.filter(|c| *c > 1) {
+        let columns = f64::from(columns);
+        zoom = zoom.min(1. / columns.mul_add(1.1, -0.1));
+    }
 
     if let Some(p) = overview_progress {
         p.mul_add(-(1. - zoom), 1.).max(0.0001)
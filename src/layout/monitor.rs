@@ -3,7 +3,11 @@ use std::iter::zip;
 use std::rc::Rc;
 use std::time::Duration;
 
-use niri_config::{CornerRadius, LayoutPart};
+use niri_config::utils::MergeWith as _;
+use niri_config::{
+    CornerRadius, FocusAfterClose, InsertHintAnimation, InsertHintRule, LayoutPart,
+    OnEmptyWorkspace,
+};
 use smithay::backend::renderer::element::utils::{
     CropRenderElement, Relocate, RelocateRenderElement, RescaleRenderElement,
 };
@@ -18,7 +22,7 @@ use super::workspace::{
     compute_working_area,
 };
 use super::{ActivateWindow, HitType, LayoutElement, Options, compute_overview_zoom};
-use crate::animation::{Animation, Clock};
+use crate::animation::{Animation, Clock, Curve};
 use crate::input::swipe_tracker::SwipeTracker;
 use crate::niri_render_elements;
 use crate::render_helpers::RenderTarget;
@@ -45,6 +49,22 @@ const WORKSPACE_GESTURE_RUBBER_BAND: RubberBand = RubberBand {
 /// This constant is tied to the default dnd-edge-workspace-switch max-speed setting.
 const WORKSPACE_DND_EDGE_SCROLL_MOVEMENT: f64 = 1500.;
 
+/// Workspace switch speed, in workspaces per second, at which the workspace shadow reaches its
+/// full motion offset.
+const WORKSPACE_SHADOW_MOTION_VELOCITY: f64 = 4.;
+
+/// Maximum motion offset of the workspace shadow, normalized to a 1080 px tall view.
+const WORKSPACE_SHADOW_MOTION_OFFSET: f64 = 15.;
+
+/// Duration of the insert hint fade-in animation.
+const INSERT_HINT_FADE_DURATION: Duration = Duration::from_millis(150);
+
+/// Duration of one insert hint pulse cycle.
+const INSERT_HINT_PULSE_PERIOD: Duration = Duration::from_millis(1000);
+
+/// Lowest alpha that the insert hint reaches while pulsing.
+const INSERT_HINT_PULSE_MIN_ALPHA: f64 = 0.4;
+
 #[derive(Debug)]
 pub struct Monitor<W: LayoutElement> {
     /// Output for this monitor.
@@ -76,6 +96,8 @@ pub struct Monitor<W: LayoutElement> {
     insert_hint_element: InsertHintElement,
     /// Location to render the insert hint element.
     insert_hint_render_loc: Option<InsertHintRenderLoc>,
+    /// Time when the insert hint started showing, for driving its animation.
+    insert_hint_shown_at: Option<Duration>,
     /// Whether the overview is open.
     pub(super) overview_open: bool,
     /// Progress of the overview zoom animation, 1 is fully in overview.
@@ -144,6 +166,8 @@ pub(super) struct InsertHint {
     pub workspace: InsertWorkspace,
     pub position: InsertPosition,
     pub corner_radius: CornerRadius,
+    /// Overrides from the window rules of the window being moved.
+    pub rule: InsertHintRule,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -339,6 +363,7 @@ impl<W: LayoutElement> Monitor<W> {
             insert_hint: None,
             insert_hint_element: InsertHintElement::new(options.layout.insert_hint),
             insert_hint_render_loc: None,
+            insert_hint_shown_at: None,
             overview_open: false,
             overview_progress: None,
             workspace_switch: None,
@@ -566,10 +591,16 @@ impl<W: LayoutElement> Monitor<W> {
         self.post_add_window(workspace_idx, activate, true);
     }
 
-    pub fn add_column(&mut self, mut workspace_idx: usize, column: Column<W>, activate: bool) {
+    pub fn add_column(
+        &mut self,
+        mut workspace_idx: usize,
+        column_idx: Option<usize>,
+        column: Column<W>,
+        activate: bool,
+    ) {
         let workspace = &mut self.workspaces[workspace_idx];
 
-        workspace.add_column(column, activate);
+        workspace.add_column(column_idx, column, activate);
 
         // After adding a new window, workspace becomes this output's own.
         if workspace.name().is_none() {
@@ -677,6 +708,78 @@ impl<W: LayoutElement> Monitor<W> {
         }
     }
 
+    /// Removes the workspace at `idx` if it's empty and the `on-empty-workspace` setting asks for
+    /// it.
+    ///
+    /// Called after the last window on a workspace closes. The last workspace and the first one
+    /// with `empty_workspace_above_first` are never removed.
+    pub fn apply_on_empty_workspace(&mut self, idx: usize) {
+        if self.workspace_switch.is_some() {
+            return;
+        }
+
+        let ws = &self.workspaces[idx];
+        if ws.has_windows() {
+            return;
+        }
+
+        let remove = match self.options.layout.on_empty_workspace {
+            OnEmptyWorkspace::Keep => false,
+            OnEmptyWorkspace::Remove => true,
+            OnEmptyWorkspace::RemoveIfUnnamed => ws.name.is_none(),
+        };
+        if !remove {
+            return;
+        }
+
+        let range_start = if self.options.layout.empty_workspace_above_first {
+            1
+        } else {
+            0
+        };
+        if idx < range_start || idx == self.workspaces.len() - 1 {
+            return;
+        }
+
+        self.workspaces.remove(idx);
+
+        if idx == self.active_workspace_idx
+            && self.options.layout.focus_after_close == FocusAfterClose::Mru
+            && self.focus_most_recent_window()
+        {
+            return;
+        }
+
+        // When removing the active workspace, focus the one above it, unless it's the first
+        // workspace, in which case the one below takes its place.
+        if idx < self.active_workspace_idx
+            || (idx == self.active_workspace_idx && idx > range_start)
+        {
+            self.active_workspace_idx -= 1;
+        }
+    }
+
+    /// Activates the most recently focused window on this monitor and its workspace.
+    ///
+    /// Returns `false` if no window on this monitor has been focused yet.
+    fn focus_most_recent_window(&mut self) -> bool {
+        let most_recent = self
+            .workspaces
+            .iter()
+            .enumerate()
+            .flat_map(|(ws_idx, ws)| ws.windows().map(move |win| (ws_idx, win)))
+            .filter_map(|(ws_idx, win)| Some((ws_idx, win.id().clone(), win.focus_timestamp()?)))
+            .max_by_key(|(_, _, timestamp)| *timestamp);
+
+        let Some((ws_idx, id, _)) = most_recent else {
+            return false;
+        };
+
+        self.workspaces[ws_idx].activate_window(&id);
+        self.active_workspace_idx = ws_idx;
+        true
+    }
+
     pub fn unname_workspace(&mut self, id: WorkspaceId) -> bool {
         let Some(ws) = self.workspaces.iter_mut().find(|ws| ws.id() == id) else {
             return false;
@@ -955,7 +1058,7 @@ impl<W: LayoutElement> Monitor<W> {
             return;
         };
 
-        self.add_column(new_idx, column, activate);
+        self.add_column(new_idx, None, column, activate);
     }
 
     pub fn move_column_to_workspace_down(&mut self, activate: bool) {
@@ -976,7 +1079,7 @@ impl<W: LayoutElement> Monitor<W> {
             return;
         };
 
-        self.add_column(new_idx, column, activate);
+        self.add_column(new_idx, None, column, activate);
     }
 
     pub fn move_column_to_workspace(&mut self, idx: usize, activate: bool) {
@@ -1002,7 +1105,7 @@ impl<W: LayoutElement> Monitor<W> {
             return;
         };
 
-        self.add_column(new_idx, column, activate);
+        self.add_column(new_idx, None, column, activate);
     }
 
     pub fn switch_workspace_up(&mut self) {
@@ -1060,6 +1163,27 @@ impl<W: LayoutElement> Monitor<W> {
         }
     }
 
+    pub fn move_to_workspace_previous(&mut self, focus: bool) {
+        let Some(idx) = self.previous_workspace_idx() else {
+            return;
+        };
+
+        let activate = if focus {
+            ActivateWindow::Smart
+        } else {
+            ActivateWindow::No
+        };
+        self.move_to_workspace(None, idx, activate);
+    }
+
+    pub fn move_column_to_workspace_previous(&mut self, activate: bool) {
+        let Some(idx) = self.previous_workspace_idx() else {
+            return;
+        };
+
+        self.move_column_to_workspace(idx, activate);
+    }
+
     pub fn active_window(&self) -> Option<&W> {
         self.active_workspace_ref().active_window()
     }
@@ -1113,6 +1237,52 @@ impl<W: LayoutElement> Monitor<W> {
             .as_ref()
             .is_some_and(|s| s.is_animation_ongoing())
             || self.workspaces.iter().any(|ws| ws.are_animations_ongoing())
+            || self.is_insert_hint_animation_ongoing()
+    }
+
+    fn is_insert_hint_animation_ongoing(&self) -> bool {
+        let Some(shown_at) = self.insert_hint_shown_at else {
+            return false;
+        };
+
+        let insert_hint = &self.options.layout.insert_hint;
+        if insert_hint.off || self.clock.should_complete_instantly() {
+            return false;
+        }
+
+        match insert_hint.animation {
+            InsertHintAnimation::None => false,
+            InsertHintAnimation::Fade => {
+                self.clock.now().saturating_sub(shown_at) < INSERT_HINT_FADE_DURATION
+            }
+            InsertHintAnimation::Pulse => true,
+        }
+    }
+
+    fn insert_hint_alpha(&self) -> f32 {
+        let Some(shown_at) = self.insert_hint_shown_at else {
+            return 1.;
+        };
+
+        if self.clock.should_complete_instantly() {
+            return 1.;
+        }
+
+        let elapsed = self.clock.now().saturating_sub(shown_at);
+        let alpha = match self.options.layout.insert_hint.animation {
+            InsertHintAnimation::None => 1.,
+            InsertHintAnimation::Fade => {
+                let progress = elapsed.as_secs_f64() / INSERT_HINT_FADE_DURATION.as_secs_f64();
+                Curve::EaseOutCubic.y(progress.min(1.))
+            }
+            InsertHintAnimation::Pulse => {
+                // Start fully opaque, then dip down to the minimum alpha and back up.
+                let phase = elapsed.as_secs_f64() / INSERT_HINT_PULSE_PERIOD.as_secs_f64();
+                let dip = (1. - (phase * std::f64::consts::TAU).cos()) / 2.;
+                1. - dip * (1. - INSERT_HINT_PULSE_MIN_ALPHA)
+            }
+        };
+        alpha as f32
     }
 
     pub fn are_transitions_ongoing(&self) -> bool {
@@ -1139,7 +1309,19 @@ impl<W: LayoutElement> Monitor<W> {
         }
 
         self.insert_hint_render_loc = None;
+
+        if self.insert_hint.is_none() {
+            self.insert_hint_shown_at = None;
+        } else if self.insert_hint_shown_at.is_none() {
+            self.insert_hint_shown_at = Some(self.clock.now());
+        }
+        let alpha = self.insert_hint_alpha();
+
         if let Some(hint) = &self.insert_hint {
+            let mut config = self.options.layout.insert_hint;
+            config.merge_with(&hint.rule);
+            self.insert_hint_element.update_config(config);
+
             match hint.workspace {
                 InsertWorkspace::Existing(ws_id) => {
                     if let Some(ws) = self.workspaces.iter().find(|ws| ws.id() == ws_id) {
@@ -1167,6 +1349,7 @@ impl<W: LayoutElement> Monitor<W> {
                                 view_rect,
                                 hint.corner_radius,
                                 scale,
+                                alpha,
                             );
                             self.insert_hint_render_loc = Some(InsertHintRenderLoc {
                                 workspace: hint.workspace,
@@ -1207,6 +1390,7 @@ impl<W: LayoutElement> Monitor<W> {
                         view_rect,
                         CornerRadius::default(),
                         scale,
+                        alpha,
                     );
                     self.insert_hint_render_loc = Some(InsertHintRenderLoc {
                         workspace: hint.workspace,
@@ -1325,13 +1509,20 @@ impl<W: LayoutElement> Monitor<W> {
         self.clean_up_workspaces();
     }
 
-    pub fn move_workspace_to_idx(&mut self, old_idx: usize, new_idx: usize) {
+    pub fn move_workspace_to_idx(&mut self, old_idx: usize, new_idx: usize, compact: bool) {
         if self.workspaces.len() <= old_idx {
             return;
         }
 
+        let ws_id = self.workspaces[old_idx].id();
+
         let mut new_idx = new_idx.clamp(0, self.workspaces.len() - 1);
         if old_idx == new_idx {
+            if compact {
+                self.workspace_switch = None;
+                self.compact_workspaces(ws_id);
+                self.clean_up_workspaces();
+            }
             return;
         }
 
@@ -1373,9 +1564,51 @@ impl<W: LayoutElement> Monitor<W> {
 
         self.workspace_switch = None;
 
+        if compact {
+            self.compact_workspaces(ws_id);
+        }
+
         self.clean_up_workspaces();
     }
 
+    /// Removes all empty unnamed workspaces except `keep`.
+    ///
+    /// Unlike [`Self::clean_up_workspaces()`], this also removes the active workspace if it is
+    /// empty, in which case `keep` becomes active. The last workspace and the first one with
+    /// `empty_workspace_above_first` are left alone, since they are the dynamic empty workspaces.
+    fn compact_workspaces(&mut self, keep: WorkspaceId) {
+        assert!(self.workspace_switch.is_none());
+
+        let range_start = if self.options.layout.empty_workspace_above_first {
+            1
+        } else {
+            0
+        };
+
+        let mut removed_active = false;
+        for idx in (range_start..self.workspaces.len() - 1).rev() {
+            let ws = &self.workspaces[idx];
+            if ws.id() == keep || ws.has_windows_or_name() {
+                continue;
+            }
+
+            self.workspaces.remove(idx);
+            if self.active_workspace_idx == idx {
+                removed_active = true;
+            } else if self.active_workspace_idx > idx {
+                self.active_workspace_idx -= 1;
+            }
+        }
+
+        if removed_active {
+            self.active_workspace_idx = self
+                .workspaces
+                .iter()
+                .position(|ws| ws.id() == keep)
+                .unwrap();
+        }
+    }
+
     /// Returns the geometry of the active tile relative to and clamped to the output.
     ///
     /// During animations, assumes the final view position.
@@ -1409,6 +1642,25 @@ impl<W: LayoutElement> Monitor<W> {
         compute_overview_zoom(&self.options, progress)
     }
 
+    /// Synchronizes the overview state from the layout.
+    ///
+    /// When `overview_output` is set, the overview is only open on that output, and this monitor
+    /// stays closed if it's on a different one.
+    pub(super) fn set_overview_state(
+        &mut self,
+        open: bool,
+        progress: Option<&super::OverviewProgress>,
+        overview_output: Option<&Output>,
+    ) {
+        let applies = overview_output.is_none_or(|output| *output == self.output);
+        self.overview_open = open && applies;
+        self.set_overview_progress(progress.filter(|_| applies));
+    }
+
+    pub const fn is_overview_open(&self) -> bool {
+        self.overview_open
+    }
+
     pub(super) fn set_overview_progress(&mut self, progress: Option<&super::OverviewProgress>) {
         let prev_render_idx = self.workspace_render_idx();
         self.overview_progress = progress.map(OverviewProgress::from);
@@ -1504,6 +1756,20 @@ impl<W: LayoutElement> Monitor<W> {
         }
     }
 
+    /// Returns the number of columns of the overview grid, if the overview uses one.
+    fn overview_grid_columns(&self) -> Option<usize> {
+        self.options
+            .overview
+            .columns
+            .map(usize::from)
+            .filter(|columns| *columns > 1)
+    }
+
+    /// Returns whether the workspaces are currently arranged in the overview grid.
+    fn is_overview_grid(&self) -> bool {
+        self.overview_grid_columns().is_some() && self.overview_progress.is_some()
+    }
+
     pub fn workspaces_render_geo(&self) -> impl Iterator<Item = Rectangle<f64, Logical>> + use<W> {
         let scale = self.scale.fractional_scale();
         let zoom = self.overview_zoom();
@@ -1517,13 +1783,43 @@ impl<W: LayoutElement> Monitor<W> {
             .to_physical_precise_round(scale)
             .to_logical(scale);
 
-        let first_ws_y = -self.workspace_render_idx() * ws_height_with_gap;
+        let render_idx = self.workspace_render_idx();
+        let first_ws_y = -render_idx * ws_height_with_gap;
         let first_ws_y = round_logical_in_physical(scale, first_ws_y);
 
+        // In the grid, the view scrolls by rows rather than by workspaces, so moving between
+        // workspaces in the same row doesn't scroll.
+        let grid = self.overview_grid_columns().and_then(|columns| {
+            let progress = self.overview_progress.as_ref()?.clamped_value();
+
+            let row_of = |idx: f64| (idx.max(0.) as usize / columns) as f64;
+            let render_idx_floor = render_idx.floor();
+            let from_row = row_of(render_idx_floor);
+            let to_row = row_of(render_idx.ceil());
+            let view_row = (to_row - from_row).mul_add(render_idx - render_idx_floor, from_row);
+
+            let grid_width = (columns as f64).mul_add(ws_size.w + gap, -gap);
+            let grid_x = (self.view_size.w - grid_width) / 2.;
+
+            Some((columns, progress.clamp(0., 1.), view_row, grid_x))
+        });
+
         // Return position for one-past-last workspace too.
         (0..=self.workspaces.len()).map(move |idx| {
             let y = (idx as f64).mul_add(ws_height_with_gap, first_ws_y);
-            let loc = Point::from((0., y)) + static_offset;
+            let mut loc = Point::from((0., y)) + static_offset;
+
+            if let Some((columns, progress, view_row, grid_x)) = grid {
+                let row = (idx / columns) as f64;
+                let col = (idx % columns) as f64;
+                let grid_loc = Point::from((
+                    col.mul_add(ws_size.w + gap, grid_x),
+                    (row - view_row).mul_add(ws_height_with_gap, static_offset.y),
+                ));
+
+                // Blend between the strip and the grid to animate opening the overview.
+                loc += (grid_loc - loc).upscale(progress);
+            }
 
             // Even though all components that go into loc are rounded to physical pixels, the
             // floating point addition may lose precision. This can result for example in the
@@ -1573,11 +1869,16 @@ impl<W: LayoutElement> Monitor<W> {
         &self,
         pos_within_output: Point<f64, Logical>,
     ) -> Option<(&Workspace<W>, Rectangle<f64, Logical>)> {
+        let is_grid = self.is_overview_grid();
         let (ws, geo) = self.workspaces_with_render_geo().find_map(|(ws, geo)| {
-            // Extend width to entire output.
-            let loc = Point::from((0., geo.loc.y));
-            let size = Size::from((self.view_size.w, geo.size.h));
-            let bounds = Rectangle::new(loc, size);
+            // Extend width to entire output, unless there are other workspaces to the sides.
+            let bounds = if is_grid {
+                geo
+            } else {
+                let loc = Point::from((0., geo.loc.y));
+                let size = Size::from((self.view_size.w, geo.size.h));
+                Rectangle::new(loc, size)
+            };
 
             bounds.contains(pos_within_output).then_some((ws, geo))
         })?;
@@ -1608,6 +1909,20 @@ impl<W: LayoutElement> Monitor<W> {
         }
     }
 
+    pub fn column_under(
+        &self,
+        pos_within_output: Point<f64, Logical>,
+    ) -> Option<(&Workspace<W>, usize)> {
+        // Clicks in the overview go to whole workspaces.
+        if self.overview_progress.is_some() {
+            return None;
+        }
+
+        let (ws, geo) = self.workspace_under(pos_within_output)?;
+        let idx = ws.column_under(pos_within_output - geo.loc)?;
+        Some((ws, idx))
+    }
+
     pub fn resize_edges_under(&self, pos_within_output: Point<f64, Logical>) -> Option<ResizeEdge> {
         if self.overview_progress.is_some() {
             return None;
@@ -1621,10 +1936,22 @@ impl<W: LayoutElement> Monitor<W> {
         &self,
         pos_within_output: Point<f64, Logical>,
     ) -> (InsertWorkspace, Rectangle<f64, Logical>) {
-        let mut iter = self.workspaces_with_render_geo_idx();
-
         let dummy = Rectangle::default();
 
+        if self.is_overview_grid() {
+            // The gaps in the grid don't map to a single insert position, so only insert into
+            // workspaces under the pointer, and into a new last workspace anywhere else.
+            let hit = self
+                .workspaces_with_render_geo()
+                .find(|(_, geo)| geo.contains(pos_within_output));
+            return match hit {
+                Some((ws, geo)) => (InsertWorkspace::Existing(ws.id()), geo),
+                None => (InsertWorkspace::NewAt(self.workspaces.len()), dummy),
+            };
+        }
+
+        let mut iter = self.workspaces_with_render_geo_idx();
+
         // Monitors always have at least one workspace.
         let ((idx, ws), geo) = iter.next().unwrap();
 
@@ -1775,6 +2102,16 @@ impl<W: LayoutElement> Monitor<W> {
         let scale = self.scale.fractional_scale();
         let zoom = self.overview_zoom();
 
+        // Make the shadow trail behind the workspaces as they move.
+        let motion_offset = if self.options.overview.workspace_shadow.motion {
+            let norm = self.view_size.h / 1080.;
+            let velocity = self.workspace_switch_velocity();
+            let amount = (velocity / WORKSPACE_SHADOW_MOTION_VELOCITY).clamp(-1., 1.);
+            Point::from((0., amount * WORKSPACE_SHADOW_MOTION_OFFSET * norm * zoom))
+        } else {
+            Point::default()
+        };
+
         for (ws, geo) in self.workspaces_with_render_geo() {
             ws.render_shadow(renderer, &mut |elem: ShadowRenderElement| {
                 let elem = elem.with_alpha(alpha);
@@ -1782,7 +2119,7 @@ impl<W: LayoutElement> Monitor<W> {
                 let elem = RescaleRenderElement::from_element(elem, Point::from((0, 0)), zoom);
                 let elem = RelocateRenderElement::from_element(
                     elem,
-                    geo.loc.to_physical_precise_round(scale),
+                    (geo.loc + motion_offset).to_physical_precise_round(scale),
                     Relocate::Relative,
                 );
                 collector.push_element(elem);
@@ -1790,6 +2127,28 @@ impl<W: LayoutElement> Monitor<W> {
         }
     }
 
+    /// Returns the speed of the ongoing workspace switch in workspaces per second.
+    fn workspace_switch_velocity(&self) -> f64 {
+        match &self.workspace_switch {
+            None => 0.,
+            Some(WorkspaceSwitch::Animation(anim)) => {
+                let now = self.clock.now();
+                let dt = Duration::from_millis(16);
+                let prev = anim.value_at(now.saturating_sub(dt));
+                (anim.value_at(now) - prev) / dt.as_secs_f64()
+            }
+            Some(WorkspaceSwitch::Gesture(gesture)) => {
+                if gesture.dnd_last_event_time.is_some() {
+                    gesture.tracker.velocity() / WORKSPACE_DND_EDGE_SCROLL_MOVEMENT
+                } else if gesture.is_touchpad {
+                    gesture.tracker.velocity() / self.touchpad_workspace_gesture_movement()
+                } else {
+                    gesture.tracker.velocity() / self.workspace_size_with_gap(1.).h
+                }
+            }
+        }
+    }
+
     pub fn render_insert_hint_between_workspaces<R, C>(&self, renderer: &mut R, collector: &mut C)
     where
         R: NiriRenderer,
@@ -1881,7 +2240,7 @@ impl<W: LayoutElement> Monitor<W> {
 
         let zoom = self.overview_zoom();
         let total_height = if gesture.is_touchpad {
-            WORKSPACE_GESTURE_MOVEMENT
+            self.touchpad_workspace_gesture_movement()
         } else {
             self.workspace_size_with_gap(1.).h
         };
@@ -2015,10 +2374,15 @@ impl<W: LayoutElement> Monitor<W> {
         let total_height = if gesture.dnd_last_event_time.is_some() {
             WORKSPACE_DND_EDGE_SCROLL_MOVEMENT
         } else if gesture.is_touchpad {
-            WORKSPACE_GESTURE_MOVEMENT
+            self.touchpad_workspace_gesture_movement()
         } else {
             self.workspace_size_with_gap(1.).h
         };
+        let min_delta = if gesture.dnd_last_event_time.is_some() {
+            0.5
+        } else {
+            self.options.gestures.workspace_switch.min_delta
+        };
 
         let Some(WorkspaceSwitch::Gesture(gesture)) = &mut self.workspace_switch else {
             return false;
@@ -2038,7 +2402,14 @@ impl<W: LayoutElement> Monitor<W> {
         let (min, max) = gesture.min_max(self.workspaces.len());
         let new_idx = gesture.start_idx + pos;
 
-        let new_idx = new_idx.clamp(min, max);
+        // Only switch to the next workspace once the gesture has moved past min_delta of it.
+        let base_idx = gesture.start_idx.round();
+        let delta = new_idx - base_idx;
+        let mut steps = delta.trunc();
+        if delta.fract().abs() >= min_delta {
+            steps += delta.signum();
+        }
+        let new_idx = (base_idx + steps).clamp(min, max);
         let new_idx = new_idx.round() as usize;
 
         velocity *= rubber_band.clamp_derivative(min, max, gesture.start_idx + current_pos);
@@ -2059,6 +2430,13 @@ impl<W: LayoutElement> Monitor<W> {
         true
     }
 
+    /// Amount of touchpad movement to scroll the height of one workspace, adjusted for the
+    /// configured sensitivity.
+    fn touchpad_workspace_gesture_movement(&self) -> f64 {
+        let sensitivity = self.options.gestures.workspace_switch.sensitivity;
+        WORKSPACE_GESTURE_MOVEMENT / sensitivity.max(0.01)
+    }
+
     pub fn dnd_scroll_gesture_end(&mut self) {
         if !matches!(
             self.workspace_switch,
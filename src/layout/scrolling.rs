@@ -5,7 +5,7 @@ use std::time::Duration;
 
 use niri_config::utils::MergeWith as _;
 use niri_config::{CenterFocusedColumn, PresetSize, Struts, WindowMoveDirection};
-use niri_ipc::{SizeChange, WindowLayout};
+use niri_ipc::{GroupDisplayMode, SizeChange, WindowLayout};
 use ordered_float::NotNan;
 use smithay::backend::renderer::gles::GlesRenderer;
 use smithay::utils::{Logical, Point, Rectangle, Scale, Serial, Size};
@@ -24,14 +24,19 @@ use crate::niri_render_elements;
 use crate::render_helpers::RenderTarget;
 use crate::render_helpers::blur::EffectsFramebuffersUserData;
 use crate::render_helpers::renderer::NiriRenderer;
-use crate::utils::ResizeEdge;
+use crate::rubber_band::RubberBand;
 use crate::utils::render::{PushRenderElement, Render};
 use crate::utils::transaction::{Transaction, TransactionBlocker};
+use crate::utils::{ResizeEdge, round_logical_in_physical};
 use crate::window::ResolvedWindowRules;
 
 /// Amount of touchpad movement to scroll the view for the width of one working area.
 const VIEW_GESTURE_WORKING_AREA_MOVEMENT: f64 = 1200.;
 
+/// Maximum distance the view can be pulled past the first or last column with overscroll, as a
+/// fraction of the working area width.
+const VIEW_GESTURE_OVERSCROLL_LIMIT: f64 = 0.1;
+
 #[derive(Clone, Debug)]
 pub struct ScrollingSpaceRenderContext {
     pub target: RenderTarget,
@@ -178,6 +183,23 @@ pub struct Column<W: LayoutElement> {
     /// Whether this column is full-width.
     is_full_width: bool,
 
+    /// Latest known width left over by the other columns, used for [`ColumnWidth::Rest`].
+    rest_width: f64,
+
+    /// Width to restore when toggling the expanded width off.
+    ///
+    /// Forgotten if the column was resized in the meantime.
+    width_before_expand: Option<WidthBeforeExpand>,
+
+    /// State to restore when toggling the group maximize off.
+    group_maximize: Option<GroupMaximize<W::Id>>,
+
+    /// Offset of the tiles from the top of the column, set by center-window-vertically.
+    ///
+    /// Only has an effect when the tiles don't fill the column height, and is clamped to the free
+    /// space left in the column.
+    vertical_offset: f64,
+
     /// Whether this column is going to be fullscreen.
     ///
     /// This is the compositor-side fullscreen state, so it changes immediately upon
@@ -219,6 +241,47 @@ pub struct Column<W: LayoutElement> {
     options: Rc<Options>,
 }
 
+/// Column width state saved by toggle-column-expanded-width.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WidthBeforeExpand {
+    /// Width before expanding.
+    width: ColumnWidth,
+    /// Preset width index before expanding.
+    preset_width_idx: Option<usize>,
+    /// Whether the column was full-width before expanding.
+    is_full_width: bool,
+    /// Width right after expanding, to detect manual resizes in the meantime.
+    expanded_width: ColumnWidth,
+    /// Whether the column was full-width right after expanding.
+    expanded_is_full_width: bool,
+}
+
+/// Column state saved by toggle-maximize-column-with-group.
+#[derive(Debug)]
+struct GroupMaximize<Id> {
+    /// Window that was focused when maximizing, its tile holds the group.
+    anchor: Id,
+    /// Whether the anchor tile was a group before maximizing.
+    was_grouped: bool,
+    /// Display mode of the anchor tile before maximizing.
+    display_mode: GroupDisplayMode,
+    /// Whether the column was full-width before maximizing.
+    is_full_width: bool,
+    /// Other tiles of the column that were merged into the group, in column order.
+    tiles: Vec<MergedTile<Id>>,
+    /// Number of merged tiles that were above the anchor tile.
+    tiles_above: usize,
+}
+
+/// Tile merged into the group by toggle-maximize-column-with-group.
+#[derive(Debug)]
+struct MergedTile<Id> {
+    /// Windows of the tile, in group order.
+    windows: Vec<Id>,
+    /// Window that was focused in the tile.
+    focused: Id,
+}
+
 /// Extra per-tile data.
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct TileData {
@@ -241,6 +304,11 @@ pub enum ColumnWidth {
     Proportion(f64),
     /// Fixed width in logical pixels.
     Fixed(f64),
+    /// Width left over by the other columns in the working area.
+    ///
+    /// Follows the other columns as they change. Multiple such columns split the leftover width
+    /// evenly.
+    Rest,
 }
 
 /// Height of a window in a column.
@@ -269,6 +337,20 @@ pub enum WindowHeight {
     Preset(usize),
 }
 
+/// Where a tile was in its column before getting maximized to edges.
+///
+/// Maximizing extracts the window from its column, so this is what lets a second
+/// `toggle-maximize-window-to-edges` put it back.
+#[derive(Debug, Clone)]
+pub struct PreMaximizeGeometry<Id> {
+    /// A window that stayed in the original column.
+    neighbor: Id,
+    /// Index of the tile in the original column.
+    tile_idx: usize,
+    /// Requested height of the tile in the original column.
+    height: WindowHeight,
+}
+
 /// Horizontal direction for an operation.
 ///
 /// As operations often have a symmetrical counterpart, e.g. focus-right/focus-left, methods
@@ -443,6 +525,14 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         Some(col.tiles[col.active_tile_idx].focused_window())
     }
 
+    pub fn active_column_preset_width_idx(&self) -> Option<usize> {
+        let col = self.columns.get(self.active_column_idx)?;
+        if col.is_full_width || col.is_pending_maximized {
+            return None;
+        }
+        col.preset_width_idx
+    }
+
     pub fn active_window_mut(&mut self) -> Option<&mut W> {
         if self.columns.is_empty() {
             return None;
@@ -1058,6 +1148,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
         let tile = column.tiles.remove(tile_idx);
         column.data.remove(tile_idx);
+        column.vertical_offset = 0.;
 
         // If an active column became non-fullscreen after removing the tile, clear the stored
         // unfullscreen offset.
@@ -1647,6 +1738,18 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         self.move_column_to(index.saturating_sub(1).min(self.columns.len() - 1));
     }
 
+    pub fn move_column_by_offset(&mut self, offset: i32) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let new_idx = self
+            .active_column_idx
+            .saturating_add_signed(offset as isize)
+            .min(self.columns.len() - 1);
+        self.move_column_to(new_idx);
+    }
+
     fn move_column_to(&mut self, new_idx: usize) {
         if self.active_column_idx == new_idx {
             return;
@@ -2408,6 +2511,59 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         new_col.tiles[0].animate_move_from(offset);
     }
 
+    /// Expels the active window into a new column to the left or right of its current column.
+    ///
+    /// The new column is activated, so focus follows the expelled window.
+    pub fn expel_window_to_column(&mut self, right: bool) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let source_col_idx = self.active_column_idx;
+        let cur_x = self.column_x(source_col_idx);
+
+        let source_column = &self.columns[source_col_idx];
+        if source_column.tiles.len() == 1 {
+            return;
+        }
+
+        let source_tile_idx = source_column.active_tile_idx;
+        let mut offset = Point::from((source_column.render_offset().x, 0.));
+        let prev_off = source_column.tile_offset(source_tile_idx);
+
+        let removed =
+            self.remove_tile_by_idx(source_col_idx, source_tile_idx, Transaction::new(), None);
+
+        let target_col_idx = if right {
+            source_col_idx + 1
+        } else {
+            source_col_idx
+        };
+
+        self.add_tile(
+            Some(target_col_idx),
+            removed.tile,
+            true,
+            removed.width,
+            removed.is_full_width,
+            Some(self.options.animations.window_movement.0),
+        );
+
+        if right {
+            offset.x += cur_x - self.column_x(target_col_idx);
+        } else {
+            // We added to the left, don't activate even further left on removal.
+            self.activate_prev_column_on_removal = None;
+
+            // The tile animates from the following column, where its old column now is.
+            offset.x += self.column_x(target_col_idx + 1) - self.column_x(target_col_idx);
+        }
+
+        let new_col = &mut self.columns[target_col_idx];
+        offset += prev_off - new_col.tile_offset(0);
+        new_col.tiles[0].animate_move_from(offset);
+    }
+
     pub fn swap_window_in_direction(&mut self, direction: ScrollDirection) {
         if self.columns.is_empty() {
             return;
@@ -2573,6 +2729,28 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         self.center_column();
     }
 
+    pub fn center_window_vertically(&mut self, window: Option<&W::Id>) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let (col, tile_idx) = if let Some(window) = window {
+            self.columns
+                .iter_mut()
+                .find_map(|col| {
+                    col.tiles
+                        .iter()
+                        .position(|tile| tile.has_window(window))
+                        .map(|tile_idx| (col, Some(tile_idx)))
+                })
+                .unwrap()
+        } else {
+            (&mut self.columns[self.active_column_idx], None)
+        };
+
+        col.center_tile_vertically(tile_idx);
+    }
+
     pub fn center_visible_columns(&mut self) {
         if self.columns.is_empty() {
             return;
@@ -2631,6 +2809,116 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         self.animate_view_offset_to_column(None, self.active_column_idx, None);
     }
 
+    pub fn distribute_columns_evenly(&mut self) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        // Consider the end of an ongoing animation because that's what compute to fit does too.
+        let view_x = self.target_view_pos();
+        let working_x = self.working_area.loc.x;
+        let working_w = self.working_area.size.w;
+
+        // Find all columns that are fully visible inside the working area.
+        let mut first = None;
+        let mut last = 0;
+        let mut active_col_visible = false;
+
+        let gap = self.options.layout.gaps;
+        let col_xs = self.column_xs(self.data.iter().copied());
+        for (idx, col_x) in col_xs.take(self.columns.len()).enumerate() {
+            if col_x < view_x + working_x + gap {
+                // Column goes off-screen to the left.
+                continue;
+            }
+
+            let width = self.data[idx].width;
+            if view_x + working_x + working_w < col_x + width + gap {
+                // Column goes off-screen to the right. We can stop here.
+                break;
+            }
+
+            if idx == self.active_column_idx {
+                active_col_visible = true;
+            }
+
+            first.get_or_insert(idx);
+            last = idx;
+        }
+
+        let Some(first) = first else {
+            return;
+        };
+        if !active_col_visible {
+            // The active column wasn't fully on screen, so we can't meaningfully do anything.
+            return;
+        }
+
+        let range = first..=last;
+        if self.columns[range.clone()]
+            .iter()
+            .any(|col| !col.pending_sizing_mode().is_normal())
+        {
+            return;
+        }
+
+        // Split the working area evenly, letting columns that can't shrink or grow enough keep
+        // their min or max width, and splitting the remaining space among the other columns.
+        let limits: Vec<_> = self.columns[range.clone()]
+            .iter()
+            .map(Column::width_limits)
+            .collect();
+        let mut widths: Vec<Option<f64>> = vec![None; limits.len()];
+        let total = working_w - gap * (limits.len() + 1) as f64;
+        loop {
+            let left = total - widths.iter().flatten().sum::<f64>();
+            let count = widths.iter().filter(|w| w.is_none()).count();
+            if count == 0 {
+                break;
+            }
+            let share = f64::max(1., left / count as f64);
+
+            let mut changed = false;
+            for (width, (min, max)) in zip(&mut widths, &limits) {
+                if width.is_none() && (share < *min || *max < share) {
+                    *width = Some(share.clamp(*min, *max));
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                for width in &mut widths {
+                    width.get_or_insert(share);
+                }
+                break;
+            }
+        }
+        let widths: Vec<f64> = widths.into_iter().flatten().collect();
+
+        for (col, width) in zip(&mut self.columns[range], &widths) {
+            cancel_resize_for_column(&mut self.interactive_resize, col);
+            col.width = ColumnWidth::Fixed(*width);
+            col.preset_width_idx = None;
+            col.is_full_width = false;
+            col.update_tile_sizes(true);
+        }
+
+        if self.is_centering_focused_column() {
+            return;
+        }
+
+        // Center the resized columns, which takes effect if some of them couldn't grow enough to
+        // fill the working area.
+        let leftover = total - widths.iter().sum::<f64>();
+        let width_before_active = widths[..self.active_column_idx - first]
+            .iter()
+            .map(|w| w + gap)
+            .sum::<f64>();
+        let new_offset = -(working_x + gap + f64::max(0., leftover) / 2. + width_before_active);
+
+        self.animate_view_offset(self.active_column_idx, new_offset);
+    }
+
     pub fn view_pos(&self) -> f64 {
         self.column_x(self.active_column_idx) + self.view_offset.current()
     }
@@ -3004,6 +3292,30 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         cancel_resize_for_column(&mut self.interactive_resize, col);
     }
 
+    pub fn fill_window_height_in_column(&mut self, window: Option<&W::Id>) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let (col, tile_idx) = if let Some(window) = window {
+            self.columns
+                .iter_mut()
+                .find_map(|col| {
+                    col.tiles
+                        .iter()
+                        .position(|tile| tile.has_window(window))
+                        .map(|tile_idx| (col, Some(tile_idx)))
+                })
+                .unwrap()
+        } else {
+            (&mut self.columns[self.active_column_idx], None)
+        };
+
+        col.fill_window_height(tile_idx);
+
+        cancel_resize_for_column(&mut self.interactive_resize, col);
+    }
+
     pub fn toggle_window_width(&mut self, window: Option<&W::Id>, forwards: bool) {
         if self.columns.is_empty() {
             return;
@@ -3149,6 +3461,313 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         self.animate_view_offset_to_column(None, self.active_column_idx, None);
     }
 
+    pub fn toggle_column_expanded_width(&mut self) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let col = &mut self.columns[self.active_column_idx];
+        if let Some(before) = col.width_before_expand.take() {
+            // If the column was resized since expanding, forget the stored width and expand again.
+            if col.width == before.expanded_width
+                && col.is_full_width == before.expanded_is_full_width
+            {
+                col.width = before.width;
+                col.preset_width_idx = before.preset_width_idx;
+                col.is_full_width = before.is_full_width;
+                col.update_tile_sizes(true);
+
+                cancel_resize_for_column(&mut self.interactive_resize, col);
+                self.animate_view_offset_to_column(None, self.active_column_idx, None);
+                return;
+            }
+        }
+
+        let width = col.width;
+        let preset_width_idx = col.preset_width_idx;
+        let is_full_width = col.is_full_width;
+
+        self.expand_column_to_available_width();
+
+        let col = &mut self.columns[self.active_column_idx];
+        if col.width != width || col.is_full_width != is_full_width {
+            col.width_before_expand = Some(WidthBeforeExpand {
+                width,
+                preset_width_idx,
+                is_full_width,
+                expanded_width: col.width,
+                expanded_is_full_width: col.is_full_width,
+            });
+        }
+    }
+
+    pub fn toggle_maximize_column_with_group(&mut self) {
+        if self.columns.is_empty() || self.interactive_resize.is_some() {
+            return;
+        }
+
+        let col_idx = self.active_column_idx;
+        if self.columns[col_idx].group_maximize.is_some() {
+            self.unmaximize_column_group(col_idx);
+        } else {
+            self.maximize_column_group(col_idx);
+        }
+
+        self.animate_view_offset_to_column(None, col_idx, None);
+    }
+
+    /// Merges all tiles of the column into one group and makes the column full-width.
+    fn maximize_column_group(&mut self, col_idx: usize) {
+        let col = &mut self.columns[col_idx];
+        if col.is_pending_fullscreen {
+            return;
+        }
+
+        if col.is_pending_maximized {
+            col.cancel_maximized_for_resize();
+        }
+
+        let anchor_tile_idx = col.active_tile_idx;
+        let anchor_tile = &mut col.tiles[anchor_tile_idx];
+        let anchor = anchor_tile.focused_window().id().clone();
+        let was_grouped = anchor_tile.is_grouped_tile();
+        let display_mode = anchor_tile.group_display_mode();
+
+        if !was_grouped {
+            anchor_tile.group();
+            anchor_tile.start_tab_indicator_open_animation();
+        }
+
+        // The maximized group shows one window at a time.
+        anchor_tile.set_group_display_mode(GroupDisplayMode::Tabbed);
+
+        let tiles = col
+            .tiles
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != anchor_tile_idx)
+            .map(|(_, tile)| MergedTile {
+                windows: tile.windows().map(|w| w.id().clone()).collect(),
+                focused: tile.focused_window().id().clone(),
+            })
+            .collect();
+
+        let saved = GroupMaximize {
+            anchor: anchor.clone(),
+            was_grouped,
+            display_mode,
+            is_full_width: col.is_full_width,
+            tiles,
+            tiles_above: anchor_tile_idx,
+        };
+
+        loop {
+            let col = &self.columns[col_idx];
+            let Some(idx) = (0..col.tiles.len()).find(|idx| *idx != col.active_tile_idx) else {
+                break;
+            };
+
+            let mut removed = self
+                .remove_tile_by_idx(col_idx, idx, Transaction::new(), None)
+                .tile;
+
+            let col = &mut self.columns[col_idx];
+            let group = &mut col.tiles[col.active_tile_idx];
+            for tile in removed.ungroup_all() {
+                group.add_window(tile.take_window());
+            }
+            group.add_window(removed.take_window());
+        }
+
+        let col = &mut self.columns[col_idx];
+        col.tiles[col.active_tile_idx].focus_window(&anchor);
+        col.is_full_width = true;
+        col.group_maximize = Some(saved);
+        col.update_tile_sizes(true);
+
+        self.update_window(&anchor, None);
+    }
+
+    /// Restores the column state saved by [`Self::maximize_column_group`].
+    ///
+    /// Windows that left the group while maximized stay where they are, and windows that were
+    /// added to the group while maximized stay in the group.
+    fn unmaximize_column_group(&mut self, col_idx: usize) {
+        let col = &mut self.columns[col_idx];
+        let Some(saved) = col.group_maximize.take() else {
+            return;
+        };
+
+        col.is_full_width = saved.is_full_width;
+
+        // If the anchor window left the column, there's no group to split anymore.
+        let Some(mut group_idx) = col.position(&saved.anchor) else {
+            col.update_tile_sizes(true);
+            return;
+        };
+
+        let focused = col.tiles[group_idx].focused_window().id().clone();
+
+        let mut tiles_below = 0;
+        for (idx, merged) in saved.tiles.into_iter().enumerate() {
+            let mut new_tile: Option<Tile<W>> = None;
+
+            for id in &merged.windows {
+                let group = &mut self.columns[col_idx].tiles[group_idx];
+                if !group.has_window(id) {
+                    continue;
+                }
+
+                let Some(window) = group.ungroup_single(id) else {
+                    continue;
+                };
+
+                match &mut new_tile {
+                    Some(tile) => {
+                        if !tile.is_grouped_tile() {
+                            tile.group();
+                            tile.start_tab_indicator_open_animation();
+                        }
+                        tile.add_window(window);
+                    }
+                    None => {
+                        new_tile = Some(Tile::new(
+                            window,
+                            self.view_size,
+                            self.scale,
+                            self.clock.clone(),
+                            self.options.clone(),
+                        ));
+                    }
+                }
+            }
+
+            let Some(mut tile) = new_tile else {
+                continue;
+            };
+            tile.focus_window(&merged.focused);
+
+            if idx < saved.tiles_above {
+                self.add_tile_to_column(col_idx, Some(group_idx), tile, false);
+                group_idx += 1;
+            } else {
+                tiles_below += 1;
+                self.add_tile_to_column(col_idx, Some(group_idx + tiles_below), tile, false);
+            }
+        }
+
+        let col = &mut self.columns[col_idx];
+        let group = &mut col.tiles[group_idx];
+        if !saved.was_grouped && group.windows().count() == 1 {
+            group.ungroup_single(&saved.anchor);
+        }
+        group.set_group_display_mode(saved.display_mode);
+
+        col.update_tile_sizes(true);
+
+        // Keep focus on the window that was focused in the group, wherever it ended up.
+        self.activate_window(&focused);
+        self.update_window(&focused, None);
+    }
+
+    pub fn set_group_display_mode(&mut self, mode: GroupDisplayMode) {
+        if self.columns.is_empty() || self.interactive_resize.is_some() {
+            return;
+        }
+
+        let col = &mut self.columns[self.active_column_idx];
+        let tile = &mut col.tiles[col.active_tile_idx];
+        if !tile.is_grouped_tile() || tile.group_display_mode() == mode {
+            return;
+        }
+
+        tile.set_group_display_mode(mode);
+        if mode == GroupDisplayMode::Tabbed {
+            tile.start_tab_indicator_open_animation();
+        }
+
+        let to_update = tile.focused_window().id().clone();
+        col.update_tile_sizes(true);
+        self.update_window(&to_update, None);
+    }
+
+    pub fn toggle_group_display_mode(&mut self) {
+        let Some(tile) = self.active_tile_mut() else {
+            return;
+        };
+
+        let mode = match tile.group_display_mode() {
+            GroupDisplayMode::Tabbed => GroupDisplayMode::Stacked,
+            GroupDisplayMode::Stacked => GroupDisplayMode::Tabbed,
+        };
+        self.set_group_display_mode(mode);
+    }
+
+    pub fn align_column_width_to_neighbor(&mut self, right: bool) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let active_idx = self.active_column_idx;
+        let neighbor_idx = if right {
+            active_idx + 1
+        } else {
+            let Some(idx) = active_idx.checked_sub(1) else {
+                return;
+            };
+            idx
+        };
+        if neighbor_idx >= self.columns.len() {
+            return;
+        }
+
+        let col = &self.columns[active_idx];
+        if !col.pending_sizing_mode().is_normal() || col.is_full_width {
+            return;
+        }
+
+        // The active column is always centered in this mode, so its edges can't stay in place.
+        if self.is_centering_focused_column() {
+            return;
+        }
+
+        // Consider the end of an ongoing animation, same as expand_column_to_available_width().
+        let view_x = self.target_view_pos();
+        let working_x = self.working_area.loc.x;
+        let working_w = self.working_area.size.w;
+        let gap = self.options.layout.gaps;
+
+        let active_x = self.column_x(active_idx);
+        let active_w = self.data[active_idx].width;
+
+        let (new_width, new_view_x) = if right {
+            // Keep the left edge in place and stop the right neighbor at the working area edge.
+            let neighbor_w = self.data[neighbor_idx].width;
+            let right_edge = view_x + working_x + working_w - gap - neighbor_w - gap;
+            (right_edge - active_x, view_x)
+        } else {
+            // Keep the right edge in place and bring the left neighbor to the working area edge.
+            let new_view_x = self.column_x(neighbor_idx) - gap - working_x;
+            (active_w - view_x + new_view_x, new_view_x)
+        };
+
+        if new_width < 1. || (new_width - active_w).abs() < 1. / self.scale {
+            return;
+        }
+
+        let col = &mut self.columns[active_idx];
+        cancel_resize_for_column(&mut self.interactive_resize, col);
+
+        col.width = ColumnWidth::Fixed(new_width);
+        col.preset_width_idx = None;
+        col.is_full_width = false;
+        col.update_tile_sizes(true);
+
+        self.animate_view_offset(active_idx, new_view_x - active_x);
+        // Just in case.
+        self.animate_view_offset_to_column(None, active_idx, None);
+    }
+
     pub fn set_fullscreen(&mut self, window: &W::Id, is_fullscreen: bool) -> bool {
         let mut col_idx = self
             .columns
@@ -3209,6 +3828,87 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         true
     }
 
+    /// Returns where the window is in its column, if maximizing it would extract it from there.
+    pub fn pre_maximize_geometry(&self, window: &W::Id) -> Option<PreMaximizeGeometry<W::Id>> {
+        let col = self.columns.iter().find(|col| col.contains(window))?;
+        if col.tiles.len() < 2 || col.is_pending_maximized || col.is_pending_fullscreen {
+            return None;
+        }
+
+        let tile_idx = col.position(window).unwrap();
+        let neighbor_idx = if tile_idx == 0 { 1 } else { tile_idx - 1 };
+        Some(PreMaximizeGeometry {
+            neighbor: col.tiles[neighbor_idx].focused_window().id().clone(),
+            tile_idx,
+            height: col.data[tile_idx].height,
+        })
+    }
+
+    /// Puts an unmaximized window back into the column it was maximized from.
+    ///
+    /// Does nothing if the original column is gone.
+    pub fn restore_pre_maximize_geometry(
+        &mut self,
+        window: &W::Id,
+        geometry: PreMaximizeGeometry<W::Id>,
+    ) {
+        let Some(source_col_idx) = self.columns.iter().position(|col| col.contains(window)) else {
+            return;
+        };
+
+        let source_column = &self.columns[source_col_idx];
+        if source_column.tiles.len() != 1
+            || source_column.is_pending_maximized
+            || source_column.is_pending_fullscreen
+        {
+            return;
+        }
+
+        let Some(target) = self
+            .columns
+            .iter()
+            .find(|col| col.contains(&geometry.neighbor))
+        else {
+            return;
+        };
+        if target.is_pending_maximized || target.is_pending_fullscreen {
+            return;
+        }
+
+        let prev_pos = Point::from((self.column_x(source_col_idx), 0.))
+            + self.columns[source_col_idx].render_offset()
+            + self.columns[source_col_idx].tile_offset(0);
+        let source_tile_was_active = self.active_column_idx == source_col_idx;
+
+        let RemovedTile { tile, .. } = self.remove_tile_by_idx(
+            source_col_idx,
+            0,
+            Transaction::new(),
+            Some(self.options.animations.window_movement.0),
+        );
+
+        let target_col_idx = self
+            .columns
+            .iter()
+            .position(|col| col.contains(&geometry.neighbor))
+            .unwrap();
+        let tile_idx = min(geometry.tile_idx, self.columns[target_col_idx].tiles.len());
+        self.add_tile_to_column(target_col_idx, Some(tile_idx), tile, source_tile_was_active);
+
+        let target_column = &mut self.columns[target_col_idx];
+        if !matches!(geometry.height, WindowHeight::Auto { .. }) {
+            // Only one window in the column can be non-auto-height.
+            target_column.convert_heights_to_auto();
+        }
+        target_column.data[tile_idx].height = geometry.height;
+        target_column.update_tile_sizes(true);
+
+        let new_pos = Point::from((self.column_x(target_col_idx), 0.))
+            + self.columns[target_col_idx].render_offset()
+            + self.columns[target_col_idx].tile_offset(tile_idx);
+        self.columns[target_col_idx].tiles[tile_idx].animate_move_from(prev_pos - new_pos);
+    }
+
     pub fn render_above_top_layer(&self) -> bool {
         // Render above the top layer if we're on a fullscreen window and the view is stationary.
         if self.columns.is_empty() {
@@ -3247,6 +3947,62 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         None
     }
 
+    /// Returns the index of the column under the position.
+    ///
+    /// The gaps are split between the columns on either side, so the position is always matched
+    /// to the nearest column, unless it's outside of all columns.
+    pub fn column_under(&self, pos: Point<f64, Logical>) -> Option<usize> {
+        if pos.y < 0. || self.view_size.h <= pos.y {
+            return None;
+        }
+
+        let gap = self.options.layout.gaps;
+        let view_pos = self.view_pos();
+        self.column_xs(self.data.iter().copied())
+            .zip(&self.data)
+            .position(|(col_x, data)| {
+                let x = col_x - view_pos;
+                x - gap / 2. <= pos.x && pos.x < x + data.width + gap / 2.
+            })
+    }
+
+    /// Returns the rubber band and view offset bounds to apply to view gestures, if overscroll is
+    /// enabled.
+    fn view_offset_overscroll(&self) -> Option<(RubberBand, f64, f64)> {
+        let config = &self.options.layout.overscroll;
+        if !config.on || self.columns.is_empty() {
+            return None;
+        }
+
+        let gaps = self.options.layout.gaps;
+        let area = self.working_area;
+
+        // Let the view move freely until the first or last column is either aligned with the
+        // working area edge or centered, whichever is further, so that all snapping points remain
+        // reachable without resistance.
+        let first_w = self.data[0].width;
+        let leftmost = f64::min(-gaps, -(area.size.w - first_w) / 2.) - area.loc.x;
+
+        let last_idx = self.columns.len() - 1;
+        let last_x = self.column_x(last_idx);
+        let last_w = self.data[last_idx].width;
+        let rightmost = f64::max(
+            last_x + last_w + gaps - area.size.w,
+            last_x - (area.size.w - last_w) / 2.,
+        ) - area.loc.x;
+
+        let active_x = self.column_x(self.active_column_idx);
+        let min = f64::min(leftmost, rightmost) - active_x;
+        let max = f64::max(leftmost, rightmost) - active_x;
+
+        let rubber_band = RubberBand {
+            stiffness: 1. - config.resistance,
+            limit: area.size.w * VIEW_GESTURE_OVERSCROLL_LIMIT,
+        };
+
+        Some((rubber_band, min, max))
+    }
+
     pub fn view_offset_gesture_begin(&mut self, is_touchpad: bool) {
         if self.columns.is_empty() {
             return;
@@ -3300,6 +4056,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         timestamp: Duration,
         is_touchpad: bool,
     ) -> Option<bool> {
+        let overscroll = self.view_offset_overscroll();
+
         let ViewOffset::Gesture(gesture) = &mut self.view_offset else {
             return None;
         };
@@ -3316,7 +4074,10 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             1.
         };
         let pos = gesture.tracker.pos() * norm_factor;
-        let view_offset = pos + gesture.delta_from_tracker;
+        let mut view_offset = pos + gesture.delta_from_tracker;
+        if let Some((rubber_band, min, max)) = overscroll {
+            view_offset = rubber_band.clamp(min, max, view_offset);
+        }
         gesture.current_view_offset = view_offset;
 
         Some(true)
@@ -3397,6 +4158,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
     }
 
     pub fn view_offset_gesture_end(&mut self, is_touchpad: Option<bool>) -> bool {
+        let overscroll = self.view_offset_overscroll();
+
         let ViewOffset::Gesture(gesture) = &mut self.view_offset else {
             return false;
         };
@@ -3419,9 +4182,16 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         } else {
             1.
         };
-        let velocity = gesture.tracker.velocity() * norm_factor;
+        let mut velocity = gesture.tracker.velocity() * norm_factor;
         let pos = gesture.tracker.pos() * norm_factor;
-        let current_view_offset = pos + gesture.delta_from_tracker;
+        let mut current_view_offset = pos + gesture.delta_from_tracker;
+        // DnD scrolling does its own clamping.
+        if let Some((rubber_band, min, max)) =
+            overscroll.filter(|_| gesture.dnd_last_event_time.is_none())
+        {
+            velocity *= rubber_band.clamp_derivative(min, max, current_view_offset);
+            current_view_offset = rubber_band.clamp(min, max, current_view_offset);
+        }
 
         if self.columns.is_empty() {
             self.view_offset = ViewOffset::Static(current_view_offset);
@@ -3873,7 +4643,37 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         self.interactive_resize = None;
     }
 
+    /// Updates the width of the columns taking up the width left over by the other columns.
+    fn update_rest_widths(&mut self) {
+        let rest_count = self
+            .columns
+            .iter()
+            .filter(|col| col.is_rest_width())
+            .count();
+        if rest_count == 0 {
+            return;
+        }
+
+        let gaps = self.options.layout.gaps;
+        let taken = zip(&self.columns, &self.data)
+            .filter(|(col, _)| !col.is_rest_width())
+            .map(|(_, data)| data.width + gaps)
+            .sum::<f64>();
+        let rest_count_f = rest_count as f64;
+        let left = self.working_area.size.w - gaps - taken - gaps * rest_count_f;
+        let width = f64::max(1., left / rest_count_f);
+
+        for col in &mut self.columns {
+            if col.is_rest_width() && col.rest_width != width {
+                col.rest_width = width;
+                col.update_tile_sizes(true);
+            }
+        }
+    }
+
     pub fn refresh(&mut self, is_active: bool, is_focused: bool) {
+        self.update_rest_widths();
+
         for (col_idx, col) in self.columns.iter_mut().enumerate() {
             let mut col_resize_data = None;
             if let Some(resize) = &self.interactive_resize
@@ -4197,6 +4997,10 @@ impl<W: LayoutElement> Column<W> {
             width,
             preset_width_idx,
             is_full_width,
+            rest_width: working_area.size.w - options.layout.gaps * 2.,
+            width_before_expand: None,
+            group_maximize: None,
+            vertical_offset: 0.,
             is_pending_maximized: false,
             is_pending_fullscreen: false,
             move_animation: None,
@@ -4474,6 +5278,7 @@ impl<W: LayoutElement> Column<W> {
 
         self.is_pending_fullscreen = false;
         self.is_pending_maximized = false;
+        self.vertical_offset = 0.;
 
         self.data
             .insert(idx, TileData::new(&tile, WindowHeight::auto_1()));
@@ -4568,9 +5373,45 @@ impl<W: LayoutElement> Column<W> {
                 (working_size.w - gaps).mul_add(proportion, -gaps)
             }
             ColumnWidth::Fixed(width) => width,
+            ColumnWidth::Rest => self.rest_width,
         }
     }
 
+    /// Returns the min and max tile width that the windows in this column allow.
+    fn width_limits(&self) -> (f64, f64) {
+        let min_width = self
+            .tiles
+            .iter()
+            .map(|tile| NotNan::new(tile.min_size_nonfullscreen().w.max(1.)).unwrap())
+            .max()
+            .map(NotNan::into_inner)
+            .unwrap();
+        let max_width = self
+            .tiles
+            .iter()
+            .filter_map(|tile| {
+                let w = tile.max_size_nonfullscreen().w;
+                if w == 0. {
+                    None
+                } else {
+                    Some(NotNan::new(w).unwrap())
+                }
+            })
+            .min()
+            .map(NotNan::into_inner)
+            .unwrap_or(f64::from(i32::MAX));
+        let max_width = f64::max(max_width, min_width);
+
+        (min_width, max_width)
+    }
+
+    /// Returns whether this column currently takes up the width left over by the other columns.
+    fn is_rest_width(&self) -> bool {
+        self.width == ColumnWidth::Rest
+            && !self.is_full_width
+            && self.pending_sizing_mode() == SizingMode::Normal
+    }
+
     fn update_tile_sizes(&mut self, animate: bool) {
         self.update_tile_sizes_with_transaction(animate, Transaction::new());
     }
@@ -4607,26 +5448,7 @@ impl<W: LayoutElement> Column<W> {
             .collect();
 
         // Compute the column width.
-        let min_width = min_size
-            .iter()
-            .map(|size| NotNan::new(size.w).unwrap())
-            .max()
-            .map(NotNan::into_inner)
-            .unwrap();
-        let max_width = max_size
-            .iter()
-            .filter_map(|size| {
-                let w = size.w;
-                if w == 0. {
-                    None
-                } else {
-                    Some(NotNan::new(w).unwrap())
-                }
-            })
-            .min()
-            .map(NotNan::into_inner)
-            .unwrap_or(f64::from(i32::MAX));
-        let max_width = f64::max(max_width, min_width);
+        let (min_width, max_width) = self.width_limits();
 
         let width = if self.is_full_width {
             ColumnWidth::Proportion(1.)
@@ -4950,7 +5772,7 @@ impl<W: LayoutElement> Column<W> {
     fn toggle_full_width(&mut self) {
         if self.is_pending_maximized {
             // Treat it as unmaximize.
-            self.is_pending_maximized = false;
+            self.cancel_maximized_for_resize();
             self.is_full_width = false;
         } else {
             self.is_full_width = !self.is_full_width;
@@ -4987,6 +5809,12 @@ impl<W: LayoutElement> Column<W> {
             (_, SizeChange::SetProportion(proportion)) => {
                 ColumnWidth::Proportion((proportion / 100.).clamp(0., MAX_F))
             }
+            (_, SizeChange::SetOutputProportion(proportion)) => {
+                let width = self.view_size.w * (proportion / 100.).clamp(0., MAX_F);
+                let width = round_logical_in_physical(self.scale, width);
+                ColumnWidth::Fixed(width.clamp(1., MAX_PX))
+            }
+            (_, SizeChange::SetRest) => ColumnWidth::Rest,
             (_, SizeChange::AdjustFixed(delta)) => {
                 let width = (current_px + f64::from(delta)).clamp(1., MAX_PX);
                 ColumnWidth::Fixed(width)
@@ -4995,7 +5823,7 @@ impl<W: LayoutElement> Column<W> {
                 let proportion = (current + delta / 100.).clamp(0., MAX_F);
                 ColumnWidth::Proportion(proportion)
             }
-            (ColumnWidth::Fixed(_), SizeChange::AdjustProportion(delta)) => {
+            (ColumnWidth::Fixed(_) | ColumnWidth::Rest, SizeChange::AdjustProportion(delta)) => {
                 let full = self.working_area.size.w - self.options.layout.gaps;
                 let current = if full == 0. {
                     1.
@@ -5010,13 +5838,21 @@ impl<W: LayoutElement> Column<W> {
         self.width = width;
         self.preset_width_idx = None;
         self.is_full_width = false;
-        self.is_pending_maximized = false;
+        self.cancel_maximized_for_resize();
         self.update_tile_sizes(animate);
     }
 
     fn set_window_height(&mut self, change: SizeChange, tile_idx: Option<usize>, animate: bool) {
         let tile_idx = tile_idx.unwrap_or(self.active_tile_idx);
 
+        // Automatic height is what fills the height left over by the other windows.
+        if change == SizeChange::SetRest {
+            self.data[tile_idx].height = WindowHeight::auto_1();
+            self.cancel_maximized_for_resize();
+            self.update_tile_sizes(animate);
+            return;
+        }
+
         // Start by converting all heights to automatic, since only one window in the column can be
         // non-auto-height. If the current tile is already non-auto, however, we can skip that
         // step. Which is not only for optimization, but also preserves automatic weights in case
@@ -5052,12 +5888,18 @@ impl<W: LayoutElement> Column<W> {
                 let tile_height = (working_size - gaps).mul_add(proportion / 100., -gaps);
                 tile.window_height_for_tile_height(tile_height)
             }
+            SizeChange::SetOutputProportion(proportion) => {
+                let tile_height = self.view_size.h * (proportion / 100.);
+                let tile_height = round_logical_in_physical(self.scale, tile_height);
+                tile.window_height_for_tile_height(tile_height)
+            }
             SizeChange::AdjustFixed(delta) => current_window_px + f64::from(delta),
             SizeChange::AdjustProportion(delta) => {
                 let proportion = current_prop + delta / 100.;
                 let tile_height = (working_size - gaps).mul_add(proportion, -gaps);
                 tile.window_height_for_tile_height(tile_height)
             }
+            SizeChange::SetRest => unreachable!(),
         };
 
         // Clamp the height according to other windows' min sizes, or simply to working area height.
@@ -5085,7 +5927,7 @@ impl<W: LayoutElement> Column<W> {
         }
 
         self.data[tile_idx].height = WindowHeight::Fixed(window_height.clamp(1., MAX_PX));
-        self.is_pending_maximized = false;
+        self.cancel_maximized_for_resize();
         self.update_tile_sizes(animate);
     }
 
@@ -5096,6 +5938,47 @@ impl<W: LayoutElement> Column<W> {
         self.update_tile_sizes(true);
     }
 
+    /// Makes the window take the height left over by the other windows in the column.
+    ///
+    /// The other windows keep their current heights. With a single other window, this window
+    /// keeps absorbing any changes to the leftover height until some height in the column is
+    /// changed manually. With more windows, only one of them can have a fixed height, so this
+    /// window gets the leftover height as its fixed height, and the others share later changes.
+    fn fill_window_height(&mut self, tile_idx: Option<usize>) {
+        if self.tiles.len() < 2 {
+            return;
+        }
+
+        let tile_idx = tile_idx.unwrap_or(self.active_tile_idx);
+
+        // Preserve the current heights, making this window automatic.
+        self.convert_heights_to_auto();
+
+        if self.tiles.len() == 2 {
+            // Pin the sibling to its current height so that this window alone fills the rest.
+            let sibling_idx = 1 - tile_idx;
+            let height = self.tiles[sibling_idx].window_size().h;
+            self.data[sibling_idx].height = WindowHeight::Fixed(height);
+        } else {
+            let gaps = self.options.layout.gaps * (self.tiles.len() + 1) as f64;
+            let siblings_height: f64 = self
+                .tiles
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| *idx != tile_idx)
+                .map(|(_, tile)| tile.tile_size().h)
+                .sum();
+            let height_left = self.working_area.size.h - gaps - siblings_height;
+
+            let tile = &self.tiles[tile_idx];
+            let height = tile.window_height_for_tile_height(height_left).round();
+            self.data[tile_idx].height = WindowHeight::Fixed(height.max(1.));
+        }
+
+        self.cancel_maximized_for_resize();
+        self.update_tile_sizes(true);
+    }
+
     fn toggle_window_height(&mut self, tile_idx: Option<usize>, forwards: bool) {
         let tile_idx = tile_idx.unwrap_or(self.active_tile_idx);
 
@@ -5147,7 +6030,7 @@ impl<W: LayoutElement> Column<W> {
             }
         };
         self.data[tile_idx].height = WindowHeight::Preset(preset_idx);
-        self.is_pending_maximized = false;
+        self.cancel_maximized_for_resize();
         self.update_tile_sizes(true);
     }
 
@@ -5182,6 +6065,13 @@ impl<W: LayoutElement> Column<W> {
             assert!(self.tiles.len() == 1);
         }
 
+        if !is_fullscreen {
+            // Leaving fullscreen forgets where the window was before maximizing.
+            for tile in &mut self.tiles {
+                tile.pre_maximize = None;
+            }
+        }
+
         self.is_pending_fullscreen = is_fullscreen;
         self.update_tile_sizes(true);
     }
@@ -5193,12 +6083,29 @@ impl<W: LayoutElement> Column<W> {
 
         if maximize {
             assert!(self.tiles.len() == 1);
+        } else {
+            for tile in &mut self.tiles {
+                tile.pre_maximize = None;
+            }
         }
 
         self.is_pending_maximized = maximize;
         self.update_tile_sizes(true);
     }
 
+    /// Stops being maximized because the window got resized.
+    ///
+    /// The window no longer has the maximized size, so its pre-maximize position is forgotten.
+    fn cancel_maximized_for_resize(&mut self) {
+        if self.is_pending_maximized {
+            for tile in &mut self.tiles {
+                tile.pre_maximize = None;
+            }
+        }
+
+        self.is_pending_maximized = false;
+    }
+
     fn tiles_origin(&self) -> Point<f64, Logical> {
         let mut origin = Point::from((0., 0.));
 
@@ -5212,10 +6119,51 @@ impl<W: LayoutElement> Column<W> {
         }
 
         origin.y += self.working_area.loc.y + self.options.layout.gaps;
+        origin.y += self.vertical_offset.clamp(0., self.free_height());
 
         origin
     }
 
+    /// Returns the height in the column not taken up by tiles and gaps.
+    fn free_height(&self) -> f64 {
+        let gaps = self.options.layout.gaps;
+        let tiles_height = self.data.iter().map(|data| data.size.h + gaps).sum::<f64>();
+        f64::max(0., self.working_area.size.h - gaps - tiles_height)
+    }
+
+    fn center_tile_vertically(&mut self, tile_idx: Option<usize>) {
+        let tile_idx = tile_idx.unwrap_or(self.active_tile_idx);
+
+        if !self.sizing_mode().is_normal() {
+            return;
+        }
+
+        // Nothing to do if the tiles already fill the column.
+        let free_height = self.free_height();
+        if free_height == 0. {
+            return;
+        }
+
+        let gaps = self.options.layout.gaps;
+        let above = self.data[..tile_idx]
+            .iter()
+            .map(|data| data.size.h + gaps)
+            .sum::<f64>();
+        let available = gaps.mul_add(-2., self.working_area.size.h);
+        let target = (available - self.data[tile_idx].size.h) / 2. - above;
+
+        let prev_offset = self.vertical_offset.clamp(0., free_height);
+        let new_offset = target.clamp(0., free_height);
+        self.vertical_offset = new_offset;
+
+        let delta = prev_offset - new_offset;
+        if delta != 0. {
+            for tile in &mut self.tiles {
+                tile.animate_move_y_from(delta);
+            }
+        }
+    }
+
     // HACK: pass a self.data iterator in manually as a workaround for the lack of method partial
     // borrowing. Note that this method's return value does not borrow the entire &Self!
     fn tile_offsets_iter<D: Iterator<Item = TileData>>(
@@ -5612,7 +6560,6 @@ mod tests {
     use niri_config::FloatOrInt;
 
     use super::*;
-    use crate::utils::round_logical_in_physical;
 
     #[test]
     fn working_area_starts_at_physical_pixel() {
@@ -5,7 +5,7 @@ use std::mem;
 
 use anyhow::ensure;
 use itertools::izip;
-use niri_config::{CornerRadius, Gradient, GradientRelativeTo, TabIndicatorPosition};
+use niri_config::{CornerRadius, Gradient, GradientRelativeTo, Shadow, TabIndicatorPosition};
 use pango::FontDescription;
 use pango::glib::property::PropertySet;
 use pangocairo::cairo::{self, ImageSurface};
@@ -66,9 +66,12 @@ struct TitleTexture {
     max_size: Size<f64, Logical>,
     // cached result of the rendered title texture
     texture: RefCell<Option<TextureBuffer<GlesTexture>>>,
+    // cached shadow rendered beneath the title texture, re-rendered together with the texture
+    shadow_texture: RefCell<Option<TextureBuffer<GlesTexture>>>,
     // the maximum size wanted by the title texture if it had infinite space
     wanted_size: RefCell<Option<Size<i32, Physical>>>,
     font_size: u32,
+    shadow: Shadow,
 }
 
 impl TabIndicator {
@@ -218,6 +221,7 @@ impl TabIndicator {
                         scale,
                         Size::new((rect.size.w - 20.).max(0.), 24.),
                         self.config.title_font_size,
+                        self.config.label_shadow,
                     )
                 })
                 .collect();
@@ -233,6 +237,7 @@ impl TabIndicator {
                     Some(scale),
                     Some(Size::new((rect.size.w - MIN_DIST_TO_EDGES).max(0.), 16384.)),
                     Some(self.config.title_font_size),
+                    Some(self.config.label_shadow),
                 );
             });
         }
@@ -467,14 +472,22 @@ impl TabInfo {
 }
 
 impl TitleTexture {
-    fn new(title: String, scale: f64, max_size: Size<f64, Logical>, font_size: u32) -> Self {
+    fn new(
+        title: String,
+        scale: f64,
+        max_size: Size<f64, Logical>,
+        font_size: u32,
+        shadow: Shadow,
+    ) -> Self {
         Self {
             title,
             scale,
             texture: Default::default(),
+            shadow_texture: Default::default(),
             max_size,
             wanted_size: Default::default(),
             font_size,
+            shadow,
         }
     }
 
@@ -484,7 +497,15 @@ impl TitleTexture {
         new_scale: Option<f64>,
         new_max_size: Option<Size<f64, Logical>>,
         new_font_size: Option<u32>,
+        new_shadow: Option<Shadow>,
     ) {
+        if let Some(new_shadow) = new_shadow {
+            if new_shadow != self.shadow {
+                self.texture.set(None);
+                self.wanted_size.set(None);
+            }
+            self.shadow = new_shadow;
+        }
         if let Some(new_font_size) = new_font_size {
             if new_font_size != self.font_size {
                 self.texture.set(None);
@@ -539,7 +560,14 @@ impl TitleTexture {
         }
     }
 
-    fn get(&self, renderer: &mut GlesRenderer) -> anyhow::Result<TextureBuffer<GlesTexture>> {
+    /// Returns the title texture along with its shadow texture, if the shadow is enabled.
+    fn get(
+        &self,
+        renderer: &mut GlesRenderer,
+    ) -> anyhow::Result<(
+        TextureBuffer<GlesTexture>,
+        Option<TextureBuffer<GlesTexture>>,
+    )> {
         let mut tex = self.texture.borrow_mut();
 
         if self.title.is_empty() {
@@ -549,30 +577,39 @@ impl TitleTexture {
         }
 
         match &*tex {
-            Some(texture) => Ok(texture.clone()),
+            Some(texture) => Ok((texture.clone(), self.shadow_texture.borrow().clone())),
             None => {
-                let (new_tex, wanted_size) = render_title_texture(
+                let (new_tex, new_shadow_tex, wanted_size) = render_title_texture(
                     renderer,
                     &self.title,
                     self.scale,
                     self.max_size,
                     self.font_size,
+                    &self.shadow,
                 )?;
                 *tex = Some(new_tex.clone());
+                self.shadow_texture.set(new_shadow_tex.clone());
                 self.wanted_size.set(Some(wanted_size));
-                Ok(new_tex)
+                Ok((new_tex, new_shadow_tex))
             }
         }
     }
 }
 
+type TitleTextures = (
+    TextureBuffer<GlesTexture>,
+    Option<TextureBuffer<GlesTexture>>,
+    Size<i32, Physical>,
+);
+
 fn render_title_texture(
     renderer: &mut GlesRenderer,
     title: &str,
     scale: f64,
     max_size: Size<f64, Logical>,
     font_size: u32,
-) -> anyhow::Result<(TextureBuffer<GlesTexture>, Size<i32, Physical>)> {
+    shadow: &Shadow,
+) -> anyhow::Result<TitleTextures> {
     let _span = tracy_client::span!("tab_indicator::render_title_texture");
 
     // TODO: expose in config
@@ -617,7 +654,139 @@ fn render_title_texture(
         Vec::new(),
     )?;
 
-    Ok((buffer, wanted_size))
+    let shadow_buffer = if shadow.on {
+        Some(render_title_shadow_texture(
+            renderer,
+            &layout,
+            scale,
+            Size::new(width, height),
+            shadow,
+        )?)
+    } else {
+        None
+    };
+
+    Ok((buffer, shadow_buffer, wanted_size))
+}
+
+/// Number of box blur passes, three passes give a close approximation of a gaussian blur.
+const SHADOW_BLUR_PASSES: usize = 3;
+
+/// Physical blur radius, spread and padding around the title for the shadow texture.
+fn title_shadow_extents(scale: f64, shadow: &Shadow) -> (usize, i32, i32) {
+    let blur_radius = (shadow.softness / 2. * scale).round().max(0.) as usize;
+    let spread = (shadow.spread * scale).round().max(0.) as i32;
+    let padding = (blur_radius * SHADOW_BLUR_PASSES) as i32 + spread;
+    (blur_radius, spread, padding)
+}
+
+fn render_title_shadow_texture(
+    renderer: &mut GlesRenderer,
+    layout: &pango::Layout,
+    scale: f64,
+    size: Size<i32, Physical>,
+    shadow: &Shadow,
+) -> anyhow::Result<TextureBuffer<GlesTexture>> {
+    let _span = tracy_client::span!("tab_indicator::render_title_shadow_texture");
+
+    let (blur_radius, spread, padding) = title_shadow_extents(scale, shadow);
+    let width = size.w + padding * 2;
+    let height = size.h + padding * 2;
+
+    let mut surface = ImageSurface::create(cairo::Format::ARgb32, width, height)?;
+    let cr = cairo::Context::new(&surface)?;
+
+    // Clip to the title size so that the shadow matches a cut off title.
+    let clip_loc = f64::from(padding - spread);
+    cr.rectangle(
+        clip_loc,
+        clip_loc,
+        f64::from(size.w + spread * 2),
+        f64::from(size.h + spread * 2),
+    );
+    cr.clip();
+
+    let [r, g, b, a] = shadow.color.to_array_unpremul();
+    cr.set_source_rgba(r.into(), g.into(), b.into(), a.into());
+    cr.move_to(f64::from(padding), f64::from(padding));
+    pangocairo::functions::layout_path(&cr, layout);
+    if spread > 0 {
+        cr.set_line_width(f64::from(spread * 2));
+        cr.set_line_join(cairo::LineJoin::Round);
+        cr.stroke_preserve()?;
+    }
+    cr.fill()?;
+
+    drop(cr);
+
+    surface.flush();
+    {
+        let mut data = surface.data()?;
+        for _ in 0..SHADOW_BLUR_PASSES {
+            box_blur(&mut data, width as usize, height as usize, blur_radius);
+        }
+    }
+
+    let data = surface.take_data().unwrap();
+    let buffer = TextureBuffer::from_memory(
+        renderer,
+        &data,
+        Fourcc::Argb8888,
+        (width, height),
+        false,
+        scale,
+        Transform::Normal,
+        Vec::new(),
+    )?;
+
+    Ok(buffer)
+}
+
+/// Blurs premultiplied ARGB pixel data in place with a separable box blur.
+fn box_blur(data: &mut [u8], width: usize, height: usize, radius: usize) {
+    if radius == 0 || width == 0 || height == 0 {
+        return;
+    }
+
+    let mut tmp = vec![0; data.len()];
+    // Horizontal pass.
+    box_blur_pass(data, &mut tmp, width, height, 4, width * 4, radius);
+    // Vertical pass.
+    box_blur_pass(&tmp, data, height, width, width * 4, 4, radius);
+}
+
+fn box_blur_pass(
+    src: &[u8],
+    dst: &mut [u8],
+    len: usize,
+    lines: usize,
+    step: usize,
+    line_step: usize,
+    radius: usize,
+) {
+    let window = (radius * 2 + 1) as u32;
+
+    for line in 0..lines {
+        let base = line * line_step;
+        for channel in 0..4 {
+            let at = |i: usize| base + i * step + channel;
+
+            // Pixels outside of the buffer are transparent, so they contribute zero.
+            let mut sum: u32 = (0..=radius.min(len - 1))
+                .map(|i| u32::from(src[at(i)]))
+                .sum();
+            for i in 0..len {
+                dst[at(i)] = (sum / window) as u8;
+
+                if i + radius + 1 < len {
+                    sum += u32::from(src[at(i + radius + 1)]);
+                }
+                if i >= radius {
+                    sum -= u32::from(src[at(i - radius)]);
+                }
+            }
+        }
+    }
 }
 
 impl<R> Render<'_, R> for TabIndicator
@@ -638,36 +807,54 @@ where
             return;
         }
 
-        (!self.config.hide_titles).then(|| {
-            zip(&self.title_textures, &self.shader_locs)
-                .filter_map(|(tex, loc)| match tex.get(renderer.as_gles_renderer()) {
-                    Ok(texture) => {
-                        let pos_x = (tex.max_size.w + MIN_DIST_TO_EDGES) / 2.
-                            - texture.logical_size().w / 2.;
-
-                        let pos_y = match self.config.position {
-                            TabIndicatorPosition::Top => -GAP_TO_BAR,
-                            TabIndicatorPosition::Bottom => GAP_TO_BAR - texture.logical_size().h,
-                        };
-
-                        Some(PrimaryGpuTextureRenderElement(
-                            TextureRenderElement::from_texture_buffer(
-                                texture,
-                                pos + *loc + Point::new(pos_x, pos_y),
-                                1.,
-                                None,
-                                None,
-                                Kind::Unspecified,
-                            ),
-                        ))
-                    }
-                    Err(_) => {
-                        // silent fail is ok, we just won't show the title
-                        None
-                    }
-                })
-                .for_each(|e| collector.push_element(e))
-        });
+        if !self.config.hide_titles {
+            for (tex, loc) in zip(&self.title_textures, &self.shader_locs) {
+                let Ok((texture, shadow_texture)) = tex.get(renderer.as_gles_renderer()) else {
+                    // silent fail is ok, we just won't show the title
+                    continue;
+                };
+
+                let pos_x =
+                    (tex.max_size.w + MIN_DIST_TO_EDGES) / 2. - texture.logical_size().w / 2.;
+
+                let pos_y = match self.config.position {
+                    TabIndicatorPosition::Top => -GAP_TO_BAR,
+                    TabIndicatorPosition::Bottom => GAP_TO_BAR - texture.logical_size().h,
+                };
+
+                let title_pos = pos + *loc + Point::new(pos_x, pos_y);
+
+                collector.push_element(PrimaryGpuTextureRenderElement(
+                    TextureRenderElement::from_texture_buffer(
+                        texture,
+                        title_pos,
+                        1.,
+                        None,
+                        None,
+                        Kind::Unspecified,
+                    ),
+                ));
+
+                // Pushed after the title so that it renders beneath it.
+                if let Some(shadow_texture) = shadow_texture {
+                    let shadow = &tex.shadow;
+                    let (_, _, padding) = title_shadow_extents(tex.scale, shadow);
+                    let padding = f64::from(padding) / tex.scale;
+                    let offset = Point::new(shadow.offset.x.0, shadow.offset.y.0);
+
+                    collector.push_element(PrimaryGpuTextureRenderElement(
+                        TextureRenderElement::from_texture_buffer(
+                            shadow_texture,
+                            title_pos + offset - Point::new(padding, padding),
+                            1.,
+                            None,
+                            None,
+                            Kind::Unspecified,
+                        ),
+                    ));
+                }
+            }
+        }
 
         let font_height = self.font_height();
 
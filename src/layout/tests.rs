@@ -3,8 +3,8 @@ use std::cell::{Cell, OnceCell, RefCell};
 use niri_config::utils::{Flag, MergeWith as _};
 use niri_config::workspace::WorkspaceName;
 use niri_config::{
-    CenterFocusedColumn, FloatOrInt, OutputName, Struts, TabIndicatorLength, TabIndicatorPosition,
-    WorkspaceReference,
+    CenterFocusedColumn, FloatOrInt, FocusAfterClose, OnEmptyWorkspace, OutputName, Struts,
+    TabIndicatorLength, TabIndicatorPosition, WorkspaceReference,
 };
 use proptest::prelude::*;
 use proptest_derive::Arbitrary;
@@ -40,6 +40,8 @@ struct TestWindowInner {
     is_pending_windowed_fullscreen: Cell<bool>,
     animate_next_configure: Cell<bool>,
     animation_snapshot: RefCell<Option<LayoutElementRenderSnapshot>>,
+    is_floating: Cell<bool>,
+    focus_timestamp: Cell<Option<Duration>>,
     rules: ResolvedWindowRules,
 }
 
@@ -92,6 +94,8 @@ impl TestWindow {
             is_pending_windowed_fullscreen: Cell::new(false),
             animate_next_configure: Cell::new(false),
             animation_snapshot: RefCell::new(None),
+            is_floating: Cell::new(false),
+            focus_timestamp: Cell::new(None),
             rules: params.rules.unwrap_or_default(),
         }))
     }
@@ -227,10 +231,12 @@ impl LayoutElement for TestWindow {
 
     fn set_active_in_column(&mut self, _active: bool) {}
 
-    fn set_floating(&mut self, _floating: bool) {}
+    fn set_floating(&mut self, floating: bool) {
+        self.0.is_floating.set(floating);
+    }
 
     fn is_floating(&self) -> bool {
-        false
+        self.0.is_floating.get()
     }
 
     fn sizing_mode(&self) -> SizingMode {
@@ -281,6 +287,10 @@ impl LayoutElement for TestWindow {
         false
     }
 
+    fn focus_timestamp(&self) -> Option<Duration> {
+        self.0.focus_timestamp.get()
+    }
+
     fn render_normal<R, C>(
         &self,
         _renderer: &mut R,
@@ -320,10 +330,12 @@ fn arbitrary_size_change() -> impl Strategy<Value = SizeChange> {
     prop_oneof![
         (0..).prop_map(SizeChange::SetFixed),
         (0f64..).prop_map(SizeChange::SetProportion),
+        (0f64..).prop_map(SizeChange::SetOutputProportion),
         any::<i32>().prop_map(SizeChange::AdjustFixed),
         any::<f64>().prop_map(SizeChange::AdjustProportion),
         // Interactive resize can have negative values here.
         Just(SizeChange::SetFixed(-100)),
+        Just(SizeChange::SetRest),
     ]
 }
 
@@ -390,6 +402,22 @@ fn arbitrary_move_direction() -> impl Strategy<Value = WindowMoveDirection> {
     ]
 }
 
+fn arbitrary_direction() -> impl Strategy<Value = Direction> {
+    prop_oneof![
+        Just(Direction::Left),
+        Just(Direction::Right),
+        Just(Direction::Up),
+        Just(Direction::Down),
+    ]
+}
+
+fn arbitrary_group_display_mode() -> impl Strategy<Value = GroupDisplayMode> {
+    prop_oneof![
+        Just(GroupDisplayMode::Tabbed),
+        Just(GroupDisplayMode::Stacked),
+    ]
+}
+
 fn arbitrary_resize_edge() -> impl Strategy<Value = ResizeEdge> {
     prop_oneof![
         Just(ResizeEdge::RIGHT),
@@ -460,6 +488,12 @@ enum Op {
         #[proptest(strategy = "1..=5usize")]
         ws_name: usize,
     },
+    ActivateNamedWorkspaceOnOutput {
+        #[proptest(strategy = "1..=5usize")]
+        ws_name: usize,
+        #[proptest(strategy = "1..=5usize")]
+        output_name: usize,
+    },
     UpdateWorkspaceLayoutConfig {
         #[proptest(strategy = "1..=5usize")]
         ws_name: usize,
@@ -488,6 +522,9 @@ enum Op {
     },
     ToggleWindowedFullscreen(#[proptest(strategy = "1..=5usize")] usize),
     ToggleGroup,
+    ToggleMaximizeColumnWithGroup,
+    SetGroupDisplayMode(#[proptest(strategy = "arbitrary_group_display_mode()")] GroupDisplayMode),
+    ToggleGroupDisplayMode,
     MoveWindowIntoOrOutOfGroup(
         #[proptest(strategy = "arbitrary_move_direction()")] WindowMoveDirection,
     ),
@@ -518,6 +555,7 @@ enum Op {
     FocusWindowBottom,
     FocusWindowDownOrTop,
     FocusWindowUpOrBottom,
+    FocusSpatial(#[proptest(strategy = "arbitrary_direction()")] Direction),
     MoveColumnLeft,
     MoveColumnRight,
     MoveColumnToFirst,
@@ -525,6 +563,7 @@ enum Op {
     MoveColumnLeftOrToMonitorLeft(#[proptest(strategy = "1..=2u8")] u8),
     MoveColumnRightOrToMonitorRight(#[proptest(strategy = "1..=2u8")] u8),
     MoveColumnToIndex(#[proptest(strategy = "1..=5usize")] usize),
+    MoveColumnByOffset(#[proptest(strategy = "-5..=5i32")] i32),
     MoveWindowDown,
     MoveWindowUp,
     MoveWindowDownOrToWorkspaceDown,
@@ -539,13 +578,20 @@ enum Op {
     },
     ConsumeWindowIntoColumn,
     ExpelWindowFromColumn,
+    ExpelWindowToColumnLeft,
+    ExpelWindowToColumnRight,
     SwapWindowInDirection(#[proptest(strategy = "arbitrary_scroll_direction()")] ScrollDirection),
     CenterColumn,
     CenterWindow {
         #[proptest(strategy = "proptest::option::of(1..=5usize)")]
         id: Option<usize>,
     },
+    CenterWindowVertically {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+    },
     CenterVisibleColumns,
+    DistributeColumnsEvenly,
     FocusWorkspaceDown,
     FocusWorkspaceUp,
     FocusWorkspace(#[proptest(strategy = "0..=4usize")] usize),
@@ -559,9 +605,11 @@ enum Op {
         #[proptest(strategy = "0..=4usize")]
         workspace_idx: usize,
     },
+    MoveWindowToWorkspacePrevious(bool),
     MoveColumnToWorkspaceDown(bool),
     MoveColumnToWorkspaceUp(bool),
     MoveColumnToWorkspace(#[proptest(strategy = "0..=4usize")] usize, bool),
+    MoveColumnToWorkspacePrevious(bool),
     MoveWorkspaceDown,
     MoveWorkspaceUp,
     MoveWorkspaceToIndex {
@@ -569,6 +617,7 @@ enum Op {
         ws_name: Option<usize>,
         #[proptest(strategy = "0..=4usize")]
         target_idx: usize,
+        compact: bool,
     },
     MoveWorkspaceToMonitor {
         #[proptest(strategy = "proptest::option::of(1..=5usize)")]
@@ -594,6 +643,8 @@ enum Op {
         #[proptest(strategy = "proptest::option::of(0..=4usize)")]
         target_ws_idx: Option<usize>,
     },
+    ConsumeWindowFromOutput(#[proptest(strategy = "1..=5usize")] usize),
+    SwapColumnWithOutput(#[proptest(strategy = "1..=5usize")] usize),
     MoveColumnToOutput {
         #[proptest(strategy = "1..=5usize")]
         output_id: usize,
@@ -624,6 +675,10 @@ enum Op {
         #[proptest(strategy = "proptest::option::of(1..=5usize)")]
         id: Option<usize>,
     },
+    ToggleMaximizeWindowToEdges {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+    },
     SetColumnWidth(#[proptest(strategy = "arbitrary_size_change()")] SizeChange),
     SetWindowWidth {
         #[proptest(strategy = "proptest::option::of(1..=5usize)")]
@@ -641,7 +696,15 @@ enum Op {
         #[proptest(strategy = "proptest::option::of(1..=5usize)")]
         id: Option<usize>,
     },
+    FillWindowHeightInColumn {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+    },
     ExpandColumnToAvailableWidth,
+    ToggleColumnExpandedWidth,
+    AlignColumnWidthToNeighborLeft,
+    AlignColumnWidthToNeighborRight,
+    ToggleZeroGaps,
     ToggleWindowFloating {
         #[proptest(strategy = "proptest::option::of(1..=5usize)")]
         id: Option<usize>,
@@ -654,6 +717,8 @@ enum Op {
     FocusFloating,
     FocusTiling,
     SwitchFocusFloatingTiling,
+    FocusNextFloatingWindow,
+    FocusPreviousFloatingWindow,
     MoveFloatingWindow {
         #[proptest(strategy = "proptest::option::of(1..=5usize)")]
         id: Option<usize>,
@@ -778,6 +843,7 @@ enum Op {
         window: usize,
     },
     ToggleOverview,
+    ToggleOverviewFocusedMonitor,
     UpdateConfig {
         #[proptest(strategy = "arbitrary_layout_part().prop_map(Box::new)")]
         layout_config: Box<niri_config::LayoutPart>,
@@ -895,6 +961,17 @@ impl Op {
             Op::UnnameWorkspace { ws_name } => {
                 layout.unname_workspace(&format!("ws{ws_name}"));
             }
+            Op::ActivateNamedWorkspaceOnOutput {
+                ws_name,
+                output_name,
+            } => {
+                let name = format!("output{output_name}");
+                let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                    return;
+                };
+
+                layout.activate_named_workspace_on_output(&output, &format!("ws{ws_name}"));
+            }
             Op::UpdateWorkspaceLayoutConfig {
                 ws_name,
                 layout_config,
@@ -1167,6 +1244,7 @@ impl Op {
             Op::FocusWindowBottom => layout.focus_window_bottom(),
             Op::FocusWindowDownOrTop => layout.focus_window_down_or_top(),
             Op::FocusWindowUpOrBottom => layout.focus_window_up_or_bottom(),
+            Op::FocusSpatial(direction) => layout.focus_spatial(direction),
             Op::MoveColumnLeft => layout.move_left(),
             Op::MoveColumnRight => layout.move_right(),
             Op::MoveColumnToFirst => layout.move_column_to_first(),
@@ -1188,6 +1266,7 @@ impl Op {
                 layout.move_column_right_or_to_output(&output);
             }
             Op::MoveColumnToIndex(index) => layout.move_column_to_index(index),
+            Op::MoveColumnByOffset(offset) => layout.move_column_by_offset(offset),
             Op::MoveWindowDown => layout.move_down(),
             Op::MoveWindowUp => layout.move_up(),
             Op::MoveWindowDownOrToWorkspaceDown => layout.move_down_or_to_workspace_down(),
@@ -1202,13 +1281,20 @@ impl Op {
             }
             Op::ConsumeWindowIntoColumn => layout.consume_into_column(),
             Op::ExpelWindowFromColumn => layout.expel_from_column(),
+            Op::ExpelWindowToColumnLeft => layout.expel_window_to_column(false),
+            Op::ExpelWindowToColumnRight => layout.expel_window_to_column(true),
             Op::SwapWindowInDirection(direction) => layout.swap_window_in_direction(direction),
             Op::CenterColumn => layout.center_column(),
             Op::CenterWindow { id } => {
                 let id = id.filter(|id| layout.has_window(id));
                 layout.center_window(id.as_ref());
             }
+            Op::CenterWindowVertically { id } => {
+                let id = id.filter(|id| layout.has_window(id));
+                layout.center_window_vertically(id.as_ref());
+            }
             Op::CenterVisibleColumns => layout.center_visible_columns(),
+            Op::DistributeColumnsEvenly => layout.distribute_columns_evenly(),
             Op::FocusWorkspaceDown => layout.switch_workspace_down(),
             Op::FocusWorkspaceUp => layout.switch_workspace_up(),
             Op::FocusWorkspace(idx) => layout.switch_workspace(idx),
@@ -1225,9 +1311,13 @@ impl Op {
                 let window_id = window_id.filter(|id| layout.has_window(id));
                 layout.move_to_workspace(window_id.as_ref(), workspace_idx, ActivateWindow::Smart);
             }
+            Op::MoveWindowToWorkspacePrevious(focus) => layout.move_to_workspace_previous(focus),
             Op::MoveColumnToWorkspaceDown(focus) => layout.move_column_to_workspace_down(focus),
             Op::MoveColumnToWorkspaceUp(focus) => layout.move_column_to_workspace_up(focus),
             Op::MoveColumnToWorkspace(idx, focus) => layout.move_column_to_workspace(idx, focus),
+            Op::MoveColumnToWorkspacePrevious(focus) => {
+                layout.move_column_to_workspace_previous(focus)
+            }
             Op::MoveWindowToOutput {
                 window_id,
                 output_id: id,
@@ -1248,6 +1338,22 @@ impl Op {
                     ActivateWindow::Smart,
                 );
             }
+            Op::ConsumeWindowFromOutput(id) => {
+                let name = format!("output{id}");
+                let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                    return;
+                };
+
+                layout.consume_window_from_output(&output);
+            }
+            Op::SwapColumnWithOutput(id) => {
+                let name = format!("output{id}");
+                let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                    return;
+                };
+
+                layout.swap_column_with_output(&output);
+            }
             Op::MoveColumnToOutput {
                 output_id: id,
                 target_ws_idx,
@@ -1265,6 +1371,7 @@ impl Op {
             Op::MoveWorkspaceToIndex {
                 ws_name: Some(ws_name),
                 target_idx,
+                compact,
             } => {
                 let MonitorSet::Normal { monitors, .. } = &mut layout.monitor_set else {
                     return;
@@ -1287,12 +1394,13 @@ impl Op {
                     return;
                 };
 
-                layout.move_workspace_to_idx(Some((Some(old_output), old_idx)), target_idx)
+                layout.move_workspace_to_idx(Some((Some(old_output), old_idx)), target_idx, compact)
             }
             Op::MoveWorkspaceToIndex {
                 ws_name: None,
                 target_idx,
-            } => layout.move_workspace_to_idx(None, target_idx),
+                compact,
+            } => layout.move_workspace_to_idx(None, target_idx, compact),
             Op::MoveWorkspaceToMonitor {
                 ws_name: None,
                 output_id: id,
@@ -1363,6 +1471,16 @@ impl Op {
                 }
                 layout.toggle_maximized(&id);
             }
+            Op::ToggleMaximizeWindowToEdges { id } => {
+                let id = id.or_else(|| layout.focus().map(|win| *win.id()));
+                let Some(id) = id else {
+                    return;
+                };
+                if !layout.has_window(&id) {
+                    return;
+                }
+                layout.toggle_maximized_with_restore(&id);
+            }
             Op::SetColumnWidth(change) => layout.set_column_width(change),
             Op::SetWindowWidth { id, change } => {
                 let id = id.filter(|id| layout.has_window(id));
@@ -1376,7 +1494,15 @@ impl Op {
                 let id = id.filter(|id| layout.has_window(id));
                 layout.reset_window_height(id.as_ref());
             }
+            Op::FillWindowHeightInColumn { id } => {
+                let id = id.filter(|id| layout.has_window(id));
+                layout.fill_window_height_in_column(id.as_ref());
+            }
             Op::ExpandColumnToAvailableWidth => layout.expand_column_to_available_width(),
+            Op::ToggleColumnExpandedWidth => layout.toggle_column_expanded_width(),
+            Op::AlignColumnWidthToNeighborLeft => layout.align_column_width_to_neighbor(false),
+            Op::AlignColumnWidthToNeighborRight => layout.align_column_width_to_neighbor(true),
+            Op::ToggleZeroGaps => layout.toggle_zero_gaps(),
             Op::ToggleWindowFloating { id } => {
                 let id = id.filter(|id| layout.has_window(id));
                 layout.toggle_window_floating(id.as_ref());
@@ -1394,6 +1520,8 @@ impl Op {
             Op::SwitchFocusFloatingTiling => {
                 layout.switch_focus_floating_tiling();
             }
+            Op::FocusNextFloatingWindow => layout.focus_floating_window_in_stack(true),
+            Op::FocusPreviousFloatingWindow => layout.focus_floating_window_in_stack(false),
             Op::MoveFloatingWindow { id, x, y, animate } => {
                 let id = id.filter(|id| layout.has_window(id));
                 layout.move_floating_window(id.as_ref(), x, y, animate);
@@ -1645,6 +1773,9 @@ impl Op {
             Op::ToggleOverview => {
                 layout.toggle_overview();
             }
+            Op::ToggleOverviewFocusedMonitor => {
+                layout.toggle_overview_on_active_output();
+            }
             Op::UpdateConfig { layout_config } => {
                 let options = Options {
                     layout: niri_config::Layout::from_part(&layout_config),
@@ -1654,6 +1785,9 @@ impl Op {
                 layout.update_options(options);
             }
             Op::ToggleGroup => layout.toggle_group(None),
+            Op::ToggleMaximizeColumnWithGroup => layout.toggle_maximize_column_with_group(),
+            Op::SetGroupDisplayMode(mode) => layout.set_group_display_mode(mode),
+            Op::ToggleGroupDisplayMode => layout.toggle_group_display_mode(),
             Op::MoveWindowIntoOrOutOfGroup(window_move_direction) => {
                 layout.move_window_into_or_out_of_group(None, window_move_direction)
             }
@@ -1783,6 +1917,9 @@ fn operations_dont_panic() {
         Op::ConsumeOrExpelWindowRight { id: None },
         Op::MoveWorkspaceToOutput(1),
         Op::ToggleGroup,
+        Op::ToggleMaximizeColumnWithGroup,
+        Op::SetGroupDisplayMode(GroupDisplayMode::Stacked),
+        Op::ToggleGroupDisplayMode,
         Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Up),
         Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Left),
         Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Right),
@@ -1968,6 +2105,9 @@ fn operations_from_starting_state_dont_panic() {
         Op::ConsumeOrExpelWindowLeft { id: None },
         Op::ConsumeOrExpelWindowRight { id: None },
         Op::ToggleGroup,
+        Op::ToggleMaximizeColumnWithGroup,
+        Op::SetGroupDisplayMode(GroupDisplayMode::Stacked),
+        Op::ToggleGroupDisplayMode,
         Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Up),
         Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Left),
         Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Right),
@@ -2429,6 +2569,46 @@ fn removing_all_outputs_preserves_empty_named_workspaces() {
     assert_eq!(workspaces.len(), 2);
 }
 
+#[test]
+fn activate_named_workspace_on_output_moves_or_creates() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddOutput(2),
+        Op::AddNamedWorkspace {
+            ws_name: 1,
+            output_name: Some(1),
+            layout_config: None,
+        },
+        Op::ActivateNamedWorkspaceOnOutput {
+            ws_name: 1,
+            output_name: 2,
+        },
+        Op::ActivateNamedWorkspaceOnOutput {
+            ws_name: 2,
+            output_name: 1,
+        },
+    ];
+
+    let layout = check_ops(ops);
+
+    let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+        unreachable!()
+    };
+
+    let active_name = |idx: usize| {
+        let mon = &monitors[idx];
+        mon.workspaces[mon.active_workspace_idx].name().cloned()
+    };
+    assert_eq!(active_name(0).as_deref(), Some("ws2"));
+    assert_eq!(active_name(1).as_deref(), Some("ws1"));
+    assert!(
+        monitors[0]
+            .workspaces
+            .iter()
+            .all(|ws| ws.name().is_none_or(|n| n != "ws1"))
+    );
+}
+
 #[test]
 fn config_change_updates_cached_sizes() {
     let mut config = Config::default();
@@ -3147,6 +3327,182 @@ fn windows_on_other_workspaces_remain_activated() {
     assert!(win.0.pending_activated.get());
 }
 
+#[test]
+fn consume_window_from_output() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddOutput(2),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::FocusOutput(2),
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::FocusOutput(1),
+        Op::ConsumeWindowFromOutput(2),
+    ];
+
+    let layout = check_ops(ops);
+    let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+        unreachable!()
+    };
+
+    let ws = &monitors[0].workspaces[0];
+    let columns: Vec<_> = ws.scrolling().columns().collect();
+    assert_eq!(columns.len(), 1);
+    let ids: Vec<_> = columns[0]
+        .tiles()
+        .map(|(tile, _)| *tile.focused_window().id())
+        .collect();
+    assert_eq!(ids, [1, 2]);
+
+    assert!(!monitors[1].has_window(&2));
+}
+
+#[test]
+fn swap_column_with_output() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddOutput(2),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::FocusColumnLeft,
+        Op::FocusOutput(2),
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::FocusOutput(1),
+        Op::SwapColumnWithOutput(2),
+    ];
+
+    let layout = check_ops(ops);
+    let MonitorSet::Normal {
+        monitors,
+        active_monitor_idx,
+        ..
+    } = layout.monitor_set
+    else {
+        unreachable!()
+    };
+
+    // Focus follows the moved column.
+    assert_eq!(active_monitor_idx, 1);
+
+    // The columns took each other's place.
+    let ids = |ws: &Workspace<TestWindow>| -> Vec<_> {
+        ws.scrolling()
+            .columns()
+            .map(|col| *col.tiles().next().unwrap().0.focused_window().id())
+            .collect()
+    };
+    assert_eq!(ids(&monitors[0].workspaces[0]), [3, 2]);
+    assert_eq!(ids(&monitors[1].workspaces[0]), [1]);
+}
+
+#[test]
+fn swap_column_with_empty_output() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddOutput(2),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::SwapColumnWithOutput(2),
+    ];
+
+    let layout = check_ops(ops);
+    let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+        unreachable!()
+    };
+
+    assert!(!monitors[0].has_window(&1));
+    assert!(monitors[1].has_window(&1));
+}
+
+#[test]
+fn cycle_floating_windows() {
+    let mut ops = vec![Op::AddOutput(1)];
+    for id in 1..=3 {
+        ops.push(Op::AddWindow {
+            params: TestWindowParams::new(id),
+        });
+        ops.push(Op::SetWindowFloating {
+            id: Some(id),
+            floating: true,
+        });
+    }
+    let mut layout = check_ops(ops);
+
+    let stack = |layout: &Layout<TestWindow>| -> Vec<usize> {
+        let ws = layout.active_workspace().unwrap();
+        ws.floating()
+            .tiles()
+            .map(|tile| *tile.focused_window().id())
+            .collect()
+    };
+    assert_eq!(stack(&layout), [3, 2, 1]);
+    assert_eq!(layout.focus().unwrap().id(), &3);
+
+    // Each step focuses and raises the next window, wrapping around.
+    check_ops_on_layout(&mut layout, [Op::FocusNextFloatingWindow]);
+    assert_eq!(stack(&layout), [2, 1, 3]);
+    assert_eq!(layout.focus().unwrap().id(), &2);
+
+    check_ops_on_layout(&mut layout, [Op::FocusNextFloatingWindow]);
+    assert_eq!(layout.focus().unwrap().id(), &1);
+
+    check_ops_on_layout(&mut layout, [Op::FocusNextFloatingWindow]);
+    assert_eq!(stack(&layout), [3, 2, 1]);
+    assert_eq!(layout.focus().unwrap().id(), &3);
+
+    check_ops_on_layout(&mut layout, [Op::FocusPreviousFloatingWindow]);
+    assert_eq!(stack(&layout), [1, 3, 2]);
+    assert_eq!(layout.focus().unwrap().id(), &1);
+}
+
+#[test]
+fn force_floating_blocks_toggle() {
+    let mut layout = check_ops([
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams {
+                is_floating: true,
+                rules: Some(ResolvedWindowRules {
+                    force_floating: Some(true),
+                    ..ResolvedWindowRules::default()
+                }),
+                ..TestWindowParams::new(1)
+            },
+        },
+        Op::ToggleWindowFloating { id: Some(1) },
+        Op::SetWindowFloating {
+            id: Some(1),
+            floating: false,
+        },
+        Op::Refresh { is_active: true },
+    ]);
+    let (_, win) = layout.windows().next().unwrap();
+    assert!(win.is_floating());
+
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::SetWindowFloating {
+                id: Some(1),
+                floating: true,
+            },
+            Op::Refresh { is_active: true },
+        ],
+    );
+    let (_, win) = layout.windows().next().unwrap();
+    assert!(win.is_floating());
+}
+
 #[test]
 fn stacking_add_parent_brings_up_child() {
     let ops = [
@@ -3459,93 +3815,1168 @@ fn preset_column_width_reset_after_set_width() {
 }
 
 #[test]
-fn move_column_to_workspace_unfocused_with_multiple_monitors() {
+fn toggle_column_expanded_width_restores_width() {
     let ops = [
         Op::AddOutput(1),
-        Op::SetWorkspaceName {
-            new_ws_name: 101,
-            ws_name: None,
-        },
-        Op::AddWindow {
-            params: TestWindowParams::new(1),
-        },
-        Op::FocusWorkspaceDown,
-        Op::SetWorkspaceName {
-            new_ws_name: 102,
-            ws_name: None,
-        },
-        Op::AddWindow {
-            params: TestWindowParams::new(2),
-        },
-        Op::AddOutput(2),
-        Op::FocusOutput(2),
-        Op::SetWorkspaceName {
-            new_ws_name: 201,
-            ws_name: None,
-        },
         Op::AddWindow {
-            params: TestWindowParams::new(3),
+            params: TestWindowParams::new(0),
         },
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
         Op::AddWindow {
-            params: TestWindowParams::new(4),
-        },
-        Op::MoveColumnToOutput {
-            output_id: 1,
-            target_ws_idx: Some(0),
-            activate: false,
+            params: TestWindowParams::new(1),
         },
-        Op::FocusOutput(1),
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
+        Op::ToggleColumnExpandedWidth,
     ];
+    let mut layout = check_ops(ops);
+    let width = |layout: &Layout<TestWindow>| {
+        let win = layout.windows().find(|(_, win)| win.id() == &1).unwrap().1;
+        win.requested_size().unwrap().w
+    };
+    assert!(width(&layout) > 300);
 
-    let layout = check_ops(ops);
-
-    assert_eq!(layout.active_workspace().unwrap().name().unwrap(), "ws102");
-
-    for (mon, win) in layout.windows() {
-        let mon = mon.unwrap();
-        let ws = mon
-            .workspaces
-            .iter()
-            .find(|w| w.has_window(win.id()))
-            .unwrap();
+    check_ops_on_layout(&mut layout, [Op::ToggleColumnExpandedWidth]);
+    assert_eq!(width(&layout), 300);
 
-        assert_eq!(
-            ws.name().unwrap(),
-            match win.id() {
-                1 | 4 => "ws101",
-                2 => "ws102",
-                3 => "ws201",
-                _ => unreachable!(),
-            }
-        );
-    }
+    // A manual resize in between forgets the stored width, so the second toggle expands again.
+    let ops = [
+        Op::ToggleColumnExpandedWidth,
+        Op::SetColumnWidth(SizeChange::AdjustFixed(-10)),
+        Op::ToggleColumnExpandedWidth,
+    ];
+    check_ops_on_layout(&mut layout, ops);
+    assert!(width(&layout) > 300);
 }
 
 #[test]
-fn move_column_to_workspace_down_focus_false_on_floating_window() {
+fn align_column_width_to_neighbor() {
     let ops = [
         Op::AddOutput(1),
         Op::AddWindow {
-            params: TestWindowParams::new(1),
+            params: TestWindowParams::new(0),
         },
+        Op::SetColumnWidth(SizeChange::SetFixed(400)),
+        Op::Communicate(0),
         Op::AddWindow {
-            params: TestWindowParams::new(2),
+            params: TestWindowParams::new(1),
         },
-        Op::ToggleWindowFloating { id: None },
-        Op::MoveColumnToWorkspaceDown(false),
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
+        Op::Communicate(1),
+        Op::FocusColumnLeft,
+        Op::CompleteAnimations,
+    ];
+    let options = Options {
+        layout: niri_config::Layout {
+            gaps: 0.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut layout = check_ops_with_options(options, ops);
+    let width = |layout: &Layout<TestWindow>, id| {
+        let win = layout.windows().find(|(_, win)| win.id() == &id).unwrap().1;
+        win.requested_size().unwrap().w
+    };
+
+    // No neighbor on the left of the first column.
+    check_ops_on_layout(&mut layout, [Op::AlignColumnWidthToNeighborLeft]);
+    assert_eq!(width(&layout, 0), 400);
+
+    // The right neighbor ends up at the right edge of the output.
+    check_ops_on_layout(&mut layout, [Op::AlignColumnWidthToNeighborRight]);
+    assert_eq!(width(&layout, 0), 1280 - 300);
+    assert_eq!(width(&layout, 1), 300);
+
+    // No neighbor on the right of the last column.
+    let ops = [Op::FocusColumnRight, Op::AlignColumnWidthToNeighborRight];
+    check_ops_on_layout(&mut layout, ops);
+    assert_eq!(width(&layout, 1), 300);
+}
+
+#[test]
+fn column_under_includes_gaps() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::SetColumnWidth(SizeChange::SetFixed(400)),
+        Op::Communicate(0),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
+        Op::Communicate(1),
+        Op::CompleteAnimations,
+    ];
+    let options = Options {
+        layout: niri_config::Layout {
+            gaps: 20.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let layout = check_ops_with_options(options, ops);
+    let output = layout.outputs().next().unwrap();
+    let column_under = |x| {
+        layout
+            .column_under(output, Point::from((x, 100.)))
+            .map(|(_, idx)| idx)
+    };
+
+    // Columns are at 20..420 and 440..740, and the gap between them is split in half.
+    assert_eq!(column_under(15.), Some(0));
+    assert_eq!(column_under(200.), Some(0));
+    assert_eq!(column_under(429.), Some(0));
+    assert_eq!(column_under(431.), Some(1));
+    assert_eq!(column_under(745.), Some(1));
+
+    // Empty workspace area.
+    assert_eq!(column_under(5.), None);
+    assert_eq!(column_under(800.), None);
+}
+
+#[test]
+fn set_column_width_rest_follows_other_columns() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
+        Op::Communicate(0),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::SetColumnWidth(SizeChange::SetRest),
+        Op::Refresh { is_active: true },
+    ];
+    let mut layout = check_ops(ops);
+    let width = |layout: &Layout<TestWindow>| {
+        let win = layout.windows().find(|(_, win)| win.id() == &1).unwrap().1;
+        win.requested_size().unwrap().w
+    };
+    // 1280 output width minus 300 for the other column and three gaps.
+    assert_eq!(width(&layout), 932);
+
+    // Resizing the other column updates the rest width.
+    let ops = [
+        Op::FocusColumnLeft,
+        Op::SetColumnWidth(SizeChange::SetFixed(400)),
+        Op::Communicate(0),
+        Op::Refresh { is_active: true },
+    ];
+    check_ops_on_layout(&mut layout, ops);
+    assert_eq!(width(&layout), 832);
+
+    // Resizing the column itself drops the rest width.
+    let ops = [
+        Op::FocusColumnRight,
+        Op::SetColumnWidth(SizeChange::SetFixed(500)),
+        Op::FocusColumnLeft,
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
+        Op::Communicate(0),
+        Op::Refresh { is_active: true },
+    ];
+    check_ops_on_layout(&mut layout, ops);
+    assert_eq!(width(&layout), 500);
+}
+
+#[test]
+fn set_column_width_output_proportion_ignores_gaps_and_struts() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::SetColumnWidth(SizeChange::SetOutputProportion(50.)),
+        Op::SetWindowHeight {
+            id: None,
+            change: SizeChange::SetOutputProportion(50.),
+        },
+        Op::Communicate(0),
+        Op::Refresh { is_active: true },
+    ];
+
+    let options = Options {
+        layout: niri_config::Layout {
+            gaps: 50.,
+            struts: Struts {
+                left: FloatOrInt(100.),
+                right: FloatOrInt(100.),
+                top: FloatOrInt(100.),
+                bottom: FloatOrInt(100.),
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let layout = check_ops_with_options(options, ops);
+
+    // Exactly half of the 1280x720 output.
+    let win = layout.windows().find(|(_, win)| win.id() == &0).unwrap().1;
+    assert_eq!(win.requested_size().unwrap(), Size::from((640, 360)));
+}
+
+#[test]
+fn move_column_by_offset() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::FocusColumnFirst,
+        Op::MoveColumnByOffset(1),
+    ];
+    let mut layout = check_ops(ops);
+    assert_eq!(column_ids(&layout), [vec![1], vec![0], vec![2]]);
+
+    // Moving past the end clamps to the last position.
+    check_ops_on_layout(&mut layout, [Op::MoveColumnByOffset(3)]);
+    assert_eq!(column_ids(&layout), [vec![1], vec![2], vec![0]]);
+    assert_eq!(layout.focus().unwrap().id(), &0);
+
+    // Moving past the start clamps to the first position.
+    check_ops_on_layout(&mut layout, [Op::MoveColumnByOffset(-5)]);
+    assert_eq!(column_ids(&layout), [vec![0], vec![1], vec![2]]);
+}
+
+#[test]
+fn toggle_zero_gaps() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::Communicate(0),
+    ];
+
+    let options = Options {
+        layout: niri_config::Layout {
+            gaps: 20.,
+            struts: Struts {
+                top: FloatOrInt(30.),
+                bottom: FloatOrInt(30.),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut layout = check_ops_with_options(options.clone(), ops);
+    let height = |layout: &Layout<TestWindow>| {
+        let win = layout.windows().find(|(_, win)| win.id() == &0).unwrap().1;
+        win.requested_size().unwrap().h
+    };
+    assert_eq!(height(&layout), 720 - 60 - 40);
+
+    check_ops_on_layout(&mut layout, [Op::ToggleZeroGaps, Op::Communicate(0)]);
+    assert_eq!(height(&layout), 720);
+
+    check_ops_on_layout(&mut layout, [Op::ToggleZeroGaps, Op::Communicate(0)]);
+    assert_eq!(height(&layout), 720 - 60 - 40);
+
+    // Reloading the config clears the override.
+    check_ops_on_layout(&mut layout, [Op::ToggleZeroGaps, Op::Communicate(0)]);
+    layout.update_options(options);
+    check_ops_on_layout(&mut layout, [Op::Communicate(0)]);
+    assert_eq!(height(&layout), 720 - 60 - 40);
+}
+
+#[test]
+fn fill_window_height_in_column() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::Communicate(0),
+        Op::Communicate(1),
+    ];
+
+    let options = |gaps| Options {
+        layout: niri_config::Layout {
+            gaps,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut layout = check_ops_with_options(options(20.), ops);
+    let heights = |layout: &Layout<TestWindow>| {
+        [0, 1].map(|id| {
+            let win = layout.windows().find(|(_, win)| win.id() == &id).unwrap().1;
+            win.requested_size().unwrap().h
+        })
+    };
+    assert_eq!(heights(&layout), [330, 330]);
+
+    // Nothing changes right away, since the column is already full.
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::FillWindowHeightInColumn { id: None },
+            Op::Communicate(0),
+            Op::Communicate(1),
+        ],
+    );
+    assert_eq!(heights(&layout), [330, 330]);
+
+    // But the filling window absorbs the change in leftover height.
+    layout.update_options(options(40.));
+    check_ops_on_layout(&mut layout, [Op::Communicate(0), Op::Communicate(1)]);
+    assert_eq!(heights(&layout), [330, 720 - 40 * 3 - 330]);
+}
+
+#[test]
+fn fill_window_height_in_column_with_three_windows() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::Communicate(0),
+        Op::Communicate(1),
+        Op::Communicate(2),
+        Op::SetWindowHeight {
+            id: Some(0),
+            change: SizeChange::SetFixed(100),
+        },
+        Op::Communicate(0),
+        Op::Communicate(1),
+        Op::Communicate(2),
+    ];
+
+    let options = Options {
+        layout: niri_config::Layout {
+            gaps: 20.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut layout = check_ops_with_options(options, ops);
+    let heights = |layout: &Layout<TestWindow>| {
+        [0, 1, 2].map(|id| {
+            let win = layout.windows().find(|(_, win)| win.id() == &id).unwrap().1;
+            win.requested_size().unwrap().h
+        })
+    };
+    assert_eq!(heights(&layout), [100, 270, 270]);
+
+    // The last window gets whatever the other two leave over.
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::FillWindowHeightInColumn { id: Some(2) },
+            Op::Communicate(0),
+            Op::Communicate(1),
+            Op::Communicate(2),
+        ],
+    );
+    assert_eq!(heights(&layout), [100, 270, 720 - 20 * 4 - 100 - 270]);
+
+    // It keeps that height, and the other windows share the change in gaps.
+    layout.update_options(Options {
+        layout: niri_config::Layout {
+            gaps: 40.,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    check_ops_on_layout(
+        &mut layout,
+        [Op::Communicate(0), Op::Communicate(1), Op::Communicate(2)],
+    );
+    let [h0, h1, h2] = heights(&layout);
+    assert_eq!(h2, 270);
+    assert_eq!(h0 + h1, 720 - 40 * 4 - 270);
+}
+
+#[test]
+fn overscroll_limits_view_offset_gesture() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::ViewOffsetGestureBegin {
+            output_idx: 1,
+            workspace_idx: None,
+            is_touchpad: false,
+        },
+        Op::ViewOffsetGestureUpdate {
+            delta: -5000.,
+            timestamp: Duration::ZERO,
+            is_touchpad: false,
+        },
+    ];
+
+    let view_pos = |layout: &Layout<TestWindow>| {
+        let ws = layout.active_workspace().unwrap();
+        ws.scrolling().view_pos()
+    };
+
+    let layout = check_ops(ops.clone());
+    assert!(view_pos(&layout) < -1280.);
+
+    let options = Options {
+        layout: niri_config::Layout {
+            overscroll: niri_config::Overscroll {
+                on: true,
+                resistance: 0.5,
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut layout = check_ops_with_options(options, ops);
+    let pos = view_pos(&layout);
+    assert!(-1280. < pos && pos < 0.);
+
+    // Ending the gesture snaps back.
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::ViewOffsetGestureEnd { is_touchpad: None },
+            Op::CompleteAnimations,
+        ],
+    );
+    assert!(view_pos(&layout) > pos);
+}
+
+#[test]
+fn center_window_vertically_in_column() {
+    let fixed_height = |id| TestWindowParams {
+        min_max_size: (Size::from((0, 100)), Size::from((0, 100))),
+        ..TestWindowParams::new(id)
+    };
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: fixed_height(1),
+        },
+        Op::AddWindow {
+            params: fixed_height(2),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::CenterWindowVertically { id: None },
+        Op::CompleteAnimations,
+    ];
+    let mut layout = check_ops(ops);
+    let center_y = |layout: &Layout<TestWindow>, id: usize| {
+        let ws = layout.active_workspace().unwrap();
+        let (tile, pos, _) = ws
+            .tiles_with_render_positions()
+            .find(|(tile, _, _)| tile.focused_window().id() == &id)
+            .unwrap();
+        pos.y + tile.tile_size().h / 2.
+    };
+    assert_eq!(center_y(&layout, 2), 360.);
+
+    // The window above moves together with the centered window.
+    assert_eq!(center_y(&layout, 1), 360. - 116.);
+
+    // Windows that fill the column are left as is.
+    let ops = [
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::CenterWindowVertically { id: Some(1) },
+        Op::CompleteAnimations,
+    ];
+    check_ops_on_layout(&mut layout, ops);
+    assert_eq!(center_y(&layout, 1), 16. + 50.);
+}
+
+#[test]
+fn move_column_to_workspace_unfocused_with_multiple_monitors() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::SetWorkspaceName {
+            new_ws_name: 101,
+            ws_name: None,
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::FocusWorkspaceDown,
+        Op::SetWorkspaceName {
+            new_ws_name: 102,
+            ws_name: None,
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::AddOutput(2),
+        Op::FocusOutput(2),
+        Op::SetWorkspaceName {
+            new_ws_name: 201,
+            ws_name: None,
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(4),
+        },
+        Op::MoveColumnToOutput {
+            output_id: 1,
+            target_ws_idx: Some(0),
+            activate: false,
+        },
+        Op::FocusOutput(1),
+    ];
+
+    let layout = check_ops(ops);
+
+    assert_eq!(layout.active_workspace().unwrap().name().unwrap(), "ws102");
+
+    for (mon, win) in layout.windows() {
+        let mon = mon.unwrap();
+        let ws = mon
+            .workspaces
+            .iter()
+            .find(|w| w.has_window(win.id()))
+            .unwrap();
+
+        assert_eq!(
+            ws.name().unwrap(),
+            match win.id() {
+                1 | 4 => "ws101",
+                2 => "ws102",
+                3 => "ws201",
+                _ => unreachable!(),
+            }
+        );
+    }
+}
+
+#[test]
+fn move_column_to_workspace_down_focus_false_on_floating_window() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::ToggleWindowFloating { id: None },
+        Op::MoveColumnToWorkspaceDown(false),
+    ];
+
+    let layout = check_ops(ops);
+
+    let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+        unreachable!()
+    };
+
+    assert_eq!(monitors[0].active_workspace_idx, 0);
+}
+
+#[test]
+fn move_column_to_workspace_focus_false_on_floating_window() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::ToggleWindowFloating { id: None },
+        Op::MoveColumnToWorkspace(1, false),
+    ];
+
+    let layout = check_ops(ops);
+
+    let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+        unreachable!()
+    };
+
+    assert_eq!(monitors[0].active_workspace_idx, 0);
+}
+
+#[test]
+fn restore_to_floating_persists_across_fullscreen_maximize() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::ToggleWindowFloating { id: None },
+        // Maximize then fullscreen.
+        Op::MaximizeWindowToEdges { id: None },
+        Op::FullscreenWindow(1),
+        // Unfullscreen.
+        Op::FullscreenWindow(1),
+    ];
+
+    let mut layout = check_ops(ops);
+
+    // Unfullscreening should return the window to the maximized state.
+    let scrolling = layout.active_workspace().unwrap().scrolling();
+    assert!(scrolling.tiles().next().is_some());
+
+    let ops = [
+        // Unmaximize.
+        Op::MaximizeWindowToEdges { id: None },
+    ];
+    check_ops_on_layout(&mut layout, ops);
+
+    // Unmaximize should return the window back to floating.
+    let scrolling = layout.active_workspace().unwrap().scrolling();
+    assert!(scrolling.tiles().next().is_none());
+}
+
+#[test]
+fn unmaximize_during_fullscreen_does_not_float() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::ToggleWindowFloating { id: None },
+        // Maximize then fullscreen.
+        Op::MaximizeWindowToEdges { id: None },
+        Op::FullscreenWindow(1),
+        // Unmaximize.
+        Op::MaximizeWindowToEdges { id: None },
+    ];
+
+    let mut layout = check_ops(ops);
+
+    // Unmaximize shouldn't have changed the window state since it's fullscreen.
+    let scrolling = layout.active_workspace().unwrap().scrolling();
+    assert!(scrolling.tiles().next().is_some());
+
+    let ops = [
+        // Unfullscreen.
+        Op::FullscreenWindow(1),
+    ];
+    check_ops_on_layout(&mut layout, ops);
+
+    // Unfullscreen should return the window back to floating.
+    let scrolling = layout.active_workspace().unwrap().scrolling();
+    assert!(scrolling.tiles().next().is_none());
+}
+
+#[test]
+fn on_empty_workspace_closing_last_window() {
+    let options = |on_empty_workspace, empty_workspace_above_first| Options {
+        layout: niri_config::Layout {
+            on_empty_workspace,
+            empty_workspace_above_first,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // Unnamed focused workspace.
+    let unnamed = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::FocusWorkspaceDown,
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::CompleteAnimations,
+        Op::CloseWindow(2),
+    ];
+
+    // Named focused workspace.
+    let named = [
+        Op::AddOutput(1),
+        Op::AddNamedWorkspace {
+            ws_name: 1,
+            output_name: None,
+            layout_config: None,
+        },
+        Op::FocusWorkspace(0),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::CompleteAnimations,
+        Op::CloseWindow(1),
     ];
 
-    let layout = check_ops(ops);
-
-    let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+    for (policy, unnamed_result, named_result) in [
+        (
+            OnEmptyWorkspace::Keep,
+            vec![vec![1], vec![], vec![]],
+            vec![vec![], vec![]],
+        ),
+        (
+            OnEmptyWorkspace::RemoveIfUnnamed,
+            vec![vec![1], vec![]],
+            vec![vec![], vec![]],
+        ),
+        (
+            OnEmptyWorkspace::Remove,
+            vec![vec![1], vec![]],
+            vec![vec![]],
+        ),
+    ] {
+        let layout = check_ops_with_options(options(policy, false), unnamed.clone());
+        assert_eq!(workspace_window_ids(&layout), unnamed_result, "{policy:?}");
+
+        let layout = check_ops_with_options(options(policy, false), named.clone());
+        assert_eq!(workspace_window_ids(&layout), named_result, "{policy:?}");
+    }
+
+    // The workspace at the top stays with empty_workspace_above_first.
+    let layout = check_ops_with_options(options(OnEmptyWorkspace::Remove, true), unnamed);
+    assert_eq!(workspace_window_ids(&layout), [vec![], vec![1], vec![]]);
+    let MonitorSet::Normal { monitors, .. } = &layout.monitor_set else {
+        unreachable!()
+    };
+    assert_eq!(monitors[0].active_workspace_idx, 1);
+}
+
+#[test]
+fn focus_after_close_picks_workspace() {
+    let options = |focus_after_close| Options {
+        layout: niri_config::Layout {
+            on_empty_workspace: OnEmptyWorkspace::Remove,
+            focus_after_close,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // Three workspaces with one window each, the last one focused.
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::FocusWorkspaceDown,
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::FocusWorkspaceDown,
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::CompleteAnimations,
+    ];
+
+    for (policy, expected) in [(FocusAfterClose::Neighbor, 2), (FocusAfterClose::Mru, 1)] {
+        let mut layout = check_ops_with_options(options(policy), ops.clone());
+
+        // Window 1 was focused more recently than window 2.
+        for (_, win) in layout.windows() {
+            let timestamp = match win.id() {
+                1 => 20,
+                2 => 10,
+                _ => 30,
+            };
+            win.0
+                .focus_timestamp
+                .set(Some(Duration::from_millis(timestamp)));
+        }
+
+        check_ops_on_layout(&mut layout, [Op::CloseWindow(3)]);
+        assert_eq!(workspace_window_ids(&layout), [vec![1], vec![2], vec![]]);
+        assert_eq!(
+            layout.focus().map(|win| *win.id()),
+            Some(expected),
+            "{policy:?}"
+        );
+    }
+}
+
+#[test]
+fn overview_on_focused_monitor() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddOutput(2),
+        Op::FocusOutput(1),
+        Op::ToggleOverviewFocusedMonitor,
+        Op::CompleteAnimations,
+    ];
+
+    let mut layout = check_ops(ops);
+    let output1 = layout
+        .outputs()
+        .find(|o| o.name() == "output1")
+        .unwrap()
+        .clone();
+    let output2 = layout
+        .outputs()
+        .find(|o| o.name() == "output2")
+        .unwrap()
+        .clone();
+    assert!(layout.is_overview_open());
+    assert!(layout.is_overview_open_on(&output1));
+    assert!(!layout.is_overview_open_on(&output2));
+    assert_eq!(layout.overview_zoom_on(&output2), 1.);
+
+    // Moving the focus to the other monitor closes the overview.
+    let ops = [
+        Op::FocusOutput(2),
+        Op::Refresh { is_active: true },
+        Op::CompleteAnimations,
+    ];
+    check_ops_on_layout(&mut layout, ops);
+    assert!(!layout.is_overview_open());
+    assert!(!layout.is_overview_open_on(&output1));
+
+    // The regular overview opens on all monitors afterwards.
+    check_ops_on_layout(&mut layout, [Op::ToggleOverview]);
+    assert!(layout.is_overview_open_on(&output1));
+    assert!(layout.is_overview_open_on(&output2));
+}
+
+#[test]
+fn overview_grid_hit_testing() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::FocusWorkspaceDown,
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::FocusWorkspaceDown,
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::ToggleOverview,
+        Op::CompleteAnimations,
+    ];
+
+    let options = Options {
+        overview: niri_config::Overview {
+            zoom: 0.25,
+            columns: Some(2),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let layout = check_ops_with_options(options, ops);
+    let output = layout.outputs().next().unwrap().clone();
+    let mon = layout.monitor_for_output(&output).unwrap();
+    let ids: Vec<_> = mon.workspaces.iter().map(|ws| ws.id()).collect();
+    let geo: Vec<_> = mon.workspaces_render_geo().collect();
+    assert_eq!(ids.len(), 4);
+
+    // Two workspaces per row.
+    assert_eq!(geo[0].loc.y, geo[1].loc.y);
+    assert_eq!(geo[2].loc.y, geo[3].loc.y);
+    assert_eq!(geo[0].loc.x, geo[2].loc.x);
+    assert!(geo[0].loc.x < geo[1].loc.x);
+    assert!(geo[0].loc.y < geo[2].loc.y);
+
+    let ws_under =
+        |pos: Point<f64, Logical>| layout.workspace_under(true, &output, pos).map(|ws| ws.id());
+
+    for (id, geo) in ids.iter().zip(&geo) {
+        let center = geo.loc + geo.size.downscale(2.).to_point();
+        assert_eq!(ws_under(center), Some(*id));
+        assert_eq!(
+            mon.insert_position(center).0,
+            InsertWorkspace::Existing(*id)
+        );
+    }
+
+    // Workspace bounds don't extend to the sides of the output in the grid.
+    let gap_x = (geo[0].loc.x + geo[0].size.w + geo[1].loc.x) / 2.;
+    assert!(gap_x < geo[1].loc.x);
+    let in_gap = Point::from((gap_x, geo[0].loc.y + 1.));
+    assert_eq!(ws_under(in_gap), None);
+    assert_eq!(ws_under(Point::from((1., geo[0].loc.y + 1.))), None);
+    assert_eq!(mon.insert_position(in_gap).0, InsertWorkspace::NewAt(4));
+}
+
+#[test]
+fn workspace_background_color_from_config() {
+    let config = Config::parse_mem(
+        r##"
+        layout {
+            gaps 8
+            background-color "#111111"
+        }
+
+        output "output1" {
+            layout {
+                background-color "#222222"
+            }
+        }
+
+        workspace "production" {
+            layout {
+                background-color "#400000"
+            }
+        }
+
+        workspace "staging" {
+            layout {
+                gaps 20
+            }
+        }
+        "##,
+    )
+    .unwrap();
+
+    let mut layout = Layout::new(Clock::with_time(Duration::ZERO), &config);
+    check_ops_on_layout(
+        &mut layout,
+        [Op::AddScaledOutput {
+            id: 1,
+            scale: 1.,
+            layout_config: config.outputs.0[0].layout.clone().map(Box::new),
+        }],
+    );
+
+    let options = |name| {
+        let (_, ws) = layout.find_workspace_by_name(name).unwrap();
+        let layout = &ws.options.layout;
+        (layout.background_color, layout.gaps)
+    };
+    let rgb = |r, g, b| niri_config::Color::from_rgba8_unpremul(r, g, b, 0xff);
+
+    // The workspace color takes precedence over the output and the top-level colors.
+    assert_eq!(options("production"), (rgb(0x40, 0, 0), 8.));
+    // Without its own color, the workspace falls back to the output color.
+    assert_eq!(options("staging"), (rgb(0x22, 0x22, 0x22), 20.));
+}
+
+fn workspace_window_ids(layout: &Layout<TestWindow>) -> Vec<Vec<usize>> {
+    let MonitorSet::Normal { monitors, .. } = &layout.monitor_set else {
+        unreachable!()
+    };
+
+    monitors[0]
+        .workspaces
+        .iter()
+        .map(|ws| ws.windows().map(|win| *win.id()).collect())
+        .collect()
+}
+
+#[test]
+fn move_workspace_to_index_compact() {
+    // Workspaces end up as: named "ws1" (empty), 1, empty (focused), 3, empty.
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddNamedWorkspace {
+            ws_name: 1,
+            output_name: None,
+            layout_config: None,
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::FocusWorkspaceDown,
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::FocusWorkspaceDown,
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::FocusWorkspace(2),
+        Op::CompleteAnimations,
+        Op::CloseWindow(2),
+    ];
+
+    let mut layout = check_ops(ops.clone());
+    assert_eq!(
+        workspace_window_ids(&layout),
+        [vec![], vec![1], vec![], vec![3], vec![]]
+    );
+
+    // Without compact, the focused empty workspace stays.
+    layout.move_workspace_to_idx(Some((None, 1)), 3, false);
+    layout.verify_invariants();
+    assert_eq!(
+        workspace_window_ids(&layout),
+        [vec![], vec![], vec![3], vec![1], vec![]]
+    );
+
+    let mut layout = check_ops(ops);
+    layout.move_workspace_to_idx(Some((None, 1)), 3, true);
+    layout.verify_invariants();
+
+    // The named workspace is preserved even though it's empty.
+    assert_eq!(
+        workspace_window_ids(&layout),
+        [vec![], vec![3], vec![1], vec![]]
+    );
+    let MonitorSet::Normal { monitors, .. } = &layout.monitor_set else {
         unreachable!()
     };
+    assert_eq!(monitors[0].workspaces[0].name().unwrap(), "ws1");
+    // The focused workspace was removed, so the moved one takes the focus.
+    assert_eq!(monitors[0].active_workspace_idx, 2);
+}
+
+#[test]
+fn move_workspace_to_index_compact_ewaf() {
+    let options = Options {
+        layout: niri_config::Layout {
+            empty_workspace_above_first: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::FocusWorkspaceDown,
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::FocusWorkspaceDown,
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::FocusWorkspace(2),
+        Op::CompleteAnimations,
+        Op::CloseWindow(2),
+    ];
+
+    let mut layout = check_ops_with_options(options, ops);
+    assert_eq!(
+        workspace_window_ids(&layout),
+        [vec![], vec![1], vec![], vec![3], vec![]]
+    );
+
+    layout.move_workspace_to_idx(Some((None, 3)), 1, true);
+    layout.verify_invariants();
+
+    // The empty workspaces at the top and at the bottom are kept.
+    assert_eq!(
+        workspace_window_ids(&layout),
+        [vec![], vec![3], vec![1], vec![]]
+    );
+}
+
+fn column_ids(layout: &Layout<TestWindow>) -> Vec<Vec<usize>> {
+    let scrolling = layout.active_workspace().unwrap().scrolling();
+    scrolling
+        .columns()
+        .map(|col| {
+            col.tiles()
+                .map(|(tile, _)| *tile.focused_window().id())
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn expel_window_to_column() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::FocusColumnFirst,
+        Op::ConsumeWindowIntoColumn,
+        Op::ConsumeWindowIntoColumn,
+        Op::FocusWindow(1),
+    ];
+    let mut layout = check_ops(ops);
+    assert_eq!(column_ids(&layout), [vec![0, 1, 2]]);
+
+    // The focused window goes left, and focus follows it.
+    check_ops_on_layout(&mut layout, [Op::ExpelWindowToColumnLeft]);
+    assert_eq!(column_ids(&layout), [vec![1], vec![0, 2]]);
+    assert_eq!(layout.focus().unwrap().id(), &1);
+
+    let ops = [
+        Op::FocusColumnRight,
+        Op::FocusWindowTop,
+        Op::ExpelWindowToColumnRight,
+    ];
+    check_ops_on_layout(&mut layout, ops);
+    assert_eq!(column_ids(&layout), [vec![1], vec![2], vec![0]]);
+    assert_eq!(layout.focus().unwrap().id(), &0);
+
+    // Single-window columns are left alone.
+    check_ops_on_layout(&mut layout, [Op::ExpelWindowToColumnLeft]);
+    assert_eq!(column_ids(&layout), [vec![1], vec![2], vec![0]]);
+}
+
+#[test]
+fn toggle_maximize_window_to_edges_restores_column() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: Some(3) },
+        Op::FocusWindow(2),
+        Op::ToggleMaximizeWindowToEdges { id: None },
+    ];
+
+    let mut layout = check_ops(ops);
+    assert_eq!(column_ids(&layout), [vec![1], vec![3], vec![2]]);
+
+    let ops = [Op::ToggleMaximizeWindowToEdges { id: None }];
+    check_ops_on_layout(&mut layout, ops);
+    assert_eq!(column_ids(&layout), [vec![1], vec![2, 3]]);
+}
+
+#[test]
+fn toggle_maximize_window_to_edges_forgets_after_resize() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::ToggleMaximizeWindowToEdges { id: None },
+        Op::SetColumnWidth(SizeChange::SetProportion(50.)),
+        Op::ToggleMaximizeWindowToEdges { id: None },
+        Op::ToggleMaximizeWindowToEdges { id: None },
+    ];
 
-    assert_eq!(monitors[0].active_workspace_idx, 0);
+    let layout = check_ops(ops);
+    assert_eq!(column_ids(&layout), [vec![1], vec![2]]);
 }
 
 #[test]
-fn move_column_to_workspace_focus_false_on_floating_window() {
+fn toggle_maximize_window_to_edges_forgets_after_fullscreen() {
     let ops = [
         Op::AddOutput(1),
         Op::AddWindow {
@@ -3554,126 +4985,377 @@ fn move_column_to_workspace_focus_false_on_floating_window() {
         Op::AddWindow {
             params: TestWindowParams::new(2),
         },
-        Op::ToggleWindowFloating { id: None },
-        Op::MoveColumnToWorkspace(1, false),
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::ToggleMaximizeWindowToEdges { id: None },
+        Op::FullscreenWindow(2),
+        Op::FullscreenWindow(2),
+        Op::ToggleMaximizeWindowToEdges { id: None },
     ];
 
     let layout = check_ops(ops);
+    assert_eq!(column_ids(&layout), [vec![1], vec![2]]);
+}
 
-    let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
-        unreachable!()
-    };
+#[test]
+fn move_column_to_workspace_maximize_and_fullscreen() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::MaximizeWindowToEdges { id: None },
+        Op::FullscreenWindow(1),
+        Op::MoveColumnToWorkspaceDown(true),
+        Op::FullscreenWindow(1),
+    ];
 
-    assert_eq!(monitors[0].active_workspace_idx, 0);
+    let layout = check_ops(ops);
+    let (_, win) = layout.windows().next().unwrap();
+
+    // Unfullscreening should return to maximized because the window was maximized before.
+    assert_eq!(win.pending_sizing_mode(), SizingMode::Maximized);
 }
 
 #[test]
-fn restore_to_floating_persists_across_fullscreen_maximize() {
+fn move_window_to_workspace_maximize_and_fullscreen() {
     let ops = [
         Op::AddOutput(1),
         Op::AddWindow {
             params: TestWindowParams::new(1),
         },
-        Op::ToggleWindowFloating { id: None },
-        // Maximize then fullscreen.
         Op::MaximizeWindowToEdges { id: None },
         Op::FullscreenWindow(1),
-        // Unfullscreen.
+        Op::MoveWindowToWorkspaceDown(true),
         Op::FullscreenWindow(1),
     ];
 
-    let mut layout = check_ops(ops);
+    let layout = check_ops(ops);
+    let (_, win) = layout.windows().next().unwrap();
 
-    // Unfullscreening should return the window to the maximized state.
-    let scrolling = layout.active_workspace().unwrap().scrolling();
-    assert!(scrolling.tiles().next().is_some());
+    // Unfullscreening should return to maximized because the window was maximized before.
+    //
+    // FIXME: it currently doesn't because windows themselves can only be either fullscreen or
+    // maximized. So when a window is fullscreen, whether it is also maximized or not is stored in
+    // the column. MoveWindowToWorkspace removes the window from the column and this information is
+    // forgotten.
+    assert_eq!(win.pending_sizing_mode(), SizingMode::Normal);
+}
+
+#[test]
+fn distribute_columns_evenly_respects_min_width() {
+    let mut constrained = TestWindowParams::new(1);
+    constrained.min_max_size = (Size::from((500, 0)), Size::from((0, 0)));
 
     let ops = [
-        // Unmaximize.
-        Op::MaximizeWindowToEdges { id: None },
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
+        Op::AddWindow {
+            params: constrained,
+        },
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::SetColumnWidth(SizeChange::SetFixed(300)),
+        Op::Communicate(0),
+        Op::Communicate(1),
+        Op::Communicate(2),
+        Op::FocusColumnFirst,
+        Op::CompleteAnimations,
+        Op::DistributeColumnsEvenly,
+        Op::Communicate(0),
+        Op::Communicate(1),
+        Op::Communicate(2),
+        Op::Refresh { is_active: true },
     ];
-    check_ops_on_layout(&mut layout, ops);
+    let layout = check_ops(ops);
 
-    // Unmaximize should return the window back to floating.
-    let scrolling = layout.active_workspace().unwrap().scrolling();
-    assert!(scrolling.tiles().next().is_none());
+    let width = |id: usize| {
+        let win = layout.windows().find(|(_, win)| win.id() == &id).unwrap().1;
+        win.requested_size().unwrap().w
+    };
+    // 1280 output width minus four gaps is 1216. The constrained column keeps its 500 min width,
+    // and the other two split the remaining 716.
+    assert_eq!(width(0), 358);
+    assert_eq!(width(1), 500);
+    assert_eq!(width(2), 358);
+
+    // The focused column stays focused.
+    assert_eq!(layout.focus().map(|win| *win.id()), Some(0));
 }
 
 #[test]
-fn unmaximize_during_fullscreen_does_not_float() {
+fn focus_spatial_in_grid() {
+    // Two columns with two windows each:
+    //
+    // 0 2
+    // 1 3
     let ops = [
         Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
         Op::AddWindow {
             params: TestWindowParams::new(1),
         },
-        Op::ToggleWindowFloating { id: None },
-        // Maximize then fullscreen.
-        Op::MaximizeWindowToEdges { id: None },
-        Op::FullscreenWindow(1),
-        // Unmaximize.
-        Op::MaximizeWindowToEdges { id: None },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::Communicate(0),
+        Op::Communicate(1),
+        Op::Communicate(2),
+        Op::Communicate(3),
+        Op::CompleteAnimations,
     ];
-
     let mut layout = check_ops(ops);
+    let focus = |layout: &Layout<TestWindow>| layout.focus().map(|win| *win.id());
+    assert_eq!(focus(&layout), Some(3));
 
-    // Unmaximize shouldn't have changed the window state since it's fullscreen.
-    let scrolling = layout.active_workspace().unwrap().scrolling();
-    assert!(scrolling.tiles().next().is_some());
+    layout.focus_spatial(Direction::Left);
+    assert_eq!(focus(&layout), Some(1));
+
+    layout.focus_spatial(Direction::Up);
+    assert_eq!(focus(&layout), Some(0));
+
+    // Nothing further up.
+    layout.focus_spatial(Direction::Up);
+    assert_eq!(focus(&layout), Some(0));
+
+    // 2 is straight to the right, while 3 is diagonal and deviates more.
+    layout.focus_spatial(Direction::Right);
+    assert_eq!(focus(&layout), Some(2));
 
+    layout.focus_spatial(Direction::Down);
+    assert_eq!(focus(&layout), Some(3));
+}
+
+#[test]
+fn focus_spatial_reaches_offscreen_columns() {
     let ops = [
-        // Unfullscreen.
-        Op::FullscreenWindow(1),
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::SetColumnWidth(SizeChange::SetProportion(100.)),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::SetColumnWidth(SizeChange::SetProportion(100.)),
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::SetColumnWidth(SizeChange::SetProportion(100.)),
+        Op::Communicate(0),
+        Op::Communicate(1),
+        Op::Communicate(2),
+        Op::FocusColumnFirst,
+        Op::CompleteAnimations,
     ];
-    check_ops_on_layout(&mut layout, ops);
+    let mut layout = check_ops(ops);
+    let focus = |layout: &Layout<TestWindow>| layout.focus().map(|win| *win.id());
+    assert_eq!(focus(&layout), Some(0));
 
-    // Unfullscreen should return the window back to floating.
+    // Each column takes the whole view, so the next one starts off-screen.
+    layout.focus_spatial(Direction::Right);
+    assert_eq!(focus(&layout), Some(1));
+
+    check_ops_on_layout(&mut layout, [Op::CompleteAnimations]);
+    layout.focus_spatial(Direction::Right);
+    assert_eq!(focus(&layout), Some(2));
+
+    // Going back picks the adjacent column rather than the one further away.
+    check_ops_on_layout(&mut layout, [Op::CompleteAnimations]);
+    layout.focus_spatial(Direction::Left);
+    assert_eq!(focus(&layout), Some(1));
+}
+
+fn active_column_tile_ids(layout: &Layout<TestWindow>) -> Vec<Vec<usize>> {
     let scrolling = layout.active_workspace().unwrap().scrolling();
-    assert!(scrolling.tiles().next().is_none());
+    let col = scrolling
+        .columns()
+        .nth(scrolling.active_column_idx())
+        .unwrap();
+    col.tiles()
+        .map(|(tile, _)| tile.windows().map(|win| *win.id()).collect())
+        .collect()
 }
 
 #[test]
-fn move_column_to_workspace_maximize_and_fullscreen() {
+fn toggle_maximize_column_with_group() {
     let ops = [
         Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
         Op::AddWindow {
             params: TestWindowParams::new(1),
         },
-        Op::MaximizeWindowToEdges { id: None },
-        Op::FullscreenWindow(1),
-        Op::MoveColumnToWorkspaceDown(true),
-        Op::FullscreenWindow(1),
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::ToggleGroup,
+        Op::ToggleMaximizeColumnWithGroup,
+        Op::Communicate(2),
     ];
+    let mut layout = check_ops(ops);
 
-    let layout = check_ops(ops);
-    let (_, win) = layout.windows().next().unwrap();
+    // All tiles are merged into the focused group, which stays on the same tab.
+    assert_eq!(active_column_tile_ids(&layout), [vec![2, 0, 1]]);
+    let (_, win) = layout.windows().find(|(_, win)| win.id() == &2).unwrap();
+    assert_eq!(win.requested_size().unwrap().w, 1280 - 16 * 2);
+    assert_eq!(layout.focus().unwrap().id(), &2);
 
-    // Unfullscreening should return to maximized because the window was maximized before.
-    assert_eq!(win.pending_sizing_mode(), SizingMode::Maximized);
+    check_ops_on_layout(&mut layout, [Op::ToggleMaximizeColumnWithGroup]);
+    assert_eq!(active_column_tile_ids(&layout), [vec![0], vec![1], vec![2]]);
+    assert_eq!(layout.focus().unwrap().id(), &2);
+
+    // Windows that leave the group while maximized are not restored.
+    let ops = [
+        Op::ToggleMaximizeColumnWithGroup,
+        Op::CloseWindow(0),
+        Op::ToggleMaximizeColumnWithGroup,
+    ];
+    check_ops_on_layout(&mut layout, ops);
+    assert_eq!(active_column_tile_ids(&layout), [vec![1], vec![2]]);
 }
 
 #[test]
-fn move_window_to_workspace_maximize_and_fullscreen() {
+fn toggle_group_display_mode() {
     let ops = [
         Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
         Op::AddWindow {
             params: TestWindowParams::new(1),
         },
-        Op::MaximizeWindowToEdges { id: None },
-        Op::FullscreenWindow(1),
-        Op::MoveWindowToWorkspaceDown(true),
-        Op::FullscreenWindow(1),
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::FocusWindowUp,
+        Op::FocusWindowUp,
+        Op::ToggleGroup,
+        Op::FocusWindowDown,
+        Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Up),
+        Op::FocusWindowDown,
+        Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Up),
+        Op::FocusPreviousWindow,
+        Op::ToggleGroupDisplayMode,
+        Op::Communicate(0),
+        Op::Communicate(1),
+        Op::Communicate(2),
     ];
+    let mut layout = check_ops(ops);
 
-    let layout = check_ops(ops);
-    let (_, win) = layout.windows().next().unwrap();
+    let height = |layout: &Layout<TestWindow>, id| {
+        let win = layout.windows().find(|(_, win)| win.id() == &id).unwrap().1;
+        win.requested_size().unwrap().h
+    };
 
-    // Unfullscreening should return to maximized because the window was maximized before.
-    //
-    // FIXME: it currently doesn't because windows themselves can only be either fullscreen or
-    // maximized. So when a window is fullscreen, whether it is also maximized or not is stored in
-    // the column. MoveWindowToWorkspace removes the window from the column and this information is
-    // forgotten.
-    assert_eq!(win.pending_sizing_mode(), SizingMode::Normal);
+    // All windows split the tile height, with gaps between them.
+    assert_eq!(active_column_tile_ids(&layout), [vec![0, 1, 2]]);
+    assert_eq!([0, 1, 2].map(|id| height(&layout, id)), [218, 218, 220]);
+    assert_eq!(layout.focus().unwrap().id(), &1);
+
+    // Switching back to tabbed keeps the active tab, which takes up the whole tile again.
+    let ops = [Op::ToggleGroupDisplayMode, Op::Communicate(1)];
+    check_ops_on_layout(&mut layout, ops);
+    assert_eq!(active_column_tile_ids(&layout), [vec![0, 1, 2]]);
+    assert_eq!(layout.focus().unwrap().id(), &1);
+    assert!(height(&layout, 1) > 220);
+
+    let ops = [
+        Op::SetGroupDisplayMode(GroupDisplayMode::Stacked),
+        Op::Communicate(1),
+    ];
+    check_ops_on_layout(&mut layout, ops);
+    assert_eq!(height(&layout, 1), 218);
+}
+
+#[test]
+fn stacked_group_applies_to_every_window() {
+    let params = |id, max_h| TestWindowParams {
+        min_max_size: (Size::from((0, 0)), Size::from((0, max_h))),
+        ..TestWindowParams::new(id)
+    };
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: params(0, 100),
+        },
+        Op::AddWindow {
+            params: params(1, 100),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::AddWindow {
+            params: params(2, 100),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::FocusWindowUp,
+        Op::FocusWindowUp,
+        Op::ToggleGroup,
+        Op::FocusWindowDown,
+        Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Up),
+        Op::FocusWindowDown,
+        Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Up),
+        Op::SetGroupDisplayMode(GroupDisplayMode::Stacked),
+        Op::Communicate(0),
+        Op::Communicate(1),
+        Op::Communicate(2),
+    ];
+    let mut layout = check_ops(ops);
+    assert_eq!(active_column_tile_ids(&layout), [vec![0, 1, 2]]);
+
+    // Every window is reported with its own size and offset.
+    let mut ipc = Vec::new();
+    layout.with_windows(|win, _, _, layout| ipc.push((*win.id(), win.size(), layout)));
+    ipc.sort_by_key(|(id, _, _)| *id);
+    assert_eq!(
+        ipc.iter().map(|(id, _, _)| *id).collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+    for (_, size, layout) in &ipc {
+        assert_eq!(layout.window_size, (size.w, size.h));
+    }
+    let offsets: Vec<_> = ipc
+        .iter()
+        .map(|(_, _, layout)| layout.window_offset_in_tile.1)
+        .collect();
+    assert!(offsets[0] < offsets[1] && offsets[1] < offsets[2]);
+
+    // The group can grow as tall as all of its windows together.
+    let max_height = |layout: &Layout<TestWindow>| {
+        let scrolling = layout.active_workspace().unwrap().scrolling();
+        let col = scrolling.columns().next().unwrap();
+        let (tile, _) = col.tiles().next().unwrap();
+        tile.max_size_nonfullscreen().h
+    };
+    assert!(max_height(&layout) >= 300.);
+
+    // But a window without a max height makes it unbounded.
+    let ops = [
+        Op::AddWindow {
+            params: params(3, 0),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::MoveWindowIntoOrOutOfGroup(WindowMoveDirection::Up),
+        Op::Communicate(3),
+    ];
+    check_ops_on_layout(&mut layout, ops);
+    assert_eq!(active_column_tile_ids(&layout), [vec![0, 1, 2, 3]]);
+    assert_eq!(max_height(&layout), 0.);
 }
 
 fn parent_id_causes_loop(layout: &Layout<TestWindow>, id: usize, mut parent_id: usize) -> bool {
@@ -3745,6 +5427,18 @@ fn arbitrary_center_focused_column() -> impl Strategy<Value = CenterFocusedColum
     ]
 }
 
+fn arbitrary_on_empty_workspace() -> impl Strategy<Value = OnEmptyWorkspace> {
+    prop_oneof![
+        Just(OnEmptyWorkspace::Keep),
+        Just(OnEmptyWorkspace::Remove),
+        Just(OnEmptyWorkspace::RemoveIfUnnamed),
+    ]
+}
+
+fn arbitrary_focus_after_close() -> impl Strategy<Value = FocusAfterClose> {
+    prop_oneof![Just(FocusAfterClose::Neighbor), Just(FocusAfterClose::Mru)]
+}
+
 fn arbitrary_tab_indicator_position() -> impl Strategy<Value = TabIndicatorPosition> {
     prop_oneof![
         Just(TabIndicatorPosition::Top),
@@ -3817,6 +5511,19 @@ prop_compose! {
     }
 }
 
+prop_compose! {
+    fn arbitrary_overscroll()(
+        off in any::<bool>(),
+        resistance in prop::option::of((0f64..=1f64).prop_map(FloatOrInt)),
+    ) -> niri_config::OverscrollPart {
+        niri_config::OverscrollPart {
+            off,
+            on: !off,
+            resistance,
+        }
+    }
+}
+
 prop_compose! {
     fn arbitrary_layout_part()(
         gaps in prop::option::of(arbitrary_spacing().prop_map(FloatOrInt)),
@@ -3828,6 +5535,9 @@ prop_compose! {
         center_focused_column in prop::option::of(arbitrary_center_focused_column()),
         always_center_single_column in prop::option::of(any::<bool>().prop_map(Flag)),
         empty_workspace_above_first in prop::option::of(any::<bool>().prop_map(Flag)),
+        on_empty_workspace in prop::option::of(arbitrary_on_empty_workspace()),
+        focus_after_close in prop::option::of(arbitrary_focus_after_close()),
+        overscroll in prop::option::of(arbitrary_overscroll()),
     ) -> niri_config::LayoutPart {
         niri_config::LayoutPart {
             gaps,
@@ -3835,10 +5545,13 @@ prop_compose! {
             center_focused_column,
             always_center_single_column,
             empty_workspace_above_first,
+            on_empty_workspace,
+            focus_after_close,
             focus_ring,
             border,
             shadow,
             tab_indicator,
+            overscroll,
             ..Default::default()
         }
     }
@@ -3,8 +3,8 @@ use std::rc::Rc;
 use std::sync::atomic::Ordering;
 
 use niri_config::utils::MergeWith as _;
-use niri_config::{Color, CornerRadius, GradientInterpolation};
-use niri_ipc::WindowLayout;
+use niri_config::{BlockOutFrom, Color, CornerRadius, GradientInterpolation};
+use niri_ipc::{GroupDisplayMode, WindowLayout};
 use portable_atomic::AtomicU8;
 use smithay::backend::renderer::element::surface::WaylandSurfaceRenderElement;
 use smithay::backend::renderer::element::{Element, Kind};
@@ -13,6 +13,7 @@ use smithay::utils::{Logical, Point, Rectangle, Scale, Size};
 
 use super::focus_ring::{FocusRing, FocusRingRenderElement};
 use super::opening_window::{OpenAnimation, OpeningWindowRenderElement};
+use super::scrolling::PreMaximizeGeometry;
 use super::shadow::Shadow;
 use super::{
     HitType, LayoutElement, LayoutElementRenderElement, LayoutElementRenderSnapshot, Options,
@@ -28,7 +29,7 @@ use crate::render_helpers::blur::element::{Blur, BlurRenderContext, BlurRenderEl
 use crate::render_helpers::border::BorderRenderElement;
 use crate::render_helpers::clipped_surface::{ClippedSurfaceRenderElement, RoundedCornerDamage};
 use crate::render_helpers::damage::ExtraDamage;
-use crate::render_helpers::offscreen::{OffscreenBuffer, OffscreenRenderElement};
+use crate::render_helpers::offscreen::{OffscreenBuffer, OffscreenData, OffscreenRenderElement};
 use crate::render_helpers::renderer::NiriRenderer;
 use crate::render_helpers::resize::ResizeRenderElement;
 use crate::render_helpers::shaders::Shaders;
@@ -41,6 +42,7 @@ use crate::utils::transaction::Transaction;
 use crate::utils::{
     baba_is_float_offset, round_logical_in_physical, round_logical_in_physical_max1,
 };
+use crate::window::ResolvedWindowRules;
 
 #[derive(Debug)]
 struct WindowSizeOverride {
@@ -357,6 +359,9 @@ where
     /// Whether the tile should float upon unfullscreening.
     pub(super) restore_to_floating: bool,
 
+    /// Where the tile was in its column before `toggle-maximize-window-to-edges`.
+    pub(super) pre_maximize: Option<PreMaximizeGeometry<W::Id>>,
+
     /// The size that the window should assume when going floating.
     ///
     /// This is generally the last size the window had when it was floating. It can be unknown if
@@ -411,6 +416,9 @@ where
     /// Tab indicator for when this tile is grouped.
     tab_indicator: TabIndicator,
 
+    /// How the windows are displayed when this tile is grouped.
+    group_display_mode: GroupDisplayMode,
+
     /// HACK: Temporary size override, since after switching tabs, there will be a few frames of the new
     /// window still having to adjust, which causes a jerking visual without this compensation.
     window_size_override: WindowSizeOverride,
@@ -508,10 +516,7 @@ where
         let sizing_mode = window.sizing_mode();
         let tab_indicator_config = options.layout.tab_indicator;
 
-        // Blur needs to be enabled explicitly
-        let mut blur_config = options.layout.blur;
-        blur_config.on = false;
-        blur_config.merge_with(&rules.blur);
+        let blur_config = Self::resolve_blur_config(&options, rules);
 
         Self {
             window: WindowInner::Single(Some(window)),
@@ -522,6 +527,7 @@ where
             sizing_mode,
             fullscreen_backdrop: SolidColorBuffer::new((0., 0.), [0., 0., 0., 1.]),
             restore_to_floating: false,
+            pre_maximize: None,
             floating_window_size: None,
             floating_pos: None,
             floating_preset_width_idx: None,
@@ -540,6 +546,7 @@ where
             options,
             window_size_override: WindowSizeOverride::new(),
             tab_indicator: TabIndicator::new(tab_indicator_config),
+            group_display_mode: GroupDisplayMode::default(),
         }
     }
 
@@ -583,11 +590,16 @@ where
         self.tab_indicator
             .update_config(self.options.layout.tab_indicator);
 
+        let blur_config = Self::resolve_blur_config(&self.options, rules);
+        self.blur.update_config(blur_config);
+    }
+
+    fn resolve_blur_config(options: &Options, rules: &ResolvedWindowRules) -> niri_config::Blur {
         // Blur needs to be enabled explicitly
-        let mut blur_config = self.options.layout.blur;
+        let mut blur_config = options.layout.blur;
         blur_config.on = false;
         blur_config.merge_with(&rules.blur);
-        self.blur.update_config(blur_config);
+        blur_config
     }
 
     pub fn update_shaders(&mut self) {
@@ -723,6 +735,12 @@ where
             }
         }
 
+        // The resize animation only covers the focused window, which would look wrong while all
+        // windows are shown.
+        if self.is_stacked() {
+            self.resize_animation = None;
+        }
+
         let round_max1 = |logical| round_logical_in_physical_max1(self.scale, logical);
 
         let rules = self.focused_window().rules().clone();
@@ -741,6 +759,11 @@ where
         let shadow_config = self.options.layout.shadow.merged_with(&rules.shadow);
         self.shadow.update_config(shadow_config);
 
+        // Rules may have changed together with the window state (e.g. is-floating), so blur needs
+        // to follow them too.
+        let blur_config = Self::resolve_blur_config(&self.options, &rules);
+        self.blur.update_config(blur_config);
+
         let window_size = self.window_size();
         let radius = rules
             .geometry_corner_radius
@@ -828,7 +851,7 @@ where
             border_window_size,
             is_active,
             !draw_border_with_background,
-            self.window.focused_window().is_urgent(),
+            self.has_urgent_window(),
             Rectangle::new(
                 view_rect.loc - Point::from((border_width, border_width)),
                 view_rect.size,
@@ -864,7 +887,7 @@ where
             animated_tile_size,
             is_active,
             !draw_focus_ring_with_background,
-            self.window.focused_window().is_urgent(),
+            self.has_urgent_window(),
             view_rect,
             radius,
             self.scale,
@@ -873,21 +896,14 @@ where
 
         self.fullscreen_backdrop.resize(animated_tile_size);
 
-        self.blur
-            .update_render_elements(self.focused_window().wants_blur());
+        let wants_blur = self
+            .visible_windows()
+            .iter()
+            .any(|(window, _)| window.wants_blur());
+        self.blur.update_render_elements(wants_blur);
 
         match &self.window {
-            WindowInner::Single(_) => {
-                self.tab_indicator.update_render_elements(
-                    vec![],
-                    false,
-                    Rectangle::new(Point::default(), self.animated_bounding_box()),
-                    view_rect,
-                    is_active,
-                    self.scale,
-                );
-            }
-            WindowInner::Multiple { windows, focus_idx } => {
+            WindowInner::Multiple { windows, focus_idx } if !self.is_stacked() => {
                 let tabs = windows
                     .iter()
                     .enumerate()
@@ -913,6 +929,16 @@ where
                     self.scale,
                 );
             }
+            _ => {
+                self.tab_indicator.update_render_elements(
+                    vec![],
+                    false,
+                    Rectangle::new(Point::default(), self.animated_bounding_box()),
+                    view_rect,
+                    is_active,
+                    self.scale,
+                );
+            }
         }
     }
 
@@ -1051,6 +1077,14 @@ where
     }
 
     pub fn remove_window(&mut self, id: &W::Id) -> usize {
+        if self.is_stacked() {
+            // The remaining windows share the whole area.
+            let area = self.window_size();
+            let out = self.window.remove_window(id);
+            self.request_stacked_size(area, false, None);
+            return out;
+        }
+
         let current_size = self.focused_window().size();
         let current_mode = self.focused_window().sizing_mode();
         self.window_size_override.set(current_size);
@@ -1078,6 +1112,10 @@ where
     }
 
     pub fn focus_next(&mut self) -> &W {
+        if self.is_stacked() {
+            return self.window.focus_next();
+        }
+
         let current_size = self.focused_window().size();
         let current_mode = self.focused_window().sizing_mode();
         self.window_size_override.set(current_size);
@@ -1091,6 +1129,10 @@ where
     }
 
     pub fn focus_prev(&mut self) -> &W {
+        if self.is_stacked() {
+            return self.window.focus_prev();
+        }
+
         let current_size = self.focused_window().size();
         let current_mode = self.focused_window().sizing_mode();
         self.window_size_override.set(current_size);
@@ -1104,6 +1146,8 @@ where
     }
 
     pub fn ungroup_single(&mut self, id: &W::Id) -> Option<W> {
+        let was_stacked = self.is_stacked();
+        let area = self.window_size();
         let current_size = self.focused_window().size();
         let current_mode = self.focused_window().sizing_mode();
         let extra_size = self.tab_indicator_extra_size();
@@ -1114,8 +1158,15 @@ where
             self.animate_move_from(self.tab_indicator_content_offset());
         }
 
-        self.focused_window_mut()
-            .request_size(current_size, current_mode, false, None);
+        if was_stacked && self.is_grouped_tile() {
+            self.request_stacked_size(area, false, None);
+        } else if was_stacked {
+            self.focused_window_mut()
+                .request_size(area.to_i32_round(), current_mode, false, None);
+        } else {
+            self.focused_window_mut()
+                .request_size(current_size, current_mode, false, None);
+        }
 
         out
     }
@@ -1137,7 +1188,7 @@ where
     }
 
     pub fn tab_indicator_content_offset(&self) -> Point<f64, Logical> {
-        if self.focused_window().sizing_mode() != SizingMode::Normal {
+        if self.focused_window().sizing_mode() != SizingMode::Normal || self.is_stacked() {
             return Point::new(0., 0.);
         }
 
@@ -1151,7 +1202,7 @@ where
     }
 
     pub fn tab_indicator_extra_size(&self) -> Size<f64, Logical> {
-        if self.focused_window().sizing_mode() != SizingMode::Normal {
+        if self.focused_window().sizing_mode() != SizingMode::Normal || self.is_stacked() {
             return Size::new(0., 0.);
         }
 
@@ -1202,6 +1253,10 @@ where
             return true;
         }
 
+        if self.is_stacked() {
+            return self.window.focus_window(id);
+        }
+
         let current_size = self.focused_window().size();
         let current_mode = self.focused_window().sizing_mode();
 
@@ -1226,6 +1281,167 @@ where
         self.window.iter_mut()
     }
 
+    pub const fn group_display_mode(&self) -> GroupDisplayMode {
+        self.group_display_mode
+    }
+
+    /// Sets how the windows are displayed when this tile is grouped.
+    ///
+    /// The focused window is kept, so switching back to tabbed shows the same tab as before.
+    pub fn set_group_display_mode(&mut self, mode: GroupDisplayMode) {
+        if self.group_display_mode == mode {
+            return;
+        }
+
+        self.group_display_mode = mode;
+        self.resize_animation = None;
+        self.window_size_override = WindowSizeOverride::new();
+    }
+
+    /// Whether this is a group showing all of its windows at once.
+    fn is_stacked(&self) -> bool {
+        self.is_grouped_tile()
+            && self.group_display_mode == GroupDisplayMode::Stacked
+            && self.focused_window().sizing_mode() == SizingMode::Normal
+    }
+
+    /// Gap between the windows of a stacked group.
+    fn stacked_gap(&self) -> f64 {
+        round_logical_in_physical(self.scale, self.options.layout.gaps)
+    }
+
+    /// Returns the windows that are shown, with their geometry relative to the window area.
+    fn visible_windows(&self) -> Vec<(&W, Rectangle<f64, Logical>)> {
+        if !self.is_stacked() {
+            let geo = Rectangle::from_size(self.window_size());
+            return vec![(self.focused_window(), geo)];
+        }
+
+        let gap = self.stacked_gap();
+        let mut y = 0.;
+        self.windows()
+            .map(|window| {
+                let size = window
+                    .size()
+                    .to_f64()
+                    .to_physical_precise_round(self.scale)
+                    .to_logical(self.scale);
+                let geo = Rectangle::new(Point::from((0., y)), size);
+                y += size.h + gap;
+                (window, geo)
+            })
+            .collect()
+    }
+
+    /// Returns the offset of the focused window within the window area.
+    fn focused_window_offset(&self) -> Point<f64, Logical> {
+        let id = self.focused_window().id();
+        self.visible_windows()
+            .into_iter()
+            .find(|(window, _)| window.id() == id)
+            .map_or_else(Point::default, |(_, geo)| geo.loc)
+    }
+
+    /// Whether any of the shown windows is urgent.
+    fn has_urgent_window(&self) -> bool {
+        self.visible_windows()
+            .iter()
+            .any(|(window, _)| window.is_urgent())
+    }
+
+    /// Returns the strictest block-out rule among the shown windows.
+    fn block_out_from(&self) -> Option<BlockOutFrom> {
+        let mut rv = None;
+        for (window, _) in self.visible_windows() {
+            match window.rules().block_out_from {
+                Some(BlockOutFrom::ScreenCapture) => return Some(BlockOutFrom::ScreenCapture),
+                Some(BlockOutFrom::Screencast) => rv = Some(BlockOutFrom::Screencast),
+                None => (),
+            }
+        }
+        rv
+    }
+
+    /// Sets the offscreen data on every shown window, since they all render into the offscreen.
+    fn set_windows_offscreen_data(&self, data: OffscreenData) {
+        for (window, _) in self.visible_windows() {
+            window.set_offscreen_data(Some(OffscreenData {
+                id: data.id.clone(),
+                states: data.states.clone(),
+            }));
+        }
+    }
+
+    /// Returns the opacity of the window from its rules, interpolated towards opaque at
+    /// fullscreen.
+    fn window_alpha(window: &W, fullscreen_progress: f64) -> f32 {
+        if window.is_ignoring_opacity_window_rule() {
+            return 1.;
+        }
+
+        let alpha = window
+            .opacity_override()
+            .or(window.rules().opacity)
+            .unwrap_or(1.)
+            .clamp(0., 1.);
+
+        // Interpolate towards alpha = 1. at fullscreen.
+        let p = fullscreen_progress as f32;
+        alpha * (1. - p) + 1. * p
+    }
+
+    /// Computes the size of the stacked window area from the size of each window.
+    fn stacked_size(&self, window_size: impl Fn(&W) -> Size<i32, Logical>) -> Size<f64, Logical> {
+        let round = |size: Size<i32, Logical>| {
+            size.to_f64()
+                .to_physical_precise_round(self.scale)
+                .to_logical(self.scale)
+        };
+
+        let gap = self.stacked_gap();
+        let mut size = round(window_size(self.focused_window()));
+        size.h = self
+            .windows()
+            .map(|window| round(window_size(window)).h + gap)
+            .sum::<f64>()
+            - gap;
+        size
+    }
+
+    /// Splits the window area vertically between all windows of a stacked group.
+    fn request_stacked_size(
+        &mut self,
+        size: Size<f64, Logical>,
+        animate: bool,
+        transaction: Option<Transaction>,
+    ) {
+        let gap = self.stacked_gap();
+        let WindowInner::Multiple { windows, .. } = &mut self.window else {
+            return;
+        };
+
+        let count = windows.len() as i32;
+        let width = f64::max(1., size.w).floor() as i32;
+        let total = f64::max(f64::from(count), size.h - gap * f64::from(count - 1)).floor() as i32;
+        let height = total / count;
+
+        for (idx, window) in windows.iter_mut().enumerate() {
+            // The last window takes up the rounding remainder.
+            let height = if idx as i32 == count - 1 {
+                total - height * (count - 1)
+            } else {
+                height
+            };
+
+            window.request_size(
+                Size::from((width, height)),
+                SizingMode::Normal,
+                animate,
+                transaction.clone(),
+            );
+        }
+    }
+
     pub const fn sizing_mode(&self) -> SizingMode {
         self.sizing_mode
     }
@@ -1361,6 +1577,10 @@ where
     }
 
     pub fn window_size(&self) -> Size<f64, Logical> {
+        if self.is_stacked() {
+            return self.stacked_size(|window| window.size());
+        }
+
         let mut size = self
             .window_size_override
             .get()
@@ -1374,6 +1594,11 @@ where
     }
 
     pub fn window_expected_or_current_size(&self) -> Size<f64, Logical> {
+        if self.is_stacked() {
+            return self
+                .stacked_size(|window| window.expected_size().unwrap_or_else(|| window.size()));
+        }
+
         let size = self.window.focused_window().expected_size();
         let mut size = size
             .unwrap_or_else(|| self.window.focused_window().size())
@@ -1421,6 +1646,7 @@ where
     pub fn buf_loc(&self) -> Point<f64, Logical> {
         let mut loc = Point::from((0., 0.));
         loc += self.window_loc();
+        loc += self.focused_window_offset();
         loc += self.window.focused_window().buf_loc().to_f64();
         loc
     }
@@ -1434,13 +1660,38 @@ where
             tile_size: self.tile_size().into(),
             window_size: self.window.focused_window().size().into(),
             tile_pos_in_workspace_view: None,
-            window_offset_in_tile: self.window_loc().into(),
+            window_offset_in_tile: (self.window_loc() + self.focused_window_offset()).into(),
         }
     }
 
-    fn is_in_input_region(&self, mut point: Point<f64, Logical>) -> bool {
-        point -= self.window_loc().to_f64();
-        self.window.focused_window().is_in_input_region(point)
+    /// Returns the shown windows along with their [`WindowLayout`]s.
+    ///
+    /// The per-window properties are filled in on top of `layout`, which has the tile ones.
+    pub fn windows_with_ipc_layouts(
+        &self,
+        layout: WindowLayout,
+    ) -> impl Iterator<Item = (&W, WindowLayout)> {
+        let window_loc = self.window_loc();
+        self.visible_windows()
+            .into_iter()
+            .map(move |(window, geo)| {
+                let layout = WindowLayout {
+                    window_size: window.size().into(),
+                    window_offset_in_tile: (window_loc + geo.loc).into(),
+                    ..layout.clone()
+                };
+                (window, layout)
+            })
+    }
+
+    /// Returns the shown window whose input region contains the point, along with its location
+    /// within this tile.
+    fn window_under(&self, point: Point<f64, Logical>) -> Option<(&W, Point<f64, Logical>)> {
+        let window_loc = self.window_loc();
+        self.visible_windows()
+            .into_iter()
+            .map(|(window, geo)| (window, window_loc + geo.loc))
+            .find(|(window, loc)| window.is_in_input_region(point - *loc))
     }
 
     fn is_in_activation_region(&self, point: Point<f64, Logical>) -> bool {
@@ -1452,14 +1703,15 @@ where
         let offset = self.bob_offset();
         let point = point - offset;
 
-        if self.is_in_input_region(point) {
-            let win_pos = self.buf_loc() + offset;
-            Some((Some(self.focused_window()), HitType::Input { win_pos }))
+        if let Some((window, loc)) = self.window_under(point) {
+            let win_pos = loc + window.buf_loc().to_f64() + offset;
+            Some((Some(window), HitType::Input { win_pos }))
         } else if self.is_in_activation_region(point) {
             if let WindowInner::Multiple {
                 windows,
                 focus_idx: _,
             } = &self.window
+                && !self.is_stacked()
                 && let Some(hit_idx) = self.tab_indicator.hit(
                     Rectangle::from_size(self.tile_bounding_box()),
                     windows.len(),
@@ -1499,6 +1751,12 @@ where
             size.h = f64::max(1., width.mul_add(-2., size.h));
         }
 
+        // Not going through is_stacked() because we might be leaving maximized or fullscreen.
+        if self.is_grouped_tile() && self.group_display_mode == GroupDisplayMode::Stacked {
+            self.request_stacked_size(size, animate, transaction);
+            return;
+        }
+
         size -= self.tab_indicator_extra_size();
 
         // The size request has to be i32 unfortunately, due to Wayland. We floor here instead of
@@ -1574,6 +1832,19 @@ where
     pub fn min_size_nonfullscreen(&self) -> Size<f64, Logical> {
         let mut size = self.window.focused_window().min_size().to_f64();
 
+        if self.is_stacked() {
+            let gap = self.stacked_gap();
+            size.w = self
+                .windows()
+                .map(|window| f64::from(window.min_size().w))
+                .fold(0., f64::max);
+            size.h = self
+                .windows()
+                .map(|window| f64::max(1., f64::from(window.min_size().h)) + gap)
+                .sum::<f64>()
+                - gap;
+        }
+
         // Can't go through effective_border_width() because we might be fullscreen.
         if !self.border.is_off() {
             let width = self.border.width();
@@ -1591,6 +1862,27 @@ where
     pub fn max_size_nonfullscreen(&self) -> Size<f64, Logical> {
         let mut size = self.window.focused_window().max_size().to_f64();
 
+        // The windows of a stacked group share the width, and stack up to their total height.
+        if self.is_stacked() {
+            let gap = self.stacked_gap();
+            size.w = self
+                .windows()
+                .map(|window| f64::from(window.max_size().w))
+                .filter(|w| *w > 0.)
+                .fold(0., |acc, w| if acc > 0. { f64::min(acc, w) } else { w });
+
+            // A window without a max height makes the whole group unbounded.
+            let heights: Vec<_> = self
+                .windows()
+                .map(|window| f64::from(window.max_size().h))
+                .collect();
+            size.h = if heights.iter().any(|h| *h <= 0.) {
+                0.
+            } else {
+                heights.iter().map(|h| h + gap).sum::<f64>() - gap
+            };
+        }
+
         // Can't go through effective_border_width() because we might be fullscreen.
         if !self.border.is_off() {
             let width = self.border.width();
@@ -1640,25 +1932,7 @@ where
         let fullscreen_progress = self.fullscreen_progress();
         let expanded_progress = self.expanded_progress();
 
-        let win_alpha = if self
-            .window
-            .focused_window()
-            .is_ignoring_opacity_window_rule()
-        {
-            1.
-        } else {
-            let alpha = self
-                .window
-                .focused_window()
-                .rules()
-                .opacity
-                .unwrap_or(1.)
-                .clamp(0., 1.);
-
-            // Interpolate towards alpha = 1. at fullscreen.
-            let p = fullscreen_progress as f32;
-            alpha * (1. - p) + 1. * p
-        };
+        let win_alpha = Self::window_alpha(self.window.focused_window(), fullscreen_progress);
 
         let tab_indicator_offset = self.tab_indicator_content_offset();
 
@@ -1703,17 +1977,21 @@ where
             .unwrap_or_default()
             .scaled_by(1. - expanded_progress as f32);
 
+        let visible_windows = self.visible_windows();
+
         // Popups go on top.
-        self.window.focused_window().render_popups(
-            renderer,
-            LayoutElementRenderContext {
-                location: window_render_loc,
-                scale,
-                alpha: win_alpha,
-                target,
-            },
-            &mut collector.as_child(),
-        );
+        for (win, win_geo) in &visible_windows {
+            win.render_popups(
+                renderer,
+                LayoutElementRenderContext {
+                    location: window_render_loc + win_geo.loc,
+                    scale,
+                    alpha: Self::window_alpha(win, fullscreen_progress),
+                    target,
+                },
+                &mut collector.as_child(),
+            );
+        }
 
         let mut pushed_resize = false;
         if let Some(resize) = &self.resize_animation {
@@ -1796,83 +2074,99 @@ where
             }
         }
 
-        // If we're not resizing, render the window itself.
+        // If we're not resizing, render the windows themselves.
         let has_border_shader = BorderRenderElement::has_shader(renderer);
-        let geo = Rectangle::new(window_render_loc, window_size);
 
         if !pushed_resize {
-            let radius = radius.fit_to(window_size.w as f32, window_size.h as f32);
-
-            let clip_shader = clip_to_geometry
+            let any_clip_to_geometry = fullscreen_progress < 1.
+                && visible_windows
+                    .iter()
+                    .any(|(win, _)| win.rules().clip_to_geometry == Some(true));
+            let clip_shader = any_clip_to_geometry
                 .then(|| Shaders::get(renderer).clipped_surface.clone())
                 .flatten();
 
-            let clip = |elem: LayoutElementRenderElement<R>| match elem {
-                LayoutElementRenderElement::Wayland(elem) => {
-                    // If we should clip to geometry, render a clipped window.
-                    if clip_to_geometry
-                        && let Some(shader) = clip_shader.clone()
-                        && ClippedSurfaceRenderElement::will_clip(&elem, scale, geo, radius)
-                    {
-                        let view_src = elem.view().src;
-                        let buf_size = elem.buffer_size();
-                        return ClippedSurfaceRenderElement::new(
-                            elem, view_src, buf_size, scale, geo, shader, radius, None, 0.,
-                        )
-                        .into();
-                    }
-
-                    // Otherwise, render it normally.
-                    LayoutElementRenderElement::Wayland(elem).into()
-                }
-                LayoutElementRenderElement::SolidColor(elem) => {
-                    // In this branch we're rendering a blocked-out window with a solid
-                    // color. We need to render it with a rounded corner shader even if
-                    // clip_to_geometry is false, because in this case we're assuming that
-                    // the unclipped window CSD already has corners rounded to the
-                    // user-provided radius, so our blocked-out rendering should match that
-                    // radius.
-                    if radius != CornerRadius::default() && has_border_shader {
-                        return BorderRenderElement::new(
-                            geo.size,
-                            Rectangle::from_size(geo.size),
-                            GradientInterpolation::default(),
-                            Color::from_color32f(elem.color()),
-                            Color::from_color32f(elem.color()),
-                            0.,
-                            Rectangle::from_size(geo.size),
-                            0.,
-                            radius,
-                            scale.x as f32,
-                            1.,
-                        )
-                        .with_location(geo.loc)
-                        .into();
-                    }
-
-                    // Otherwise, render the solid color as is.
-                    LayoutElementRenderElement::SolidColor(elem).into()
-                }
-            };
-
-            if clip_to_geometry && clip_shader.is_some() {
+            if any_clip_to_geometry && clip_shader.is_some() {
                 let damage = self.rounded_corner_damage.element();
                 collector.push_element(damage.with_location(window_render_loc));
             }
 
-            self.window.focused_window().render_normal(
-                renderer,
-                LayoutElementRenderContext {
-                    location: window_render_loc,
-                    scale,
-                    alpha: win_alpha,
-                    target,
-                },
-                &mut |elem| collector.push_element::<TileRenderElement<R>>(clip(elem)),
-            );
+            for (win, win_geo) in &visible_windows {
+                let geo = Rectangle::new(window_render_loc + win_geo.loc, win_geo.size);
+
+                // Each window of a stacked group follows its own rules.
+                let rules = win.rules();
+                let clip_to_geometry =
+                    fullscreen_progress < 1. && rules.clip_to_geometry == Some(true);
+                let radius = rules
+                    .geometry_corner_radius
+                    .unwrap_or_default()
+                    .scaled_by(1. - expanded_progress as f32)
+                    .fit_to(geo.size.w as f32, geo.size.h as f32);
+                let win_alpha = Self::window_alpha(win, fullscreen_progress);
+
+                let clip = |elem: LayoutElementRenderElement<R>| match elem {
+                    LayoutElementRenderElement::Wayland(elem) => {
+                        // If we should clip to geometry, render a clipped window.
+                        if clip_to_geometry
+                            && let Some(shader) = clip_shader.clone()
+                            && ClippedSurfaceRenderElement::will_clip(&elem, scale, geo, radius)
+                        {
+                            let view_src = elem.view().src;
+                            let buf_size = elem.buffer_size();
+                            return ClippedSurfaceRenderElement::new(
+                                elem, view_src, buf_size, scale, geo, shader, radius, None, 0.,
+                            )
+                            .into();
+                        }
+
+                        // Otherwise, render it normally.
+                        LayoutElementRenderElement::Wayland(elem).into()
+                    }
+                    LayoutElementRenderElement::SolidColor(elem) => {
+                        // In this branch we're rendering a blocked-out window with a solid
+                        // color. We need to render it with a rounded corner shader even if
+                        // clip_to_geometry is false, because in this case we're assuming that
+                        // the unclipped window CSD already has corners rounded to the
+                        // user-provided radius, so our blocked-out rendering should match that
+                        // radius.
+                        if radius != CornerRadius::default() && has_border_shader {
+                            return BorderRenderElement::new(
+                                geo.size,
+                                Rectangle::from_size(geo.size),
+                                GradientInterpolation::default(),
+                                Color::from_color32f(elem.color()),
+                                Color::from_color32f(elem.color()),
+                                0.,
+                                Rectangle::from_size(geo.size),
+                                0.,
+                                radius,
+                                scale.x as f32,
+                                1.,
+                            )
+                            .with_location(geo.loc)
+                            .into();
+                        }
+
+                        // Otherwise, render the solid color as is.
+                        LayoutElementRenderElement::SolidColor(elem).into()
+                    }
+                };
+
+                win.render_normal(
+                    renderer,
+                    LayoutElementRenderContext {
+                        location: geo.loc,
+                        scale,
+                        alpha: win_alpha,
+                        target,
+                    },
+                    &mut |elem| collector.push_element::<TileRenderElement<R>>(clip(elem)),
+                );
+            }
         }
 
-        if self.focused_window().sizing_mode() == SizingMode::Normal {
+        if self.focused_window().sizing_mode() == SizingMode::Normal && !self.is_stacked() {
             self.tab_indicator
                 .render(renderer, tab_indicator_loc, &mut collector.as_child());
         }
@@ -2005,7 +2299,7 @@ where
         RenderSnapshot {
             contents,
             blocked_out_contents,
-            block_out_from: self.window.focused_window().rules().block_out_from,
+            block_out_from: self.block_out_from(),
             size: self.animated_bounding_box(),
             texture: Default::default(),
             blocked_out_texture: Default::default(),
@@ -2028,6 +2322,11 @@ where
         &self.options
     }
 
+    #[cfg(test)]
+    pub fn blur_config(&self) -> &niri_config::Blur {
+        self.blur.config()
+    }
+
     #[cfg(test)]
     pub fn view_size(&self) -> Size<f64, Logical> {
         self.view_size
@@ -2071,7 +2370,9 @@ where
             .map_or(1., |alpha| alpha.anim.clamped_value()) as f32;
 
         let mut pushed = false;
-        self.window.focused_window().set_offscreen_data(None);
+        for window in self.windows() {
+            window.set_offscreen_data(None);
+        }
 
         if let Some(open) = &self.open_animation {
             let renderer = renderer.as_gles_renderer();
@@ -2093,7 +2394,7 @@ where
                 tile_alpha,
             ) {
                 Ok((elem, data)) => {
-                    self.window.focused_window().set_offscreen_data(Some(data));
+                    self.set_windows_offscreen_data(data);
                     collector.push_element(elem);
                     pushed = true;
                 }
@@ -2118,7 +2419,7 @@ where
                     let offset = elem.offset();
                     let elem = elem.with_alpha(tile_alpha).with_offset(location + offset);
 
-                    self.window.focused_window().set_offscreen_data(Some(data));
+                    self.set_windows_offscreen_data(data);
                     collector.push_element(elem);
                     pushed = true;
                 }
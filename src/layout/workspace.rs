@@ -7,7 +7,7 @@ use niri_config::{
     CenterFocusedColumn, CornerRadius, OutputName, PresetSize, WindowMoveDirection,
     Workspace as WorkspaceConfig,
 };
-use niri_ipc::{PositionChange, SizeChange, WindowLayout};
+use niri_ipc::{Direction, GroupDisplayMode, PositionChange, SizeChange, WindowLayout};
 use smithay::backend::renderer::element::Kind;
 use smithay::backend::renderer::gles::GlesRenderer;
 use smithay::desktop::{Window, layer_map_for_output};
@@ -41,7 +41,7 @@ use crate::utils::id::IdCounter;
 use crate::utils::render::{PushRenderElement, Render};
 use crate::utils::transaction::{Transaction, TransactionBlocker};
 use crate::utils::{
-    ResizeEdge, ensure_min_max_size, ensure_min_max_size_maybe_zero, output_size,
+    ResizeEdge, client_scale, ensure_min_max_size, ensure_min_max_size_maybe_zero, output_size,
     send_scale_transform,
 };
 use crate::window::ResolvedWindowRules;
@@ -72,6 +72,9 @@ pub struct Workspace<W: LayoutElement> {
     /// disconnected, preserved until a new output is connected.
     scale: smithay::output::Scale,
 
+    /// Latest known scale that clients should render at, see [`client_scale()`].
+    client_scale: smithay::output::Scale,
+
     /// Latest known output transform for this workspace.
     ///
     /// This should be set from the current workspace output, or, if all outputs have been
@@ -110,6 +113,9 @@ pub struct Workspace<W: LayoutElement> {
     /// Optional name of this workspace.
     pub(super) name: Option<String>,
 
+    /// Stable UUID of this workspace, assigned while it has a name.
+    uuid: Option<String>,
+
     /// Layout config overrides for this workspace.
     layout_config: Option<niri_config::LayoutPart>,
 
@@ -263,6 +269,7 @@ impl<W: LayoutElement> Workspace<W> {
             floating_is_active: FloatingActive::No,
             original_output,
             scale,
+            client_scale: client_scale(&output),
             transform: output.current_transform(),
             view_size,
             working_area,
@@ -273,6 +280,7 @@ impl<W: LayoutElement> Workspace<W> {
             base_options,
             options,
             name: config.map(|c| c.name.0),
+            uuid: None,
             layout_config,
             id: WorkspaceId::next(),
         }
@@ -327,6 +335,7 @@ impl<W: LayoutElement> Workspace<W> {
             floating_is_active: FloatingActive::No,
             output: None,
             scale,
+            client_scale: scale,
             transform: Transform::Normal,
             original_output,
             view_size,
@@ -337,6 +346,7 @@ impl<W: LayoutElement> Workspace<W> {
             base_options,
             options,
             name: config.map(|c| c.name.0),
+            uuid: None,
             layout_config,
             id: WorkspaceId::next(),
         }
@@ -356,6 +366,15 @@ impl<W: LayoutElement> Workspace<W> {
 
     pub fn unname(&mut self) {
         self.name = None;
+        self.uuid = None;
+    }
+
+    pub const fn uuid(&self) -> Option<&String> {
+        self.uuid.as_ref()
+    }
+
+    pub fn set_uuid(&mut self, uuid: Option<String>) {
+        self.uuid = uuid;
     }
 
     pub fn has_windows_or_name(&self) -> bool {
@@ -531,7 +550,7 @@ impl<W: LayoutElement> Workspace<W> {
 
     fn enter_output_for_window(&self, window: &W) {
         if let Some(output) = &self.output {
-            window.set_preferred_scale_transform(self.scale, self.transform);
+            window.set_preferred_scale_transform(self.client_scale, self.transform);
             window.output_enter(output);
         }
     }
@@ -539,29 +558,35 @@ impl<W: LayoutElement> Workspace<W> {
     pub fn update_output_size(&mut self) {
         let output = self.output.as_ref().unwrap();
         let scale = output.current_scale();
+        let client_scale = client_scale(output);
         let transform = output.current_transform();
         let view_size = output_size(output);
         let working_area = compute_working_area(output);
-        self.set_view_size(scale, transform, view_size, working_area);
+        self.set_view_size(scale, client_scale, transform, view_size, working_area);
     }
 
     fn set_view_size(
         &mut self,
         scale: smithay::output::Scale,
+        client_scale: smithay::output::Scale,
         transform: Transform,
         size: Size<f64, Logical>,
         working_area: Rectangle<f64, Logical>,
     ) {
+        let fractional_scale_changed = self.scale.fractional_scale() != scale.fractional_scale();
         let scale_transform_changed = self.transform != transform
-            || self.scale.integer_scale() != scale.integer_scale()
-            || self.scale.fractional_scale() != scale.fractional_scale();
-        if !scale_transform_changed && self.view_size == size && self.working_area == working_area {
+            || self.client_scale.integer_scale() != client_scale.integer_scale()
+            || self.client_scale.fractional_scale() != client_scale.fractional_scale();
+        if !fractional_scale_changed
+            && !scale_transform_changed
+            && self.view_size == size
+            && self.working_area == working_area
+        {
             return;
         }
 
-        let fractional_scale_changed = self.scale.fractional_scale() != scale.fractional_scale();
-
         self.scale = scale;
+        self.client_scale = client_scale;
         self.transform = transform;
         self.view_size = size;
         self.working_area = working_area;
@@ -593,7 +618,7 @@ impl<W: LayoutElement> Workspace<W> {
 
         if scale_transform_changed {
             for window in self.windows() {
-                window.set_preferred_scale_transform(self.scale, self.transform);
+                window.set_preferred_scale_transform(self.client_scale, self.transform);
             }
         }
     }
@@ -777,12 +802,12 @@ impl<W: LayoutElement> Workspace<W> {
         }
     }
 
-    pub fn add_column(&mut self, column: Column<W>, activate: bool) {
+    pub fn add_column(&mut self, idx: Option<usize>, column: Column<W>, activate: bool) {
         for (tile, _) in column.tiles() {
             self.enter_output_for_window(tile.focused_window());
         }
 
-        self.scrolling.add_column(None, column, activate, None);
+        self.scrolling.add_column(idx, column, activate, None);
 
         if activate {
             self.floating_is_active = FloatingActive::No;
@@ -929,6 +954,25 @@ impl<W: LayoutElement> Workspace<W> {
         }
     }
 
+    /// Returns the remembered preset width for a new window, if remembering them is enabled.
+    pub fn resolve_remembered_width(&self, preset_idx: Option<usize>) -> Option<PresetSize> {
+        let layout = &self.options.layout;
+        if !layout.remember_column_width_per_app {
+            return None;
+        }
+
+        preset_idx.and_then(|idx| layout.preset_column_widths.get(idx).copied())
+    }
+
+    /// Returns the preset width index of the active column, if it should be remembered.
+    pub fn active_column_preset_width_idx(&self) -> Option<usize> {
+        if !self.options.layout.remember_column_width_per_app || self.floating_is_active.get() {
+            return None;
+        }
+
+        self.scrolling.active_column_preset_width_idx()
+    }
+
     pub const fn resolve_default_height(
         &self,
         default_height: Option<Option<PresetSize>>,
@@ -983,7 +1027,7 @@ impl<W: LayoutElement> Workspace<W> {
         rules: &ResolvedWindowRules,
     ) {
         window.with_surfaces(|surface, data| {
-            send_scale_transform(surface, data, self.scale, self.transform);
+            send_scale_transform(surface, data, self.client_scale, self.transform);
         });
 
         let toplevel = window.toplevel().expect("no x11 support");
@@ -1207,6 +1251,13 @@ impl<W: LayoutElement> Workspace<W> {
         self.scrolling.move_column_to_index(index);
     }
 
+    pub fn move_column_by_offset(&mut self, offset: i32) {
+        if self.floating_is_active.get() {
+            return;
+        }
+        self.scrolling.move_column_by_offset(offset);
+    }
+
     pub fn move_down(&mut self) -> bool {
         if self.floating_is_active.get() {
             self.floating.move_down();
@@ -1241,6 +1292,62 @@ impl<W: LayoutElement> Workspace<W> {
         }
     }
 
+    /// Focuses the nearest window in the direction, across tiled and floating windows.
+    ///
+    /// Windows are compared by their centers. The window whose center deviates the least from the
+    /// direction wins, with ties broken by distance. Columns scrolled off-screen are candidates
+    /// too, at their positions in the scrolling layout; only hidden floating windows are skipped.
+    pub fn focus_spatial(&mut self, direction: Direction) -> bool {
+        let Some(active) = self.active_window() else {
+            return false;
+        };
+        let active = active.id().clone();
+
+        let mut origin = None;
+        let mut candidates = Vec::new();
+        // Scrolling tiles are always "visible" here, even when they are off-screen.
+        for (tile, pos, visible) in self.tiles_with_render_positions() {
+            let center = pos + tile.tile_size().to_point().downscale(2.);
+            let id = tile.focused_window().id();
+            if *id == active {
+                origin = Some(center);
+            } else if visible {
+                candidates.push((id.clone(), center));
+            }
+        }
+        let Some(origin) = origin else {
+            return false;
+        };
+
+        let best = candidates
+            .into_iter()
+            .filter_map(|(id, center)| {
+                let delta = center - origin;
+                // Distance along the direction and across it.
+                let (along, across) = match direction {
+                    Direction::Left => (-delta.x, delta.y),
+                    Direction::Right => (delta.x, delta.y),
+                    Direction::Up => (-delta.y, delta.x),
+                    Direction::Down => (delta.y, delta.x),
+                };
+                if along <= 0. {
+                    return None;
+                }
+
+                let angle = across.abs().atan2(along);
+                let distance = along.hypot(across);
+                Some((id, angle, distance))
+            })
+            .min_by(|(_, a_angle, a_dist), (_, b_angle, b_dist)| {
+                a_angle.total_cmp(b_angle).then(a_dist.total_cmp(b_dist))
+            });
+
+        let Some((id, _, _)) = best else {
+            return false;
+        };
+        self.activate_window(&id)
+    }
+
     pub fn move_window_into_or_out_of_group(
         &mut self,
         window: Option<&W::Id>,
@@ -1298,6 +1405,13 @@ impl<W: LayoutElement> Workspace<W> {
         self.scrolling.expel_from_column();
     }
 
+    pub fn expel_window_to_column(&mut self, right: bool) {
+        if self.floating_is_active.get() {
+            return;
+        }
+        self.scrolling.expel_window_to_column(right);
+    }
+
     pub fn swap_window_in_direction(&mut self, direction: ScrollDirection) {
         if self.floating_is_active.get() {
             return;
@@ -1323,6 +1437,16 @@ impl<W: LayoutElement> Workspace<W> {
         }
     }
 
+    pub fn center_window_vertically(&mut self, id: Option<&W::Id>) {
+        if id.map_or(self.floating_is_active.get(), |id| {
+            self.floating.has_window(id)
+        }) {
+            self.floating.center_window_vertically(id);
+        } else {
+            self.scrolling.center_window_vertically(id);
+        }
+    }
+
     pub fn center_visible_columns(&mut self) {
         if self.floating_is_active.get() {
             return;
@@ -1330,6 +1454,13 @@ impl<W: LayoutElement> Workspace<W> {
         self.scrolling.center_visible_columns();
     }
 
+    pub fn distribute_columns_evenly(&mut self) {
+        if self.floating_is_active.get() {
+            return;
+        }
+        self.scrolling.distribute_columns_evenly();
+    }
+
     pub fn toggle_width(&mut self, forwards: bool) {
         if self.floating_is_active.get() {
             self.floating.toggle_window_width(None, forwards);
@@ -1384,6 +1515,15 @@ impl<W: LayoutElement> Workspace<W> {
         self.scrolling.reset_window_height(window);
     }
 
+    pub fn fill_window_height_in_column(&mut self, window: Option<&W::Id>) {
+        if window.map_or(self.floating_is_active.get(), |id| {
+            self.floating.has_window(id)
+        }) {
+            return;
+        }
+        self.scrolling.fill_window_height_in_column(window);
+    }
+
     pub fn toggle_window_width(&mut self, window: Option<&W::Id>, forwards: bool) {
         if window.map_or(self.floating_is_active.get(), |id| {
             self.floating.has_window(id)
@@ -1411,6 +1551,41 @@ impl<W: LayoutElement> Workspace<W> {
         self.scrolling.expand_column_to_available_width();
     }
 
+    pub fn toggle_column_expanded_width(&mut self) {
+        if self.floating_is_active.get() {
+            return;
+        }
+        self.scrolling.toggle_column_expanded_width();
+    }
+
+    pub fn toggle_maximize_column_with_group(&mut self) {
+        if self.floating_is_active.get() {
+            return;
+        }
+        self.scrolling.toggle_maximize_column_with_group();
+    }
+
+    pub fn set_group_display_mode(&mut self, mode: GroupDisplayMode) {
+        if self.floating_is_active.get() {
+            return;
+        }
+        self.scrolling.set_group_display_mode(mode);
+    }
+
+    pub fn toggle_group_display_mode(&mut self) {
+        if self.floating_is_active.get() {
+            return;
+        }
+        self.scrolling.toggle_group_display_mode();
+    }
+
+    pub fn align_column_width_to_neighbor(&mut self, right: bool) {
+        if self.floating_is_active.get() {
+            return;
+        }
+        self.scrolling.align_column_width_to_neighbor(right);
+    }
+
     pub fn set_fullscreen(&mut self, window: &W::Id, is_fullscreen: bool) {
         let mut restore_to_floating = false;
         if self.floating.has_window(window) {
@@ -1527,6 +1702,31 @@ impl<W: LayoutElement> Workspace<W> {
         self.set_maximized(window, !current);
     }
 
+    /// Toggles maximized, putting the window back into its original column when unmaximizing.
+    pub fn toggle_maximized_with_restore(&mut self, window: &W::Id) {
+        let current = self
+            .scrolling
+            .columns()
+            .find(|col| col.contains(window))
+            .is_some_and(|col| col.is_pending_maximized());
+
+        if current {
+            let tile = self.scrolling.find_and_focus_window(window).unwrap();
+            let geometry = tile.pre_maximize.take();
+            self.set_maximized(window, false);
+            if let Some(geometry) = geometry {
+                self.scrolling
+                    .restore_pre_maximize_geometry(window, geometry);
+            }
+        } else {
+            let geometry = self.scrolling.pre_maximize_geometry(window);
+            self.set_maximized(window, true);
+            if let Some(tile) = self.scrolling.find_and_focus_window(window) {
+                tile.pre_maximize = geometry;
+            }
+        }
+    }
+
     pub fn toggle_window_floating(&mut self, id: Option<&W::Id>) {
         let active_id = self.active_window().map(|win| win.id().clone());
         let target_is_active = id.is_none_or(|id| Some(id) == active_id.as_ref());
@@ -1534,12 +1734,18 @@ impl<W: LayoutElement> Workspace<W> {
             return;
         };
 
-        let (_, render_pos, _) = self
+        let (tile, render_pos, _) = self
             .tiles_with_render_positions()
             .find(|(tile, _, _)| tile.has_window(&id))
             .unwrap();
 
-        if self.floating.has_window(&id) {
+        // Window rules may pin the window to its current layer.
+        let is_floating = self.floating.has_window(&id);
+        if tile.focused_window().rules().force_floating == Some(is_floating) {
+            return;
+        }
+
+        if is_floating {
             let removed = self.floating.remove_tile(&id);
             // FIXME: compute closest pos?
             self.scrolling.add_tile(
@@ -1612,6 +1818,12 @@ impl<W: LayoutElement> Workspace<W> {
         }
     }
 
+    pub fn focus_floating_window_in_stack(&mut self, next: bool) {
+        if self.floating.focus_window_in_stack(next) {
+            self.focus_floating();
+        }
+    }
+
     pub fn focus_tiling(&mut self) {
         if self.floating_is_active.get() {
             self.switch_focus_floating_tiling();
@@ -1931,6 +2143,10 @@ impl<W: LayoutElement> Workspace<W> {
         self.scrolling.window_under(pos)
     }
 
+    pub fn column_under(&self, pos: Point<f64, Logical>) -> Option<usize> {
+        self.scrolling.column_under(pos)
+    }
+
     pub fn resize_edges_under(&self, pos: Point<f64, Logical>) -> Option<ResizeEdge> {
         self.tiles_with_render_positions()
             .find_map(|(tile, tile_pos, visible)| {
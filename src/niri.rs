@@ -109,7 +109,7 @@ use smithay::wayland::tablet_manager::TabletManagerState;
 use smithay::wayland::text_input::TextInputManagerState;
 use smithay::wayland::viewporter::ViewporterState;
 use smithay::wayland::virtual_keyboard::VirtualKeyboardManagerState;
-use smithay::wayland::xdg_activation::XdgActivationState;
+use smithay::wayland::xdg_activation::{XdgActivationState, XdgActivationToken};
 use smithay::wayland::xdg_foreign::XdgForeignState;
 use smithay::wayland::xdg_toplevel_tag::XdgToplevelTagManager;
 use tokio::io::AsyncWriteExt;
@@ -119,6 +119,7 @@ use crate::a11y::A11y;
 use crate::animation::{Animation, Clock};
 use crate::backend::tty::SurfaceDmabufFeedback;
 use crate::backend::{Backend, Headless, RenderResult, Tty, Winit};
+use crate::clipboard_history::ClipboardHistory;
 use crate::cursor::{CursorManager, CursorTextureCache, RenderCursor, XCursor};
 #[cfg(feature = "dbus")]
 use crate::dbus::freedesktop_locale1::Locale1ToNiri;
@@ -130,14 +131,16 @@ use crate::dbus::gnome_shell_introspect::{self, IntrospectToNiri};
 use crate::dbus::mutter_screen_cast::{self, ScreenCastToNiri};
 use crate::frame_clock::FrameClock;
 use crate::handlers::{XDG_ACTIVATION_TOKEN_TIMEOUT, configure_lock_surface};
+use crate::input::floating_resize_mode::FloatingResizeMode;
+use crate::input::focus_history::FocusHistoryWalk;
+use crate::input::mod_double_tap::ModDoubleTapTracker;
 use crate::input::pick_color_grab::PickColorGrab;
 use crate::input::pick_output_grab::PickOutputGrab;
 use crate::input::pick_window_grab::PickWindowGrab;
 use crate::input::scroll_swipe_gesture::ScrollSwipeGesture;
 use crate::input::scroll_tracker::ScrollTracker;
 use crate::input::{
-    TabletData, apply_libinput_settings, mods_with_finger_scroll_binds, mods_with_mouse_binds,
-    mods_with_wheel_binds,
+    TabletData, mods_with_finger_scroll_binds, mods_with_mouse_binds, mods_with_wheel_binds,
 };
 use crate::ipc::server::IpcServer;
 use crate::layer::MappedLayer;
@@ -158,22 +161,28 @@ use crate::protocols::gamma_control::GammaControlManagerState;
 use crate::protocols::kde_blur::OrgKdeKwinBlurManagerState;
 use crate::protocols::kde_output_order::KdeOutputOrderV1State;
 use crate::protocols::mutter_x11_interop::MutterX11InteropManagerState;
-use crate::protocols::output_management::OutputManagementManagerState;
+use crate::protocols::output_management::{
+    OutputManagementManagerState, PendingOutputConfiguration,
+};
+use crate::protocols::registry::RawProtocols;
 use crate::protocols::screencopy::{Screencopy, ScreencopyBuffer, ScreencopyManagerState};
 use crate::protocols::virtual_pointer::VirtualPointerManagerState;
 use crate::pw_utils::{Cast, PipeWire};
 #[cfg(feature = "xdp-gnome-screencast")]
 use crate::pw_utils::{CastSizeChange, PwToNiri};
 use crate::render_helpers::blur::{EffectsFramebuffers, EffectsFramebuffersUserData};
-use crate::render_helpers::debug::draw_opaque_regions;
+use crate::render_helpers::debug::{
+    draw_opaque_regions, draw_render_heatmap, set_record_draw_times,
+};
+use crate::render_helpers::offscreen::{OffscreenBuffer, OffscreenRenderElement};
 use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
 use crate::render_helpers::renderer::NiriRenderer;
 use crate::render_helpers::solid_color::{SolidColorBuffer, SolidColorRenderElement};
 use crate::render_helpers::surface::push_elements_from_surface_tree;
-use crate::render_helpers::texture::TextureBuffer;
+use crate::render_helpers::texture::{TextureBuffer, TextureRenderElement};
 use crate::render_helpers::{
-    RenderTarget, encompassing_geo, render_to_dmabuf, render_to_encompassing_texture,
-    render_to_shm, render_to_texture, render_to_vec, shaders,
+    RenderTarget, clip_pixels_to_rounded_rect, encompassing_geo, render_to_dmabuf,
+    render_to_encompassing_texture, render_to_shm, render_to_texture, render_to_vec, shaders,
 };
 use crate::ui::config_error_notification::ConfigErrorNotification;
 use crate::ui::exit_confirm_dialog::{ExitConfirmDialog, ExitConfirmDialogRenderElement};
@@ -183,15 +192,18 @@ use crate::ui::screen_transition::{self, ScreenTransition};
 use crate::ui::screenshot_ui::{OutputScreenshot, ScreenshotUi, ScreenshotUiRenderElement};
 use crate::utils::render::{PushRenderElement, Render};
 use crate::utils::scale::{closest_representable_scale, guess_monitor_scale};
-use crate::utils::spawning::{CHILD_DISPLAY, CHILD_ENV};
+use crate::utils::spawning::{CHILD_DISPLAY, CHILD_ENV, run_with_stdin};
 use crate::utils::transaction::{Transaction, TransactionBlocker};
 use crate::utils::vblank_throttle::VBlankThrottle;
 use crate::utils::watcher::Watcher;
+use crate::utils::workspace_uuids::WorkspaceUuids;
 use crate::utils::xwayland::satellite::Satellite;
 use crate::utils::{
-    center, center_f64, expand_home, get_monotonic_time, ipc_transform_to_smithay, is_mapped,
-    logical_output, make_screenshot_path, output_matches_name, output_size, panel_orientation,
-    send_scale_transform, write_png_rgba8, xwayland,
+    center, center_f64, client_scale, expand_home, format_screenshot_path, get_monotonic_time,
+    get_spawn_activation_token_for_surface, ipc_transform_to_smithay, is_mapped, logical_output,
+    make_screenshot_path, on_demand_vrr_active_until, output_matches_name, output_size,
+    panel_orientation, screenshot_path_for_output, send_scale_transform, set_scale_rounding,
+    with_toplevel_role, write_png_rgba8, xwayland,
 };
 use crate::window::mapped::MappedId;
 use crate::window::{InitialConfigureState, Mapped, ResolvedWindowRules, Unmapped, WindowRef};
@@ -203,6 +215,13 @@ const CLEAR_COLOR_LOCKED: [f32; 4] = [0.3, 0.1, 0.1, 1.];
 // should be ~1.995 seconds.
 const FRAME_CALLBACK_THROTTLE: Option<Duration> = Some(Duration::from_millis(995));
 
+// Range of scales that set-output-scale and cycle-output-scale can set.
+const MIN_OUTPUT_SCALE: f64 = 0.5;
+const MAX_OUTPUT_SCALE: f64 = 4.;
+
+// How long spawn-on-workspace waits for the spawned process to open a window.
+const SPAWN_ON_WORKSPACE_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub struct Niri {
     pub config: Rc<RefCell<Config>>,
 
@@ -250,6 +269,11 @@ pub struct Niri {
     // Windows which don't have a buffer attached yet.
     pub unmapped_windows: HashMap<WlSurface, Unmapped>,
 
+    /// Preset column width index last picked for each app id.
+    ///
+    /// Used with `remember-column-width-per-app`.
+    pub preset_column_width_by_app_id: HashMap<String, usize>,
+
     /// Layer surfaces which don't have a buffer attached yet.
     pub unmapped_layer_surfaces: HashSet<WlSurface>,
 
@@ -271,6 +295,11 @@ pub struct Niri {
     pub blocker_cleared_rx: Receiver<Client>,
 
     pub output_state: HashMap<Output, OutputState>,
+    /// Outputs that show the contents of another output instead of their own, mapped to the
+    /// output that they mirror.
+    pub mirrored_outputs: HashMap<Output, Output>,
+    /// Output management configurations applied since the last redraw.
+    pub pending_output_configurations: Vec<PendingOutputConfiguration>,
 
     // When false, we're idling with monitors powered off.
     pub monitors_active: bool,
@@ -284,6 +313,9 @@ pub struct Niri {
     pub devices: HashSet<input::Device>,
     pub tablets: HashMap<input::Device, TabletData>,
     pub touch: HashSet<input::Device>,
+    pub trackpoints: HashSet<input::Device>,
+    /// Output that tablets are mapped to with `map-to-output`, if it's connected.
+    pub tablet_output: Option<Output>,
 
     // Smithay state.
     pub compositor_state: CompositorState,
@@ -329,6 +361,7 @@ pub struct Niri {
     pub ext_background_effect_manager_state: ExtBackgroundEffectManagerState,
     pub kde_output_order_v1_state: KdeOutputOrderV1State,
     pub xdg_toplevel_tag_manager: XdgToplevelTagManager,
+    pub raw_protocols: RawProtocols,
 
     // This will not work as is outside of tests, so it is gated with #[cfg(test)] for now. In
     // particular, shaders will need to learn about the single pixel buffer. Also, it must be
@@ -346,6 +379,8 @@ pub struct Niri {
     pub suppressed_buttons: HashSet<u32>,
     pub bind_cooldown_timers: HashMap<Key, RegistrationToken>,
     pub bind_repeat_timer: Option<RegistrationToken>,
+    /// Timer for the bind whose key is being held down, waiting for its `hold-ms` to pass.
+    pub bind_hold_timer: Option<(Keycode, RegistrationToken)>,
     pub keyboard_focus: KeyboardFocus,
     pub layer_shell_on_demand_focus: Option<LayerSurface>,
     pub idle_inhibiting_surfaces: HashSet<WlSurface>,
@@ -355,6 +390,12 @@ pub struct Niri {
     /// Most recent XKB settings from org.freedesktop.locale1.
     pub xkb_from_locale1: Option<Xkb>,
 
+    /// Last used keyboard layout for each app id, with `track-layout-per-app`.
+    pub app_keyboard_layouts: HashMap<String, KeyboardLayout>,
+
+    /// Recent clipboard selections, with `clipboard { history-size }`.
+    pub clipboard_history: ClipboardHistory,
+
     /// Whether to reset the keymap on the next physical keyboard event.
     ///
     /// Set to true when handling virtual keyboard events which override the keymap.
@@ -379,6 +420,10 @@ pub struct Niri {
     /// which passes it down through grabs, which decide what to do with it as they see fit.
     pub pointer_contents: PointContents,
     pub pointer_visibility: PointerVisibility,
+    /// Pointer motion distance accumulated since the pointer got hidden while typing.
+    ///
+    /// Used for the cursor reveal-motion-threshold.
+    pub pointer_motion_since_typing_hide: Option<f64>,
     pub pointer_inactivity_timer: Option<RegistrationToken>,
     /// Whether the pointer inactivity timer got reset this event loop iteration.
     ///
@@ -393,6 +438,10 @@ pub struct Niri {
     pub pointer_inside_hot_corner: bool,
     pub tablet_cursor_location: Option<Point<f64, Logical>>,
     pub gesture_swipe_3f_cumulative: Option<(f64, f64)>,
+    /// Finger count and cumulative delta of a swipe that may trigger a swipe bind.
+    pub gesture_swipe_bind_cumulative: Option<(u32, f64, f64)>,
+    /// Whether the current swipe triggered a swipe bind.
+    pub gesture_swipe_bind_triggered: bool,
     pub overview_scroll_swipe_gesture: ScrollSwipeGesture,
     pub vertical_wheel_tracker: ScrollTracker,
     pub horizontal_wheel_tracker: ScrollTracker,
@@ -419,8 +468,13 @@ pub struct Niri {
     pub pick_output: Option<tokio::sync::oneshot::Sender<Option<String>>>,
     pub pick_color: Option<tokio::sync::oneshot::Sender<Option<niri_ipc::PickedColor>>>,
 
+    pub floating_resize_mode: Option<FloatingResizeMode>,
+    pub mod_double_tap: ModDoubleTapTracker,
+    pub focus_history_walk: FocusHistoryWalk<Window>,
+
     pub debug_draw_opaque_regions: bool,
     pub debug_draw_damage: bool,
+    pub debug_draw_render_heatmap: bool,
 
     #[cfg(feature = "dbus")]
     pub dbus: Option<crate::dbus::DBusServers>,
@@ -434,6 +488,12 @@ pub struct Niri {
     pub ipc_server: Option<IpcServer>,
     pub ipc_outputs_changed: bool,
 
+    /// Stable UUIDs of named workspaces, persisted to a state file.
+    pub workspace_uuids: WorkspaceUuids,
+
+    /// One-shot rules from spawn-on-workspace waiting for the spawned process to open a window.
+    pub pending_spawns_on_workspace: Vec<PendingSpawnOnWorkspace>,
+
     pub satellite: Option<Satellite>,
 
     // Casts are dropped before PipeWire to prevent a double-free (yay).
@@ -484,6 +544,8 @@ pub struct OutputState {
     pub frame_clock: FrameClock,
     pub redraw_state: RedrawState,
     pub on_demand_vrr_enabled: bool,
+    /// Timer to re-evaluate on-demand VRR once the windows stop committing.
+    pub on_demand_vrr_idle_timer: Option<RegistrationToken>,
     // After the last redraw, some ongoing animations still remain.
     pub unfinished_animations_remain: bool,
     /// Last sequence received in a vblank event.
@@ -515,9 +577,13 @@ pub struct OutputState {
     pub lock_render_state: LockRenderState,
     pub lock_surface: Option<LockSurface>,
     pub lock_color_buffer: SolidColorBuffer,
+    /// Whether this output was powered off individually with the power-off-monitor action.
+    pub powered_off: bool,
     screen_transition: Option<ScreenTransition>,
     /// Damage tracker used for the debug damage visualization.
     pub debug_damage_tracker: OutputDamageTracker,
+    /// Buffer for the contents of the output that this output mirrors.
+    pub mirror_buffer: OffscreenBuffer,
 }
 
 #[derive(Debug, Default)]
@@ -616,6 +682,15 @@ pub enum CastTarget {
     Window { id: u64 },
 }
 
+/// One-shot rule that opens the first window of a spawned process on a workspace.
+#[derive(Debug)]
+pub struct PendingSpawnOnWorkspace {
+    /// Activation token passed to the spawned process in its environment.
+    token: XdgActivationToken,
+    /// Name of the workspace to open the window on.
+    workspace: String,
+}
+
 /// Pending update to a window's focus timestamp.
 #[derive(Debug)]
 pub struct PendingMruCommit {
@@ -802,6 +877,12 @@ enum WriteToDisk {
     ConfiguredName,
     /// Use specified name
     Name(PathBuf),
+    /// Use the path template, or screenshot_path from config if unset, with the output name
+    /// substituted in
+    ForOutput {
+        template: Option<String>,
+        output: String,
+    },
     /// Do not save to disk
     No,
 }
@@ -825,6 +906,15 @@ pub struct LegacyScreenshotOutput {
     copy_to_clipboard: bool,
 }
 impl LegacyScreenshotOutput {
+    /// Saves the screenshot of one of several outputs to disk, without copying it to the
+    /// clipboard.
+    pub const fn for_output(template: Option<String>, output: String) -> Self {
+        Self {
+            copy_to_clipboard: false,
+            write_to_disk: WriteToDisk::ForOutput { template, output },
+        }
+    }
+
     pub fn new(write_to_disk: bool, path: Option<String>) -> Self {
         Self {
             copy_to_clipboard: true,
@@ -840,6 +930,7 @@ impl LegacyScreenshotOutput {
 
 pub struct LegacyScreenshotPipe {
     path: Option<(PathBuf, bool)>,
+    show_notification: bool,
     collector: io::Cursor<Vec<u8>>,
     data: ScreenshotData,
 
@@ -901,9 +992,11 @@ impl ScreenshotPipe for LegacyScreenshotPipe {
             debug!("disk saving is not requested")
         }
 
-        #[cfg(feature = "dbus")]
-        if let Err(err) = crate::utils::show_screenshot_notification(image_path.as_deref()) {
-            warn!("error showing screenshot notification: {err:?}");
+        if self.show_notification {
+            #[cfg(feature = "dbus")]
+            if let Err(err) = crate::utils::show_screenshot_notification(image_path.as_deref()) {
+                warn!("error showing screenshot notification: {err:?}");
+            }
         }
 
         if let Some(copy_to_clipboard) = self.copy_to_clipboard {
@@ -948,6 +1041,18 @@ impl ScreenshotOutput for LegacyScreenshotOutput {
                 }
             },
             WriteToDisk::Name(path) => Some((path, false)),
+            WriteToDisk::ForOutput { template, output } => {
+                let (path, create_parent) = match template {
+                    Some(template) => (format_screenshot_path(&template), false),
+                    None => (make_screenshot_path(&state.config.borrow()), true),
+                };
+                match path {
+                    Ok(path) => Some((screenshot_path_for_output(&path, &output), create_parent)),
+                    Err(err) => {
+                        bail!("error making screenshot path: {err:?}");
+                    }
+                }
+            }
             WriteToDisk::No => None,
         };
 
@@ -988,6 +1093,8 @@ impl ScreenshotOutput for LegacyScreenshotOutput {
 
         Ok(LegacyScreenshotPipe {
             path,
+            // Screenshots of several outputs would show a notification each.
+            show_notification: self.copy_to_clipboard,
             collector: io::Cursor::new(Vec::new()),
             data,
             copy_to_clipboard,
@@ -996,6 +1103,83 @@ impl ScreenshotOutput for LegacyScreenshotOutput {
     }
 }
 
+/// Pipes the screenshot into a command as PNG.
+pub struct CommandScreenshotOutput {
+    command: Vec<String>,
+}
+
+impl CommandScreenshotOutput {
+    pub const fn new(command: Vec<String>) -> Self {
+        Self { command }
+    }
+}
+
+pub struct CommandScreenshotPipe {
+    command: Vec<String>,
+    collector: io::Cursor<Vec<u8>>,
+    data: ScreenshotData,
+}
+
+impl ScreenshotPipe for CommandScreenshotPipe {
+    type Output = ();
+
+    async fn init_stream(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn write_pixels(&mut self, pixels: &[u8]) -> anyhow::Result<()> {
+        tokio::io::AsyncWriteExt::write_all(&mut self.collector, pixels)
+            .await
+            .context("failed to write to cursor")
+    }
+
+    async fn flush(&mut self) -> anyhow::Result<()> {
+        tokio::io::AsyncWriteExt::flush(&mut self.collector)
+            .await
+            .context("failed to flush cursor")
+    }
+
+    fn finish_success(self) -> anyhow::Result<()> {
+        let mut buf = vec![];
+        write_png_rgba8(
+            io::Cursor::new(&mut buf),
+            self.data.width,
+            self.data.height,
+            &self.collector.into_inner(),
+        )?;
+
+        debug!("piping screenshot into {:?}", self.command);
+        run_with_stdin(&self.command, &buf)
+            .with_context(|| format!("error piping screenshot into {:?}", self.command))
+    }
+
+    fn finish_failure(self, err: anyhow::Error) {
+        warn!("failed to write screenshot: {err:?}")
+    }
+}
+
+impl ScreenshotOutput for CommandScreenshotOutput {
+    type Pipe = CommandScreenshotPipe;
+
+    fn image_meta_failed(self, err: anyhow::Error) {
+        warn!("failed to capture screenshot: {err:?}")
+    }
+
+    fn image_meta_success(
+        self,
+        _state: &mut Niri,
+        data: ScreenshotData,
+    ) -> anyhow::Result<Self::Pipe> {
+        ensure!(!self.command.is_empty(), "command is empty");
+
+        Ok(CommandScreenshotPipe {
+            command: self.command,
+            collector: io::Cursor::new(Vec::new()),
+            data,
+        })
+    }
+}
+
 pub struct State {
     pub backend: Backend,
     pub niri: Niri,
@@ -1066,6 +1250,7 @@ impl State {
         self.niri.advance_animations();
 
         self.niri.redraw_queued_outputs(&mut self.backend);
+        self.finish_output_configurations();
 
         {
             let _span = tracy_client::span!("flush_clients");
@@ -1118,6 +1303,8 @@ impl State {
         self.niri.refresh_idle_inhibit();
         self.refresh_pointer_contents();
         foreign_toplevel::refresh(self);
+        // Needs to be called before sending workspaces over ext-workspace and IPC.
+        self.niri.refresh_workspace_uuids();
         ext_workspace::refresh(self);
 
         #[cfg(feature = "xdp-gnome-screencast")]
@@ -1436,6 +1623,13 @@ impl State {
             }
         }
 
+        // Leave the floating resize mode once its window loses focus or the session locks.
+        if self.niri.floating_resize_mode.is_some()
+            && self.niri.floating_resize_mode_window().is_none()
+        {
+            self.niri.floating_resize_mode = None;
+        }
+
         // Compute the current focus.
         let focus = if self.niri.exit_confirm_dialog.is_open() {
             KeyboardFocus::ExitConfirmDialog
@@ -1506,7 +1700,7 @@ impl State {
             let focus_on_layer =
                 |layer| excl_focus_on_layer(layer).or_else(|| on_d_focus_on_layer(layer));
 
-            let is_overview_open = self.niri.layout.is_overview_open();
+            let is_overview_open = mon.is_overview_open();
 
             let mut surface = grab_on_layer(Layer::Overlay);
             // FIXME: we shouldn't prioritize the top layer grabs over regular overlay input or a
@@ -1624,7 +1818,12 @@ impl State {
                 self.niri.popup_grab = None;
             }
 
-            if self.niri.config.borrow().input.keyboard.track_layout == TrackLayout::Window {
+            let (track_layout, per_app) = {
+                let config = self.niri.config.borrow();
+                let c = &config.input.keyboard;
+                (c.track_layout, c.track_layout_per_app)
+            };
+            if track_layout == TrackLayout::Window {
                 let current_layout = keyboard.with_xkb_state(self, |context| {
                     let xkb = context.xkb().lock().unwrap();
                     xkb.active_layout()
@@ -1639,14 +1838,31 @@ impl State {
                             .get_or_insert::<Cell<KeyboardLayout>, _>(Cell::default);
                         cell.set(current_layout);
                     });
+
+                    // Also remember it for new windows of the same app.
+                    if per_app && let Some(app_id) = self.niri.surface_app_id(current_focus) {
+                        self.niri
+                            .app_keyboard_layouts
+                            .insert(app_id, current_layout);
+                    }
                 }
 
                 if let Some(focus) = focus.surface() {
+                    let app_layout = if per_app {
+                        self.niri
+                            .surface_app_id(focus)
+                            .and_then(|app_id| self.niri.app_keyboard_layouts.get(&app_id))
+                            .copied()
+                    } else {
+                        None
+                    };
+
                     new_layout = with_states(focus, |data| {
                         let cell = data.data_map.get_or_insert::<Cell<KeyboardLayout>, _>(|| {
                             // The default layout is effectively the first layout in the
-                            // keymap, so use it for new windows.
-                            Cell::new(KeyboardLayout::default())
+                            // keymap, so use it for new windows, unless we remember a layout
+                            // for this app.
+                            Cell::new(app_layout.unwrap_or_default())
                         });
                         cell.get()
                     });
@@ -1676,10 +1892,33 @@ impl State {
 
         let keymap = std::fs::read_to_string(xkb_file).context("failed to read xkb_file")?;
 
-        let xkb = self.niri.seat.get_keyboard().unwrap();
-        xkb.set_keymap_from_string(self, keymap)
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        let num_lock = keyboard.modifier_state().num_lock;
+        let active_layout = keyboard.with_xkb_state(self, |context| {
+            let xkb = context.xkb().lock().unwrap();
+            xkb.active_layout()
+        });
+
+        // On error, smithay keeps the previous keymap.
+        keyboard
+            .set_keymap_from_string(self, keymap)
             .context("failed to set keymap")?;
 
+        // Restore the active layout if the new keymap still has it.
+        keyboard.with_xkb_state(self, |mut context| {
+            let num_layouts = context.xkb().lock().unwrap().layouts().count();
+            if (active_layout.0 as usize) < num_layouts {
+                context.set_layout(active_layout);
+            }
+        });
+
+        // Restore num lock to its previous value.
+        let mut mods_state = keyboard.modifier_state();
+        if mods_state.num_lock != num_lock {
+            mods_state.num_lock = num_lock;
+            keyboard.set_modifier_state(mods_state);
+        }
+
         Ok(())
     }
 
@@ -1708,6 +1947,50 @@ impl State {
         }
     }
 
+    /// Applies only the binds from the config, leaving the rest of the state untouched.
+    ///
+    /// Falls back to a full reload if the layout or input config changed.
+    pub fn reload_binds(&mut self, config: Result<Config, ()>) {
+        let _span = tracy_client::span!("State::reload_binds");
+
+        let config = match config {
+            Ok(config) => config,
+            Err(()) => {
+                // This shows the config error notification.
+                self.reload_config(Err(()));
+                return;
+            }
+        };
+
+        let needs_full_reload = {
+            let old_config = self.niri.config.borrow();
+            config.layout != old_config.layout || config.input != old_config.input
+        };
+        if needs_full_reload {
+            debug!("layout or input config changed, reloading the whole config");
+            self.reload_config(Ok(config));
+            return;
+        }
+
+        self.niri.config_error_notification.hide();
+
+        let mut old_config = self.niri.config.borrow_mut();
+        if config.binds != old_config.binds {
+            let mod_key = self.backend.mod_key(&old_config);
+            old_config.binds = config.binds;
+
+            self.niri.hotkey_overlay.on_hotkey_config_updated(mod_key);
+            self.niri.mods_with_mouse_binds = mods_with_mouse_binds(mod_key, &old_config.binds);
+            self.niri.mods_with_wheel_binds = mods_with_wheel_binds(mod_key, &old_config.binds);
+            self.niri.mods_with_finger_scroll_binds =
+                mods_with_finger_scroll_binds(mod_key, &old_config.binds);
+        }
+        drop(old_config);
+
+        // For the config error notification and the hotkey overlay.
+        self.niri.queue_redraw_all();
+    }
+
     pub fn reload_config(&mut self, config: Result<Config, ()>) {
         let _span = tracy_client::span!("State::reload_config");
 
@@ -1776,7 +2059,12 @@ impl State {
         }
 
         // We need &mut self to reload the xkb config, so just store it here.
-        if config.input.keyboard.xkb != old_config.input.keyboard.xkb {
+        //
+        // When an xkb file is set, always reload it, since its contents may have changed even if
+        // the path stayed the same.
+        if config.input.keyboard.xkb != old_config.input.keyboard.xkb
+            || config.input.keyboard.xkb.file.is_some()
+        {
             reload_xkb = Some(config.input.keyboard.xkb.clone());
         }
 
@@ -1797,6 +2085,10 @@ impl State {
             || config.input.trackpoint != old_config.input.trackpoint
             || config.input.tablet != old_config.input.tablet
             || config.input.touch != old_config.input.touch
+            || config.input.keyboard.disabled_on_external_keyboard
+                != old_config.input.keyboard.disabled_on_external_keyboard
+            || config.input.keyboard.external_keyboards
+                != old_config.input.keyboard.external_keyboards
         {
             libinput_config_changed = true;
         }
@@ -1835,6 +2127,11 @@ impl State {
             layer_rules_changed = true;
         }
 
+        if config.block_out_all_from_screencast != old_config.block_out_all_from_screencast {
+            window_rules_changed = true;
+            layer_rules_changed = true;
+        }
+
         if config.animations.window_resize.custom_shader
             != old_config.animations.window_resize.custom_shader
         {
@@ -1895,6 +2192,9 @@ impl State {
             xwls_changed = true;
         }
 
+        let history_size = usize::from(config.clipboard.history_size);
+        self.niri.clipboard_history.truncate(history_size);
+
         *old_config = config;
 
         if let Some(outputs) = preserved_output_config {
@@ -1911,14 +2211,18 @@ impl State {
             // It's fine to .take() the xkb file, as this is a
             // clone and the file field is not used in the XkbConfig.
             if let Some(xkb_file) = xkb.file.take() {
-                match self.set_xkb_file(xkb_file) {
-                    Err(err) => {
-                        warn!("error reloading xkb_file: {err:?}");
-                    }
-                    _ => {
-                        // We successfully set xkb file so we don't need to fallback to XkbConfig.
-                        set_xkb_config = false;
-                    }
+                // Whether the file loaded or not, we don't fall back to XkbConfig: on failure,
+                // keep the previous keymap and let the user know.
+                set_xkb_config = false;
+
+                if let Err(err) = self.set_xkb_file(xkb_file) {
+                    warn!("error reloading xkb_file: {err:?}");
+
+                    self.niri.config_error_notification.show();
+                    self.niri.queue_redraw_all();
+
+                    #[cfg(feature = "dbus")]
+                    self.niri.a11y_announce_config_error();
                 }
             }
 
@@ -1936,10 +2240,8 @@ impl State {
         }
 
         if libinput_config_changed {
-            let config = self.niri.config.borrow();
-            for mut device in self.niri.devices.iter().cloned() {
-                apply_libinput_settings(&config.input, &mut device);
-            }
+            self.reapply_libinput_settings();
+            self.niri.refresh_tablet_output();
         }
 
         if ignored_nodes_changed {
@@ -2031,6 +2333,7 @@ impl State {
                     guess_monitor_scale(size_mm, resolution)
                 });
             let scale = closest_representable_scale(scale.clamp(0.1, 10.));
+            let rounding = config.map(|c| c.scale_rounding).unwrap_or_default();
 
             let transform = panel_orientation(output)
                 + config
@@ -2047,6 +2350,10 @@ impl State {
                     None,
                 );
                 self.niri.ipc_outputs_changed = true;
+                set_scale_rounding(output, rounding);
+                resized_outputs.push(output.clone());
+            } else if set_scale_rounding(output, rounding) {
+                // Only the scale that clients render at changed.
                 resized_outputs.push(output.clone());
             }
 
@@ -2148,6 +2455,55 @@ impl State {
         fun(config);
     }
 
+    /// Reports the result of output management configurations applied before the last redraw.
+    fn finish_output_configurations(&mut self) {
+        for pending in mem::take(&mut self.niri.pending_output_configurations) {
+            if pending
+                .configs
+                .iter()
+                .all(|config| self.is_output_config_applied(config))
+            {
+                pending.conf.succeeded();
+                continue;
+            }
+
+            warn!("error applying output configuration, rolling back");
+            self.niri.config.borrow_mut().outputs = pending.old_outputs;
+            self.reload_output_config();
+            pending.conf.failed();
+        }
+    }
+
+    /// Returns whether the output is in the state that the config asks for.
+    pub fn is_output_config_applied(&self, config: &niri_config::Output) -> bool {
+        let output = self.niri.output_by_name_match(&config.name);
+        let Some(output) = output else {
+            return config.off;
+        };
+        if config.off {
+            return false;
+        }
+
+        // Modelines and custom modes don't necessarily end up with the exact refresh rate.
+        let Some(target) = config.mode.filter(|mode| !mode.custom) else {
+            return true;
+        };
+        if config.modeline.is_some() {
+            return true;
+        }
+
+        let Some(mode) = output.current_mode() else {
+            return false;
+        };
+        let target = target.mode;
+        if (mode.size.w, mode.size.h) != (i32::from(target.width), i32::from(target.height)) {
+            return false;
+        }
+        target
+            .refresh
+            .is_none_or(|refresh| (refresh * 1000.).round() as i32 == mode.refresh)
+    }
+
     pub fn apply_transient_output_config(&mut self, name: &str, action: niri_ipc::OutputAction) {
         self.modify_output_config(name, move |config| match action {
             niri_ipc::OutputAction::Off => config.off = true,
@@ -2211,9 +2567,14 @@ impl State {
                 }
             }
             niri_ipc::OutputAction::Vrr { vrr } => {
+                let on_demand_idle_ms = config
+                    .variable_refresh_rate
+                    .as_ref()
+                    .and_then(|vrr| vrr.on_demand_idle_ms);
                 config.variable_refresh_rate = if vrr.vrr {
                     Some(niri_config::Vrr {
                         on_demand: vrr.on_demand,
+                        on_demand_idle_ms,
                     })
                 } else {
                     None
@@ -2224,6 +2585,23 @@ impl State {
         self.reload_output_config();
     }
 
+    /// Sets the scale of the output, as a transient output config change.
+    pub fn set_output_scale(&mut self, output: &Output, scale: f64) {
+        let scale = scale.clamp(MIN_OUTPUT_SCALE, MAX_OUTPUT_SCALE);
+        let name = output.name();
+        let action = niri_ipc::OutputAction::Scale {
+            scale: niri_ipc::ScaleToSet::Specific(scale),
+        };
+        self.apply_transient_output_config(&name, action);
+    }
+
+    /// Sets the transform of the output, as a transient output config change.
+    pub fn set_output_transform(&mut self, output: &Output, transform: niri_ipc::Transform) {
+        let name = output.name();
+        let action = niri_ipc::OutputAction::Transform { transform };
+        self.apply_transient_output_config(&name, action);
+    }
+
     pub fn refresh_ipc_outputs(&mut self) {
         if !self.niri.ipc_outputs_changed {
             return;
@@ -2673,8 +3051,6 @@ impl State {
 
     #[cfg(feature = "dbus")]
     pub fn on_introspect_msg(&mut self, msg: IntrospectToNiri) {
-        use crate::utils::with_toplevel_role;
-
         let IntrospectToNiri::GetWindows(sender) = msg;
         let _span = tracy_client::span!("GetWindows");
 
@@ -2689,6 +3065,9 @@ impl State {
             },
         );
 
+        let config = self.niri.config.borrow();
+        let app_id_map = &config.app_id_map;
+
         self.niri.layout.with_windows(|mapped, _, _, _| {
             let id = mapped.id().get();
             let props = with_toplevel_role(mapped.toplevel(), |role| {
@@ -2699,8 +3078,9 @@ impl State {
                         .as_ref()
                         // We don't do proper .desktop file tracking (it's quite involved), and
                         // Wayland windows can set any app id they want. However, this seems to
-                        // work well enough in practice.
-                        .map(|app_id| format!("{app_id}.desktop"))
+                        // work well enough in practice, and the user can fix up the rest with
+                        // app-id-map.
+                        .map(|app_id| format!("{}.desktop", app_id_map.desktop_id(app_id)))
                         .unwrap_or_default(),
                 }
             });
@@ -2970,12 +3350,20 @@ impl Niri {
                         client,
                         restricted: false,
                         credentials_unknown: false,
+                        activation_token: None,
                     });
                 })
                 .unwrap();
             socket_name
         });
 
+        // Don't touch the real state file from tests.
+        let workspace_uuids = if cfg!(test) {
+            WorkspaceUuids::default()
+        } else {
+            WorkspaceUuids::load()
+        };
+
         let ipc_server = match IpcServer::start(&event_loop, socket_name.as_deref()) {
             Ok(server) => Some(server),
             Err(err) => {
@@ -3040,7 +3428,10 @@ impl Niri {
             global_space: Space::default(),
             sorted_outputs: Vec::default(),
             output_state: HashMap::new(),
+            mirrored_outputs: HashMap::new(),
+            pending_output_configurations: Vec::new(),
             unmapped_windows: HashMap::new(),
+            preset_column_width_by_app_id: HashMap::new(),
             unmapped_layer_surfaces: HashSet::new(),
             mapped_layer_surfaces: HashMap::new(),
             closing_layers: Vec::new(),
@@ -3054,6 +3445,8 @@ impl Niri {
             devices: HashSet::new(),
             tablets: HashMap::new(),
             touch: HashSet::new(),
+            trackpoints: HashSet::new(),
+            tablet_output: None,
 
             compositor_state,
             xdg_shell_state,
@@ -3093,6 +3486,7 @@ impl Niri {
             suppressed_buttons: HashSet::new(),
             bind_cooldown_timers: HashMap::new(),
             bind_repeat_timer: Option::default(),
+            bind_hold_timer: None,
             presentation_state,
             security_context_state,
             gamma_control_manager_state,
@@ -3102,6 +3496,7 @@ impl Niri {
             ext_background_effect_manager_state,
             kde_output_order_v1_state,
             xdg_toplevel_tag_manager,
+            raw_protocols: RawProtocols::default(),
 
             #[cfg(test)]
             single_pixel_buffer_state,
@@ -3113,6 +3508,8 @@ impl Niri {
             is_fdo_idle_inhibited: Arc::new(AtomicBool::new(false)),
             keyboard_shortcuts_inhibiting_surfaces: HashMap::new(),
             xkb_from_locale1: None,
+            app_keyboard_layouts: HashMap::new(),
+            clipboard_history: ClipboardHistory::default(),
             reset_keymap: false,
             cursor_manager,
             cursor_texture_cache: Default::default(),
@@ -3120,12 +3517,15 @@ impl Niri {
             dnd_icon: None,
             pointer_contents: PointContents::default(),
             pointer_visibility: PointerVisibility::Visible,
+            pointer_motion_since_typing_hide: None,
             pointer_inactivity_timer: None,
             pointer_inactivity_timer_got_reset: false,
             notified_activity_this_iteration: false,
             pointer_inside_hot_corner: false,
             tablet_cursor_location: None,
             gesture_swipe_3f_cumulative: None,
+            gesture_swipe_bind_cumulative: None,
+            gesture_swipe_bind_triggered: false,
             overview_scroll_swipe_gesture: ScrollSwipeGesture::new(),
             vertical_wheel_tracker: ScrollTracker::new(120),
             horizontal_wheel_tracker: ScrollTracker::new(120),
@@ -3150,10 +3550,14 @@ impl Niri {
 
             pick_window: None,
             pick_color: None,
+            floating_resize_mode: None,
+            mod_double_tap: ModDoubleTapTracker::default(),
+            focus_history_walk: FocusHistoryWalk::default(),
             pick_output: None,
 
             debug_draw_opaque_regions: false,
             debug_draw_damage: false,
+            debug_draw_render_heatmap: false,
 
             #[cfg(feature = "dbus")]
             dbus: None,
@@ -3167,6 +3571,10 @@ impl Niri {
             ipc_server,
             ipc_outputs_changed: false,
 
+            workspace_uuids,
+
+            pending_spawns_on_workspace: Vec::new(),
+
             satellite: None,
 
             pipewire: None,
@@ -3191,6 +3599,7 @@ impl Niri {
             client,
             restricted,
             credentials_unknown,
+            activation_token,
         } = client;
 
         let config = self.config.borrow();
@@ -3200,6 +3609,7 @@ impl Niri {
             primary_selection_disabled: config.clipboard.disable_primary,
             restricted,
             credentials_unknown,
+            activation_token,
         });
 
         if let Err(err) = self.display_handle.insert_client(client, data) {
@@ -3355,6 +3765,10 @@ impl Niri {
                 self.queue_redraw(&output);
             }
         }
+
+        // The set of outputs may have changed, so the tablet mapping target may have appeared or
+        // disappeared.
+        self.refresh_tablet_output();
     }
 
     pub fn add_output(&mut self, output: Output, refresh_interval: Option<Duration>, vrr: bool) {
@@ -3370,6 +3784,7 @@ impl Niri {
             guess_monitor_scale(size_mm, resolution)
         });
         let scale = closest_representable_scale(scale.clamp(0.1, 10.));
+        let rounding = c.map(|c| c.scale_rounding).unwrap_or_default();
 
         let transform = panel_orientation(&output)
             + c.map(|c| ipc_transform_to_smithay(c.transform))
@@ -3397,9 +3812,18 @@ impl Niri {
             Some(output::Scale::Fractional(scale)),
             None,
         );
+        set_scale_rounding(&output, rounding);
 
         self.layout.add_output(output.clone(), layout_config);
 
+        // Windows whose workspaces came back with the output don't need to return anymore.
+        let connector = output.name();
+        for mapped in self.layout.windows_for_output_mut(&output) {
+            if mapped.last_output() == Some(connector.as_str()) {
+                mapped.set_last_output(None);
+            }
+        }
+
         let lock_render_state = if self.is_locked() {
             // We haven't rendered anything yet so it's as good as locked.
             LockRenderState::Locked
@@ -3412,6 +3836,7 @@ impl Niri {
             global,
             redraw_state: RedrawState::Idle,
             on_demand_vrr_enabled: false,
+            on_demand_vrr_idle_timer: None,
             unfinished_animations_remain: false,
             frame_clock: FrameClock::new(refresh_interval, vrr),
             last_drm_sequence: None,
@@ -3421,8 +3846,10 @@ impl Niri {
             lock_render_state,
             lock_surface: None,
             lock_color_buffer: SolidColorBuffer::new(size, CLEAR_COLOR_LOCKED),
+            powered_off: false,
             screen_transition: None,
             debug_damage_tracker: OutputDamageTracker::from_output(&output),
+            mirror_buffer: OffscreenBuffer::default(),
         };
         let rv = self.output_state.insert(output.clone(), state);
         assert!(rv.is_none(), "output was already tracked");
@@ -3431,14 +3858,40 @@ impl Niri {
         self.reposition_outputs(Some(&output));
     }
 
+    /// Activates the default workspace configured for a newly connected output.
+    pub fn activate_output_default_workspace(&mut self, output: &Output) {
+        let name = output.user_data().get::<OutputName>().unwrap();
+        let config = self.config.borrow();
+        let Some(ws_name) = config
+            .outputs
+            .find(name)
+            .and_then(|c| c.default_workspace.clone())
+        else {
+            return;
+        };
+        drop(config);
+
+        self.layout
+            .activate_named_workspace_on_output(output, &ws_name);
+        self.queue_redraw(output);
+    }
+
     pub fn remove_output(&mut self, output: &Output) {
         for layer in layer_map_for_output(output).layers() {
             layer.layer_surface().send_close();
         }
 
+        // Remember where the windows were, so that move-window-to-last-output can bring them back.
+        let name = output.name();
+        for mapped in self.layout.windows_for_output_mut(output) {
+            mapped.set_last_output(Some(name.clone()));
+        }
+
         self.layout.remove_output(output);
         self.global_space.unmap_output(output);
         self.reposition_outputs(None);
+        self.mirrored_outputs
+            .retain(|target, source| target != output && source != output);
         self.gamma_control_manager_state.output_removed(output);
 
         let state = self.output_state.remove(output).unwrap();
@@ -3451,6 +3904,10 @@ impl Niri {
             RedrawState::WaitingForEstimatedVBlankAndQueued(token) => self.event_loop.remove(token),
         }
 
+        if let Some(token) = state.on_demand_vrr_idle_timer {
+            self.event_loop.remove(token);
+        }
+
         #[cfg(feature = "xdp-gnome-screencast")]
         self.stop_casts_for_target(CastTarget::Output(output.downgrade()));
 
@@ -3510,13 +3967,14 @@ impl Niri {
     pub fn output_resized(&mut self, output: &Output) {
         let output_size = output_size(output);
         let scale = output.current_scale();
+        let client_scale = client_scale(output);
         let transform = output.current_transform();
 
         {
             let mut layer_map = layer_map_for_output(output);
             for layer in layer_map.layers() {
                 layer.with_surfaces(|surface, data| {
-                    send_scale_transform(surface, data, scale, transform);
+                    send_scale_transform(surface, data, client_scale, transform);
                 });
 
                 if let Some(mapped) = self.mapped_layer_surfaces.get_mut(layer) {
@@ -3577,6 +4035,45 @@ impl Niri {
         self.queue_redraw_all();
     }
 
+    /// Marks all monitors as active again after the session resumes, e.g. after a VT switch.
+    ///
+    /// This includes monitors powered off individually, since the backend turns every connector
+    /// back on when resuming.
+    pub fn resume_monitors(&mut self) {
+        self.monitors_active = true;
+        for state in self.output_state.values_mut() {
+            state.powered_off = false;
+        }
+
+        self.queue_redraw_all();
+    }
+
+    pub fn deactivate_monitor(&mut self, backend: &mut Backend, output: &Output) {
+        let Some(state) = self.output_state.get_mut(output) else {
+            return;
+        };
+        if state.powered_off {
+            return;
+        }
+
+        state.powered_off = true;
+        backend.set_output_active(output, false);
+    }
+
+    pub fn activate_monitor(&mut self, backend: &mut Backend, output: &Output) {
+        let Some(state) = self.output_state.get_mut(output) else {
+            return;
+        };
+        if !state.powered_off {
+            return;
+        }
+
+        state.powered_off = false;
+        backend.set_output_active(output, true);
+
+        self.queue_redraw(output);
+    }
+
     pub fn output_under(&self, pos: Point<f64, Logical>) -> Option<(&Output, Point<f64, Logical>)> {
         let output = self.global_space.output_under(pos).next()?;
         let pos_within_output = pos
@@ -3692,7 +4189,7 @@ impl Niri {
         output: &Output,
         pos_within_output: Point<f64, Logical>,
     ) -> bool {
-        if self.layout.is_overview_open() {
+        if self.layout.is_overview_open_on(output) {
             return false;
         }
 
@@ -3767,6 +4264,20 @@ impl Niri {
         self.workspace_under(extended_bounds, pos)
     }
 
+    /// Returns the column under the cursor to be activated.
+    ///
+    /// The return value is an output, a workspace id and a column index in it.
+    pub fn column_under_cursor(&self) -> Option<(Output, WorkspaceId, usize)> {
+        let pos = self.seat.get_pointer().unwrap().current_location();
+
+        // This checks for everything that could be covering the layout.
+        self.workspace_under(true, pos)?;
+
+        let (output, pos_within_output) = self.output_under(pos)?;
+        let (ws, idx) = self.layout.column_under(output, pos_within_output)?;
+        Some((output.clone(), ws.id(), idx))
+    }
+
     /// Returns the window under the position to be activated.
     ///
     /// The cursor may be inside the window's activation region, but not within the window's input
@@ -3938,7 +4449,7 @@ impl Niri {
         let mut under =
             layer_popup_under(Layer::Overlay).or_else(|| layer_toplevel_under(Layer::Overlay));
 
-        let is_overview_open = self.layout.is_overview_open();
+        let is_overview_open = mon.is_overview_open();
 
         // When rendering above the top layer, we put the regular monitor elements first.
         // Otherwise, we will render all layer-shell pop-ups and the top layer on top.
@@ -3998,6 +4509,12 @@ impl Niri {
         self.global_space.output_under(pos).next().cloned()
     }
 
+    /// Returns whether the overview is open on the output under the cursor.
+    pub fn is_overview_open_under_cursor(&self) -> bool {
+        self.output_under_cursor()
+            .is_some_and(|output| self.layout.is_overview_open_on(&output))
+    }
+
     pub fn output_left_of(&self, current: &Output) -> Option<Output> {
         let current_geo = self.global_space.output_geometry(current)?;
         let extended_geo = Rectangle::new(
@@ -4062,6 +4579,66 @@ impl Niri {
             .cloned()
     }
 
+    /// Returns the outputs sharing a row (if `horizontal`) or a column with `current`.
+    fn outputs_in_line_with(
+        &self,
+        current: &Output,
+        horizontal: bool,
+    ) -> Option<impl Iterator<Item = (&Output, Rectangle<i32, Logical>)>> {
+        let current_geo = self.global_space.output_geometry(current)?;
+        let extended_geo = if horizontal {
+            Rectangle::new(
+                Point::from((i32::MIN / 2, current_geo.loc.y)),
+                Size::from((i32::MAX, current_geo.size.h)),
+            )
+        } else {
+            Rectangle::new(
+                Point::from((current_geo.loc.x, i32::MIN / 2)),
+                Size::from((current_geo.size.w, i32::MAX)),
+            )
+        };
+
+        let outputs = self
+            .global_space
+            .outputs()
+            .filter(move |&output| output != current)
+            .map(move |output| (output, self.global_space.output_geometry(output).unwrap()))
+            .filter(move |(_, geo)| geo.overlaps(extended_geo));
+        Some(outputs)
+    }
+
+    pub fn output_left_of_wrapping(&self, current: &Output) -> Option<Output> {
+        self.output_left_of(current).or_else(|| {
+            self.outputs_in_line_with(current, true)?
+                .max_by_key(|(_, geo)| center(*geo).x)
+                .map(|(output, _)| output.clone())
+        })
+    }
+
+    pub fn output_right_of_wrapping(&self, current: &Output) -> Option<Output> {
+        self.output_right_of(current).or_else(|| {
+            self.outputs_in_line_with(current, true)?
+                .min_by_key(|(_, geo)| center(*geo).x)
+                .map(|(output, _)| output.clone())
+        })
+    }
+
+    pub fn output_up_of_wrapping(&self, current: &Output) -> Option<Output> {
+        self.output_up_of(current).or_else(|| {
+            self.outputs_in_line_with(current, false)?
+                .max_by_key(|(_, geo)| center(*geo).y)
+                .map(|(output, _)| output.clone())
+        })
+    }
+
+    pub fn output_down_of_wrapping(&self, current: &Output) -> Option<Output> {
+        self.output_down_of(current).or_else(|| {
+            self.outputs_in_line_with(current, false)?
+                .min_by_key(|(_, geo)| center(*geo).y)
+                .map(|(output, _)| output.clone())
+        })
+    }
+
     pub fn output_previous_of(&self, current: &Output) -> Option<Output> {
         self.sorted_outputs
             .iter()
@@ -4103,6 +4680,26 @@ impl Niri {
         self.output_down_of(active)
     }
 
+    pub fn output_left_wrapping(&self) -> Option<Output> {
+        let active = self.layout.active_output()?;
+        self.output_left_of_wrapping(active)
+    }
+
+    pub fn output_right_wrapping(&self) -> Option<Output> {
+        let active = self.layout.active_output()?;
+        self.output_right_of_wrapping(active)
+    }
+
+    pub fn output_up_wrapping(&self) -> Option<Output> {
+        let active = self.layout.active_output()?;
+        self.output_up_of_wrapping(active)
+    }
+
+    pub fn output_down_wrapping(&self) -> Option<Output> {
+        let active = self.layout.active_output()?;
+        self.output_down_of_wrapping(active)
+    }
+
     pub fn output_previous(&self) -> Option<Output> {
         let active = self.layout.active_output()?;
         self.output_previous_of(active)
@@ -4122,6 +4719,7 @@ impl Niri {
                 return Some((None, index.saturating_sub(1) as usize));
             }
             WorkspaceReference::Name(name) => self.layout.find_workspace_by_name(&name)?,
+            WorkspaceReference::Uuid(uuid) => self.layout.find_workspace_by_uuid(&uuid)?,
             WorkspaceReference::Id(id) => {
                 let id = WorkspaceId::specific(id);
                 self.layout.find_workspace_by_id(id)?
@@ -4140,9 +4738,41 @@ impl Niri {
     }
 
     pub fn output_for_tablet(&self) -> Option<&Output> {
+        self.tablet_output.as_ref()
+    }
+
+    /// Re-resolves the output that tablets are mapped to.
+    ///
+    /// Called when outputs are connected or disconnected, and when the config changes.
+    pub fn refresh_tablet_output(&mut self) {
         let config = self.config.borrow();
-        let map_to_output = config.input.tablet.map_to_output.as_ref();
-        map_to_output.and_then(|name| self.output_by_name_match(name))
+        let map_to_output = config.input.tablet.map_to_output.as_deref();
+        let output = map_to_output
+            .and_then(|target| self.output_for_input_mapping(target))
+            .cloned();
+        drop(config);
+
+        if self.tablet_output != output {
+            match &output {
+                Some(output) => debug!("mapping tablets to output {}", output.name()),
+                // Leave the tablet mapped to the whole desktop until the target shows up.
+                None => debug!("mapping tablets to all outputs"),
+            }
+            self.tablet_output = output;
+        }
+    }
+
+    /// Finds the output that an input device should be mapped to.
+    ///
+    /// On top of the regular output name matching, this also matches by make and model alone, so
+    /// that the mapping survives the monitor moving to a different connector.
+    pub fn output_for_input_mapping(&self, target: &str) -> Option<&Output> {
+        self.output_by_name_match(target).or_else(|| {
+            self.global_space.outputs().find(|output| {
+                let name = output.user_data().get::<OutputName>().unwrap();
+                name.matches_make_model(target)
+            })
+        })
     }
 
     pub fn output_for_touch(&self) -> Option<&Output> {
@@ -4176,6 +4806,20 @@ impl Niri {
         self.layout.outputs().find(has_layer_surface)
     }
 
+    /// Returns the window in the floating resize mode if the mode still applies.
+    ///
+    /// The mode applies while the session is unlocked and its window is the focused floating
+    /// window.
+    pub fn floating_resize_mode_window(&self) -> Option<&Window> {
+        let mode = self.floating_resize_mode.as_ref()?;
+        if self.is_locked() || !self.layout.is_focus_floating() {
+            return None;
+        }
+
+        let mapped = self.layout.focus()?;
+        (mapped.window == mode.window).then_some(&mode.window)
+    }
+
     pub fn lock_surface_focus(&self) -> Option<WlSurface> {
         let output_under_cursor = self.output_under_cursor();
         let output = output_under_cursor
@@ -4198,6 +4842,47 @@ impl Niri {
     pub fn queue_redraw(&mut self, output: &Output) {
         let state = self.output_state.get_mut(output).unwrap();
         state.redraw_state.queue_redraw();
+
+        // Outputs mirroring this one need to show its new contents too.
+        for (target, source) in &self.mirrored_outputs {
+            if source == output
+                && let Some(state) = self.output_state.get_mut(target)
+            {
+                state.redraw_state.queue_redraw();
+            }
+        }
+    }
+
+    /// Starts showing the contents of `source` on `target`.
+    pub fn mirror_output(&mut self, source: &Output, target: &Output) {
+        if source == target {
+            return;
+        }
+
+        // Only mirror outputs that show their own contents, to avoid chains and cycles.
+        if self.mirrored_outputs.contains_key(source)
+            || self.mirrored_outputs.values().any(|s| s == target)
+        {
+            warn!(
+                "can't mirror {} onto {}: mirroring chains are not supported",
+                source.name(),
+                target.name()
+            );
+            return;
+        }
+
+        self.mirrored_outputs.insert(target.clone(), source.clone());
+        self.queue_redraw(target);
+    }
+
+    /// Starts or stops showing the contents of `source` on `target`.
+    pub fn toggle_output_mirror(&mut self, source: &Output, target: &Output) {
+        if self.mirrored_outputs.get(target) == Some(source) {
+            self.mirrored_outputs.remove(target);
+            self.queue_redraw(target);
+        } else {
+            self.mirror_output(source, target);
+        }
     }
 
     pub fn redraw_queued_outputs(&mut self, backend: &mut Backend) {
@@ -4465,6 +5150,62 @@ impl Niri {
         self.layout.refresh(layout_is_active);
     }
 
+    /// Assigns stable UUIDs to named workspaces, and clears them from unnamed ones.
+    pub fn refresh_workspace_uuids(&mut self) {
+        let _span = tracy_client::span!("Niri::refresh_workspace_uuids");
+
+        for ws in self.layout.workspaces_mut() {
+            let uuid = ws.name().map(|name| self.workspace_uuids.uuid_for(name));
+            if ws.uuid().map(String::as_str) != uuid {
+                let uuid = uuid.map(String::from);
+                ws.set_uuid(uuid);
+            }
+        }
+
+        self.workspace_uuids.save_if_changed();
+    }
+
+    /// Opens the next window of the process spawned with this token on the named workspace.
+    pub fn add_pending_spawn_on_workspace(&mut self, token: XdgActivationToken, workspace: String) {
+        self.pending_spawns_on_workspace
+            .push(PendingSpawnOnWorkspace {
+                token: token.clone(),
+                workspace,
+            });
+
+        // Drop the rule if the process doesn't open a window in time.
+        let res = self.event_loop.insert_source(
+            Timer::from_duration(SPAWN_ON_WORKSPACE_TIMEOUT),
+            move |_, _, state| {
+                state
+                    .niri
+                    .pending_spawns_on_workspace
+                    .retain(|pending| pending.token != token);
+                TimeoutAction::Drop
+            },
+        );
+        if let Err(err) = res {
+            warn!("error inserting spawn-on-workspace timer: {err:?}");
+        }
+    }
+
+    /// Takes the spawn-on-workspace target for a new toplevel, if its process has one pending.
+    ///
+    /// The process is matched by the activation token in its initial environment, so this also
+    /// works for children of the spawned process, like when spawning through a shell.
+    pub fn take_spawn_on_workspace_target(&mut self, surface: &WlSurface) -> Option<String> {
+        if self.pending_spawns_on_workspace.is_empty() {
+            return None;
+        }
+
+        let token = get_spawn_activation_token_for_surface(surface)?;
+        let idx = self
+            .pending_spawns_on_workspace
+            .iter()
+            .position(|pending| pending.token.as_str() == token)?;
+        Some(self.pending_spawns_on_workspace.remove(idx).workspace)
+    }
+
     pub fn refresh_idle_inhibit(&mut self) {
         let _span = tracy_client::span!("Niri::refresh_idle_inhibit");
 
@@ -4498,7 +5239,11 @@ impl Niri {
         let mut windows = vec![];
         let mut outputs = HashSet::new();
         self.layout.with_windows_mut(|mapped, output| {
-            if mapped.recompute_window_rules_if_needed(window_rules, self.is_at_startup) {
+            if mapped.recompute_window_rules_if_needed(
+                window_rules,
+                self.is_at_startup,
+                config.block_out_all_from_screencast,
+            ) {
                 windows.push(mapped.window.clone());
 
                 if let Some(output) = output {
@@ -4515,6 +5260,7 @@ impl Niri {
 
         for win in windows {
             self.layout.update_window(&win, None);
+            self.apply_force_floating(&win);
             win.toplevel()
                 .expect("no X11 support")
                 .send_pending_configure();
@@ -4524,6 +5270,18 @@ impl Niri {
         }
     }
 
+    /// Moves the window to the layer that its `force-floating` window rule asks for.
+    fn apply_force_floating(&mut self, window: &Window) {
+        let Some((_, mapped)) = self.layout.windows().find(|(_, m)| m.window == *window) else {
+            return;
+        };
+        let Some(floating) = mapped.rules().force_floating else {
+            return;
+        };
+
+        self.layout.set_window_floating(Some(window), floating);
+    }
+
     #[cfg(feature = "xdp-gnome-screencast")]
     pub fn refresh_mapped_cast_window_rules(&mut self) {
         // O(N^2) but should be fine since there aren't many casts usually.
@@ -4663,9 +5421,69 @@ impl Niri {
             draw_opaque_regions(&mut elements, output_scale);
         }
 
+        if self.debug_draw_render_heatmap {
+            let output_scale = Scale::from(output.current_scale().fractional_scale());
+            draw_render_heatmap(&mut elements, output_scale);
+        }
+
         elements
     }
 
+    /// Renders the contents of `source` scaled to fit `output`, for output mirroring.
+    ///
+    /// The source is rendered into an offscreen texture with the primary renderer, then
+    /// letterboxed onto the output, so outputs with different sizes and refresh rates work.
+    pub fn render_mirror<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output: &Output,
+        source: &Output,
+    ) -> Vec<OutputRenderElements<R>> {
+        let _span = tracy_client::span!("Niri::render_mirror");
+
+        let Some(state) = self.output_state.get(output) else {
+            return Vec::new();
+        };
+
+        let renderer = renderer.as_gles_renderer();
+
+        let source_scale = Scale::from(source.current_scale().fractional_scale());
+        let source_size = output_size(source);
+
+        // Render through an offscreen buffer kept across frames, so that only the damaged parts
+        // of the source are redrawn.
+        let elements = self.render::<GlesRenderer>(renderer, source, true, RenderTarget::Output);
+        let elem = match state
+            .mirror_buffer
+            .render(renderer, source_scale, &elements)
+        {
+            Ok((elem, _sync, _data)) => elem,
+            Err(err) => {
+                warn!(
+                    "error rendering output {} for mirroring: {err:?}",
+                    source.name()
+                );
+                return Vec::new();
+            }
+        };
+
+        // Letterbox the source contents to preserve the aspect ratio.
+        let output_scale = Scale::from(output.current_scale().fractional_scale());
+        let output_size = output_size(output);
+        let ratio = f64::min(output_size.w / source_size.w, output_size.h / source_size.h);
+        let fit_size = source_size.upscale(ratio);
+        let loc = Point::from((
+            (output_size.w - fit_size.w) / 2.,
+            (output_size.h - fit_size.h) / 2.,
+        ));
+
+        let elem = RescaleRenderElement::from_element(elem, Point::from((0, 0)), ratio);
+        let loc = loc.to_physical_precise_round(output_scale);
+        let elem = RelocateRenderElement::from_element(elem, loc, Relocate::Relative);
+
+        vec![OutputRenderElements::Mirror(elem)]
+    }
+
     pub fn render_pushing<R, C>(
         &self,
         renderer: &mut R,
@@ -5170,7 +5988,8 @@ impl Niri {
         self.update_render_elements(Some(output));
 
         let mut res = RenderResult::Skipped;
-        if self.monitors_active {
+        let is_active = self.monitors_active && !self.output_state[output].powered_off;
+        if is_active {
             let state = self.output_state.get_mut(output).unwrap();
             state.unfinished_animations_remain = self.layout.are_animations_ongoing(Some(output));
             state.unfinished_animations_remain |=
@@ -5216,10 +6035,10 @@ impl Niri {
             };
         }
 
-        // Update the lock render state on successful render, or if monitors are inactive. When
+        // Update the lock render state on successful render, or if the monitor is inactive. When
         // monitors are inactive on a TTY, they have no framebuffer attached, so no sensitive data
         // from a last render will be visible.
-        if res != RenderResult::Skipped || !self.monitors_active {
+        if res != RenderResult::Skipped || !is_active {
             state.lock_render_state = if is_locked {
                 LockRenderState::Locked
             } else {
@@ -5289,29 +6108,81 @@ impl Niri {
         let _span = tracy_client::span!("Niri::refresh_on_demand_vrr");
 
         let name = output.user_data().get::<OutputName>().unwrap();
-        let on_demand = self
-            .config
-            .borrow()
+        let config = self.config.borrow();
+        let Some(vrr) = config
             .outputs
             .find(name)
-            .is_some_and(|output| output.is_vrr_on_demand());
-        if !on_demand {
+            .and_then(|output| output.variable_refresh_rate.as_ref())
+            .filter(|vrr| vrr.on_demand)
+        else {
             return;
-        }
+        };
+        let idle_timeout = vrr
+            .on_demand_idle_ms
+            .filter(|ms| *ms != 0)
+            .map(|ms| Duration::from_millis(u64::from(ms)));
+        drop(config);
 
-        let current = self.layout.windows_for_output(output).any(|mapped| {
-            mapped.rules().variable_refresh_rate == Some(true) && {
-                let mut visible = false;
-                mapped.window.with_surfaces(|surface, states| {
-                    if !visible
-                        && surface_primary_scanout_output(surface, states).as_ref() == Some(output)
-                    {
-                        visible = true;
+        let now = get_monotonic_time();
+        let mut current = false;
+        let mut active_until = None;
+        for mapped in self.layout.windows_for_output(output) {
+            if mapped.rules().variable_refresh_rate != Some(true) {
+                continue;
+            }
+
+            // Static content (e.g. a paused video) can flicker with VRR, so with an idle timeout,
+            // only enable it while the window keeps committing.
+            let until = match idle_timeout {
+                Some(timeout) => {
+                    let last_commit_time = mapped.last_commit_time().get();
+                    match on_demand_vrr_active_until(last_commit_time, timeout, now) {
+                        Some(until) => Some(until),
+                        None => continue,
                     }
-                });
-                visible
+                }
+                None => None,
+            };
+
+            let mut visible = false;
+            mapped.window.with_surfaces(|surface, states| {
+                if !visible
+                    && surface_primary_scanout_output(surface, states).as_ref() == Some(output)
+                {
+                    visible = true;
+                }
+            });
+            if !visible {
+                continue;
             }
-        });
+
+            current = true;
+            active_until = active_until.max(until);
+        }
+
+        // Nothing redraws the output once the windows stop committing, so schedule a timer to
+        // disable VRR when they go idle.
+        let output_state = self.output_state.get_mut(output).unwrap();
+        if let Some(token) = output_state.on_demand_vrr_idle_timer.take() {
+            self.event_loop.remove(token);
+        }
+        if let Some(until) = active_until {
+            let output = output.clone();
+            let timer = Timer::from_duration(until.saturating_sub(now));
+            let token = self
+                .event_loop
+                .insert_source(timer, move |_, _, state| {
+                    if let Some(output_state) = state.niri.output_state.get_mut(&output) {
+                        output_state.on_demand_vrr_idle_timer = None;
+                        state
+                            .niri
+                            .refresh_on_demand_vrr(&mut state.backend, &output);
+                    }
+                    TimeoutAction::Drop
+                })
+                .unwrap();
+            output_state.on_demand_vrr_idle_timer = Some(token);
+        }
 
         backend.set_output_on_demand_vrr(self, output, current);
     }
@@ -5828,7 +6699,7 @@ impl Niri {
         output: &Output,
         scale: Scale<f64>,
     ) -> Option<Point<f64, Logical>> {
-        if self.layout.is_overview_open() {
+        if self.layout.is_overview_open_on(output) {
             return None;
         }
 
@@ -6255,6 +7126,12 @@ impl Niri {
         self.queue_redraw_all();
     }
 
+    pub fn debug_toggle_render_heatmap(&mut self) {
+        self.debug_draw_render_heatmap = !self.debug_draw_render_heatmap;
+        set_record_draw_times(self.debug_draw_render_heatmap);
+        self.queue_redraw_all();
+    }
+
     pub fn capture_screenshots<'a>(
         &'a self,
         renderer: &'a mut GlesRenderer,
@@ -6360,6 +7237,87 @@ impl Niri {
         Ok((size, scale, pixels))
     }
 
+    /// Captures all outputs stitched together according to their positions.
+    ///
+    /// Outputs are rendered at the highest output scale, and gaps between outputs are left
+    /// transparent.
+    fn screenshot_all_outputs_raw(
+        &mut self,
+        renderer: &mut GlesRenderer,
+        include_pointer: bool,
+    ) -> anyhow::Result<(Size<i32, Physical>, Scale<f64>, Vec<u8>)> {
+        let _span = tracy_client::span!("Niri::screenshot_all_outputs");
+
+        let outputs: Vec<_> = self.global_space.outputs().cloned().collect();
+        let bbox = outputs
+            .iter()
+            .filter_map(|output| self.global_space.output_geometry(output))
+            .reduce(|a, b| a.merge(b))
+            .context("no outputs")?;
+        let max_scale = outputs
+            .iter()
+            .map(|output| output.current_scale().fractional_scale())
+            .fold(1., f64::max);
+
+        let mut elements = Vec::with_capacity(outputs.len());
+        for output in &outputs {
+            let Some(geo) = self.global_space.output_geometry(output) else {
+                continue;
+            };
+
+            self.update_render_elements(Some(output));
+
+            let size = output.current_mode().unwrap().size;
+            let transform = output.current_transform();
+            let size = transform.transform_size(size);
+
+            let scale = Scale::from(output.current_scale().fractional_scale());
+            let output_elements = self.render::<GlesRenderer>(
+                renderer,
+                output,
+                include_pointer,
+                RenderTarget::ScreenCapture,
+            );
+            let (texture, _) = render_to_texture(
+                renderer,
+                size,
+                scale,
+                Transform::Normal,
+                Fourcc::Abgr8888,
+                output_elements.iter().rev(),
+            )?;
+
+            let buffer = TextureBuffer::from_texture(
+                renderer,
+                texture,
+                scale,
+                Transform::Normal,
+                Vec::new(),
+            );
+            elements.push(TextureRenderElement::from_texture_buffer(
+                buffer,
+                (geo.loc - bbox.loc).to_f64(),
+                1.,
+                None,
+                Some(geo.size.to_f64()),
+                Kind::Unspecified,
+            ));
+        }
+
+        let scale = Scale::from(max_scale);
+        let size = bbox.size.to_physical_precise_ceil(scale);
+        let pixels = render_to_vec(
+            renderer,
+            size,
+            scale,
+            Transform::Normal,
+            Fourcc::Abgr8888,
+            elements.iter(),
+        )?;
+
+        Ok((size, scale, pixels))
+    }
+
     fn screenshot_window_raw(
         &self,
         renderer: &mut GlesRenderer,
@@ -6373,7 +7331,7 @@ impl Niri {
             if mapped.sizing_mode().is_fullscreen() || mapped.is_ignoring_opacity_window_rule() {
                 1.
             } else {
-                mapped.rules().opacity.unwrap_or(1.).clamp(0., 1.)
+                mapped.opacity().unwrap_or(1.).clamp(0., 1.)
             };
         // FIXME: pointer.
         let mut elements = Vec::new();
@@ -6401,6 +7359,28 @@ impl Niri {
             elements,
         )?;
 
+        // Match what is shown on screen, where the window is clipped to its geometry with rounded
+        // corners.
+        let rules = mapped.rules();
+        if rules.clip_to_geometry == Some(true) && !mapped.sizing_mode().is_fullscreen() {
+            let window_size = mapped.size();
+            let window_geo = Rectangle::new(mapped.window.geometry().loc, window_size)
+                .to_f64()
+                .to_physical_precise_round(scale);
+            let window_geo = Rectangle::new(window_geo.loc - geo.loc, window_geo.size)
+                .intersection(Rectangle::from_size(geo.size));
+
+            if let Some(window_geo) = window_geo {
+                let radius = rules
+                    .geometry_corner_radius
+                    .unwrap_or_default()
+                    .fit_to(window_size.w as f32, window_size.h as f32)
+                    .scaled_by(scale.x as f32);
+                let pixels = clip_pixels_to_rounded_rect(&pixels, geo.size, window_geo, radius);
+                return Ok((window_geo.size, pixels));
+            }
+        }
+
         Ok((geo.size, pixels))
     }
 
@@ -6537,11 +7517,29 @@ impl Niri {
     pub fn screenshot_all_outputs_to_pipe<O: ScreenshotOutput>(
         &mut self,
         renderer: &mut GlesRenderer,
+        include_pointer: bool,
         out: O,
     ) {
-        // FIXME: Screenshoting multiple outputs is not implemented, captures the current output instead
-        // Not a regression, as old version of this function had ensure!(outputs.len() == 1)
-        self.screenshot_output_to_pipe(renderer, None, false, out)
+        let (size, scale, pixels) = match self.screenshot_all_outputs_raw(renderer, include_pointer)
+        {
+            Ok(v) => v,
+            Err(e) => {
+                out.image_meta_failed(anyhow::anyhow!("failed to capture screenshot: {e:?}"));
+                return;
+            }
+        };
+
+        self.save_to_pipe(
+            ScreenshotData {
+                width: size.w as u32,
+                height: size.h as u32,
+                scale: scale.x,
+                screen: None,
+                window_id: None,
+            },
+            pixels,
+            out,
+        );
     }
 
     pub fn screenshot_to_pipe<O: ScreenshotOutput>(
@@ -6562,7 +7560,9 @@ impl Niri {
             ScreenshotTarget::Window(mapped_id) => {
                 self.screenshot_window_to_pipe(renderer, Some(mapped_id), out)
             }
-            ScreenshotTarget::AllOutputs => self.screenshot_all_outputs_to_pipe(renderer, out),
+            ScreenshotTarget::AllOutputs => {
+                self.screenshot_all_outputs_to_pipe(renderer, include_pointer, out)
+            }
         }
     }
 
@@ -6603,6 +7603,7 @@ impl Niri {
         }
 
         info!("locking session");
+        self.floating_resize_mode = None;
 
         if self.output_state.is_empty() {
             // There are no outputs, lock the session right away.
@@ -6879,6 +7880,12 @@ impl Niri {
         root.clone()
     }
 
+    /// Returns the app id of the window that the surface belongs to, if any.
+    pub fn surface_app_id(&self, surface: &WlSurface) -> Option<String> {
+        let (mapped, _) = self.layout.find_window_and_output(surface)?;
+        with_toplevel_role(mapped.toplevel(), |role| role.app_id.clone())
+    }
+
     #[cfg(feature = "dbus")]
     pub fn on_ipc_outputs_changed(&self) {
         let _span = tracy_client::span!("Niri::on_ipc_outputs_changed");
@@ -6941,7 +7948,10 @@ impl Niri {
         }
 
         if let Some(window) = &new_focus.window
-            && !self.layout.is_overview_open()
+            && !new_focus
+                .output
+                .as_ref()
+                .is_some_and(|output| self.layout.is_overview_open_on(output))
             && current_focus.window.as_ref() != Some(window)
         {
             let (window, hit) = window;
@@ -7056,13 +8066,16 @@ impl Niri {
         let _span = tracy_client::span!("Niri::recompute_window_rules");
 
         let changed = {
-            let window_rules = &self.config.borrow().window_rules;
+            let config = self.config.borrow();
+            let window_rules = &config.window_rules;
+            let block_out_all = config.block_out_all_from_screencast;
 
             for unmapped in self.unmapped_windows.values_mut() {
                 let new_rules = ResolvedWindowRules::compute(
                     window_rules,
                     WindowRef::Unmapped(unmapped),
                     self.is_at_startup,
+                    block_out_all,
                 );
                 if let InitialConfigureState::Configured { rules, .. } = &mut unmapped.state {
                     *rules = new_rules;
@@ -7071,13 +8084,14 @@ impl Niri {
 
             let mut windows = vec![];
             self.layout.with_windows_mut(|mapped, _| {
-                if mapped.recompute_window_rules(window_rules, self.is_at_startup) {
+                if mapped.recompute_window_rules(window_rules, self.is_at_startup, block_out_all) {
                     windows.push(mapped.window.clone());
                 }
             });
             let changed = !windows.is_empty();
             for win in windows {
                 self.layout.update_window(&win, None);
+                self.apply_force_floating(&win);
             }
             changed
         };
@@ -7097,7 +8111,11 @@ impl Niri {
             let rules = &config.layer_rules;
 
             for mapped in self.mapped_layer_surfaces.values_mut() {
-                if mapped.recompute_layer_rules(rules, self.is_at_startup) {
+                if mapped.recompute_layer_rules(
+                    rules,
+                    self.is_at_startup,
+                    config.block_out_all_from_screencast,
+                ) {
                     changed = true;
                     mapped.update_config(&config);
                 }
@@ -7193,6 +8211,12 @@ impl Niri {
         }
     }
 
+    pub fn mru_cancel_keyboard_commit(&mut self) {
+        if let Some(pending) = self.pending_mru_commit.take() {
+            self.event_loop.remove(pending.token);
+        }
+    }
+
     pub fn queue_redraw_mru_output(&mut self) {
         if let Some(output) = self.window_mru_ui.output().cloned() {
             self.queue_redraw(&output);
@@ -7204,6 +8228,8 @@ pub struct NewClient {
     pub client: UnixStream,
     pub restricted: bool,
     pub credentials_unknown: bool,
+    /// Activation token to use instead of the one in the process environment.
+    pub activation_token: Option<String>,
 }
 
 pub struct ClientState {
@@ -7214,6 +8240,8 @@ pub struct ClientState {
     pub restricted: bool,
     /// We cannot retrieve this client's socket credentials.
     pub credentials_unknown: bool,
+    /// Activation token to use instead of the one in the process environment.
+    pub activation_token: Option<String>,
 }
 
 impl ClientData for ClientState {
@@ -7259,6 +8287,7 @@ niri_render_elements! {
         WindowMruUi = WindowMruUiRenderElement<R>,
         ExitConfirmDialog = ExitConfirmDialogRenderElement,
         Texture = PrimaryGpuTextureRenderElement,
+        Mirror = RelocateRenderElement<RescaleRenderElement<OffscreenRenderElement>>,
         // Used for the CPU-rendered panels.
         RelocatedMemoryBuffer = RelocateRenderElement<MemoryRenderBufferRenderElement<R>>,
     }
@@ -55,6 +55,10 @@ impl GammaControlManagerState {
         }
     }
 
+    pub fn has_gamma_control(&self, output: &Output) -> bool {
+        self.gamma_controls.contains_key(output)
+    }
+
     pub fn output_removed(&mut self, output: &Output) {
         if let Some(gamma_control) = self.gamma_controls.remove(output) {
             gamma_control.failed();
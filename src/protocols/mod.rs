@@ -6,6 +6,7 @@ pub mod kde_blur;
 pub mod kde_output_order;
 pub mod mutter_x11_interop;
 pub mod output_management;
+pub mod registry;
 pub mod screencopy;
 pub mod virtual_keyboard;
 pub mod virtual_pointer;
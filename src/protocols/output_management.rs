@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
+use std::hash::Hash;
 use std::iter::zip;
 use std::mem;
 
@@ -47,7 +48,23 @@ pub struct OutputManagementManagerGlobalData {
 
 pub trait OutputManagementHandler {
     fn output_management_state(&mut self) -> &mut OutputManagementManagerState;
-    fn apply_output_config(&mut self, config: niri_config::Outputs);
+    /// Applies the configuration of the given outputs, leaving other outputs as is.
+    ///
+    /// The result must be reported on `conf` once the new configuration has taken effect. On
+    /// failure, the previous configuration must be restored.
+    fn apply_output_config(
+        &mut self,
+        configs: Vec<niri_config::Output>,
+        conf: ZwlrOutputConfigurationV1,
+    );
+}
+
+/// Applied output configuration waiting for its result to be reported.
+pub struct PendingOutputConfiguration {
+    pub conf: ZwlrOutputConfigurationV1,
+    pub configs: Vec<niri_config::Output>,
+    /// Output config to restore if the new configuration fails to apply.
+    pub old_outputs: niri_config::Outputs,
 }
 
 #[derive(Debug)]
@@ -515,15 +532,13 @@ where
                     return;
                 };
 
-                let any_enabled = new_config.values().any(|c| !c.off);
-                if !any_enabled {
+                if let Err(err) = check_config(&g_state.current_state, &new_config) {
+                    debug!("OutputConfiguration: rejecting configuration: {err}");
                     conf.failed();
                     return;
                 }
 
-                state.apply_output_config(new_config.into_values().collect());
-                // FIXME: verify that it had been applied successfully (which may be difficult).
-                conf.succeeded();
+                state.apply_output_config(new_config.into_values().collect(), conf.clone());
             }
             zwlr_output_configuration_v1::Request::Test => {
                 if outdated {
@@ -545,13 +560,13 @@ where
                     return;
                 };
 
-                let any_enabled = new_config.values().any(|c| !c.off);
-                if !any_enabled {
+                if let Err(err) = check_config(&g_state.current_state, &new_config) {
+                    debug!("OutputConfiguration: test failed: {err}");
                     conf.failed();
                     return;
                 }
 
-                // FIXME: actually test the configuration with TTY.
+                // FIXME: actually test the modeset with TTY.
                 conf.succeeded()
             }
             zwlr_output_configuration_v1::Request::Destroy => {
@@ -722,7 +737,10 @@ where
             }
             zwlr_output_configuration_head_v1::Request::SetAdaptiveSync { state } => {
                 let vrr = match state {
-                    WEnum::Value(AdaptiveSyncState::Enabled) => Some(Vrr { on_demand: false }),
+                    WEnum::Value(AdaptiveSyncState::Enabled) => Some(Vrr {
+                        on_demand: false,
+                        on_demand_idle_ms: None,
+                    }),
                     WEnum::Value(AdaptiveSyncState::Disabled) => None,
                     _ => {
                         warn!("SetAdaptativeSync: unknown requested adaptative sync");
@@ -823,6 +841,53 @@ macro_rules! delegate_output_management{
     };
 }
 
+/// Checks that the configuration can be applied on top of the current state.
+fn check_config<K: Eq + Hash>(
+    current_state: &HashMap<K, niri_ipc::Output>,
+    new_config: &HashMap<K, niri_config::Output>,
+) -> Result<(), &'static str> {
+    // Outputs that the configuration doesn't mention keep their current state.
+    let any_enabled = current_state
+        .iter()
+        .any(|(id, output)| match new_config.get(id) {
+            Some(config) => !config.off,
+            None => output.logical.is_some(),
+        });
+    if !any_enabled {
+        return Err("no outputs would remain enabled");
+    }
+
+    for (id, config) in new_config {
+        if config.off {
+            continue;
+        }
+
+        let Some(output) = current_state.get(id) else {
+            return Err("output is gone");
+        };
+
+        // Custom modes aren't advertised, so there's nothing to check them against.
+        if let Some(mode) = config.mode.filter(|mode| !mode.custom)
+            && config.modeline.is_none()
+            && !output.modes.iter().any(|m| mode_matches(m, &mode.mode))
+        {
+            return Err("requested mode is not supported by the output");
+        }
+    }
+
+    Ok(())
+}
+
+fn mode_matches(mode: &niri_ipc::Mode, target: &niri_ipc::ConfiguredMode) -> bool {
+    if (mode.width, mode.height) != (target.width, target.height) {
+        return false;
+    }
+
+    target
+        .refresh
+        .is_none_or(|refresh| (refresh * 1000.).round() as u32 == mode.refresh_rate)
+}
+
 fn notify_removed_head(clients: &mut HashMap<ClientId, ClientData>, head: &OutputId) {
     for data in clients.values_mut() {
         if let Some((head, mods)) = data.heads.remove(head) {
@@ -921,3 +986,101 @@ fn send_new_head<D>(
     // new_head.serial_number(output.serial);
     client_data.heads.insert(output, (new_head, new_modes));
 }
+
+#[cfg(test)]
+mod tests {
+    use niri_ipc::{ConfiguredMode, LogicalOutput, Mode};
+
+    use super::*;
+
+    fn mode(width: u16, height: u16, refresh_rate: u32) -> Mode {
+        Mode {
+            width,
+            height,
+            refresh_rate,
+            is_preferred: false,
+        }
+    }
+
+    fn output(enabled: bool) -> niri_ipc::Output {
+        niri_ipc::Output {
+            name: String::from("DP-1"),
+            make: String::new(),
+            model: String::new(),
+            serial: None,
+            physical_size: None,
+            modes: vec![mode(1920, 1080, 60000), mode(1920, 1080, 144000)],
+            current_mode: enabled.then_some(0),
+            is_custom_mode: false,
+            vrr_supported: false,
+            vrr_enabled: false,
+            logical: enabled.then_some(LogicalOutput {
+                x: 0,
+                y: 0,
+                width: 1920,
+                height: 1080,
+                scale: 1.,
+                transform: Transform::Normal,
+            }),
+        }
+    }
+
+    fn config(off: bool, mode: Option<ConfiguredMode>) -> niri_config::Output {
+        niri_config::Output {
+            off,
+            mode: mode.map(|mode| niri_config::output::Mode {
+                custom: false,
+                mode,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn configured(width: u16, height: u16, refresh: Option<f64>) -> ConfiguredMode {
+        ConfiguredMode {
+            width,
+            height,
+            refresh,
+        }
+    }
+
+    #[test]
+    fn mode_matches_size_and_refresh() {
+        let m = mode(1920, 1080, 59940);
+        assert!(mode_matches(&m, &configured(1920, 1080, None)));
+        assert!(mode_matches(&m, &configured(1920, 1080, Some(59.94))));
+        assert!(!mode_matches(&m, &configured(1920, 1080, Some(60.))));
+        assert!(!mode_matches(&m, &configured(1280, 720, None)));
+    }
+
+    #[test]
+    fn check_config_keeps_an_output_enabled() {
+        let current = HashMap::from([(1, output(true)), (2, output(false))]);
+
+        let new = HashMap::from([(1, config(true, None))]);
+        assert!(check_config(&current, &new).is_err());
+
+        // Enabling the other output at the same time is fine.
+        let new = HashMap::from([(1, config(true, None)), (2, config(false, None))]);
+        assert_eq!(check_config(&current, &new), Ok(()));
+
+        // Outputs left out of the configuration keep their current state.
+        let new = HashMap::from([(2, config(true, None))]);
+        assert_eq!(check_config(&current, &new), Ok(()));
+    }
+
+    #[test]
+    fn check_config_rejects_unsupported_modes() {
+        let current = HashMap::from([(1, output(true))]);
+
+        let new = HashMap::from([(1, config(false, Some(configured(1920, 1080, Some(144.)))))]);
+        assert_eq!(check_config(&current, &new), Ok(()));
+
+        let new = HashMap::from([(1, config(false, Some(configured(2560, 1440, None))))]);
+        assert!(check_config(&current, &new).is_err());
+
+        // Outputs that aren't there anymore can't be configured.
+        let new = HashMap::from([(2, config(false, None))]);
+        assert!(check_config(&current, &new).is_err());
+    }
+}
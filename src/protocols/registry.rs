@@ -0,0 +1,57 @@
+//! Extension point for experimental Wayland protocols.
+//!
+//! A protocol implementing [`RawProtocol`] creates its own globals and keeps its own state, so
+//! prototyping a new protocol doesn't need new fields in [`Niri`](crate::niri::Niri) or changes
+//! to its constructor. Dispatch is done by implementing `GlobalDispatch` and `Dispatch` for
+//! [`State`] right next to the protocol, then the protocol is registered at startup with
+//! [`State::register_raw_protocol`].
+
+use std::any::Any;
+
+use smithay::reexports::calloop::LoopHandle;
+use smithay::reexports::wayland_server::DisplayHandle;
+
+use crate::niri::State;
+
+/// Protocol that can be registered with the compositor at runtime.
+pub trait RawProtocol: Any {
+    /// Name of the protocol, used for logging.
+    fn name(&self) -> &'static str;
+
+    /// Creates the protocol globals.
+    ///
+    /// This is also the place to insert any event loop sources the protocol needs.
+    fn init(&mut self, display: &DisplayHandle, event_loop: &LoopHandle<'static, State>);
+}
+
+/// Registered raw protocols and their state.
+#[derive(Default)]
+pub struct RawProtocols {
+    protocols: Vec<Box<dyn RawProtocol>>,
+}
+
+impl RawProtocols {
+    /// Returns the state of a registered protocol.
+    pub fn get<T: RawProtocol>(&self) -> Option<&T> {
+        self.protocols
+            .iter()
+            .find_map(|p| (p.as_ref() as &dyn Any).downcast_ref())
+    }
+
+    /// Returns the mutable state of a registered protocol.
+    pub fn get_mut<T: RawProtocol>(&mut self) -> Option<&mut T> {
+        self.protocols
+            .iter_mut()
+            .find_map(|p| (p.as_mut() as &mut dyn Any).downcast_mut())
+    }
+}
+
+impl State {
+    /// Registers a protocol and creates its globals.
+    pub fn register_raw_protocol(&mut self, mut protocol: impl RawProtocol) {
+        debug!("registering raw protocol {}", protocol.name());
+
+        protocol.init(&self.niri.display_handle, &self.niri.event_loop);
+        self.niri.raw_protocols.protocols.push(Box::new(protocol));
+    }
+}
@@ -30,6 +30,14 @@ use super::shaders::Shaders;
 
 use std::sync::MutexGuard;
 
+/// Returns whether true blur renders correctly on an output with this transform.
+///
+/// Elsewhere, blur falls back to the optimized variant, for example after rotating an output.
+// FIXME: true blur is broken on 90/270 transformed monitors
+pub fn supports_true_blur(transform: Transform) -> bool {
+    matches!(transform, Transform::Normal | Transform::Flipped180)
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 enum CurrentBuffer {
     /// We are currently sampling from normal buffer, and rendering in the swapped/alternative.
@@ -921,3 +929,17 @@ fn build_texture_mat(
 
     tex_mat
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn true_blur_falls_back_on_rotated_outputs() {
+        assert!(supports_true_blur(Transform::Normal));
+        assert!(supports_true_blur(Transform::Flipped180));
+        assert!(!supports_true_blur(Transform::_90));
+        assert!(!supports_true_blur(Transform::_270));
+        assert!(!supports_true_blur(Transform::Flipped90));
+    }
+}
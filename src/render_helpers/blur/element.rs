@@ -29,7 +29,7 @@ use crate::utils::region::Region;
 use crate::utils::render::{PushRenderElement, Render};
 use smithay::backend::allocator::Fourcc;
 
-use super::{CurrentBuffer, EffectsFramebuffers};
+use super::{CurrentBuffer, EffectsFramebuffers, supports_true_blur};
 
 #[derive(Debug, Clone)]
 enum BlurVariant {
@@ -123,6 +123,11 @@ impl Blur {
         self.config = config;
     }
 
+    #[cfg(test)]
+    pub fn config(&self) -> &niri_config::Blur {
+        &self.config
+    }
+
     pub fn clear_alpha_tex(&self) {
         if self.alpha_tex.borrow().is_some() {
             self.inner.borrow_mut().iter_mut().for_each(|e| {
@@ -200,11 +205,7 @@ where
             return;
         }
 
-        // FIXME: true blur is broken on 90/270 transformed monitors
-        if !matches!(
-            fx_buffers.borrow().transform(),
-            Transform::Normal | Transform::Flipped180,
-        ) {
+        if !supports_true_blur(fx_buffers.borrow().transform()) {
             true_blur = false;
         }
 
@@ -1,3 +1,8 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
 use smithay::backend::renderer::Color32F;
 use smithay::backend::renderer::damage::OutputDamageTracker;
 use smithay::backend::renderer::element::{Element, Id, Kind};
@@ -8,6 +13,88 @@ use super::renderer::NiriRenderer;
 use super::solid_color::SolidColorRenderElement;
 use crate::niri::OutputRenderElements;
 
+/// Draw time at and below which an element gets no heatmap tint.
+const HEATMAP_COLD: Duration = Duration::from_micros(20);
+/// Draw time at and above which an element gets the full heatmap tint.
+const HEATMAP_HOT: Duration = Duration::from_millis(2);
+
+static RECORD_DRAW_TIMES: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Last draw duration of every render element, for the render heatmap.
+    static DRAW_TIMES: RefCell<HashMap<Id, Duration>> = RefCell::new(HashMap::new());
+}
+
+pub fn set_record_draw_times(enabled: bool) {
+    RECORD_DRAW_TIMES.store(enabled, Ordering::Relaxed);
+
+    if !enabled {
+        DRAW_TIMES.with_borrow_mut(HashMap::clear);
+    }
+}
+
+/// Runs the element draw function, recording how long it took if the render heatmap is on.
+///
+/// This measures the time spent on the CPU submitting the draw, not the GPU time, so it's only an
+/// approximation. It's still good enough to spot elements with expensive shaders, like blur.
+pub fn record_draw_time<T>(id: &Id, draw: impl FnOnce() -> T) -> T {
+    if !RECORD_DRAW_TIMES.load(Ordering::Relaxed) {
+        return draw();
+    }
+
+    let start = Instant::now();
+    let rv = draw();
+    let elapsed = start.elapsed();
+
+    DRAW_TIMES.with_borrow_mut(|times| times.insert(id.clone(), elapsed));
+
+    rv
+}
+
+/// Tints every element red according to how long it took to draw the last time it was drawn.
+pub fn draw_render_heatmap<R: NiriRenderer>(
+    elements: &mut Vec<OutputRenderElements<R>>,
+    scale: Scale<f64>,
+) {
+    let _span = tracy_client::span!("draw_render_heatmap");
+
+    DRAW_TIMES.with_borrow_mut(|times| {
+        // Forget elements that went away.
+        times.retain(|id, _| elements.iter().any(|elem| elem.id() == id));
+
+        let cold = HEATMAP_COLD.as_secs_f32().ln();
+        let hot = HEATMAP_HOT.as_secs_f32().ln();
+
+        let mut i = 0;
+        while i < elements.len() {
+            let elem = &elements[i];
+            i += 1;
+
+            let Some(time) = times.get(elem.id()) else {
+                continue;
+            };
+
+            // Draw times span orders of magnitude, so interpolate on a log scale.
+            let heat = (time.as_secs_f32().max(f32::MIN_POSITIVE).ln() - cold) / (hot - cold);
+            let heat = heat.clamp(0., 1.);
+            if heat == 0. {
+                continue;
+            }
+
+            let alpha = heat * 0.6;
+            let color = SolidColorRenderElement::new(
+                Id::new(),
+                elem.geometry(scale).to_f64().to_logical(scale),
+                CommitCounter::default(),
+                Color32F::from([alpha, 0., 0., alpha]),
+                Kind::Unspecified,
+            );
+            elements.insert(i - 1, OutputRenderElements::SolidColor(color));
+            i += 1;
+        }
+    });
+}
+
 pub fn draw_opaque_regions<R: NiriRenderer>(
     elements: &mut Vec<OutputRenderElements<R>>,
     scale: Scale<f64>,
@@ -1,7 +1,7 @@
 use std::ptr;
 
 use anyhow::{Context, ensure};
-use niri_config::BlockOutFrom;
+use niri_config::{BlockOutFrom, CornerRadius};
 use smithay::backend::allocator::dmabuf::Dmabuf;
 use smithay::backend::allocator::{Buffer, Fourcc};
 use smithay::backend::renderer::element::utils::{Relocate, RelocateRenderElement};
@@ -258,6 +258,64 @@ pub fn render_to_vec(
     Ok(copy.to_vec())
 }
 
+/// Crops premultiplied RGBA pixels to `rect` and makes them transparent outside the rounded
+/// corners.
+///
+/// `radius` is in physical pixels, like `rect`, which must lie within `size`.
+pub fn clip_pixels_to_rounded_rect(
+    pixels: &[u8],
+    size: Size<i32, Physical>,
+    rect: Rectangle<i32, Physical>,
+    radius: CornerRadius,
+) -> Vec<u8> {
+    let stride = size.w as usize * 4;
+    let row_len = rect.size.w as usize * 4;
+    let (w, h) = (rect.size.w as f32, rect.size.h as f32);
+
+    let mut rv = Vec::with_capacity(row_len * rect.size.h as usize);
+    for y in 0..rect.size.h {
+        let start = (rect.loc.y + y) as usize * stride + rect.loc.x as usize * 4;
+        rv.extend_from_slice(&pixels[start..start + row_len]);
+    }
+
+    for (idx, pixel) in rv.chunks_exact_mut(4).enumerate() {
+        let x = (idx % rect.size.w as usize) as f32 + 0.5;
+        let y = (idx / rect.size.w as usize) as f32 + 0.5;
+
+        let corner = if x < radius.top_left && y < radius.top_left {
+            Some((radius.top_left, radius.top_left, radius.top_left))
+        } else if x > w - radius.top_right && y < radius.top_right {
+            Some((w - radius.top_right, radius.top_right, radius.top_right))
+        } else if x > w - radius.bottom_right && y > h - radius.bottom_right {
+            Some((
+                w - radius.bottom_right,
+                h - radius.bottom_right,
+                radius.bottom_right,
+            ))
+        } else if x < radius.bottom_left && y > h - radius.bottom_left {
+            Some((
+                radius.bottom_left,
+                h - radius.bottom_left,
+                radius.bottom_left,
+            ))
+        } else {
+            None
+        };
+
+        if let Some((cx, cy, r)) = corner {
+            // Antialias the edge over one pixel.
+            let dist = f32::hypot(x - cx, y - cy);
+            let coverage = (r - dist + 0.5).clamp(0., 1.);
+            // The pixels are premultiplied, so scale the color along with the alpha.
+            for channel in pixel {
+                *channel = (f32::from(*channel) * coverage).round() as u8;
+            }
+        }
+    }
+
+    rv
+}
+
 pub fn render_to_dmabuf(
     renderer: &mut GlesRenderer,
     mut dmabuf: Dmabuf,
@@ -359,3 +417,37 @@ fn render_elements(
 
     frame.finish().context("error finishing frame")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clip_pixels_to_rounded_rect_corners() {
+        // An opaque white 20×20 image with a 16×16 window in the middle.
+        let size = Size::from((20, 20));
+        let pixels = vec![255; 20 * 20 * 4];
+        let rect = Rectangle::new(Point::from((2, 2)), Size::from((16, 16)));
+
+        let clipped = clip_pixels_to_rounded_rect(&pixels, size, rect, CornerRadius::from(6.));
+        assert_eq!(clipped.len(), 16 * 16 * 4);
+
+        let pixel = |x: usize, y: usize| &clipped[(y * 16 + x) * 4..][..4];
+
+        // The corners are fully transparent, including the color.
+        assert_eq!(pixel(0, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(15, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(15, 15), [0, 0, 0, 0]);
+        assert_eq!(pixel(0, 15), [0, 0, 0, 0]);
+
+        // The antialiased edge stays premultiplied.
+        let edge = pixel(1, 2);
+        assert!(0 < edge[3] && edge[3] < 255);
+        assert!(edge[..3].iter().all(|&c| c == edge[3]));
+
+        // The rest of the window is untouched.
+        assert_eq!(pixel(8, 0), [255; 4]);
+        assert_eq!(pixel(0, 8), [255; 4]);
+        assert_eq!(pixel(8, 8), [255; 4]);
+    }
+}
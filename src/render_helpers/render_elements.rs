@@ -108,7 +108,9 @@ macro_rules! niri_render_elements {
             ) -> Result<(), smithay::backend::renderer::gles::GlesError> {
                 match self {
                     $($name::$variant(elem) => {
-                        smithay::backend::renderer::element::RenderElement::<smithay::backend::renderer::gles::GlesRenderer>::draw(elem, frame, src, dst, damage, opaque_regions)
+                        $crate::render_helpers::debug::record_draw_time(smithay::backend::renderer::element::Element::id(elem), || {
+                            smithay::backend::renderer::element::RenderElement::<smithay::backend::renderer::gles::GlesRenderer>::draw(elem, frame, src, dst, damage, opaque_regions)
+                        })
                     })+
                 }
             }
@@ -133,7 +135,9 @@ macro_rules! niri_render_elements {
             ) -> Result<(), $crate::backend::tty::TtyRendererError<'render>> {
                 match self {
                     $($name::$variant(elem) => {
-                        smithay::backend::renderer::element::RenderElement::<$crate::backend::tty::TtyRenderer<'render>>::draw(elem, frame, src, dst, damage, opaque_regions)
+                        $crate::render_helpers::debug::record_draw_time(smithay::backend::renderer::element::Element::id(elem), || {
+                            smithay::backend::renderer::element::RenderElement::<$crate::backend::tty::TtyRenderer<'render>>::draw(elem, frame, src, dst, damage, opaque_regions)
+                        })
                     })+
                 }
             }
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use niri_config::{Action, Bind, Key, Modifiers, Trigger};
+use smithay::input::keyboard::Keysym;
+use smithay::reexports::calloop::RegistrationToken;
+
+use super::*;
+
+fn bind(trigger: Trigger) -> Bind {
+    Bind {
+        key: Key {
+            trigger,
+            modifiers: Modifiers::COMPOSITOR,
+        },
+        action: Action::ToggleOverview,
+        repeat: false,
+        cooldown: Some(Duration::from_secs(60)),
+        hold: None,
+        allow_when_locked: false,
+        allow_inhibiting: true,
+        hotkey_overlay_title: None,
+    }
+}
+
+fn set_up() -> Fixture {
+    let mut f = Fixture::new();
+    f.add_output(1, (1920, 1080));
+    f
+}
+
+fn cooldown_timer(f: &mut Fixture, key: &Key) -> Option<RegistrationToken> {
+    f.niri().bind_cooldown_timers.get(key).copied()
+}
+
+// Does what the cooldown timer does when it runs out, without waiting for it.
+fn expire_cooldown(f: &mut Fixture, key: &Key) {
+    let niri = f.niri();
+    let token = niri.bind_cooldown_timers.remove(key).unwrap();
+    niri.event_loop.remove(token);
+}
+
+#[test]
+fn key_bind_cooldown_limits_rate() {
+    let mut f = set_up();
+    let bind = bind(Trigger::Keysym(Keysym::o));
+
+    f.niri_state().handle_bind(bind.clone());
+    assert!(f.niri().layout.is_overview_open());
+    let timer = cooldown_timer(&mut f, &bind.key);
+    assert!(timer.is_some());
+
+    // Key repeat within the cooldown doesn't fire the bind, nor does it restart the cooldown.
+    f.niri_state().handle_bind(bind.clone());
+    assert!(f.niri().layout.is_overview_open());
+    assert_eq!(cooldown_timer(&mut f, &bind.key), timer);
+
+    // The bind fires again once the cooldown runs out.
+    expire_cooldown(&mut f, &bind.key);
+    f.niri_state().handle_bind(bind);
+    assert!(!f.niri().layout.is_overview_open());
+}
+
+#[test]
+fn scroll_bind_cooldown_debounces() {
+    let mut f = set_up();
+    let bind = bind(Trigger::WheelScrollDown);
+
+    f.niri_state().handle_bind(bind.clone());
+    assert!(f.niri().layout.is_overview_open());
+    let timer = cooldown_timer(&mut f, &bind.key);
+    assert!(timer.is_some());
+
+    // Continued scrolling doesn't fire the bind and restarts the cooldown.
+    f.niri_state().handle_bind(bind.clone());
+    assert!(f.niri().layout.is_overview_open());
+    let restarted = cooldown_timer(&mut f, &bind.key);
+    assert!(restarted.is_some());
+    assert_ne!(restarted, timer);
+
+    // The bind fires again once scrolling pauses for the cooldown.
+    expire_cooldown(&mut f, &bind.key);
+    f.niri_state().handle_bind(bind);
+    assert!(!f.niri().layout.is_overview_open());
+}
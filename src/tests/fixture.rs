@@ -96,11 +96,21 @@ impl Fixture {
     }
 
     pub fn add_client(&mut self) -> ClientId {
+        self.insert_client(None)
+    }
+
+    /// Adds a client that behaves as if niri had spawned it with this activation token.
+    pub fn add_client_with_activation_token(&mut self, token: &str) -> ClientId {
+        self.insert_client(Some(token.to_owned()))
+    }
+
+    fn insert_client(&mut self, activation_token: Option<String>) -> ClientId {
         let (sock1, sock2) = UnixStream::pair().unwrap();
         self.niri().insert_client(NewClient {
             client: sock1,
             restricted: false,
             credentials_unknown: false,
+            activation_token,
         });
 
         let client = Client::new(sock2);
@@ -1,8 +1,9 @@
 use client::ClientId;
 use insta::assert_snapshot;
-use niri_config::Config;
-use niri_ipc::SizeChange;
-use smithay::utils::Point;
+use niri_config::{Action, Config};
+use niri_ipc::{PositionChange, SizeChange};
+use smithay::output::Mode;
+use smithay::utils::{Point, Size};
 use wayland_client::protocol::wl_surface::WlSurface;
 
 use super::*;
@@ -970,6 +971,112 @@ fn unmap_from_floating() {
     f.double_roundtrip(id);
 }
 
+#[test]
+fn is_floating_rule_updates_blur() {
+    let config = r##"
+window-rule {
+    match is-floating=true
+
+    blur {
+        on
+    }
+}
+"##;
+    let config = Config::parse_mem(config).unwrap();
+    let (mut f, id, _surface) = set_up_with_config(config);
+
+    let blur_on = |f: &mut Fixture| {
+        let (_, _, ws) = f.niri().layout.workspaces().next().unwrap();
+        let tile = ws.tiles().next().unwrap();
+        tile.blur_config().on
+    };
+    assert!(!blur_on(&mut f));
+
+    f.niri().layout.toggle_window_floating(None);
+    f.double_roundtrip(id);
+    assert!(blur_on(&mut f));
+
+    f.niri().layout.toggle_window_floating(None);
+    f.double_roundtrip(id);
+    assert!(!blur_on(&mut f));
+}
+
+#[test]
+fn floating_resize_mode_resizes_focused_window() {
+    let (mut f, id, surface) = set_up();
+
+    f.niri().layout.toggle_window_floating(None);
+    f.double_roundtrip(id);
+    let _ = f.client(id).window(&surface).recent_configures();
+
+    f.niri_state()
+        .do_action(Action::ToggleFloatingResizeMode, false);
+    f.niri_state().do_action(
+        Action::FloatingResizeModeAdjust {
+            width: 50,
+            height: 0,
+        },
+        false,
+    );
+    f.double_roundtrip(id);
+
+    assert_snapshot!(
+        f.client(id).window(&surface).format_recent_configures(),
+        @"size: 150 × 100, bounds: 1920 × 1080, states: [Activated]"
+    );
+}
+
+#[test]
+fn floating_resize_mode_ends_on_focus_change() {
+    let (mut f, id, surface) = set_up();
+
+    f.niri().layout.toggle_window_floating(None);
+    f.double_roundtrip(id);
+
+    f.niri_state()
+        .do_action(Action::ToggleFloatingResizeMode, false);
+    assert!(f.niri().floating_resize_mode_window().is_some());
+
+    // Focus the other output, which has no windows.
+    f.niri_focus_output(2);
+    f.double_roundtrip(id);
+    assert!(f.niri().floating_resize_mode.is_none());
+
+    // Focusing the window back doesn't resume the mode.
+    f.niri_focus_output(1);
+    f.double_roundtrip(id);
+    let _ = f.client(id).window(&surface).recent_configures();
+
+    f.niri_state().do_action(
+        Action::FloatingResizeModeAdjust {
+            width: 50,
+            height: 0,
+        },
+        false,
+    );
+    f.double_roundtrip(id);
+    assert_snapshot!(f.client(id).window(&surface).format_recent_configures(), @"");
+}
+
+#[test]
+fn floating_resize_mode_ends_on_unmap() {
+    let (mut f, id, surface) = set_up();
+
+    f.niri().layout.toggle_window_floating(None);
+    f.double_roundtrip(id);
+
+    f.niri_state()
+        .do_action(Action::ToggleFloatingResizeMode, false);
+    assert!(f.niri().floating_resize_mode.is_some());
+
+    let window = f.client(id).window(&surface);
+    window.attach_null();
+    window.commit();
+    f.double_roundtrip(id);
+
+    assert!(f.niri().floating_resize_mode.is_none());
+}
+
 #[test]
 fn unfullscreen_to_floating_doesnt_send_extra_configure() {
     let (mut f, id, surface) = set_up();
@@ -1364,3 +1471,39 @@ fn repeated_size_request() {
         @""
     );
 }
+
+#[test]
+fn keep_floating_on_screen_after_output_resize() {
+    let mut config = Config::default();
+    config.layout.keep_floating_on_screen = true;
+    let (mut f, id, _surface) = set_up_with_config(config);
+
+    f.niri().layout.toggle_window_floating(None);
+    f.double_roundtrip(id);
+
+    // Move the window into the bottom right corner, partially off-screen.
+    f.niri().layout.move_floating_window(
+        None,
+        PositionChange::SetFixed(1900.),
+        PositionChange::SetFixed(1000.),
+        false,
+    );
+
+    // Shrink the output.
+    let output = f.niri_output(1);
+    let mode = Mode {
+        size: Size::from((1280, 720)),
+        refresh: 60_000,
+    };
+    output.change_current_state(Some(mode), None, None, None);
+    f.niri().output_resized(&output);
+    f.double_roundtrip(id);
+
+    let mut pos = None;
+    f.niri().layout.with_windows(|_, _, _, layout| {
+        pos = layout.tile_pos_in_workspace_view;
+    });
+
+    // The 100 × 100 window is back fully on-screen.
+    assert_eq!(pos, Some((1180., 620.)));
+}
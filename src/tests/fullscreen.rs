@@ -1,5 +1,6 @@
 use client::ClientId;
 use insta::assert_snapshot;
+use niri_config::Config;
 use smithay::utils::Point;
 use wayland_client::protocol::wl_surface::WlSurface;
 
@@ -8,7 +9,11 @@ use crate::layout::LayoutElement as _;
 
 // Sets up a fixture with two outputs and 100×100 window.
 fn set_up() -> (Fixture, ClientId, WlSurface) {
-    let mut f = Fixture::new();
+    set_up_with_config(Config::default())
+}
+
+fn set_up_with_config(config: Config) -> (Fixture, ClientId, WlSurface) {
+    let mut f = Fixture::with_config(config);
     f.add_output(1, (1920, 1080));
     f.add_output(2, (1280, 720));
 
@@ -295,3 +300,43 @@ fn interactive_move_unmaximize_to_scrolling_restores_size() {
         @"size: 936 × 1048, bounds: 1920 × 1080, states: [Activated]"
     );
 }
+
+#[test]
+fn is_fullscreen_window_rule_follows_fullscreen_state() {
+    let config = r##"
+window-rule {
+    match is-fullscreen=true
+    opacity 0.5
+}
+"##;
+    let config = Config::parse_mem(config).unwrap();
+    let (mut f, id, surface) = set_up_with_config(config);
+
+    let mapped = f.niri().layout.windows().next().unwrap().1;
+    assert_eq!(mapped.rules().opacity, None);
+    let window = mapped.window.clone();
+
+    // The rule should apply as soon as fullscreen is requested.
+    f.niri().layout.set_fullscreen(&window, true);
+    f.double_roundtrip(id);
+
+    let mapped = f.niri().layout.windows().next().unwrap().1;
+    assert_eq!(mapped.rules().opacity, Some(0.5));
+
+    f.client(id).window(&surface).ack_last_and_commit();
+    f.roundtrip(id);
+
+    // And stop applying once the window is unfullscreened.
+    f.niri().layout.set_fullscreen(&window, false);
+    f.double_roundtrip(id);
+
+    let mapped = f.niri().layout.windows().next().unwrap().1;
+    assert_eq!(mapped.rules().opacity, None);
+
+    // Windowed fullscreen doesn't count as fullscreen.
+    f.niri().layout.toggle_windowed_fullscreen(&window);
+    f.double_roundtrip(id);
+
+    let mapped = f.niri().layout.windows().next().unwrap().1;
+    assert_eq!(mapped.rules().opacity, None);
+}
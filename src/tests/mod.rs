@@ -5,8 +5,14 @@ mod fixture;
 mod server;
 
 mod animations;
+mod binds;
 mod floating;
 mod fullscreen;
 mod layer_shell;
+mod monitor_power;
+mod move_window;
+mod output_focus;
+mod output_mirror;
+mod raw_protocol;
 mod transactions;
 mod window_opening;
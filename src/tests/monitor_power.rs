@@ -0,0 +1,45 @@
+use niri_config::Action;
+
+use super::*;
+
+fn is_powered_off(f: &mut Fixture, n: u8) -> bool {
+    let output = f.niri_output(n);
+    f.niri().output_state[&output].powered_off
+}
+
+#[test]
+fn power_off_single_monitor() {
+    let mut f = Fixture::new();
+    f.add_output(1, (1920, 1080));
+    f.add_output(2, (1920, 1080));
+
+    let state = f.niri_state();
+    state.do_action(Action::PowerOffMonitor(String::from("headless-1")), false);
+    assert!(is_powered_off(&mut f, 1));
+    assert!(!is_powered_off(&mut f, 2));
+
+    // Waking up the monitors on input keeps the output off.
+    let state = f.niri_state();
+    state.do_action(Action::PowerOffMonitors, false);
+    state.niri.activate_monitors(&mut state.backend);
+    assert!(is_powered_off(&mut f, 1));
+
+    let state = f.niri_state();
+    state.do_action(Action::PowerOnMonitor(String::from("headless-1")), false);
+    assert!(!is_powered_off(&mut f, 1));
+}
+
+#[test]
+fn session_resume_powers_on_single_monitor() {
+    let mut f = Fixture::new();
+    f.add_output(1, (1920, 1080));
+
+    let state = f.niri_state();
+    state.do_action(Action::PowerOffMonitor(String::from("headless-1")), false);
+    assert!(is_powered_off(&mut f, 1));
+
+    // This is what the TTY backend does when switching back to niri's VT.
+    f.niri().resume_monitors();
+    assert!(f.niri().monitors_active);
+    assert!(!is_powered_off(&mut f, 1));
+}
@@ -0,0 +1,206 @@
+use niri_config::{Action, WorkspaceReference};
+use smithay::output::Output;
+
+use super::*;
+use crate::tests::window_opening::open_window;
+
+// Sets up a fixture with two outputs and a window on the first one.
+fn set_up() -> Fixture {
+    let mut f = Fixture::new();
+    f.add_output(1, (1920, 1080));
+    f.add_output(2, (1280, 720));
+
+    let id = f.add_client();
+    open_window(&mut f, id);
+
+    f
+}
+
+fn window_output(f: &mut Fixture) -> Option<Output> {
+    let (mon, _) = f.niri().layout.windows().next().unwrap();
+    mon.map(|mon| mon.output().clone())
+}
+
+#[test]
+fn move_window_to_monitor_workspace_creates_workspace() {
+    let mut f = set_up();
+    let output1 = f.niri_output(1);
+    let output2 = f.niri_output(2);
+    assert_eq!(window_output(&mut f), Some(output1));
+
+    f.niri_state().do_action(
+        Action::MoveWindowToMonitorWorkspace(
+            String::from("headless-2"),
+            WorkspaceReference::Name(String::from("chat")),
+            true,
+            true,
+        ),
+        false,
+    );
+
+    let niri = f.niri();
+    let (_, ws) = niri.layout.find_workspace_by_name("chat").unwrap();
+    assert_eq!(ws.current_output(), Some(&output2));
+    assert!(ws.has_windows());
+    assert_eq!(niri.layout.active_output(), Some(&output2));
+    assert_eq!(window_output(&mut f), Some(output2));
+}
+
+#[test]
+fn move_window_to_monitor_workspace_without_focus() {
+    let mut f = set_up();
+    let output1 = f.niri_output(1);
+    let output2 = f.niri_output(2);
+
+    f.niri_state().do_action(
+        Action::MoveWindowToMonitorWorkspace(
+            String::from("headless-2"),
+            WorkspaceReference::Index(1),
+            false,
+            false,
+        ),
+        false,
+    );
+
+    assert_eq!(f.niri().layout.active_output(), Some(&output1));
+    assert_eq!(window_output(&mut f), Some(output2));
+}
+
+#[test]
+fn move_window_to_monitor_workspace_missing_output() {
+    let mut f = set_up();
+    let output1 = f.niri_output(1);
+
+    f.niri_state().do_action(
+        Action::MoveWindowToMonitorWorkspace(
+            String::from("headless-3"),
+            WorkspaceReference::Name(String::from("chat")),
+            true,
+            true,
+        ),
+        false,
+    );
+
+    let niri = f.niri();
+    assert!(niri.layout.find_workspace_by_name("chat").is_none());
+    assert_eq!(niri.layout.active_output(), Some(&output1));
+    assert_eq!(window_output(&mut f), Some(output1));
+}
+
+#[test]
+fn move_window_to_monitor_workspace_on_other_output_is_noop() {
+    let mut f = set_up();
+    let output1 = f.niri_output(1);
+
+    // The named workspace exists, but on the first output.
+    f.niri()
+        .layout
+        .put_named_workspace_on_output(&output1, "chat", false);
+
+    f.niri_state().do_action(
+        Action::MoveWindowToMonitorWorkspace(
+            String::from("headless-2"),
+            WorkspaceReference::Name(String::from("chat")),
+            true,
+            true,
+        ),
+        false,
+    );
+
+    let niri = f.niri();
+    let (_, ws) = niri.layout.find_workspace_by_name("chat").unwrap();
+    assert_eq!(ws.current_output(), Some(&output1));
+    assert!(!ws.has_windows());
+    assert_eq!(window_output(&mut f), Some(output1));
+}
+
+fn window_last_output(f: &mut Fixture) -> Option<String> {
+    let (_, mapped) = f.niri().layout.windows().next().unwrap();
+    mapped.last_output().map(String::from)
+}
+
+// Moves the window to the second output, disconnects it, and moves the window to a workspace that
+// stays on the first output when the second one comes back.
+fn set_up_disconnected_window() -> Fixture {
+    let mut f = set_up();
+    let output1 = f.niri_output(1);
+    let output2 = f.niri_output(2);
+
+    f.niri()
+        .layout
+        .put_named_workspace_on_output(&output1, "main", false);
+    f.niri_state().do_action(
+        Action::MoveWindowToMonitor(String::from("headless-2")),
+        false,
+    );
+    assert_eq!(window_output(&mut f), Some(output2.clone()));
+
+    f.niri().remove_output(&output2);
+    assert_eq!(window_output(&mut f), Some(output1.clone()));
+    assert_eq!(window_last_output(&mut f).as_deref(), Some("headless-2"));
+
+    f.niri_state().do_action(
+        Action::MoveWindowToWorkspace(WorkspaceReference::Name(String::from("main")), true),
+        false,
+    );
+
+    f.add_output(2, (1280, 720));
+    assert_eq!(window_output(&mut f), Some(output1));
+    f
+}
+
+#[test]
+fn move_window_to_last_output() {
+    let mut f = set_up_disconnected_window();
+    let output2 = f.niri_output(2);
+
+    f.niri_state()
+        .do_action(Action::MoveWindowToLastOutput, false);
+
+    assert_eq!(window_output(&mut f), Some(output2.clone()));
+    assert_eq!(f.niri().layout.active_output(), Some(&output2));
+    assert_eq!(window_last_output(&mut f), None);
+}
+
+#[test]
+fn move_window_to_last_output_by_id_while_that_output_is_active() {
+    let mut f = set_up_disconnected_window();
+    let output2 = f.niri_output(2);
+    f.niri_focus_output(2);
+
+    let id = f.niri().layout.windows().next().unwrap().1.id().get();
+    f.niri_state()
+        .do_action(Action::MoveWindowToLastOutputById(id), false);
+
+    assert_eq!(window_output(&mut f), Some(output2));
+    assert_eq!(window_last_output(&mut f), None);
+}
+
+#[test]
+fn reconnecting_output_clears_last_output() {
+    let mut f = set_up();
+    let output1 = f.niri_output(1);
+    let output2 = f.niri_output(2);
+
+    f.niri_state().do_action(
+        Action::MoveWindowToMonitor(String::from("headless-2")),
+        false,
+    );
+    f.niri().remove_output(&output2);
+    assert_eq!(window_last_output(&mut f).as_deref(), Some("headless-2"));
+
+    // The workspace comes back together with the output.
+    f.add_output(2, (1280, 720));
+    let output2 = f.niri_output(2);
+    assert_eq!(window_output(&mut f), Some(output2));
+    assert_eq!(window_last_output(&mut f), None);
+
+    // So the action no longer moves the window anywhere.
+    f.niri_state().do_action(
+        Action::MoveWindowToMonitor(String::from("headless-1")),
+        false,
+    );
+    f.niri_state()
+        .do_action(Action::MoveWindowToLastOutput, false);
+    assert_eq!(window_output(&mut f), Some(output1));
+}
@@ -0,0 +1,73 @@
+use super::*;
+
+fn three_outputs_in_a_row() -> Fixture {
+    let mut f = Fixture::new();
+    f.add_output(1, (1920, 1080));
+    f.add_output(2, (1920, 1080));
+    f.add_output(3, (1920, 1080));
+    f
+}
+
+#[test]
+fn focus_right_wrapping() {
+    let mut f = three_outputs_in_a_row();
+    let output1 = f.niri_output(1);
+    let output2 = f.niri_output(2);
+    let output3 = f.niri_output(3);
+    let niri = f.niri();
+
+    // Doesn't wrap when there's a monitor in the requested direction.
+    assert_eq!(
+        niri.output_right_of_wrapping(&output1),
+        Some(output2.clone())
+    );
+    assert_eq!(
+        niri.output_right_of_wrapping(&output2),
+        Some(output3.clone())
+    );
+    // Wraps from the rightmost to the leftmost monitor.
+    assert_eq!(niri.output_right_of(&output3), None);
+    assert_eq!(niri.output_right_of_wrapping(&output3), Some(output1));
+}
+
+#[test]
+fn focus_left_wrapping() {
+    let mut f = three_outputs_in_a_row();
+    let output1 = f.niri_output(1);
+    let output2 = f.niri_output(2);
+    let output3 = f.niri_output(3);
+    let niri = f.niri();
+
+    assert_eq!(
+        niri.output_left_of_wrapping(&output3),
+        Some(output2.clone())
+    );
+    assert_eq!(
+        niri.output_left_of_wrapping(&output2),
+        Some(output1.clone())
+    );
+    assert_eq!(niri.output_left_of(&output1), None);
+    assert_eq!(niri.output_left_of_wrapping(&output1), Some(output3));
+}
+
+#[test]
+fn focus_up_down_wrapping_in_a_row() {
+    let mut f = three_outputs_in_a_row();
+    let output2 = f.niri_output(2);
+    let niri = f.niri();
+
+    // There are no other monitors in the same column, so there's nothing to wrap to.
+    assert_eq!(niri.output_up_of_wrapping(&output2), None);
+    assert_eq!(niri.output_down_of_wrapping(&output2), None);
+}
+
+#[test]
+fn focus_wrapping_single_output() {
+    let mut f = Fixture::new();
+    f.add_output(1, (1920, 1080));
+    let output1 = f.niri_output(1);
+    let niri = f.niri();
+
+    assert_eq!(niri.output_left_of_wrapping(&output1), None);
+    assert_eq!(niri.output_right_of_wrapping(&output1), None);
+}
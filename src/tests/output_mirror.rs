@@ -0,0 +1,64 @@
+use super::*;
+
+fn three_outputs() -> Fixture {
+    let mut f = Fixture::new();
+    f.add_output(1, (1920, 1080));
+    f.add_output(2, (1280, 720));
+    f.add_output(3, (1280, 720));
+    f
+}
+
+#[test]
+fn toggle_mirror() {
+    let mut f = three_outputs();
+    let output1 = f.niri_output(1);
+    let output2 = f.niri_output(2);
+    let niri = f.niri();
+
+    niri.toggle_output_mirror(&output1, &output2);
+    assert_eq!(niri.mirrored_outputs.get(&output2), Some(&output1));
+    assert_eq!(niri.mirrored_outputs.get(&output1), None);
+
+    niri.toggle_output_mirror(&output1, &output2);
+    assert!(niri.mirrored_outputs.is_empty());
+}
+
+#[test]
+fn mirror_chains_are_refused() {
+    let mut f = three_outputs();
+    let output1 = f.niri_output(1);
+    let output2 = f.niri_output(2);
+    let output3 = f.niri_output(3);
+    let niri = f.niri();
+
+    niri.mirror_output(&output1, &output2);
+
+    // Output 2 shows output 1, so it can't be a source.
+    niri.mirror_output(&output2, &output3);
+    // Output 1 is a source, so it can't mirror anything.
+    niri.mirror_output(&output3, &output1);
+    // An output can't mirror itself.
+    niri.mirror_output(&output3, &output3);
+
+    assert_eq!(niri.mirrored_outputs.len(), 1);
+    assert_eq!(niri.mirrored_outputs.get(&output2), Some(&output1));
+}
+
+#[test]
+fn removing_output_stops_mirroring() {
+    let mut f = three_outputs();
+    let output1 = f.niri_output(1);
+    let output2 = f.niri_output(2);
+    let output3 = f.niri_output(3);
+    let niri = f.niri();
+
+    niri.mirror_output(&output1, &output2);
+    niri.mirror_output(&output1, &output3);
+    assert_eq!(niri.mirrored_outputs.len(), 2);
+
+    niri.remove_output(&output2);
+    assert_eq!(niri.mirrored_outputs.get(&output3), Some(&output1));
+
+    niri.remove_output(&output1);
+    assert!(niri.mirrored_outputs.is_empty());
+}
@@ -0,0 +1,62 @@
+use smithay::reexports::calloop::LoopHandle;
+use smithay::reexports::wayland_server::{Client, DataInit, DisplayHandle, GlobalDispatch, New};
+
+use super::*;
+use crate::niri::State;
+use crate::protocols::raw::mutter_x11_interop::v1::server::mutter_x11_interop::MutterX11Interop;
+use crate::protocols::registry::RawProtocol;
+
+/// Example protocol creating a second mutter_x11_interop global.
+///
+/// Requests on the bound resources go through the existing `Dispatch` impl.
+#[derive(Default)]
+struct ExampleProtocol {
+    initialized: bool,
+}
+
+struct ExampleGlobalData;
+
+impl RawProtocol for ExampleProtocol {
+    fn name(&self) -> &'static str {
+        "example"
+    }
+
+    fn init(&mut self, display: &DisplayHandle, _event_loop: &LoopHandle<'static, State>) {
+        display.create_global::<State, MutterX11Interop, _>(1, ExampleGlobalData);
+        self.initialized = true;
+    }
+}
+
+impl GlobalDispatch<MutterX11Interop, ExampleGlobalData> for State {
+    fn bind(
+        _state: &mut Self,
+        _handle: &DisplayHandle,
+        _client: &Client,
+        resource: New<MutterX11Interop>,
+        _global_data: &ExampleGlobalData,
+        data_init: &mut DataInit<'_, Self>,
+    ) {
+        data_init.init(resource, ());
+    }
+}
+
+#[test]
+fn registered_protocol_global_is_advertised() {
+    let mut f = Fixture::new();
+    f.niri_state()
+        .register_raw_protocol(ExampleProtocol::default());
+
+    let id = f.add_client();
+    f.roundtrip(id);
+
+    let count = f
+        .client(id)
+        .globals
+        .iter()
+        .filter(|global| global.interface == "mutter_x11_interop")
+        .count();
+    assert_eq!(count, 2);
+
+    let protocol = f.niri().raw_protocols.get::<ExampleProtocol>().unwrap();
+    assert!(protocol.initialized);
+}
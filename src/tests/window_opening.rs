@@ -1,8 +1,10 @@
 use std::fmt::{self, Write as _};
 
+use client::ClientId;
 use insta::assert_snapshot;
-use niri_config::Config;
+use niri_config::{Action, Config};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use wayland_client::protocol::wl_surface::WlSurface;
 
 use super::*;
 use crate::layout::LayoutElement as _;
@@ -84,6 +86,88 @@ fn dont_ack_initial_configure() {
     f.double_roundtrip(id);
 }
 
+fn open_with_app_id(f: &mut Fixture, id: ClientId, app_id: &str) -> WlSurface {
+    let window = f.client(id).create_window();
+    let surface = window.surface.clone();
+    window.set_app_id(app_id);
+    window.commit();
+    f.roundtrip(id);
+    surface
+}
+
+fn last_configured_width(f: &mut Fixture, id: ClientId, surface: &WlSurface) -> i32 {
+    let window = f.client(id).window(surface);
+    window.recent_configures().last().unwrap().size.0
+}
+
+#[test]
+fn remember_column_width_per_app() {
+    let mut config = Config::default();
+    config.layout.remember_column_width_per_app = true;
+    let mut f = Fixture::with_config(config);
+    f.add_output(1, (1920, 1080));
+
+    let id = f.add_client();
+    let surface = open_with_app_id(&mut f, id, "foo");
+    let default_width = last_configured_width(&mut f, id, &surface);
+
+    let window = f.client(id).window(&surface);
+    window.attach_new_buffer();
+    window.ack_last_and_commit();
+    f.double_roundtrip(id);
+
+    f.niri_state()
+        .do_action(Action::SwitchPresetColumnWidth, false);
+    f.double_roundtrip(id);
+    let switched_width = last_configured_width(&mut f, id, &surface);
+    assert_ne!(switched_width, default_width);
+
+    // Another window of the same app opens with the width that it switched to.
+    let surface = open_with_app_id(&mut f, id, "foo");
+    assert_eq!(last_configured_width(&mut f, id, &surface), switched_width);
+
+    // Other apps still use the default width.
+    let surface = open_with_app_id(&mut f, id, "bar");
+    assert_eq!(last_configured_width(&mut f, id, &surface), default_width);
+}
+
+fn opacity_with_matcher(matcher: &str) -> Option<f32> {
+    let config = format!(
+        r##"
+window-rule {{
+    match {matcher}
+    opacity 0.5
+}}
+"##
+    );
+    let config = Config::parse_mem(&config).unwrap();
+
+    let mut f = Fixture::with_config(config);
+    f.add_output(1, (1920, 1080));
+
+    let id = f.add_client();
+    open_window(&mut f, id);
+
+    let (_, mapped) = f.niri().layout.windows().next().unwrap();
+    mapped.rules().opacity
+}
+
+#[test]
+fn match_pid_and_process() {
+    // Test clients run in this same process.
+    let pid = std::process::id();
+    let exe = std::env::current_exe().unwrap();
+    let name = exe.file_name().unwrap().to_str().unwrap();
+
+    assert_eq!(opacity_with_matcher(&format!("pid={pid}")), Some(0.5));
+    assert_eq!(opacity_with_matcher(&format!("pid={}", pid + 1)), None);
+    assert_eq!(
+        opacity_with_matcher(&format!("process=\"^{name}$\"")),
+        Some(0.5)
+    );
+    assert_eq!(opacity_with_matcher("process=\"^not-this-process$\""), None);
+}
+
 #[derive(Clone, Copy)]
 enum WantFullscreen {
     No,
@@ -434,6 +518,103 @@ post-map configures:
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn open_on_workspace_focused() {
+    let (active, target) = check_open_on_workspace_focus("true");
+    assert_eq!(active, "ws-2");
+    assert_eq!(target, "ws-2");
+}
+
+#[test]
+fn open_on_workspace_not_focused() {
+    let (active, target) = check_open_on_workspace_focus("false");
+    assert_eq!(active, "ws-1");
+    assert_eq!(target, "ws-2");
+}
+
+#[test]
+fn spawn_on_workspace_opens_window_on_target() {
+    let config = r##"
+workspace "ws-1"
+workspace "ws-2"
+"##;
+    let config = Config::parse_mem(config).unwrap();
+
+    let mut f = Fixture::with_config(config);
+    f.add_output(1, (1920, 1080));
+
+    let niri = f.niri();
+    let (token, _) = niri.activation_state.create_external_token(None);
+    let token = token.clone();
+    niri.add_pending_spawn_on_workspace(token.clone(), String::from("ws-2"));
+
+    // A window from an unrelated client does not take the pending target.
+    let other = f.add_client();
+    open_window(&mut f, other);
+    assert_eq!(f.niri().pending_spawns_on_workspace.len(), 1);
+
+    let id = f.add_client_with_activation_token(token.as_str());
+    open_window(&mut f, id);
+    assert!(f.niri().pending_spawns_on_workspace.is_empty());
+
+    let counts: Vec<_> = f
+        .niri()
+        .layout
+        .workspaces()
+        .filter_map(|(_, _, ws)| Some((ws.name()?.clone(), ws.windows().count())))
+        .collect();
+    assert_eq!(
+        counts,
+        [(String::from("ws-1"), 1), (String::from("ws-2"), 1)]
+    );
+}
+
+/// Creates a window for the client and maps it.
+pub(super) fn open_window(f: &mut Fixture, id: ClientId) {
+    let window = f.client(id).create_window();
+    let surface = window.surface.clone();
+    window.commit();
+    f.roundtrip(id);
+
+    let window = f.client(id).window(&surface);
+    window.attach_new_buffer();
+    window.ack_last_and_commit();
+    f.double_roundtrip(id);
+}
+
+/// Opens a window with `open-on-workspace "ws-2"` and the given `open-focused` value.
+///
+/// Returns the names of the active workspace and of the workspace the window ended up on.
+fn check_open_on_workspace_focus(open_focused: &str) -> (String, String) {
+    let config = format!(
+        r##"
+workspace "ws-1"
+workspace "ws-2"
+
+window-rule {{
+    open-on-workspace "ws-2"
+    open-focused {open_focused}
+}}
+"##
+    );
+    let config = Config::parse_mem(&config).unwrap();
+
+    let mut f = Fixture::with_config(config);
+    f.add_output(1, (1920, 1080));
+
+    let id = f.add_client();
+    open_window(&mut f, id);
+
+    let layout = &f.niri().layout;
+    let active = layout.active_workspace().unwrap().name().cloned().unwrap();
+    let target = layout
+        .workspaces()
+        .find(|(_, _, ws)| ws.windows().next().is_some())
+        .and_then(|(_, _, ws)| ws.name().cloned())
+        .unwrap();
+    (active, target)
+}
+
 #[test]
 fn target_size() {
     if std::env::var_os("RUN_SLOW_TESTS").is_none() {
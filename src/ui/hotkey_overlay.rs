@@ -6,6 +6,7 @@ use std::iter::zip;
 use std::rc::Rc;
 
 use niri_config::{Action, Bind, Config, Key, ModKey, Modifiers, Trigger};
+use niri_ipc::HotkeyOverlayEntry;
 use pangocairo::cairo::{self, ImageSurface};
 use pangocairo::pango::{AttrColor, AttrInt, AttrList, AttrString, FontDescription, Weight};
 use smithay::backend::renderer::element::Kind;
@@ -149,6 +150,28 @@ impl HotkeyOverlay {
 
         buf
     }
+
+    pub fn ipc_entries(&self) -> Vec<HotkeyOverlayEntry> {
+        let config = self.config.borrow();
+
+        collect_actions(&config)
+            .into_iter()
+            .filter_map(|action| {
+                let (key, title) = format_bind(&config.binds.0, action)?;
+
+                let title = match pango::parse_markup(&title, '\0') {
+                    Ok((_attrs, text, _accel)) => text.into(),
+                    Err(_) => title,
+                };
+
+                Some(HotkeyOverlayEntry {
+                    key: key.map(|key| key_name(false, self.mod_key, &key)),
+                    title,
+                    action: action_config_name(action).to_owned(),
+                })
+            })
+            .collect()
+    }
 }
 
 fn format_bind(binds: &[Bind], action: &Action) -> Option<(Option<Key>, String)> {
@@ -279,7 +302,10 @@ fn collect_actions(config: &Config) -> Vec<&Action> {
 
     // Add the spawn actions.
     for bind in binds.iter().filter(|bind| {
-        matches!(bind.action, Action::Spawn(_) | Action::SpawnSh(_))
+        matches!(
+            bind.action,
+            Action::Spawn(_) | Action::SpawnSh(_) | Action::SpawnOnWorkspace(..)
+        )
             // Only show binds with Mod or Super to filter out stuff like volume up/down.
             && (bind.key.modifiers.contains(Modifiers::COMPOSITOR)
                 || bind.key.modifiers.contains(Modifiers::SUPER))
@@ -488,10 +514,282 @@ fn action_name(action: &Action) -> String {
             // Fairly crude but should get the job done in most cases.
             command.split_ascii_whitespace().next().unwrap_or("")
         ),
+        Action::SpawnOnWorkspace(args, workspace) => format!(
+            "Spawn <span face='monospace' bgcolor='#000000'>{}</span> on {workspace}",
+            args.first().unwrap_or(&String::new())
+        ),
         _ => String::from("FIXME: Unknown"),
     }
 }
 
+/// Returns the name of the action as written in the config, like `focus-column-left`.
+///
+/// Actions that can only be triggered by id or from niri itself use the name of the matching
+/// bindable action where there is one.
+fn action_config_name(action: &Action) -> &'static str {
+    match action {
+        Action::Quit(..) => "quit",
+        Action::ChangeVt(..) => "change-vt",
+        Action::Suspend => "suspend",
+        Action::PowerOffMonitors => "power-off-monitors",
+        Action::PowerOnMonitors => "power-on-monitors",
+        Action::PowerOffMonitor(..) => "power-off-monitor",
+        Action::PowerOnMonitor(..) => "power-on-monitor",
+        Action::SetOutputScale(..) => "set-output-scale",
+        Action::CycleOutputScale(..) => "cycle-output-scale",
+        Action::RotateOutput(..) => "rotate-output",
+        Action::CycleOutputTransform => "cycle-output-transform",
+        Action::ChangeBacklight(..) => "change-backlight",
+        Action::SetBacklight(..) => "set-backlight",
+        Action::ToggleDebugTint => "toggle-debug-tint",
+        Action::DebugToggleOpaqueRegions => "debug-toggle-opaque-regions",
+        Action::DebugToggleDamage => "debug-toggle-damage",
+        Action::DebugToggleRenderHeatmap => "debug-toggle-render-heatmap",
+        Action::Spawn(..) => "spawn",
+        Action::SpawnSh(..) => "spawn-sh",
+        Action::SpawnOnWorkspace(..) => "spawn-on-workspace",
+        Action::DoScreenTransition(..) => "do-screen-transition",
+        Action::ConfirmScreenshot { .. } => "confirm-screenshot",
+        Action::CancelScreenshot => "cancel-screenshot",
+        Action::ScreenshotTogglePointer => "screenshot-toggle-pointer",
+        Action::Screenshot(..) => "screenshot",
+        Action::ScreenshotScreen(..) => "screenshot-screen",
+        Action::ScreenshotEachOutput(..) => "screenshot-each-output",
+        Action::ScreenshotWindow(..) | Action::ScreenshotWindowById { .. } => "screenshot-window",
+        Action::ScreenshotToCommand(..) => "screenshot-to-command",
+        Action::ToggleKeyboardShortcutsInhibit => "toggle-keyboard-shortcuts-inhibit",
+        Action::CloseWindow | Action::CloseWindowById(..) => "close-window",
+        Action::ToggleGroup => "toggle-group",
+        Action::MoveWindowIntoOrOutOfGroup(..) => "move-window-into-or-out-of-group",
+        Action::FocusNextWindow => "focus-next-window",
+        Action::FocusPreviousWindow => "focus-previous-window",
+        Action::SetGroupDisplayMode(..) => "set-group-display-mode",
+        Action::ToggleGroupDisplayMode => "toggle-group-display-mode",
+        Action::FullscreenWindow | Action::FullscreenWindowById(..) => "fullscreen-window",
+        Action::ToggleWindowedFullscreen | Action::ToggleWindowedFullscreenById(..) => {
+            "toggle-windowed-fullscreen"
+        }
+        Action::FocusWindow(..) => "focus-window",
+        Action::FocusWindowInColumn(..) => "focus-window-in-column",
+        Action::FocusWindowPrevious => "focus-window-previous",
+        Action::FocusColumnLeft => "focus-column-left",
+        Action::FocusColumnLeftUnderMouse => "focus-column-left-under-mouse",
+        Action::FocusColumnRight => "focus-column-right",
+        Action::FocusColumnRightUnderMouse => "focus-column-right-under-mouse",
+        Action::FocusColumnUnderMouse => "focus-column-under-mouse",
+        Action::FocusColumnFirst => "focus-column-first",
+        Action::FocusColumnLast => "focus-column-last",
+        Action::FocusColumnRightOrFirst => "focus-column-right-or-first",
+        Action::FocusColumnLeftOrLast => "focus-column-left-or-last",
+        Action::FocusColumn(..) => "focus-column",
+        Action::FocusWindowOrMonitorUp => "focus-window-or-monitor-up",
+        Action::FocusWindowOrMonitorDown => "focus-window-or-monitor-down",
+        Action::FocusColumnOrMonitorLeft => "focus-column-or-monitor-left",
+        Action::FocusColumnOrMonitorRight => "focus-column-or-monitor-right",
+        Action::FocusWindowDown => "focus-window-down",
+        Action::FocusWindowUp => "focus-window-up",
+        Action::FocusWindowDownOrColumnLeft => "focus-window-down-or-column-left",
+        Action::FocusWindowDownOrColumnRight => "focus-window-down-or-column-right",
+        Action::FocusWindowUpOrColumnLeft => "focus-window-up-or-column-left",
+        Action::FocusWindowUpOrColumnRight => "focus-window-up-or-column-right",
+        Action::FocusWindowOrWorkspaceDown => "focus-window-or-workspace-down",
+        Action::FocusWindowOrWorkspaceUp => "focus-window-or-workspace-up",
+        Action::FocusWindowTop => "focus-window-top",
+        Action::FocusWindowBottom => "focus-window-bottom",
+        Action::FocusWindowDownOrTop => "focus-window-down-or-top",
+        Action::FocusWindowUpOrBottom => "focus-window-up-or-bottom",
+        Action::FocusSpatial(..) => "focus-spatial",
+        Action::MoveColumnLeft => "move-column-left",
+        Action::MoveColumnRight => "move-column-right",
+        Action::MoveColumnToFirst => "move-column-to-first",
+        Action::MoveColumnToLast => "move-column-to-last",
+        Action::MoveColumnLeftOrToMonitorLeft => "move-column-left-or-to-monitor-left",
+        Action::MoveColumnRightOrToMonitorRight => "move-column-right-or-to-monitor-right",
+        Action::MoveColumnToIndex(..) => "move-column-to-index",
+        Action::MoveColumnByOffset(..) => "move-column-by-offset",
+        Action::MoveWindowDown => "move-window-down",
+        Action::MoveWindowUp => "move-window-up",
+        Action::MoveWindowDownOrToWorkspaceDown => "move-window-down-or-to-workspace-down",
+        Action::MoveWindowUpOrToWorkspaceUp => "move-window-up-or-to-workspace-up",
+        Action::ConsumeOrExpelWindowLeft | Action::ConsumeOrExpelWindowLeftById(..) => {
+            "consume-or-expel-window-left"
+        }
+        Action::ConsumeOrExpelWindowRight | Action::ConsumeOrExpelWindowRightById(..) => {
+            "consume-or-expel-window-right"
+        }
+        Action::ConsumeWindowIntoColumn => "consume-window-into-column",
+        Action::ExpelWindowFromColumn => "expel-window-from-column",
+        Action::ExpelWindowToColumnLeft => "expel-window-to-column-left",
+        Action::ExpelWindowToColumnRight => "expel-window-to-column-right",
+        Action::ConsumeWindowFromMonitorLeft => "consume-window-from-monitor-left",
+        Action::ConsumeWindowFromMonitorRight => "consume-window-from-monitor-right",
+        Action::SwapWindowLeft => "swap-window-left",
+        Action::SwapWindowRight => "swap-window-right",
+        Action::CenterColumn => "center-column",
+        Action::CenterWindow | Action::CenterWindowById(..) => "center-window",
+        Action::CenterWindowVertically | Action::CenterWindowVerticallyById(..) => {
+            "center-window-vertically"
+        }
+        Action::CenterVisibleColumns => "center-visible-columns",
+        Action::DistributeColumnsEvenly => "distribute-columns-evenly",
+        Action::FocusWorkspaceDown => "focus-workspace-down",
+        Action::FocusWorkspaceDownUnderMouse => "focus-workspace-down-under-mouse",
+        Action::FocusWorkspaceUp => "focus-workspace-up",
+        Action::FocusWorkspaceUpUnderMouse => "focus-workspace-up-under-mouse",
+        Action::FocusWorkspace(..) => "focus-workspace",
+        Action::FocusWorkspacePrevious => "focus-workspace-previous",
+        Action::MoveWindowToWorkspaceDown(..) => "move-window-to-workspace-down",
+        Action::MoveWindowToWorkspaceUp(..) => "move-window-to-workspace-up",
+        Action::MoveWindowToWorkspace(..) | Action::MoveWindowToWorkspaceById { .. } => {
+            "move-window-to-workspace"
+        }
+        Action::MoveWindowToWorkspacePrevious(..) => "move-window-to-workspace-previous",
+        Action::MoveColumnToWorkspaceDown(..) => "move-column-to-workspace-down",
+        Action::MoveColumnToWorkspaceUp(..) => "move-column-to-workspace-up",
+        Action::MoveColumnToWorkspace(..) => "move-column-to-workspace",
+        Action::MoveColumnToWorkspacePrevious(..) => "move-column-to-workspace-previous",
+        Action::MoveWorkspaceDown => "move-workspace-down",
+        Action::MoveWorkspaceUp => "move-workspace-up",
+        Action::MoveWorkspaceToIndex(..) | Action::MoveWorkspaceToIndexByRef { .. } => {
+            "move-workspace-to-index"
+        }
+        Action::MoveWorkspaceToMonitorByRef { .. } | Action::MoveWorkspaceToMonitor(..) => {
+            "move-workspace-to-monitor"
+        }
+        Action::SetWorkspaceName(..) | Action::SetWorkspaceNameByRef { .. } => "set-workspace-name",
+        Action::UnsetWorkspaceName | Action::UnsetWorkSpaceNameByRef(..) => "unset-workspace-name",
+        Action::FocusMonitorLeft => "focus-monitor-left",
+        Action::FocusMonitorRight => "focus-monitor-right",
+        Action::FocusMonitorDown => "focus-monitor-down",
+        Action::FocusMonitorUp => "focus-monitor-up",
+        Action::FocusMonitorPrevious => "focus-monitor-previous",
+        Action::FocusMonitorNext => "focus-monitor-next",
+        Action::FocusMonitorLeftWrap => "focus-monitor-left-wrap",
+        Action::FocusMonitorRightWrap => "focus-monitor-right-wrap",
+        Action::FocusMonitorDownWrap => "focus-monitor-down-wrap",
+        Action::FocusMonitorUpWrap => "focus-monitor-up-wrap",
+        Action::FocusMonitor(..) => "focus-monitor",
+        Action::FocusMonitorByWorkspace(..) => "focus-monitor-by-workspace",
+        Action::MoveWindowToMonitorLeft => "move-window-to-monitor-left",
+        Action::MoveWindowToMonitorRight => "move-window-to-monitor-right",
+        Action::MoveWindowToMonitorDown => "move-window-to-monitor-down",
+        Action::MoveWindowToMonitorUp => "move-window-to-monitor-up",
+        Action::MoveWindowToMonitorPrevious => "move-window-to-monitor-previous",
+        Action::MoveWindowToMonitorNext => "move-window-to-monitor-next",
+        Action::MoveWindowToMonitor(..) | Action::MoveWindowToMonitorById { .. } => {
+            "move-window-to-monitor"
+        }
+        Action::MoveWindowToLastOutput | Action::MoveWindowToLastOutputById(..) => {
+            "move-window-to-last-output"
+        }
+        Action::MoveWindowToMonitorWorkspace(..) => "move-window-to-monitor-workspace",
+        Action::MoveColumnToMonitorLeft => "move-column-to-monitor-left",
+        Action::MoveColumnToMonitorRight => "move-column-to-monitor-right",
+        Action::MoveColumnToMonitorDown => "move-column-to-monitor-down",
+        Action::MoveColumnToMonitorUp => "move-column-to-monitor-up",
+        Action::MoveColumnToMonitorPrevious => "move-column-to-monitor-previous",
+        Action::MoveColumnToMonitorNext => "move-column-to-monitor-next",
+        Action::MoveColumnToMonitor(..) => "move-column-to-monitor",
+        Action::SwapColumnToMonitorLeft => "swap-column-to-monitor-left",
+        Action::SwapColumnToMonitorRight => "swap-column-to-monitor-right",
+        Action::SetWindowWidth(..) | Action::SetWindowWidthById { .. } => "set-window-width",
+        Action::SetWindowHeight(..) | Action::SetWindowHeightById { .. } => "set-window-height",
+        Action::ResetWindowHeight | Action::ResetWindowHeightById(..) => "reset-window-height",
+        Action::FillWindowHeightInColumn | Action::FillWindowHeightInColumnById(..) => {
+            "fill-window-height-in-column"
+        }
+        Action::SwitchPresetColumnWidth => "switch-preset-column-width",
+        Action::SwitchPresetColumnWidthBack => "switch-preset-column-width-back",
+        Action::SwitchPresetWindowWidth | Action::SwitchPresetWindowWidthById(..) => {
+            "switch-preset-window-width"
+        }
+        Action::SwitchPresetWindowWidthBack | Action::SwitchPresetWindowWidthBackById(..) => {
+            "switch-preset-window-width-back"
+        }
+        Action::SwitchPresetWindowHeight | Action::SwitchPresetWindowHeightById(..) => {
+            "switch-preset-window-height"
+        }
+        Action::SwitchPresetWindowHeightBack | Action::SwitchPresetWindowHeightBackById(..) => {
+            "switch-preset-window-height-back"
+        }
+        Action::MaximizeColumn => "maximize-column",
+        Action::ToggleMaximizeColumnWithGroup => "toggle-maximize-column-with-group",
+        Action::MaximizeWindowToEdges | Action::MaximizeWindowToEdgesById(..) => {
+            "maximize-window-to-edges"
+        }
+        Action::ToggleMaximizeWindowToEdges | Action::ToggleMaximizeWindowToEdgesById(..) => {
+            "toggle-maximize-window-to-edges"
+        }
+        Action::SetColumnWidth(..) => "set-column-width",
+        Action::ExpandColumnToAvailableWidth => "expand-column-to-available-width",
+        Action::ToggleColumnExpandedWidth => "toggle-column-expanded-width",
+        Action::AlignColumnWidthToNeighborLeft => "align-column-width-to-neighbor-left",
+        Action::AlignColumnWidthToNeighborRight => "align-column-width-to-neighbor-right",
+        Action::ToggleZeroGaps => "toggle-zero-gaps",
+        Action::SwitchLayout(..) => "switch-layout",
+        Action::ToggleNumLock => "toggle-num-lock",
+        Action::ShowHotkeyOverlay => "show-hotkey-overlay",
+        Action::MoveWorkspaceToMonitorLeft => "move-workspace-to-monitor-left",
+        Action::MoveWorkspaceToMonitorRight => "move-workspace-to-monitor-right",
+        Action::MoveWorkspaceToMonitorDown => "move-workspace-to-monitor-down",
+        Action::MoveWorkspaceToMonitorUp => "move-workspace-to-monitor-up",
+        Action::MoveWorkspaceToMonitorPrevious => "move-workspace-to-monitor-previous",
+        Action::MoveWorkspaceToMonitorNext => "move-workspace-to-monitor-next",
+        Action::MoveWorkspaceToMonitorUnderMouse => "move-workspace-to-monitor-under-mouse",
+        Action::ToggleMirrorToMonitor(..) => "toggle-mirror-to-monitor",
+        Action::MirrorMonitor { .. } => "mirror-monitor",
+        Action::ToggleWindowFloating | Action::ToggleWindowFloatingById(..) => {
+            "toggle-window-floating"
+        }
+        Action::MoveWindowToFloating | Action::MoveWindowToFloatingById(..) => {
+            "move-window-to-floating"
+        }
+        Action::MoveWindowToTiling | Action::MoveWindowToTilingById(..) => "move-window-to-tiling",
+        Action::FocusFloating => "focus-floating",
+        Action::FocusTiling => "focus-tiling",
+        Action::FocusNextFloatingWindow => "focus-next-floating-window",
+        Action::FocusPreviousFloatingWindow => "focus-previous-floating-window",
+        Action::SwitchFocusBetweenFloatingAndTiling => "switch-focus-between-floating-and-tiling",
+        Action::FocusFloatingOrTilingUnderMouse => "focus-floating-or-tiling-under-mouse",
+        Action::ToggleFloatingResizeMode => "toggle-floating-resize-mode",
+        Action::FloatingResizeModeAdjust { .. } => "floating-resize-mode-adjust",
+        Action::FloatingResizeModeConfirm => "floating-resize-mode-confirm",
+        Action::FloatingResizeModeCancel => "floating-resize-mode-cancel",
+        Action::MoveFloatingWindowById { .. } => "move-floating-window",
+        Action::ToggleWindowRuleOpacity | Action::ToggleWindowRuleOpacityById(..) => {
+            "toggle-window-rule-opacity"
+        }
+        Action::SetWindowOpacity(..) | Action::SetWindowOpacityById { .. } => "set-window-opacity",
+        Action::ToggleServerDecorations | Action::ToggleServerDecorationsById(..) => {
+            "toggle-server-decorations"
+        }
+        Action::SetDynamicCastWindow | Action::SetDynamicCastWindowById(..) => {
+            "set-dynamic-cast-window"
+        }
+        Action::SetDynamicCastMonitor(..) => "set-dynamic-cast-monitor",
+        Action::ClearDynamicCastTarget => "clear-dynamic-cast-target",
+        Action::ToggleOverview => "toggle-overview",
+        Action::ToggleOverviewFocusedMonitor => "toggle-overview-focused-monitor",
+        Action::OpenOverview => "open-overview",
+        Action::CloseOverview => "close-overview",
+        Action::ToggleFocusedWindowUrgent => "toggle-focused-window-urgent",
+        Action::ToggleWindowUrgent(..) => "toggle-window-urgent",
+        Action::SetWindowUrgent(..) => "set-window-urgent",
+        Action::UnsetWindowUrgent(..) => "unset-window-urgent",
+        Action::LoadConfigFile => "load-config-file",
+        Action::ReloadBinds => "reload-binds",
+        Action::RestoreClipboardHistoryEntry(..) => "restore-clipboard-history-entry",
+        Action::MruAdvance { .. } => "mru-advance",
+        Action::MruConfirm => "mru-confirm",
+        Action::MruCancel => "mru-cancel",
+        Action::MruCloseCurrentWindow => "mru-close-current-window",
+        Action::MruFirst => "mru-first",
+        Action::MruLast => "mru-last",
+        Action::MruSetScope(..) => "mru-set-scope",
+        Action::MruCycleScope => "mru-cycle-scope",
+    }
+}
+
 fn key_name(screen_reader: bool, mod_key: ModKey, key: &Key) -> String {
     let mut name = String::new();
 
@@ -703,4 +1001,225 @@ mod tests {
             @" Super + P : Hello"
         );
     }
+
+    #[test]
+    fn test_action_config_name() {
+        // Every action that can be bound in the config.
+        let actions = [
+            "quit",
+            "suspend",
+            "power-off-monitors",
+            "power-on-monitors",
+            r#"power-off-monitor "DP-1""#,
+            r#"power-on-monitor "DP-1""#,
+            "set-output-scale 1.5",
+            "cycle-output-scale 1 2",
+            r#"rotate-output "90""#,
+            "cycle-output-transform",
+            "change-backlight 10",
+            "set-backlight 50",
+            "toggle-debug-tint",
+            "debug-toggle-opaque-regions",
+            "debug-toggle-damage",
+            "debug-toggle-render-heatmap",
+            r#"spawn "alacritty""#,
+            r#"spawn-sh "alacritty""#,
+            r#"spawn-on-workspace "alacritty" workspace="chat""#,
+            "do-screen-transition",
+            "screenshot",
+            "screenshot-screen",
+            "screenshot-each-output",
+            "screenshot-window",
+            r#"screenshot-to-command "satty""#,
+            "toggle-keyboard-shortcuts-inhibit",
+            "close-window",
+            "toggle-group",
+            r#"move-window-into-or-out-of-group "left""#,
+            "focus-next-window",
+            "focus-previous-window",
+            r#"set-group-display-mode "tabbed""#,
+            "toggle-group-display-mode",
+            "fullscreen-window",
+            "toggle-windowed-fullscreen",
+            "focus-window-in-column 1",
+            "focus-window-previous",
+            "focus-column-left",
+            "focus-column-right",
+            "focus-column-first",
+            "focus-column-last",
+            "focus-column-right-or-first",
+            "focus-column-left-or-last",
+            "focus-column 1",
+            "focus-window-or-monitor-up",
+            "focus-window-or-monitor-down",
+            "focus-column-or-monitor-left",
+            "focus-column-or-monitor-right",
+            "focus-window-down",
+            "focus-window-up",
+            "focus-window-down-or-column-left",
+            "focus-window-down-or-column-right",
+            "focus-window-up-or-column-left",
+            "focus-window-up-or-column-right",
+            "focus-window-or-workspace-down",
+            "focus-window-or-workspace-up",
+            "focus-window-top",
+            "focus-window-bottom",
+            "focus-window-down-or-top",
+            "focus-window-up-or-bottom",
+            r#"focus-spatial "left""#,
+            "move-column-left",
+            "move-column-right",
+            "move-column-to-first",
+            "move-column-to-last",
+            "move-column-left-or-to-monitor-left",
+            "move-column-right-or-to-monitor-right",
+            "move-column-to-index 1",
+            "move-column-by-offset -1",
+            "move-window-down",
+            "move-window-up",
+            "move-window-down-or-to-workspace-down",
+            "move-window-up-or-to-workspace-up",
+            "consume-or-expel-window-left",
+            "consume-or-expel-window-right",
+            "consume-window-into-column",
+            "expel-window-from-column",
+            "expel-window-to-column-left",
+            "expel-window-to-column-right",
+            "consume-window-from-monitor-left",
+            "consume-window-from-monitor-right",
+            "swap-window-left",
+            "swap-window-right",
+            "center-column",
+            "center-window",
+            "center-window-vertically",
+            "center-visible-columns",
+            "distribute-columns-evenly",
+            "focus-workspace-down",
+            "focus-workspace-up",
+            "focus-workspace 1",
+            "focus-workspace-previous",
+            "move-window-to-workspace-down",
+            "move-window-to-workspace-up",
+            "move-window-to-workspace 1",
+            "move-window-to-workspace-previous",
+            "move-column-to-workspace-down",
+            "move-column-to-workspace-up",
+            "move-column-to-workspace 1",
+            "move-column-to-workspace-previous",
+            "move-workspace-down",
+            "move-workspace-up",
+            "move-workspace-to-index 1",
+            r#"move-workspace-to-monitor "DP-1""#,
+            r#"set-workspace-name "chat""#,
+            "unset-workspace-name",
+            "focus-monitor-left",
+            "focus-monitor-right",
+            "focus-monitor-down",
+            "focus-monitor-up",
+            "focus-monitor-previous",
+            "focus-monitor-next",
+            "focus-monitor-left-wrap",
+            "focus-monitor-right-wrap",
+            "focus-monitor-down-wrap",
+            "focus-monitor-up-wrap",
+            r#"focus-monitor "DP-1""#,
+            r#"focus-monitor-by-workspace "chat""#,
+            "move-window-to-monitor-left",
+            "move-window-to-monitor-right",
+            "move-window-to-monitor-down",
+            "move-window-to-monitor-up",
+            "move-window-to-monitor-previous",
+            "move-window-to-monitor-next",
+            r#"move-window-to-monitor "DP-1""#,
+            "move-window-to-last-output",
+            r#"move-window-to-monitor-workspace "DP-1" 1"#,
+            "move-column-to-monitor-left",
+            "move-column-to-monitor-right",
+            "move-column-to-monitor-down",
+            "move-column-to-monitor-up",
+            "move-column-to-monitor-previous",
+            "move-column-to-monitor-next",
+            r#"move-column-to-monitor "DP-1""#,
+            "swap-column-to-monitor-left",
+            "swap-column-to-monitor-right",
+            r#"set-window-width "50%""#,
+            r#"set-window-height "50%""#,
+            "reset-window-height",
+            "fill-window-height-in-column",
+            "switch-preset-column-width",
+            "switch-preset-column-width-back",
+            "switch-preset-window-width",
+            "switch-preset-window-width-back",
+            "switch-preset-window-height",
+            "switch-preset-window-height-back",
+            "maximize-column",
+            "toggle-maximize-column-with-group",
+            "maximize-window-to-edges",
+            "toggle-maximize-window-to-edges",
+            r#"set-column-width "50%""#,
+            "expand-column-to-available-width",
+            "toggle-column-expanded-width",
+            "align-column-width-to-neighbor-left",
+            "align-column-width-to-neighbor-right",
+            "toggle-zero-gaps",
+            r#"switch-layout "next""#,
+            "toggle-num-lock",
+            "show-hotkey-overlay",
+            "move-workspace-to-monitor-left",
+            "move-workspace-to-monitor-right",
+            "move-workspace-to-monitor-down",
+            "move-workspace-to-monitor-up",
+            "move-workspace-to-monitor-previous",
+            "move-workspace-to-monitor-next",
+            "move-workspace-to-monitor-under-mouse",
+            r#"toggle-mirror-to-monitor "DP-1""#,
+            "toggle-window-floating",
+            "move-window-to-floating",
+            "move-window-to-tiling",
+            "focus-floating",
+            "focus-tiling",
+            "focus-next-floating-window",
+            "focus-previous-floating-window",
+            "switch-focus-between-floating-and-tiling",
+            "focus-floating-or-tiling-under-mouse",
+            "toggle-floating-resize-mode",
+            "toggle-window-rule-opacity",
+            "set-window-opacity 0.5",
+            "toggle-server-decorations",
+            "set-dynamic-cast-window",
+            "set-dynamic-cast-monitor",
+            "clear-dynamic-cast-target",
+            "toggle-overview",
+            "toggle-overview-focused-monitor",
+            "open-overview",
+            "close-overview",
+            "toggle-focused-window-urgent",
+            "reload-binds",
+            "restore-clipboard-history-entry 0",
+        ];
+
+        for config_action in actions {
+            let config =
+                Config::parse_mem(&format!("binds {{ Mod+T {{ {config_action}; }} }}")).unwrap();
+            let action = &config.binds.0[0].action;
+            let name = config_action.split(' ').next().unwrap();
+            assert_eq!(action_config_name(action), name, "{action:?}");
+        }
+
+        // Actions by id use the name of the bindable action.
+        assert_eq!(
+            action_config_name(&Action::CloseWindowById(1)),
+            "close-window"
+        );
+        assert_eq!(
+            action_config_name(&Action::MoveWindowToLastOutputById(1)),
+            "move-window-to-last-output"
+        );
+        assert_eq!(
+            action_config_name(&Action::UnsetWorkSpaceNameByRef(
+                niri_config::WorkspaceReference::Index(1)
+            )),
+            "unset-workspace-name"
+        );
+    }
 }
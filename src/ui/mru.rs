@@ -1897,6 +1897,7 @@ fn make_preset_opened_binds() -> Vec<Bind> {
             action,
             repeat: true,
             cooldown: None,
+            hold: None,
             allow_when_locked: false,
             allow_inhibiting: false,
             hotkey_overlay_title: None,
@@ -477,7 +477,8 @@ impl ScreenshotUi {
 
         let new_size = match change {
             SizeChange::SetFixed(fixed) => to_physical_precise_round(data.scale, fixed),
-            SizeChange::SetProportion(prop) => {
+            // The selection is within the whole output, so both proportions are the same.
+            SizeChange::SetProportion(prop) | SizeChange::SetOutputProportion(prop) => {
                 let prop = (prop / 100.).clamp(0., 1.);
                 (available_size * prop).round() as i32
             }
@@ -490,6 +491,7 @@ impl ScreenshotUi {
                 let prop = (current_prop + delta / 100.).clamp(0., 1.);
                 (available_size * prop).round() as i32
             }
+            SizeChange::SetRest => data.size.w - min(a.x, b.x),
         };
         let new_size = new_size.clamp(1, data.size.w - min(a.x, b.x)) - 1;
         a.x = min(a.x, b.x);
@@ -515,7 +517,8 @@ impl ScreenshotUi {
 
         let new_size = match change {
             SizeChange::SetFixed(fixed) => to_physical_precise_round(data.scale, fixed),
-            SizeChange::SetProportion(prop) => {
+            // The selection is within the whole output, so both proportions are the same.
+            SizeChange::SetProportion(prop) | SizeChange::SetOutputProportion(prop) => {
                 let prop = (prop / 100.).clamp(0., 1.);
                 (available_size * prop).round() as i32
             }
@@ -528,6 +531,7 @@ impl ScreenshotUi {
                 let prop = (current_prop + delta / 100.).clamp(0., 1.);
                 (available_size * prop).round() as i32
             }
+            SizeChange::SetRest => data.size.h - min(a.y, b.y),
         };
         let new_size = new_size.clamp(1, data.size.h - min(a.y, b.y)) - 1;
         a.y = min(a.y, b.y);
@@ -0,0 +1,265 @@
+//! Gamma ramps from ICC color profiles.
+//!
+//! Display calibration tools store the 1D LUT meant to be loaded into the gamma tables of the
+//! display in the `vcgt` (video card gamma table) tag of the ICC profile.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, bail, ensure};
+
+use crate::utils::expand_home;
+
+const VCGT_SIGNATURE: &[u8] = b"vcgt";
+
+/// Offset of the tag table in an ICC profile, right after the header.
+const TAG_TABLE_OFFSET: usize = 128;
+
+/// Gamma curves of the red, green and blue channels, mapping 0..=1 to 0..=1.
+#[derive(Debug, PartialEq)]
+enum Vcgt {
+    Table([Vec<f64>; 3]),
+    Formula([Formula; 3]),
+}
+
+#[derive(Debug, PartialEq)]
+struct Formula {
+    gamma: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Vcgt {
+    fn eval(&self, channel: usize, x: f64) -> f64 {
+        match self {
+            Vcgt::Table(tables) => {
+                let table = &tables[channel];
+                let pos = x * (table.len() - 1) as f64;
+                let idx = (pos.floor() as usize).min(table.len() - 1);
+                let next = (idx + 1).min(table.len() - 1);
+                let frac = pos - idx as f64;
+                table[idx] + (table[next] - table[idx]) * frac
+            }
+            Vcgt::Formula(formulas) => {
+                let Formula { gamma, min, max } = formulas[channel];
+                min + (max - min) * x.powf(gamma)
+            }
+        }
+    }
+}
+
+/// Loads the gamma ramp from the ICC profile at `path`.
+///
+/// The ramp contains `gamma_size` red values, then green, then blue, like gamma control ramps.
+pub fn load_gamma_ramp(path: &Path, gamma_size: u32) -> anyhow::Result<Vec<u16>> {
+    let expanded = expand_home(path).context("error expanding ~")?;
+    let path = expanded.as_deref().unwrap_or(path);
+
+    let data = fs::read(path).context("error reading the profile")?;
+    gamma_ramp(&data, gamma_size)
+}
+
+fn gamma_ramp(profile: &[u8], gamma_size: u32) -> anyhow::Result<Vec<u16>> {
+    let vcgt = parse_vcgt(profile)?;
+
+    let size = gamma_size as usize;
+    let mut ramp = Vec::with_capacity(size * 3);
+    for channel in 0..3 {
+        for i in 0..size {
+            let x = if size > 1 {
+                i as f64 / (size - 1) as f64
+            } else {
+                0.
+            };
+            let value = vcgt.eval(channel, x).clamp(0., 1.);
+            ramp.push((value * f64::from(u16::MAX)).round() as u16);
+        }
+    }
+
+    Ok(ramp)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> anyhow::Result<u16> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .context("unexpected end of data")?;
+    Ok(u16::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> anyhow::Result<u32> {
+    let bytes = data
+        .get(offset..offset + 4)
+        .context("unexpected end of data")?;
+    Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+/// Reads an s15Fixed16Number.
+fn read_fixed(data: &[u8], offset: usize) -> anyhow::Result<f64> {
+    Ok(f64::from(read_u32(data, offset)? as i32) / 65536.)
+}
+
+fn parse_vcgt(profile: &[u8]) -> anyhow::Result<Vcgt> {
+    ensure!(
+        profile.get(36..40) == Some(&b"acsp"[..]),
+        "not an ICC profile"
+    );
+
+    let tag_count = read_u32(profile, TAG_TABLE_OFFSET)? as usize;
+    let tags_start = TAG_TABLE_OFFSET + 4;
+    ensure!(
+        tags_start + tag_count * 12 <= profile.len(),
+        "tag table is truncated"
+    );
+
+    let entry = (0..tag_count)
+        .map(|i| tags_start + i * 12)
+        .find(|&entry| &profile[entry..entry + 4] == VCGT_SIGNATURE)
+        .context("profile has no vcgt tag")?;
+    let offset = read_u32(profile, entry + 4)? as usize;
+    let size = read_u32(profile, entry + 8)? as usize;
+    let tag = offset
+        .checked_add(size)
+        .and_then(|end| profile.get(offset..end))
+        .context("vcgt tag is out of bounds")?;
+
+    ensure!(
+        tag.get(0..4) == Some(VCGT_SIGNATURE),
+        "vcgt tag has a wrong type"
+    );
+
+    match read_u32(tag, 8)? {
+        0 => {
+            let channels = usize::from(read_u16(tag, 12)?);
+            let count = usize::from(read_u16(tag, 14)?);
+            let entry_size = usize::from(read_u16(tag, 16)?);
+
+            ensure!(
+                channels == 1 || channels == 3,
+                "unsupported number of channels: {channels}"
+            );
+            ensure!(count >= 2, "too few entries: {count}");
+
+            let max = match entry_size {
+                1 => f64::from(u8::MAX),
+                2 => f64::from(u16::MAX),
+                _ => bail!("unsupported entry size: {entry_size}"),
+            };
+
+            let values = tag
+                .get(18..18 + channels * count * entry_size)
+                .context("vcgt table is truncated")?;
+            let mut tables = values
+                .chunks_exact(entry_size * count)
+                .map(|table| {
+                    table
+                        .chunks_exact(entry_size)
+                        .map(|value| match *value {
+                            [x] => f64::from(x) / max,
+                            [hi, lo] => f64::from(u16::from_be_bytes([hi, lo])) / max,
+                            _ => unreachable!(),
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            if channels == 1 {
+                let table = tables.pop().unwrap();
+                Ok(Vcgt::Table([table.clone(), table.clone(), table]))
+            } else {
+                Ok(Vcgt::Table(tables.try_into().unwrap()))
+            }
+        }
+        1 => {
+            let formula = |channel: usize| -> anyhow::Result<Formula> {
+                let offset = 12 + channel * 12;
+                Ok(Formula {
+                    gamma: read_fixed(tag, offset)?,
+                    min: read_fixed(tag, offset + 4)?,
+                    max: read_fixed(tag, offset + 8)?,
+                })
+            };
+            Ok(Vcgt::Formula([formula(0)?, formula(1)?, formula(2)?]))
+        }
+        x => bail!("unknown vcgt type: {x}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Makes a minimal ICC profile with a single vcgt tag.
+    fn profile(vcgt: &[u8]) -> Vec<u8> {
+        let mut data = vec![0; TAG_TABLE_OFFSET];
+        data[36..40].copy_from_slice(b"acsp");
+
+        data.extend_from_slice(&1u32.to_be_bytes());
+        data.extend_from_slice(VCGT_SIGNATURE);
+        data.extend_from_slice(&(TAG_TABLE_OFFSET as u32 + 16).to_be_bytes());
+        data.extend_from_slice(&(vcgt.len() as u32 + 8).to_be_bytes());
+
+        data.extend_from_slice(VCGT_SIGNATURE);
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(vcgt);
+        data
+    }
+
+    fn table(channels: u16, entry_size: u16, values: &[u8]) -> Vec<u8> {
+        let count = values.len() / usize::from(channels * entry_size);
+        let mut data = 0u32.to_be_bytes().to_vec();
+        data.extend_from_slice(&channels.to_be_bytes());
+        data.extend_from_slice(&(count as u16).to_be_bytes());
+        data.extend_from_slice(&entry_size.to_be_bytes());
+        data.extend_from_slice(values);
+        data
+    }
+
+    #[test]
+    fn single_channel_table() {
+        let profile = profile(&table(1, 1, &[0, 255]));
+        let ramp = gamma_ramp(&profile, 3).unwrap();
+        assert_eq!(ramp, [0, 32768, 65535, 0, 32768, 65535, 0, 32768, 65535]);
+    }
+
+    #[test]
+    fn three_channel_table() {
+        #[rustfmt::skip]
+        let values = [
+            0, 0, 0xff, 0xff,
+            0, 0, 0x80, 0x00,
+            0x80, 0x00, 0, 0,
+        ];
+        let profile = profile(&table(3, 2, &values));
+        let ramp = gamma_ramp(&profile, 2).unwrap();
+        assert_eq!(ramp, [0, 65535, 0, 32768, 32768, 0]);
+    }
+
+    #[test]
+    fn formula() {
+        let mut vcgt = 1u32.to_be_bytes().to_vec();
+        for _ in 0..3 {
+            // gamma 2, min 0, max 1
+            vcgt.extend_from_slice(&0x20000u32.to_be_bytes());
+            vcgt.extend_from_slice(&0u32.to_be_bytes());
+            vcgt.extend_from_slice(&0x10000u32.to_be_bytes());
+        }
+        let ramp = gamma_ramp(&profile(&vcgt), 3).unwrap();
+        assert_eq!(ramp, [0, 16384, 65535, 0, 16384, 65535, 0, 16384, 65535]);
+    }
+
+    #[test]
+    fn malformed_profiles() {
+        assert!(gamma_ramp(&[0; 16], 256).is_err());
+
+        let mut no_vcgt = profile(&table(1, 1, &[0, 255]));
+        no_vcgt[TAG_TABLE_OFFSET + 4..TAG_TABLE_OFFSET + 8].copy_from_slice(b"desc");
+        assert!(gamma_ramp(&no_vcgt, 256).is_err());
+
+        let mut truncated = profile(&table(1, 2, &[0, 0, 0xff, 0xff]));
+        truncated.truncate(truncated.len() - 1);
+        assert!(gamma_ramp(&truncated, 256).is_err());
+
+        assert!(gamma_ramp(&profile(&table(2, 1, &[0, 255, 0, 255])), 256).is_err());
+        assert!(gamma_ramp(&profile(&2u32.to_be_bytes()), 256).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::cmp::{max, min};
 use std::ffi::{CString, OsStr};
 use std::io::Write;
@@ -12,7 +13,7 @@ use anyhow::{Context, bail, ensure};
 use bitflags::bitflags;
 use directories::UserDirs;
 use git_version::git_version;
-use niri_config::{Config, OutputName};
+use niri_config::{Config, OutputName, ScaleRounding};
 use smithay::backend::renderer::utils::with_renderer_surface_state;
 use smithay::input::pointer::CursorIcon;
 use smithay::output::{self, Output};
@@ -32,7 +33,9 @@ use wayland_backend::server::Credentials;
 
 use crate::handlers::KdeDecorationsModeState;
 use crate::niri::ClientState;
+use crate::utils::scale::apply_scale_rounding;
 
+pub mod color_profile;
 pub mod id;
 pub mod region;
 pub mod render;
@@ -42,6 +45,7 @@ pub mod spawning;
 pub mod transaction;
 pub mod vblank_throttle;
 pub mod watcher;
+pub mod workspace_uuids;
 pub mod xwayland;
 
 pub static IS_SYSTEMD_SERVICE: AtomicBool = AtomicBool::new(false);
@@ -112,6 +116,17 @@ pub fn get_monotonic_time() -> Duration {
     Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
 }
 
+/// Returns until when a window that last committed at `last_commit_time` keeps on-demand VRR
+/// enabled, or `None` if it has already been idle for `idle_timeout`.
+pub fn on_demand_vrr_active_until(
+    last_commit_time: Option<Duration>,
+    idle_timeout: Duration,
+    now: Duration,
+) -> Option<Duration> {
+    let until = last_commit_time? + idle_timeout;
+    (now < until).then_some(until)
+}
+
 pub fn center(rect: Rectangle<i32, Logical>) -> Point<i32, Logical> {
     rect.loc + rect.size.downscale(2).to_point()
 }
@@ -185,6 +200,37 @@ pub fn panel_orientation(output: &Output) -> Transform {
         .unwrap_or(Transform::Normal)
 }
 
+/// Scale rounding policy of an output, see [`client_scale()`].
+struct OutputScaleRounding(Cell<ScaleRounding>);
+
+/// Sets the scale rounding policy of the output, returning whether it changed.
+pub fn set_scale_rounding(output: &Output, rounding: ScaleRounding) -> bool {
+    let data = output.user_data();
+    data.insert_if_missing(|| OutputScaleRounding(Cell::new(ScaleRounding::Exact)));
+    let current = &data.get::<OutputScaleRounding>().unwrap().0;
+    current.replace(rounding) != rounding
+}
+
+/// Returns the scale that clients on the output should render at.
+///
+/// This is the output scale with its rounding policy applied. The output itself keeps the exact
+/// scale, so its logical size doesn't change.
+pub fn client_scale(output: &Output) -> output::Scale {
+    let scale = output.current_scale();
+    let rounding = output
+        .user_data()
+        .get::<OutputScaleRounding>()
+        .map(|x| x.0.get())
+        .unwrap_or_default();
+    match rounding {
+        ScaleRounding::Exact => scale,
+        rounding => {
+            let rounded = apply_scale_rounding(scale.fractional_scale(), rounding);
+            output::Scale::Integer(rounded as i32)
+        }
+    }
+}
+
 pub const fn ipc_transform_to_smithay(transform: niri_ipc::Transform) -> Transform {
     match transform {
         niri_ipc::Transform::Normal => Transform::Normal,
@@ -198,6 +244,22 @@ pub const fn ipc_transform_to_smithay(transform: niri_ipc::Transform) -> Transfo
     }
 }
 
+/// Returns the transform rotated by another 90° counter-clockwise, keeping the flip.
+pub const fn next_transform_in_cycle(transform: niri_ipc::Transform) -> niri_ipc::Transform {
+    use niri_ipc::Transform as T;
+
+    match transform {
+        T::Normal => T::_90,
+        T::_90 => T::_180,
+        T::_180 => T::_270,
+        T::_270 => T::Normal,
+        T::Flipped => T::Flipped90,
+        T::Flipped90 => T::Flipped180,
+        T::Flipped180 => T::Flipped270,
+        T::Flipped270 => T::Flipped,
+    }
+}
+
 pub fn is_mapped(surface: &WlSurface) -> bool {
     // None if the surface hadn't committed yet.
     with_renderer_surface_state(surface, |state| state.buffer().is_some()).unwrap_or(false)
@@ -229,7 +291,12 @@ pub fn make_screenshot_path(config: &Config) -> anyhow::Result<PathBuf> {
         bail!("screenshot directory is not set");
     };
 
-    let format = CString::new(path.clone()).context("path must not contain nul bytes")?;
+    format_screenshot_path(path)
+}
+
+/// Formats a screenshot path template with `strftime()` and expands `~`.
+pub fn format_screenshot_path(template: &str) -> anyhow::Result<PathBuf> {
+    let format = CString::new(template).context("path must not contain nul bytes")?;
 
     let mut buf = [0u8; 2048];
     let mut path;
@@ -253,6 +320,26 @@ pub fn make_screenshot_path(config: &Config) -> anyhow::Result<PathBuf> {
     Ok(path)
 }
 
+/// Substitutes the `{output}` token in a screenshot path with the output name.
+///
+/// If the path has no such token, appends the output name to the file name before the extension,
+/// so that screenshots of different outputs don't overwrite each other.
+pub fn screenshot_path_for_output(path: &Path, output_name: &str) -> PathBuf {
+    let path_str = path.to_string_lossy();
+    if path_str.contains("{output}") {
+        return PathBuf::from(path_str.replace("{output}", output_name));
+    }
+
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(" ");
+    file_name.push(output_name);
+    if let Some(ext) = path.extension() {
+        file_name.push(".");
+        file_name.push(ext);
+    }
+    path.with_file_name(file_name)
+}
+
 type PendingWriter<W> = Box<dyn FnOnce() -> io::Result<W> + Send + 'static>;
 
 /// Writer, which initializes itself on first write
@@ -388,6 +475,24 @@ pub fn with_toplevel_last_uncommitted_configure<T>(
     })
 }
 
+/// Sets the xdg-decoration mode to request from the toplevel.
+///
+/// Returns `false` without changing anything if the toplevel doesn't use xdg-decoration.
+pub fn set_decoration_mode(toplevel: &ToplevelSurface, server_side: bool) -> bool {
+    toplevel.with_pending_state(|state| {
+        if state.decoration_mode.is_none() {
+            return false;
+        }
+
+        state.decoration_mode = Some(if server_side {
+            zxdg_toplevel_decoration_v1::Mode::ServerSide
+        } else {
+            zxdg_toplevel_decoration_v1::Mode::ClientSide
+        });
+        true
+    })
+}
+
 pub fn update_tiled_state(
     toplevel: &ToplevelSurface,
     prefer_no_csd: bool,
@@ -464,6 +569,63 @@ pub fn get_credentials_for_surface(surface: &WlSurface) -> Option<Credentials> {
     client.get_credentials(&dh).ok()
 }
 
+/// Cached name of the process that owns a surface.
+struct SurfaceProcessName(Option<String>);
+
+/// Returns the executable name of the process that owns the surface.
+///
+/// The name is resolved once through the client credentials and cached on the surface.
+pub fn get_process_name_for_surface(surface: &WlSurface) -> Option<String> {
+    with_states(surface, |states| {
+        let name = states.data_map.get_or_insert(|| {
+            let credentials = get_credentials_for_surface(surface);
+            SurfaceProcessName(credentials.and_then(|c| process_name_for_pid(c.pid)))
+        });
+        name.0.clone()
+    })
+}
+
+fn process_name_for_pid(pid: i32) -> Option<String> {
+    let exe = std::fs::read_link(format!("/proc/{pid}/exe")).ok();
+    if let Some(name) = exe.as_deref().and_then(Path::file_name) {
+        return Some(name.to_string_lossy().into_owned());
+    }
+
+    // Reading exe can fail e.g. for processes of another user, but comm is always readable.
+    let comm = std::fs::read_to_string(format!("/proc/{pid}/comm")).ok()?;
+    Some(comm.trim_end().to_owned())
+}
+
+/// Returns the activation token that niri spawned the surface's process with, if any.
+pub fn get_spawn_activation_token_for_surface(surface: &WlSurface) -> Option<String> {
+    let handle = surface.handle().upgrade()?;
+    let dh = DisplayHandle::from(handle);
+
+    let client = dh.get_client(surface.id()).ok()?;
+    let data = client.get_data::<ClientState>().unwrap();
+    if let Some(token) = &data.activation_token {
+        return Some(token.clone());
+    }
+    if data.credentials_unknown {
+        return None;
+    }
+
+    let pid = client.get_credentials(&dh).ok()?.pid;
+    spawn_activation_token_for_pid(pid)
+}
+
+/// Returns the activation token that niri put into the initial environment of the process.
+///
+/// This reads the environment the process started with, so it keeps working if the process has
+/// since unset the variable.
+pub fn spawn_activation_token_for_pid(pid: i32) -> Option<String> {
+    let environ = std::fs::read(format!("/proc/{pid}/environ")).ok()?;
+    let token = environ
+        .split(|c| *c == 0)
+        .find_map(|var| var.strip_prefix(b"XDG_ACTIVATION_TOKEN="))?;
+    Some(String::from_utf8_lossy(token).into_owned())
+}
+
 pub fn ensure_min_max_size(mut x: i32, min_size: i32, max_size: i32) -> i32 {
     if max_size > 0 {
         x = min(x, max_size);
@@ -576,6 +738,82 @@ pub fn cause_panic() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_screenshot_path_for_output() {
+        let check = |path: &str, expected: &str| {
+            let path = screenshot_path_for_output(Path::new(path), "DP-1");
+            assert_eq!(path, Path::new(expected));
+        };
+
+        check("/tmp/shot {output}.png", "/tmp/shot DP-1.png");
+        check("/tmp/{output}/shot.png", "/tmp/DP-1/shot.png");
+        check("/tmp/shot.png", "/tmp/shot DP-1.png");
+        check("/tmp/shot", "/tmp/shot DP-1");
+    }
+
+    #[test]
+    fn test_client_scale() {
+        let output = Output::new(
+            String::from("DP-1"),
+            output::PhysicalProperties {
+                size: Size::from((0, 0)),
+                subpixel: output::Subpixel::Unknown,
+                make: String::new(),
+                model: String::new(),
+                serial_number: String::new(),
+            },
+        );
+        output.change_current_state(None, None, Some(output::Scale::Fractional(1.5)), None);
+
+        assert_eq!(client_scale(&output).fractional_scale(), 1.5);
+
+        assert!(set_scale_rounding(&output, ScaleRounding::NearestInt));
+        assert!(!set_scale_rounding(&output, ScaleRounding::NearestInt));
+        assert_eq!(client_scale(&output).fractional_scale(), 2.);
+        assert_eq!(client_scale(&output).integer_scale(), 2);
+
+        set_scale_rounding(&output, ScaleRounding::Floor);
+        assert_eq!(client_scale(&output).fractional_scale(), 1.);
+
+        // The output itself keeps the exact scale.
+        assert_eq!(output.current_scale().fractional_scale(), 1.5);
+    }
+
+    #[test]
+    fn test_on_demand_vrr_active_until() {
+        let ms = Duration::from_millis;
+        let timeout = ms(100);
+
+        assert_eq!(on_demand_vrr_active_until(None, timeout, ms(0)), None);
+        assert_eq!(
+            on_demand_vrr_active_until(Some(ms(1000)), timeout, ms(1000)),
+            Some(ms(1100))
+        );
+        assert_eq!(
+            on_demand_vrr_active_until(Some(ms(1000)), timeout, ms(1099)),
+            Some(ms(1100))
+        );
+        assert_eq!(
+            on_demand_vrr_active_until(Some(ms(1000)), timeout, ms(1100)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_next_transform_in_cycle() {
+        use niri_ipc::Transform as T;
+
+        let mut transform = T::Normal;
+        let mut seen = vec![];
+        for _ in 0..4 {
+            transform = next_transform_in_cycle(transform);
+            seen.push(transform);
+        }
+        assert_eq!(seen, [T::_90, T::_180, T::_270, T::Normal]);
+
+        assert_eq!(next_transform_in_cycle(T::Flipped270), T::Flipped);
+    }
+
     #[test]
     fn test_clamp_preferring_top_left() {
         fn check(
@@ -86,6 +86,49 @@ where
         self.rects.sort_rects();
     }
 
+    pub fn subtract_region(&mut self, other: &Self) {
+        if other.is_empty() {
+            return;
+        }
+
+        self.rects = Rectangle::subtract_rects_many(self.rects.iter(), other.rects())
+            .into_iter()
+            .collect();
+
+        self.rects.sort_rects();
+    }
+
+    /// Merges rectangles that contain each other or form a larger rectangle together.
+    ///
+    /// Subtracting and adding rectangles can split the region into many small fragments, this
+    /// brings their number back down.
+    pub fn normalize(&mut self) {
+        if !matches!(self.rects, RegionInner::Multiple(_)) {
+            return;
+        }
+
+        let mut rects: Vec<_> = self.rects.iter().collect();
+
+        let mut merged = true;
+        while merged {
+            merged = false;
+
+            'outer: for i in 0..rects.len() {
+                for j in (i + 1)..rects.len() {
+                    if let Some(rect) = merge_rects(rects[i], rects[j]) {
+                        rects[i] = rect;
+                        rects.swap_remove(j);
+                        merged = true;
+                        break 'outer;
+                    }
+                }
+            }
+        }
+
+        self.rects = rects.into_iter().collect();
+        self.rects.sort_rects();
+    }
+
     pub fn add_rect(&mut self, rect: Rectangle<N, Kind>) {
         if !self.rects.iter().any(|r| r.intersection(rect).is_some()) {
             // nothing intersects, so we can just add this rectangle as-is
@@ -120,6 +163,34 @@ where
     }
 }
 
+/// Returns the union of two rectangles if it is a rectangle itself.
+fn merge_rects<N, Kind>(a: Rectangle<N, Kind>, b: Rectangle<N, Kind>) -> Option<Rectangle<N, Kind>>
+where
+    N: Coordinate + Default + PartialOrd + Add + Sub + Copy + AddAssign,
+{
+    if a.contains_rect(b) {
+        return Some(a);
+    }
+    if b.contains_rect(a) {
+        return Some(b);
+    }
+
+    let a_right = a.loc.x.saturating_add(a.size.w);
+    let a_bottom = a.loc.y.saturating_add(a.size.h);
+    let b_right = b.loc.x.saturating_add(b.size.w);
+    let b_bottom = b.loc.y.saturating_add(b.size.h);
+
+    // Same row, touching or overlapping horizontally.
+    let same_row = a.loc.y == b.loc.y && a.size.h == b.size.h;
+    let touch_x = a.loc.x <= b_right && b.loc.x <= a_right;
+
+    // Same column, touching or overlapping vertically.
+    let same_column = a.loc.x == b.loc.x && a.size.w == b.size.w;
+    let touch_y = a.loc.y <= b_bottom && b.loc.y <= a_bottom;
+
+    ((same_row && touch_x) || (same_column && touch_y)).then(|| a.merge(b))
+}
+
 impl Region<i32, Logical> {
     pub fn from_region_attributes(value: RegionAttributes) -> Self {
         value.rects.into_iter().fold(
@@ -338,3 +409,78 @@ where
         (remaining_elems, Some(remaining_elems))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use smithay::utils::Size;
+
+    use super::*;
+
+    fn rect(x: i32, y: i32, w: i32, h: i32) -> Rectangle<i32, Logical> {
+        Rectangle::new(Point::from((x, y)), Size::from((w, h)))
+    }
+
+    #[test]
+    fn normalize_merges_adjacent_rects() {
+        let mut region = Region::from_rects([
+            rect(0, 0, 10, 10),
+            rect(10, 0, 10, 10),
+            rect(0, 10, 10, 10),
+            rect(10, 10, 10, 10),
+        ]);
+        assert_eq!(region.len(), 4);
+
+        region.normalize();
+        assert_eq!(region.len(), 1);
+        assert_eq!(region.rects().next(), Some(rect(0, 0, 20, 20)));
+    }
+
+    #[test]
+    fn normalize_merges_contained_rects() {
+        let mut region = Region::from_rects([rect(0, 0, 20, 20), rect(5, 5, 5, 5)]);
+
+        region.normalize();
+        assert_eq!(region.len(), 1);
+        assert_eq!(region.rects().next(), Some(rect(0, 0, 20, 20)));
+    }
+
+    #[test]
+    fn normalize_keeps_disjoint_rects() {
+        let mut region = Region::from_rects([rect(0, 0, 10, 10), rect(20, 0, 10, 10)]);
+
+        region.normalize();
+        assert_eq!(region.len(), 2);
+    }
+
+    #[test]
+    fn subtract_then_add_back_normalizes_to_one_rect() {
+        let mut region = Region::from_rects([rect(0, 0, 30, 30)]);
+        region.subtract_rect(rect(10, 10, 10, 10));
+        assert!(region.len() > 1);
+
+        region.add_rect(rect(10, 10, 10, 10));
+        let len = region.len();
+
+        region.normalize();
+        assert!(region.len() < len);
+        assert_eq!(region.len(), 1);
+        assert_eq!(region.rects().next(), Some(rect(0, 0, 30, 30)));
+    }
+
+    #[test]
+    fn subtract_region() {
+        let mut region = Region::from_rects([rect(0, 0, 20, 10)]);
+        let other = Region::from_rects([rect(10, 0, 10, 10), rect(0, 5, 5, 5)]);
+
+        region.subtract_region(&other);
+        region.normalize();
+
+        let area: i32 = region.rects().map(|r| r.size.w * r.size.h).sum();
+        assert_eq!(area, 75);
+        assert!(
+            region
+                .rects()
+                .all(|r| r.intersection(rect(10, 0, 10, 10)).is_none())
+        );
+    }
+}
@@ -3,6 +3,7 @@
 //! This module follows logic and tests from Mutter:
 //! <https://gitlab.gnome.org/GNOME/mutter/-/blob/gnome-46/src/backends/meta-monitor.c>
 
+use niri_config::ScaleRounding;
 use smithay::utils::{Physical, Raw, Size};
 
 const MIN_SCALE: i32 = 1;
@@ -57,6 +58,28 @@ pub fn closest_representable_scale(scale: f64) -> f64 {
     (scale * FRACTIONAL_SCALE_DENOM).round() / FRACTIONAL_SCALE_DENOM
 }
 
+/// Rounds the scale according to the output's rounding policy.
+///
+/// Integer rounding never goes below 1, since a scale of 0 is invalid.
+pub fn apply_scale_rounding(scale: f64, rounding: ScaleRounding) -> f64 {
+    match rounding {
+        ScaleRounding::Exact => scale,
+        ScaleRounding::NearestInt => scale.round().max(1.),
+        ScaleRounding::Floor => scale.floor().max(1.),
+    }
+}
+
+/// Returns the scale that follows `current` in `scales`.
+///
+/// Falls back to the first scale when `current` is not in the list.
+pub fn next_scale_in_cycle(current: f64, scales: &[f64]) -> Option<f64> {
+    let idx = scales
+        .iter()
+        .position(|&scale| (closest_representable_scale(scale) - current).abs() < 0.001);
+    let next = idx.map_or(0, |idx| (idx + 1) % scales.len());
+    scales.get(next).copied()
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_snapshot;
@@ -117,4 +140,23 @@ mod tests {
         assert_snapshot!(closest_representable_scale(1.34), @"1.3416666666666666");
         assert_snapshot!(closest_representable_scale(1.35), @"1.35");
     }
+
+    #[test]
+    fn test_apply_scale_rounding() {
+        assert_eq!(apply_scale_rounding(1.75, ScaleRounding::Exact), 1.75);
+        assert_eq!(apply_scale_rounding(1.75, ScaleRounding::NearestInt), 2.);
+        assert_eq!(apply_scale_rounding(1.25, ScaleRounding::NearestInt), 1.);
+        assert_eq!(apply_scale_rounding(1.75, ScaleRounding::Floor), 1.);
+        assert_eq!(apply_scale_rounding(0.5, ScaleRounding::Floor), 1.);
+    }
+
+    #[test]
+    fn test_next_scale_in_cycle() {
+        let scales = [1., 1.25, 1.5];
+        assert_eq!(next_scale_in_cycle(1., &scales), Some(1.25));
+        assert_eq!(next_scale_in_cycle(1.5, &scales), Some(1.));
+        assert_eq!(next_scale_in_cycle(2., &scales), Some(1.));
+        assert_eq!(next_scale_in_cycle(1.3, &[1.3, 2.]), Some(2.));
+        assert_eq!(next_scale_in_cycle(1., &[]), None);
+    }
 }
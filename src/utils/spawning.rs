@@ -1,4 +1,5 @@
 use std::ffi::OsStr;
+use std::io::Write as _;
 use std::os::unix::process::CommandExt;
 use std::path::Path;
 use std::process::{Child, Command, Stdio};
@@ -6,6 +7,7 @@ use std::sync::RwLock;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::{io, thread};
 
+use anyhow::{Context as _, ensure};
 use atomic::Atomic;
 use libc::{RLIMIT_NOFILE, getrlimit, rlim_t, rlimit, setrlimit};
 use niri_config::Environment;
@@ -92,6 +94,42 @@ pub fn spawn_sh(command: String, token: Option<XdgActivationToken>) {
     spawn(vec![String::from("sh"), String::from("-c"), command], token);
 }
 
+/// Runs the command, writes `input` to its stdin, and waits for it to exit.
+///
+/// Unlike [`spawn`], this blocks, so it should be called from a separate thread.
+pub fn run_with_stdin(command: &[String], input: &[u8]) -> anyhow::Result<()> {
+    let _span = tracy_client::span!();
+
+    let (command, args) = command.split_first().context("command is empty")?;
+
+    let mut process = prepare_command(command.as_ref(), args, None);
+    process.stdin(Stdio::piped());
+    unsafe {
+        process.pre_exec(|| {
+            restore_nofile_rlimit();
+            Ok(())
+        });
+    }
+
+    let mut child = process
+        .spawn()
+        .with_context(|| format!("error spawning {command:?}"))?;
+
+    let mut stdin = child.stdin.take().unwrap();
+    let res = stdin.write_all(input);
+    // Close stdin so that the process sees the end of input.
+    drop(stdin);
+
+    let status = child.wait().context("error waiting for child")?;
+    res.context("error writing to child stdin")?;
+    ensure!(
+        status.success(),
+        "child did not exit successfully: {status:?}"
+    );
+
+    Ok(())
+}
+
 fn spawn_sync(
     command: impl AsRef<OsStr>,
     args: impl IntoIterator<Item = impl AsRef<OsStr>>,
@@ -99,7 +137,32 @@ fn spawn_sync(
 ) {
     let _span = tracy_client::span!();
 
-    let mut command = command.as_ref();
+    let command = command.as_ref();
+    let process = prepare_command(command, args, token.as_ref());
+
+    let Some(mut child) = do_spawn(command, process) else {
+        return;
+    };
+
+    match child.wait() {
+        Ok(status) => {
+            if !status.success() {
+                warn!("child did not exit successfully: {status:?}");
+            }
+        }
+        Err(err) => {
+            warn!("error waiting for child: {err:?}");
+        }
+    }
+}
+
+/// Creates the command with the environment set up for processes spawned by niri.
+fn prepare_command(
+    command: &OsStr,
+    args: impl IntoIterator<Item = impl AsRef<OsStr>>,
+    token: Option<&XdgActivationToken>,
+) -> Command {
+    let mut command = command;
 
     // Expand `~` at the start.
     let expanded = expand_home(Path::new(command));
@@ -145,27 +208,14 @@ fn spawn_sync(
     }
     drop(env);
 
-    if let Some(token) = token.as_ref() {
+    if let Some(token) = token {
         process.env("XDG_ACTIVATION_TOKEN", token.as_str());
         process.env("DESKTOP_STARTUP_ID", token.as_str());
     }
 
     unsafe { process.pre_exec(crate::utils::signals::unblock_all) };
 
-    let Some(mut child) = do_spawn(command, process) else {
-        return;
-    };
-
-    match child.wait() {
-        Ok(status) => {
-            if !status.success() {
-                warn!("child did not exit successfully: {status:?}");
-            }
-        }
-        Err(err) => {
-            warn!("error waiting for child: {err:?}");
-        }
-    }
+    process
 }
 
 #[cfg(not(feature = "systemd"))]
@@ -14,7 +14,18 @@ use crate::niri::State;
 const POLLING_INTERVAL: Duration = Duration::from_millis(500);
 
 pub struct Watcher {
-    load_config: mpsc::Sender<()>,
+    load_config: mpsc::Sender<ConfigReload>,
+    /// The paths we're watching.
+    path: ConfigPath,
+}
+
+/// How a loaded config should be applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigReload {
+    /// Apply the whole config.
+    Full,
+    /// Apply only the binds, unless other important sections changed.
+    BindsOnly,
 }
 
 struct WatcherInner {
@@ -56,20 +67,21 @@ impl Watcher {
         path: ConfigPath,
         includes: Vec<PathBuf>,
         mut process: impl FnMut(&ConfigPath) -> ConfigParseResult<Config, ()> + Send + 'static,
-        changed: SyncSender<Result<Config, ()>>,
+        changed: SyncSender<(Result<Config, ()>, ConfigReload)>,
     ) -> Self {
         let (load_config, load_config_rx) = mpsc::channel();
 
+        let inner_path = path.clone();
         thread::Builder::new()
             .name(format!("Filesystem Watcher for {path:?}"))
             .spawn(move || {
-                let mut inner = WatcherInner::new(path, includes);
+                let mut inner = WatcherInner::new(inner_path, includes);
 
                 loop {
-                    let mut should_load = match load_config_rx.recv_timeout(POLLING_INTERVAL) {
-                        Ok(()) => true,
+                    let mut reload = match load_config_rx.recv_timeout(POLLING_INTERVAL) {
+                        Ok(reload) => Some(reload),
                         Err(mpsc::RecvTimeoutError::Disconnected) => break,
-                        Err(mpsc::RecvTimeoutError::Timeout) => false,
+                        Err(mpsc::RecvTimeoutError::Timeout) => None,
                     };
 
                     match inner.check() {
@@ -77,14 +89,15 @@ impl Watcher {
                         CheckResult::Unchanged => (),
                         CheckResult::Changed => {
                             trace!("config file changed");
-                            should_load = true;
+                            // The file changed on its own, so apply all of it.
+                            reload = Some(ConfigReload::Full);
                         }
                     }
 
-                    if should_load {
+                    if let Some(reload) = reload {
                         let res = process(&inner.path);
 
-                        if let Err(err) = changed.send(res.config) {
+                        if let Err(err) = changed.send((res.config, reload)) {
                             warn!("error sending change notification: {err:?}");
                             break;
                         }
@@ -102,11 +115,19 @@ impl Watcher {
             })
             .unwrap();
 
-        Self { load_config }
+        Self { load_config, path }
+    }
+
+    pub const fn path(&self) -> &ConfigPath {
+        &self.path
     }
 
     pub fn load_config(&self) {
-        let _ = self.load_config.send(());
+        let _ = self.load_config.send(ConfigReload::Full);
+    }
+
+    pub fn reload_binds(&self) {
+        let _ = self.load_config.send(ConfigReload::BindsOnly);
     }
 }
 
@@ -192,13 +213,18 @@ pub fn setup(state: &mut State, config_path: &ConfigPath, includes: Vec<PathBuf>
         .event_loop
         .insert_source(
             rx,
-            |event: calloop::channel::Event<Result<Config, ()>>, _, state| match event {
-                calloop::channel::Event::Msg(config) => {
-                    let failed = config.is_err();
-                    state.reload_config(config);
-                    state.ipc_config_loaded(failed);
+            |event: calloop::channel::Event<(Result<Config, ()>, ConfigReload)>, _, state| {
+                match event {
+                    calloop::channel::Event::Msg((config, reload)) => {
+                        let failed = config.is_err();
+                        match reload {
+                            ConfigReload::Full => state.reload_config(config),
+                            ConfigReload::BindsOnly => state.reload_binds(config),
+                        }
+                        state.ipc_config_loaded(failed);
+                    }
+                    calloop::channel::Event::Closed => (),
                 }
-                calloop::channel::Event::Closed => (),
             },
         )
         .unwrap();
@@ -0,0 +1,197 @@
+//! Stable UUIDs for named workspaces.
+//!
+//! Workspace ids come from a counter and change across niri restarts. Named workspaces
+//! additionally get a UUID that is persisted to a state file, so that tools can refer to the same
+//! workspace across restarts.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use anyhow::Context;
+use directories::BaseDirs;
+
+#[derive(Debug, Default)]
+pub struct WorkspaceUuids {
+    /// Path to the state file, or `None` to keep the UUIDs in memory only.
+    path: Option<PathBuf>,
+    /// Map from lowercase workspace name to its UUID.
+    uuids: HashMap<String, String>,
+    /// Whether some UUIDs were assigned since the last save.
+    changed: bool,
+    /// Number of saves started so far.
+    generation: u64,
+    /// Generation of the last finished save.
+    ///
+    /// Saves happen on separate threads, so this keeps an older save from overwriting a newer one.
+    saved_generation: Arc<Mutex<u64>>,
+}
+
+impl WorkspaceUuids {
+    /// Loads the UUIDs from the default state file location.
+    pub fn load() -> Self {
+        let Some(path) = default_path() else {
+            warn!("error retrieving the state directory, workspace UUIDs will not persist");
+            return Self::default();
+        };
+
+        Self::load_from(path)
+    }
+
+    pub fn load_from(path: PathBuf) -> Self {
+        let uuids = match read(&path) {
+            Ok(uuids) => uuids,
+            Err(err) => {
+                warn!("error reading workspace UUIDs from {path:?}: {err:?}");
+                HashMap::new()
+            }
+        };
+
+        Self {
+            path: Some(path),
+            uuids,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the UUID for the workspace with this name, assigning a new one if needed.
+    pub fn uuid_for(&mut self, name: &str) -> &str {
+        let key = name.to_lowercase();
+        if !self.uuids.contains_key(&key) {
+            self.uuids.insert(key.clone(), generate_uuid());
+            self.changed = true;
+        }
+
+        &self.uuids[&key]
+    }
+
+    /// Writes the UUIDs to the state file on a separate thread, if any were assigned since the
+    /// last save.
+    pub fn save_if_changed(&mut self) -> Option<JoinHandle<()>> {
+        if !self.changed {
+            return None;
+        }
+        self.changed = false;
+
+        let path = self.path.clone()?;
+        let uuids = self.uuids.clone();
+        self.generation += 1;
+        let generation = self.generation;
+        let saved_generation = self.saved_generation.clone();
+
+        let res = thread::Builder::new()
+            .name("Workspace UUID Saver".to_owned())
+            .spawn(move || {
+                let _span = tracy_client::span!("WorkspaceUuids::save");
+
+                let mut saved_generation = saved_generation.lock().unwrap();
+                if generation < *saved_generation {
+                    return;
+                }
+
+                if let Err(err) = write(&path, &uuids) {
+                    warn!("error writing workspace UUIDs to {path:?}: {err:?}");
+                }
+                *saved_generation = generation;
+            });
+
+        match res {
+            Ok(handle) => Some(handle),
+            Err(err) => {
+                warn!("error spawning a thread to save workspace UUIDs: {err:?}");
+                None
+            }
+        }
+    }
+}
+
+/// Returns the default state file location, `$XDG_STATE_HOME/niri/workspace-uuids.json`.
+pub fn default_path() -> Option<PathBuf> {
+    let dirs = BaseDirs::new()?;
+    let mut path = dirs.state_dir()?.to_path_buf();
+    path.push("niri");
+    path.push("workspace-uuids.json");
+    Some(path)
+}
+
+fn read(path: &Path) -> anyhow::Result<HashMap<String, String>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(err) => return Err(err).context("error reading file"),
+    };
+
+    serde_json::from_str(&contents).context("error parsing file")
+}
+
+fn write(path: &Path, uuids: &HashMap<String, String>) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("error creating parent directory")?;
+    }
+
+    let contents = serde_json::to_string_pretty(uuids).context("error serializing UUIDs")?;
+    fs::write(path, contents).context("error writing file")
+}
+
+/// Generates a random version 4 UUID.
+fn generate_uuid() -> String {
+    let mut bytes = fastrand::u128(..).to_be_bytes();
+    // Version 4.
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    // Variant 1.
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uuid_format() {
+        let uuid = generate_uuid();
+        assert_eq!(uuid.len(), 36);
+        assert!(niri_ipc::WorkspaceReferenceArg::is_uuid(&uuid));
+        assert_eq!(&uuid[14..15], "4");
+    }
+
+    #[test]
+    fn uuids_roundtrip_through_state_file() {
+        let sh = xshell::Shell::new().unwrap();
+        let temp_dir = sh.create_temp_dir().unwrap();
+        let path = temp_dir.path().join("niri/workspace-uuids.json");
+
+        let mut uuids = WorkspaceUuids::load_from(path.clone());
+        let chat = uuids.uuid_for("chat").to_owned();
+        let browser = uuids.uuid_for("browser").to_owned();
+        uuids.save_if_changed().unwrap().join().unwrap();
+
+        // Nothing new to save.
+        uuids.uuid_for("Chat");
+        assert!(uuids.save_if_changed().is_none());
+
+        let mut loaded = WorkspaceUuids::load_from(path);
+        assert_eq!(loaded.uuid_for("chat"), chat);
+        assert_eq!(loaded.uuid_for("browser"), browser);
+        assert!(loaded.save_if_changed().is_none());
+    }
+
+    #[test]
+    fn uuids_are_stable_per_name() {
+        let mut uuids = WorkspaceUuids::default();
+        let a = uuids.uuid_for("chat").to_owned();
+        assert_eq!(uuids.uuid_for("Chat"), a);
+        assert_ne!(uuids.uuid_for("browser"), a);
+    }
+}
@@ -42,8 +42,9 @@ use crate::utils::id::IdCounter;
 use crate::utils::render::{PushRenderElement, Render};
 use crate::utils::transaction::Transaction;
 use crate::utils::{
-    ResizeEdge, get_credentials_for_surface, send_scale_transform, update_tiled_state,
-    with_toplevel_last_uncommitted_configure, with_toplevel_role, with_toplevel_role_and_current,
+    ResizeEdge, get_credentials_for_surface, send_scale_transform, set_decoration_mode,
+    update_tiled_state, with_toplevel_last_uncommitted_configure, with_toplevel_role,
+    with_toplevel_role_and_current,
 };
 
 #[derive(Debug)]
@@ -87,6 +88,11 @@ pub struct Mapped {
     /// Whether this has an urgent indicator.
     is_urgent: bool,
 
+    /// Whether the user flagged this window as urgent while it was focused.
+    ///
+    /// Such urgency persists when the window loses focus, unlike urgency requested by the client.
+    keep_urgent_on_unfocus: bool,
+
     /// Whether this window has the keyboard focus.
     is_focused: bool,
 
@@ -102,6 +108,9 @@ pub struct Mapped {
     /// Whether this window should ignore opacity set through window rules.
     ignore_opacity_window_rule: bool,
 
+    /// Opacity set at runtime, takes precedence over the opacity window rule.
+    opacity_override: Option<f32>,
+
     /// Buffer to draw instead of the window when it should be blocked out.
     block_out_buffer: RefCell<SolidColorBuffer>,
 
@@ -131,6 +140,14 @@ pub struct Mapped {
     /// Used for double-resize-click tracking.
     last_interactive_resize_start: Cell<Option<(Duration, ResizeEdge)>>,
 
+    /// Last time this window or one of its subsurfaces committed.
+    ///
+    /// Used to check whether the window is actively updating for on-demand VRR.
+    last_commit_time: Cell<Option<Duration>>,
+
+    /// Name of the output this window was on before that output got disconnected.
+    last_output: Option<String>,
+
     /// Whether this window is in windowed (fake) fullscreen.
     ///
     /// In this mode, the underlying window is told that it's fullscreen, while keeping it as
@@ -273,11 +290,13 @@ impl Mapped {
             needs_frame_callback: false,
             offscreen_data: RefCell::new(None),
             is_urgent: false,
+            keep_urgent_on_unfocus: false,
             is_focused: false,
             is_active_in_column: true,
             is_floating: false,
             is_window_cast_target: false,
             ignore_opacity_window_rule: false,
+            opacity_override: None,
             block_out_buffer: RefCell::new(SolidColorBuffer::new((0., 0.), [0., 0., 0., 1.])),
             animate_next_configure: false,
             animate_serials: Vec::new(),
@@ -287,6 +306,8 @@ impl Mapped {
             pending_transactions: Vec::new(),
             interactive_resize: None,
             last_interactive_resize_start: Cell::new(None),
+            last_commit_time: Cell::new(None),
+            last_output: None,
             is_windowed_fullscreen: false,
             is_pending_windowed_fullscreen: false,
             uncommitted_windowed_fullscreen: Vec::new(),
@@ -308,17 +329,31 @@ impl Mapped {
     }
 
     /// Recomputes the resolved window rules and returns whether they changed.
-    pub fn recompute_window_rules(&mut self, rules: &[WindowRule], is_at_startup: bool) -> bool {
+    pub fn recompute_window_rules(
+        &mut self,
+        rules: &[WindowRule],
+        is_at_startup: bool,
+        block_out_all_from_screencast: bool,
+    ) -> bool {
         self.need_to_recompute_rules = false;
 
-        let new_rules = ResolvedWindowRules::compute(rules, WindowRef::Mapped(self), is_at_startup);
+        let new_rules = ResolvedWindowRules::compute(
+            rules,
+            WindowRef::Mapped(self),
+            is_at_startup,
+            block_out_all_from_screencast,
+        );
         if new_rules == self.rules {
             return false;
         }
 
-        // If the opacity window rule no longer makes the window semitransparent, reset the ignore
-        // flag to reduce surprises down the line.
-        if !new_rules.opacity.is_some_and(|o| o < 1.) {
+        // If the opacity no longer makes the window semitransparent, reset the ignore flag to
+        // reduce surprises down the line.
+        if !self
+            .opacity_override
+            .or(new_rules.opacity)
+            .is_some_and(|o| o < 1.)
+        {
             self.ignore_opacity_window_rule = false;
         }
 
@@ -330,12 +365,13 @@ impl Mapped {
         &mut self,
         rules: &[WindowRule],
         is_at_startup: bool,
+        block_out_all_from_screencast: bool,
     ) -> bool {
         if !self.need_to_recompute_rules {
             return false;
         }
 
-        self.recompute_window_rules(rules, is_at_startup)
+        self.recompute_window_rules(rules, is_at_startup, block_out_all_from_screencast)
     }
 
     pub const fn set_needs_configure(&mut self) {
@@ -374,13 +410,28 @@ impl Mapped {
         self.ignore_opacity_window_rule = !self.ignore_opacity_window_rule;
     }
 
+    /// Sets the opacity of the window for the rest of the session, overriding the window rule.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        // Don't let the window become fully invisible.
+        self.opacity_override = Some(opacity.clamp(0.05, 1.));
+        self.ignore_opacity_window_rule = false;
+    }
+
+    /// Returns the opacity set at runtime or through the window rule.
+    pub fn opacity(&self) -> Option<f32> {
+        self.opacity_override.or(self.rules.opacity)
+    }
+
     pub const fn set_is_focused(&mut self, is_focused: bool) {
         if self.is_focused == is_focused {
             return;
         }
 
         self.is_focused = is_focused;
-        self.is_urgent = false;
+        if is_focused || !self.keep_urgent_on_unfocus {
+            self.is_urgent = false;
+        }
+        self.keep_urgent_on_unfocus = false;
         self.need_to_recompute_rules = true;
     }
 
@@ -480,6 +531,18 @@ impl Mapped {
         &self.last_interactive_resize_start
     }
 
+    pub const fn last_commit_time(&self) -> &Cell<Option<Duration>> {
+        &self.last_commit_time
+    }
+
+    pub fn last_output(&self) -> Option<&str> {
+        self.last_output.as_deref()
+    }
+
+    pub fn set_last_output(&mut self, name: Option<String>) {
+        self.last_output = name;
+    }
+
     pub fn render_for_screen_cast<R, C>(
         &self,
         renderer: &mut R,
@@ -582,10 +645,32 @@ impl Mapped {
         update_tiled_state(self.toplevel(), prefer_no_csd, self.rules.tiled_state);
     }
 
+    /// Switches the decoration mode requested from the window between server- and client-side.
+    ///
+    /// Returns `false` if the window doesn't use xdg-decoration. The client may still ignore the
+    /// requested mode.
+    pub fn toggle_server_decorations(&mut self, prefer_no_csd: bool) -> bool {
+        let toplevel = self.toplevel();
+        let mode = toplevel.with_pending_state(|state| state.decoration_mode);
+        let server_side = mode != Some(zxdg_toplevel_decoration_v1::Mode::ServerSide);
+        if !set_decoration_mode(toplevel, server_side) {
+            return false;
+        }
+
+        self.update_tiled_state(prefer_no_csd);
+        self.set_needs_configure();
+        true
+    }
+
     pub const fn is_windowed_fullscreen(&self) -> bool {
         self.is_windowed_fullscreen
     }
 
+    /// Returns whether the window has a pending real (non-windowed) fullscreen state.
+    pub fn is_pending_fullscreen(&self) -> bool {
+        self.pending_sizing_mode().is_fullscreen()
+    }
+
     pub const fn set_urgent(&mut self, urgent: bool) {
         if self.is_focused && urgent {
             return;
@@ -596,6 +681,18 @@ impl Mapped {
         self.need_to_recompute_rules |= changed;
     }
 
+    /// Sets urgency on behalf of the user.
+    ///
+    /// Unlike [`Self::set_urgent()`], this also works on the focused window, which then stays
+    /// urgent until it's focused again.
+    pub const fn set_urgent_by_user(&mut self, urgent: bool) {
+        self.keep_urgent_on_unfocus = self.is_focused && urgent;
+
+        let changed = self.is_urgent != urgent;
+        self.is_urgent = urgent;
+        self.need_to_recompute_rules |= changed;
+    }
+
     pub const fn is_urgent(&self) -> bool {
         self.is_urgent
     }
@@ -634,6 +731,8 @@ impl LayoutElement for Mapped {
         animate: bool,
         transaction: Option<Transaction>,
     ) {
+        let was_fullscreen = self.is_pending_fullscreen();
+
         // Going into real fullscreen resets windowed fullscreen.
         if mode == SizingMode::Fullscreen {
             self.is_pending_windowed_fullscreen = false;
@@ -683,6 +782,8 @@ impl LayoutElement for Mapped {
         if let Some(transaction) = transaction {
             self.transaction_for_next_configure = Some(transaction);
         }
+
+        self.need_to_recompute_rules |= was_fullscreen != self.is_pending_fullscreen();
     }
 
     fn request_size_once(&mut self, size: Size<i32, Logical>, animate: bool) {
@@ -740,6 +841,7 @@ impl LayoutElement for Mapped {
             return;
         }
 
+        let was_fullscreen = self.is_pending_fullscreen();
         let changed = self.toplevel().with_pending_state(|state| {
             let changed = state.size != Some(size);
             state.size = Some(size);
@@ -755,6 +857,7 @@ impl LayoutElement for Mapped {
         }
 
         self.request_size_once = Some(RequestSizeOnce::WaitingForConfigure);
+        self.need_to_recompute_rules |= was_fullscreen != self.is_pending_fullscreen();
     }
 
     fn min_size(&self) -> Size<i32, Logical> {
@@ -1085,6 +1188,10 @@ impl LayoutElement for Mapped {
         self.ignore_opacity_window_rule
     }
 
+    fn opacity_override(&self) -> Option<f32> {
+        self.opacity_override
+    }
+
     fn requested_size(&self) -> Option<Size<i32, Logical>> {
         self.toplevel().with_pending_state(|state| state.size)
     }
@@ -1186,6 +1293,7 @@ impl LayoutElement for Mapped {
             return;
         }
 
+        let was_fullscreen = self.is_pending_fullscreen();
         self.is_pending_windowed_fullscreen = value;
 
         // Set the fullscreen state to match.
@@ -1207,6 +1315,7 @@ impl LayoutElement for Mapped {
 
         // Make sure we receive a commit later to update self.is_windowed_fullscreen.
         self.needs_configure = true;
+        self.need_to_recompute_rules |= was_fullscreen != self.is_pending_fullscreen();
     }
 
     fn is_child_of(&self, parent: &Self) -> bool {
@@ -1294,6 +1403,10 @@ impl LayoutElement for Mapped {
     fn title(&self) -> Option<String> {
         with_toplevel_role(self.toplevel(), |role| role.title.clone())
     }
+
+    fn focus_timestamp(&self) -> Option<Duration> {
+        self.get_focus_timestamp()
+    }
     ///
     /// Set the preferred blurred state of this window.
     fn set_proto_wants_blur(&mut self, new_blurred: bool) {
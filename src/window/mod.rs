@@ -3,8 +3,8 @@ use std::cmp::{max, min};
 use niri_config::utils::MergeWith as _;
 use niri_config::window_rule::{Match, WindowRule};
 use niri_config::{
-    BlockOutFrom, BlurRule, BorderRule, CornerRadius, FloatingPosition, PresetSize, ShadowRule,
-    TabIndicatorRule,
+    BlockOutFrom, BlurRule, BorderRule, CornerRadius, FloatingPosition, InsertHintRule, PresetSize,
+    ShadowRule, TabIndicatorRule,
 };
 use smithay::reexports::wayland_protocols::xdg::shell::server::xdg_toplevel;
 use smithay::utils::{Logical, Size};
@@ -13,7 +13,7 @@ use smithay::wayland::shell::xdg::{
     SurfaceCachedState, ToplevelSurface, XdgToplevelSurfaceRoleAttributes,
 };
 
-use crate::utils::with_toplevel_role;
+use crate::utils::{get_credentials_for_surface, get_process_name_for_surface, with_toplevel_role};
 
 pub mod mapped;
 pub use mapped::Mapped;
@@ -31,6 +31,9 @@ pub enum WindowRef<'a> {
 /// Rules fully resolved for a window.
 #[derive(Debug, Default, PartialEq, Clone)]
 pub struct ResolvedWindowRules {
+    /// Indices of the window rules that matched this window.
+    pub matched_rules: Vec<usize>,
+
     /// Default width for this window.
     ///
     /// - `None`: unset (global default should be used).
@@ -69,6 +72,9 @@ pub struct ResolvedWindowRules {
     /// Whether the window should open focused.
     pub open_focused: Option<bool>,
 
+    /// Whether the window should be kept floating (or tiled) for its entire lifetime.
+    pub force_floating: Option<bool>,
+
     /// Extra bound on the minimum window width.
     pub min_width: Option<u16>,
     /// Extra bound on the minimum window height.
@@ -88,6 +94,8 @@ pub struct ResolvedWindowRules {
     pub blur: BlurRule,
     /// Tab indicator overrides.
     pub tab_indicator: TabIndicatorRule,
+    /// Insert hint overrides, used while this window is being moved.
+    pub insert_hint: InsertHintRule,
 
     /// Whether or not to draw the border with a solid background.
     ///
@@ -117,6 +125,9 @@ pub struct ResolvedWindowRules {
 
     /// Override whether to set the Tiled xdg-toplevel state on the window.
     pub tiled_state: Option<bool>,
+
+    /// Decoration mode to request from the window when it supports xdg-decoration.
+    pub prefer_server_decorations: Option<bool>,
 }
 
 impl<'a> WindowRef<'a> {
@@ -163,6 +174,15 @@ impl<'a> WindowRef<'a> {
         }
     }
 
+    pub fn is_fullscreen(self) -> bool {
+        match self {
+            // Same as is-floating: the open-fullscreen rule decides this for unmapped windows, so
+            // matching on it here would make a cycle.
+            WindowRef::Unmapped(_) => false,
+            WindowRef::Mapped(mapped) => mapped.is_pending_fullscreen(),
+        }
+    }
+
     pub const fn is_window_cast_target(self) -> bool {
         match self {
             WindowRef::Unmapped(_) => false,
@@ -172,10 +192,35 @@ impl<'a> WindowRef<'a> {
 }
 
 impl ResolvedWindowRules {
-    pub fn compute(rules: &[WindowRule], window: WindowRef, is_at_startup: bool) -> Self {
+    pub fn compute(
+        rules: &[WindowRule],
+        window: WindowRef,
+        is_at_startup: bool,
+        block_out_all_from_screencast: bool,
+    ) -> Self {
         let _span = tracy_client::span!("ResolvedWindowRules::compute");
 
         let mut resolved = Self::default();
+        let mut allow_in_screencast = false;
+
+        // Resolve these outside with_toplevel_role() since they need to access the surface states.
+        // Looking up the process name reads /proc, so only do it when some rule needs it.
+        let uses_matcher = |f: fn(&Match) -> bool| {
+            rules
+                .iter()
+                .any(|rule| rule.matches.iter().chain(&rule.excludes).any(f))
+        };
+        let surface = window.toplevel().wl_surface();
+        let process_name = if uses_matcher(|m| m.process.is_some()) {
+            get_process_name_for_surface(surface)
+        } else {
+            None
+        };
+        let pid = if uses_matcher(|m| m.pid.is_some()) {
+            get_credentials_for_surface(surface).map(|c| c.pid)
+        } else {
+            None
+        };
 
         with_toplevel_role(window.toplevel(), |role| {
             // Ensure server_pending like in Smithay's with_pending_state().
@@ -186,7 +231,7 @@ impl ResolvedWindowRules {
             let mut open_on_output = None;
             let mut open_on_workspace = None;
 
-            for rule in rules {
+            for (idx, rule) in rules.iter().enumerate() {
                 let matches = |m: &Match| {
                     if let Some(at_startup) = m.at_startup
                         && at_startup != is_at_startup
@@ -194,7 +239,7 @@ impl ResolvedWindowRules {
                         return false;
                     }
 
-                    window_matches(window, role, m)
+                    window_matches(window, role, process_name.as_deref(), pid, m)
                 };
 
                 if !(rule.matches.is_empty() || rule.matches.iter().any(matches)) {
@@ -205,6 +250,8 @@ impl ResolvedWindowRules {
                     continue;
                 }
 
+                resolved.matched_rules.push(idx);
+
                 if let Some(x) = rule.default_column_width {
                     resolved.default_width = Some(x.0);
                 }
@@ -245,6 +292,10 @@ impl ResolvedWindowRules {
                     resolved.open_focused = Some(x);
                 }
 
+                if let Some(x) = rule.force_floating {
+                    resolved.force_floating = Some(x);
+                }
+
                 if let Some(x) = rule.min_width {
                     resolved.min_width = Some(x);
                 }
@@ -262,6 +313,7 @@ impl ResolvedWindowRules {
                 resolved.border.merge_with(&rule.border);
                 resolved.shadow.merge_with(&rule.shadow);
                 resolved.tab_indicator.merge_with(&rule.tab_indicator);
+                resolved.insert_hint.merge_with(&rule.insert_hint);
                 resolved.blur.merge_with(&rule.blur);
 
                 if let Some(x) = rule.draw_border_with_background {
@@ -282,6 +334,9 @@ impl ResolvedWindowRules {
                 if let Some(x) = rule.block_out_from {
                     resolved.block_out_from = Some(x);
                 }
+                if let Some(x) = rule.allow_in_screencast {
+                    allow_in_screencast = x;
+                }
                 if let Some(x) = rule.variable_refresh_rate {
                     resolved.variable_refresh_rate = Some(x);
                 }
@@ -291,12 +346,23 @@ impl ResolvedWindowRules {
                 if let Some(x) = rule.tiled_state {
                     resolved.tiled_state = Some(x);
                 }
+                if let Some(x) = rule.prefer_server_decorations {
+                    resolved.prefer_server_decorations = Some(x);
+                }
             }
 
             resolved.open_on_output = open_on_output.map(|x| x.to_owned());
             resolved.open_on_workspace = open_on_workspace.map(|x| x.to_owned());
         });
 
+        // An explicit block-out-from takes precedence over the global default.
+        if block_out_all_from_screencast
+            && !allow_in_screencast
+            && resolved.block_out_from.is_none()
+        {
+            resolved.block_out_from = Some(BlockOutFrom::Screencast);
+        }
+
         resolved
     }
 
@@ -345,7 +411,7 @@ impl ResolvedWindowRules {
     }
 
     pub fn compute_open_floating(&self, toplevel: &ToplevelSurface) -> bool {
-        if let Some(res) = self.open_floating {
+        if let Some(res) = self.force_floating.or(self.open_floating) {
             return res;
         }
 
@@ -366,7 +432,13 @@ impl ResolvedWindowRules {
     }
 }
 
-fn window_matches(window: WindowRef, role: &XdgToplevelSurfaceRoleAttributes, m: &Match) -> bool {
+fn window_matches(
+    window: WindowRef,
+    role: &XdgToplevelSurfaceRoleAttributes,
+    process_name: Option<&str>,
+    pid: Option<i32>,
+    m: &Match,
+) -> bool {
     // Must be ensured by the caller.
     let server_pending = role.server_pending.as_ref().unwrap();
 
@@ -410,6 +482,21 @@ fn window_matches(window: WindowRef, role: &XdgToplevelSurfaceRoleAttributes, m:
         }
     }
 
+    if let Some(process_re) = &m.process {
+        let Some(process_name) = process_name else {
+            return false;
+        };
+        if !process_re.0.is_match(process_name) {
+            return false;
+        }
+    }
+
+    if let Some(want_pid) = m.pid
+        && pid != Some(want_pid)
+    {
+        return false;
+    }
+
     if let Some(is_active_in_column) = m.is_active_in_column
         && window.is_active_in_column() != is_active_in_column
     {
@@ -422,6 +509,12 @@ fn window_matches(window: WindowRef, role: &XdgToplevelSurfaceRoleAttributes, m:
         return false;
     }
 
+    if let Some(is_fullscreen) = m.is_fullscreen
+        && window.is_fullscreen() != is_fullscreen
+    {
+        return false;
+    }
+
     if let Some(is_window_cast_target) = m.is_window_cast_target
         && window.is_window_cast_target() != is_window_cast_target
     {